@@ -8,11 +8,30 @@
 //! ([`CurrentScreen`]) driving the TUI layout, handling user inputs, managing the list selection
 //! states, and communicating asynchronously with the background OPC DA client provider.
 
-use opc_da_client::{OpcError, OpcProvider, OpcValue, TagValue, WriteResult, friendly_com_hint};
+use crate::config::{self, BrowseEstimates};
+use crate::connectivity;
+use crate::op_id::{OpId, op_suffix, without_op_suffix};
+use crate::output::OutputFormat;
+use crate::palette::CommandPalette;
+use crate::recording::{self, Recorder};
+use crate::snapshot::ServerSnapshot;
+use crate::theme::Theme;
+use anyhow::Context;
+use opc_da_client::com_worker::ComWorker;
+use opc_da_client::{
+    AccessRightsFilter, BrowseProgress, BrowsedTag, ComConnector, EngineeringUnits, OpcError,
+    OpcProvider, OpcValue, ReadStatus, ServerMetadata, TagValue, WriteResult, friendly_com_hint,
+    is_ipv6_literal, local_host_hint, normalize_host, vt_to_string,
+};
 use ratatui::widgets::{ListState, TableState}; // Added TableState
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::fs::File;
+use std::io::Write;
+use std::path::PathBuf;
 use std::sync::Arc;
-use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
 use tokio::sync::oneshot;
+use tracing::Instrument;
 
 /// Default timeout for OPC operations (server listing and tag browsing).
 const OPC_TIMEOUT_SECS: u64 = 300;
@@ -20,6 +39,66 @@ const OPC_TIMEOUT_SECS: u64 = 300;
 /// Maximum tags to retrieve when browsing an OPC server namespace.
 const MAX_BROWSE_TAGS: usize = 10000;
 
+/// Minimum interval between two server-list fetches started from the Home screen.
+const FETCH_DEBOUNCE: std::time::Duration = std::time::Duration::from_millis(500);
+
+/// Worker queue depth above which a backpressure warning is shown (75% full).
+const QUEUE_WARN_DEPTH: usize = 24;
+
+/// Worker queue depth at which auto-refresh is paused (queue full).
+const QUEUE_PAUSE_DEPTH: usize = opc_da_client::com_worker::REQUEST_QUEUE_CAPACITY;
+
+/// Worker queue depth below which a paused auto-refresh resumes.
+const QUEUE_RESUME_DEPTH: usize = 16;
+
+/// Interval between auto-refresh reads of displayed tag values.
+const AUTO_REFRESH_INTERVAL: std::time::Duration = std::time::Duration::from_secs(1);
+
+/// Consecutive auto-refresh reads failing with a COM error after which the
+/// server is assumed to have restarted and is browsed again.
+const RECONNECT_AFTER_READ_ERRORS: u8 = 5;
+
+/// Interval between heartbeat pings of the OPC worker.
+const WORKER_PING_INTERVAL: std::time::Duration = std::time::Duration::from_secs(5);
+
+/// Longest a single server may take to connect during a latency measurement
+/// before it is counted as timed out.
+const LATENCY_PROBE_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(5);
+
+/// `ProgID` of OPCEnum, the service that lists a host's servers; its
+/// registration is checked when listing the local servers fails.
+const OPC_ENUM_PROG_ID: &str = "OPC.ServerList.1";
+
+/// Search queries kept in the search history.
+const SEARCH_HISTORY_LEN: usize = 20;
+
+/// Default idle time after which the connection is flagged as possibly stale.
+pub const DEFAULT_STALE_AFTER_SECS: u64 = 300;
+
+/// Default for `--read-batch-size`: tags per `read_tag_values` call, and
+/// per OPC group once `main` passes it on as `max_items_per_group`.
+pub const DEFAULT_READ_BATCH_SIZE: usize = 50;
+
+/// Share of their changes two tags must have in common to be listed as
+/// changing together.
+pub const CORRELATION_THRESHOLD: f64 = 0.8;
+
+/// Largest Tag Values table whose value changes are correlated; pairs grow
+/// with the square of the row count.
+const MAX_CORRELATION_TAGS: usize = 500;
+
+/// Most servers that can be open at once in multi-server mode.
+pub const MAX_SESSIONS: usize = 4;
+
+/// Most columns the tag list can be split into (`Ctrl+W`).
+pub const MAX_TAG_COLUMNS: u8 = 4;
+
+/// Default step applied by `+`/`-` on the Tag Values screen.
+pub const DEFAULT_INCREMENT_STEP: f64 = 1.0;
+
+/// Default step applied by `Alt` + `+`/`-` on the Tag Values screen.
+pub const DEFAULT_LARGE_INCREMENT_STEP: f64 = 10.0;
+
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
 pub enum CurrentScreen {
     Home,
@@ -28,52 +107,378 @@ pub enum CurrentScreen {
     TagList,
     TagValues,
     WriteInput,
+    /// Pairs of tags that tend to change value in the same refresh.
+    Correlation,
+    /// The command palette, drawn over the screen it was opened from.
+    CommandPalette,
+    /// Keybinding help for [`App::help_from_screen`] (`F1`).
+    Help,
     Exiting,
 }
 
+/// Health of the link to the server, shown in the status bar.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum ConnectionState {
+    /// Nothing sent to a server yet, or idle long enough that the pooled
+    /// connection has likely been dropped; also while viewing a snapshot.
+    Idle,
+    /// The last operation succeeded.
+    Connected,
+    /// Reads are failing and being retried, or the server is being browsed
+    /// again after repeated failures.
+    Reconnecting,
+    /// The last operation failed, or the OPC worker stopped answering.
+    Disconnected,
+}
+
+impl ConnectionState {
+    /// Short name shown in the indicator.
+    pub const fn label(self) -> &'static str {
+        match self {
+            Self::Idle => "idle",
+            Self::Connected => "connected",
+            Self::Reconnecting => "reconnecting",
+            Self::Disconnected => "disconnected",
+        }
+    }
+}
+
+/// One server opened in multi-server mode.
+///
+/// The session at [`App::current_session`] lives in `App`'s own fields
+/// (`tags`, `tag_values`, `list_state`, ...); its entry in
+/// [`App::active_sessions`] is only written when switching away from it.
+/// Background sessions keep auto-refreshing through `read_result_rx`.
+#[derive(Debug, Default)]
+pub struct ServerSession {
+    /// Host the server was discovered on.
+    pub host: String,
+    /// The server `ProgID`.
+    pub server: String,
+    pub tags: Vec<BrowsedTag>,
+    /// When `tags` was browsed.
+    pub browse_time: Option<chrono::DateTime<chrono::Local>>,
+    /// See [`App::browse_partial`].
+    pub browse_partial: Option<Arc<AtomicUsize>>,
+    pub selected_tags: Vec<bool>,
+    pub tag_values: Vec<TagValue>,
+    /// Scroll position of the tag list.
+    pub list_state: ListState,
+    /// Scroll position of the tag values table.
+    pub table_state: TableState,
+    pub selected_index: Option<usize>,
+    /// Tag IDs re-read on every auto-refresh cycle.
+    pub refresh_tag_ids: Vec<String>,
+    pub last_read_time: Option<std::time::Instant>,
+    pub read_result_rx: Option<oneshot::Receiver<Result<Vec<TagValue>, OpcError>>>,
+    /// See [`App::consecutive_read_errors`]; background refreshes count too.
+    pub consecutive_read_errors: u8,
+    /// See [`App::reconnect_tag_ids`].
+    pub reconnect_tag_ids: Vec<String>,
+    /// See [`App::last_server_activity`]; background refreshes update it.
+    pub last_server_activity: Option<std::time::Instant>,
+    /// See [`App::last_operation_failed`]; background refreshes update it.
+    pub last_operation_failed: bool,
+}
+
+impl ServerSession {
+    /// `host: ProgID`, or just the `ProgID` when the host is unknown.
+    ///
+    /// Keeps the same `ProgID` on different machines distinguishable.
+    pub fn display_name(&self) -> String {
+        if self.host.is_empty() {
+            self.server.clone()
+        } else {
+            format!("{}: {}", self.host, self.server)
+        }
+    }
+}
+
+/// Engineering units fetched for some tags, keyed like
+/// [`App::engineering_units`].
+pub type EngineeringUnitsBatch = Vec<((String, String), Option<EngineeringUnits>)>;
+
+/// Tags delivered by a browse.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BrowseResult {
+    pub tags: Vec<BrowsedTag>,
+    /// The browse timed out and `tags` is what it had found by then.
+    pub partial: bool,
+}
+
+impl From<Vec<BrowsedTag>> for BrowseResult {
+    /// A complete browse.
+    fn from(tags: Vec<BrowsedTag>) -> Self {
+        Self {
+            tags,
+            partial: false,
+        }
+    }
+}
+
 /// Main application state for the OPC DA Client TUI.
 ///
 /// Manages the current screen, loaded servers and tags, search state,
 /// and terminal interaction through `ratatui`.
 pub struct App {
     pub host_input: String,
+    /// Why `host_input` was rejected, shown under the input on the Home screen.
+    pub host_error: Option<String>,
     pub servers: Vec<String>,
-    pub tags: Vec<String>,
+    /// Host `servers` was enumerated on, shown next to each `ProgID`.
+    pub server_host: Option<String>,
+    /// Tags found by the last browse, with where in the namespace they were found.
+    pub tags: Vec<BrowsedTag>,
     pub selected_index: Option<usize>,
     pub current_screen: CurrentScreen,
+    /// Breadcrumbs leading to the current screen, `Home` at the bottom.
+    ///
+    /// Transient screens (`Loading`, `CommandPalette`, `Exiting`) never enter
+    /// the stack; `go_back` pops it to find where to return.
+    pub screen_stack: Vec<CurrentScreen>,
     pub opc_provider: Arc<dyn OpcProvider>,
     pub messages: Vec<String>,
+    /// How many times in a row the last entry of `messages` was added; shown
+    /// as a `[×N]` suffix once it repeats.
+    pub last_message_count: usize,
     pub list_state: ListState,
     pub table_state: TableState, // New field
     pub browse_progress: Arc<AtomicUsize>,
-    pub browse_result_rx: Option<oneshot::Receiver<Result<Vec<String>, OpcError>>>,
+    /// Tags the running browse is expected to find: the count of the last
+    /// complete browse of the same server. `None` when unknown.
+    pub estimated_total_tags: Option<usize>,
+    /// Tag counts of earlier complete browses, by server.
+    pub browse_estimates: BrowseEstimates,
+    /// Where `browse_estimates` is saved after each complete browse; `None`
+    /// keeps them in memory only.
+    pub browse_estimates_path: Option<PathBuf>,
+    /// Branch depth the running browse is exploring, updated by the worker.
+    pub browse_depth: Arc<AtomicUsize>,
+    /// Set by the worker to the browse depth limit if the running browse
+    /// skipped branches below it; `0` otherwise.
+    pub browse_depth_limit_hit: Arc<AtomicUsize>,
+    /// Tags the running browse has found so far, shared with the worker so a
+    /// browse that dies part-way still leaves something to show.
+    pub browse_tags_sink: Arc<std::sync::Mutex<Option<Vec<BrowsedTag>>>>,
+    pub browse_result_rx: Option<oneshot::Receiver<Result<BrowseResult, OpcError>>>,
+    /// Correlation ID of the browse behind `browse_result_rx`.
+    pub browse_op_id: Option<OpId>,
+    /// Whether the running browse lists public groups instead of the namespace.
+    pub public_group_browse: bool,
     pub fetch_result_rx: Option<oneshot::Receiver<Result<Vec<String>, OpcError>>>,
+    /// Correlation ID of the server listing behind `fetch_result_rx`.
+    pub fetch_op_id: Option<OpId>,
+    /// Check that a remote host's DCOM port answers before listing its
+    /// servers (off with `--no-probe`).
+    pub probe_dcom: bool,
+    /// How the DCOM port is checked; swapped out in tests.
+    pub dcom_probe: connectivity::DcomProbe,
+    /// Host and result of the port check started by `start_fetch_servers`.
+    pub dcom_probe_rx: Option<oneshot::Receiver<(String, bool)>>,
     pub selected_tags: Vec<bool>,
     pub tag_values: Vec<TagValue>,
     pub read_result_rx: Option<oneshot::Receiver<Result<Vec<TagValue>, OpcError>>>,
+    /// Correlation ID of the read behind `read_result_rx`.
+    pub read_op_id: Option<OpId>,
+    /// Tags read so far by the read started from the tag list, updated by
+    /// the provider as each chunk completes.
+    pub read_progress: Arc<AtomicUsize>,
+    /// Number of tags in that read; `0` when none is running.
+    pub read_total: usize,
+    /// Largest number of tags passed to one `read_tag_values` call; bigger
+    /// selections are read in batches of this size, one after another, since
+    /// some servers reject long item lists with `RPC_S_STRING_TOO_LONG`.
+    /// Only the first read of a selection is batched here, so its progress
+    /// can be shown; auto-refresh reads pass the whole list and the provider
+    /// splits it by `max_items_per_group`. `main` sets both to the same
+    /// value, so a batch from here is never split again.
+    pub read_batch_size: usize,
+    /// Batches of the running read that have completed.
+    pub read_chunks_done: Arc<AtomicUsize>,
+    /// Number of batches in the running read.
+    pub read_chunk_total: usize,
     /// Context for auto-refresh: server used for the last read.
     pub refresh_server: Option<String>,
     /// Context for auto-refresh: tag IDs from the last read.
     pub refresh_tag_ids: Vec<String>,
     /// Tracks when the last successful read completed.
     pub last_read_time: Option<std::time::Instant>,
+    /// Auto-refresh reads in a row that failed with a COM error.
+    pub consecutive_read_errors: u8,
+    /// Browse the server again and re-read the same tags once
+    /// [`RECONNECT_AFTER_READ_ERRORS`] auto-refresh reads in a row fail, as
+    /// they do after the server restarts.
+    pub auto_reconnect: bool,
+    /// Tags to select and read once the reconnect browse completes; empty
+    /// when no reconnect is running.
+    pub reconnect_tag_ids: Vec<String>,
+    /// How many reads changed the values of both rows `(i, j)` of
+    /// `tag_values`, with `i < j`. Cleared when the set of tags changes.
+    pub change_correlation: HashMap<(usize, usize), u32>,
+    /// How many reads changed the value of each row of `tag_values`.
+    pub change_counts: Vec<u32>,
+    /// Frame counter for the loading spinner, advanced once per main-loop
+    /// iteration by [`tick_loading`](Self::tick_loading).
+    pub loading_tick: u8,
+    /// When the current wait for the server began; `None` when idle.
+    pub loading_since: Option<std::time::Instant>,
+    /// When the current session's server last answered an operation (list,
+    /// browse, read or write). Kept per session like `tags`.
+    pub last_server_activity: Option<std::time::Instant>,
+    /// Whether the current session's last list, browse, read or write
+    /// failed outright. Kept per session like `tags`.
+    pub last_operation_failed: bool,
+    /// Idle time after which the pooled connection has likely been dropped
+    /// and the next operation will reconnect; `None` never warns.
+    pub stale_after: Option<std::time::Duration>,
+    /// When `tags` was browsed, for the tag list export header.
+    pub browse_time: Option<chrono::DateTime<chrono::Local>>,
+    /// Set when `tags` holds the partial results of a timed-out browse: the
+    /// progress counter of the walk, which keeps counting in the background
+    /// and gives the approximate total shown in the tag list title.
+    pub browse_partial: Option<Arc<AtomicUsize>>,
+    /// File name being entered for a tag list export (`Ctrl+L`); `Some` while the prompt is open.
+    pub export_path_input: Option<String>,
+    /// Number of side-by-side columns the tag list is drawn in, 1 to
+    /// [`MAX_TAG_COLUMNS`]. Tags fill the columns top to bottom, left to right.
+    pub tag_columns: u8,
+    /// First row shown in each column of a multi-column tag list; kept in
+    /// step with the selection when drawing.
+    pub tag_column_offset: usize,
     /// Whether the tag list is in search/filter mode.
     pub search_mode: bool,
     /// Current search query string.
     pub search_query: String,
+    /// Whether search compares case, for tags where it carries meaning
+    /// (`PLC.IO.Bit_01` vs `PLC.IO.bit_01`). Toggled with Ctrl+C in search.
+    pub search_case_sensitive: bool,
     /// Indices into `self.tags` that match the current query.
     pub search_matches: Vec<usize>,
     /// Current position within `search_matches` (cycles).
     pub search_match_index: usize,
+    /// Earlier search queries, newest first, at most [`SEARCH_HISTORY_LEN`].
+    pub search_history: VecDeque<String>,
+    /// Entry of `search_history` shown in the query while Up/Down browse
+    /// it; `None` while typing.
+    pub search_history_index: Option<usize>,
+    /// Query being typed when Up started browsing the history, restored by
+    /// Down past the newest entry.
+    pub search_draft: String,
+    /// Where `search_history` is saved when a search ends; `None` keeps it
+    /// in memory only.
+    pub search_history_path: Option<PathBuf>,
 
     /// The tag currently being edited for writing.
     pub write_tag_id: Option<String>,
     /// User-entered value string for writing.
     pub write_value_input: String,
+    /// Multi-line write editor (`Ctrl+E`): Enter inserts a newline and the
+    /// input is submitted as JSON, so arrays can be written.
+    pub write_multiline: bool,
+    /// Receiver for a re-read of the failed tags (`r` on Tag Values).
+    pub retry_result_rx: Option<oneshot::Receiver<Result<Vec<TagValue>, OpcError>>>,
+    /// Correlation ID of the retry behind `retry_result_rx`.
+    pub retry_op_id: Option<OpId>,
     /// Receiver for background write result.
     pub write_result_rx: Option<oneshot::Receiver<Result<WriteResult, OpcError>>>,
+    /// Correlation ID of the running single or bulk write.
+    pub write_op_id: Option<OpId>,
+    /// Error from the last failed write, shown on the write screen until dismissed.
+    pub write_error: Option<String>,
+    /// Tags a write started from the Tag List (`w`) goes to; the value is
+    /// written to all of them in one batch. Empty for single-tag writes.
+    pub bulk_write_tags: Vec<String>,
+    /// Receiver for the per-tag results of a bulk write.
+    pub bulk_write_rx: Option<oneshot::Receiver<Result<Vec<WriteResult>, OpcError>>>,
+    /// In-place edit of a Tag Values cell: `(row, value, cursor)`, with the
+    /// cursor counted in characters.
+    pub inline_edit: Option<(usize, String, usize)>,
     /// The server `ProgID` that was used for the current tag browse.
     pub browsed_server: Option<String>,
+    /// Measured round-trip latency per entry in `servers` (`None` = not measured or timed out).
+    pub server_latencies: Vec<Option<std::time::Duration>>,
+    /// Whether `servers` is currently ordered by ascending latency.
+    pub servers_sorted_by_latency: bool,
+    /// Receiver for the connect time of each server, measured by `l`.
+    pub server_latency_rx: Option<oneshot::Receiver<Vec<(String, Option<std::time::Duration>)>>>,
+    /// When the last server-list fetch was started (for debouncing).
+    pub last_fetch_start: Option<std::time::Instant>,
+    /// A fetch requested inside the debounce window, fired once the window expires.
+    pub pending_fetch: bool,
+    /// Set when viewing a loaded snapshot: the time the snapshot was taken.
+    ///
+    /// While set, the app never talks to the server and writes are disabled.
+    pub offline_snapshot: Option<String>,
+    /// Where this run's session log is written; `None` disables it.
+    pub session_log_path: Option<PathBuf>,
+    /// Append handle for the session log, opened on the first message.
+    pub session_log: Option<File>,
+    /// Whether read results are being recorded (`Ctrl+R`).
+    pub record_mode: bool,
+    /// Writes each read result to the recording while `record_mode` is on.
+    pub recorder: Option<Recorder>,
+    /// Directory new recordings are created in.
+    pub recordings_dir: PathBuf,
+    /// Whether auto-refresh reads the cache of a persistent server-side group.
+    pub live_mode: bool,
+    /// Whether the Tag Values table shows the VARIANT type column.
+    pub show_vt_column: bool,
+    /// Whether the tag list and Tag Values show only the last segment of
+    /// each tag ID. Reads, writes and exports still use the full ID.
+    pub show_leaf_names: bool,
+    /// Whether values are shown with their engineering units and range.
+    pub show_engineering_units: bool,
+    /// Engineering units per `(server, tag ID)`; `None` for tags that have none.
+    pub engineering_units: HashMap<(String, String), Option<EngineeringUnits>>,
+    /// Receiver for engineering units being fetched for the displayed tags.
+    pub engineering_units_rx: Option<oneshot::Receiver<Result<EngineeringUnitsBatch, OpcError>>>,
+    /// Receiver for the registration checklist gathered after a connection
+    /// failed with a COM error.
+    pub diagnostics_rx: Option<oneshot::Receiver<Result<ServerMetadata, OpcError>>>,
+    /// Accept `,` as the decimal separator in typed write values (`3,5` is
+    /// written as 3.5 instead of the text `"3,5"`).
+    pub decimal_comma: bool,
+    /// Step used by `+`/`-` to nudge a numeric value.
+    pub increment_step: f64,
+    /// Step used by `Alt` + `+`/`-` to nudge a numeric value.
+    pub large_increment_step: f64,
+    /// Server-side access-rights filter applied when browsing tags.
+    pub browse_filter: AccessRightsFilter,
+    /// Colours used when rendering.
+    pub theme: Theme,
+    /// The open command palette, while `current_screen` is
+    /// [`CurrentScreen::CommandPalette`].
+    pub command_palette: Option<CommandPalette>,
+    /// Screen the help was opened from, whose keys it lists and which
+    /// `go_back` returns to.
+    pub help_from_screen: CurrentScreen,
+    /// Lines the help text is scrolled down by.
+    pub help_scroll: u16,
+    /// Number of requests queued on the OPC worker, shared with the worker;
+    /// `None` when the provider does not expose it.
+    pub worker_queue_depth: Option<Arc<AtomicUsize>>,
+    /// Whether the queue-nearly-full warning was shown for the current backlog.
+    pub queue_warning_shown: bool,
+    /// Whether auto-refresh is paused until the worker queue drains.
+    pub refresh_paused: bool,
+    /// The OPC worker, pinged every [`WORKER_PING_INTERVAL`] as a heartbeat;
+    /// `None` when the provider has no COM worker.
+    pub worker_heartbeat: Option<Arc<ComWorker<ComConnector>>>,
+    /// When the worker was last pinged.
+    pub last_worker_ping: Option<std::time::Instant>,
+    /// Receiver for the answer to the ping in flight.
+    pub worker_ping_rx: Option<oneshot::Receiver<bool>>,
+    /// Whether the last ping went unanswered.
+    pub worker_unresponsive: bool,
+    /// Set from outside the event loop when the process is asked to stop
+    /// (console closed, SIGTERM, SSH session dropped).
+    pub shutdown_requested: Arc<AtomicBool>,
+    /// Servers open at once (up to [`MAX_SESSIONS`]), created by each browse.
+    pub active_sessions: Vec<ServerSession>,
+    /// Index into `active_sessions` of the session shown on screen.
+    pub current_session: usize,
+    /// Set while picking another server to open alongside the current ones.
+    pub opening_session: bool,
 }
 
 impl App {
@@ -81,52 +486,359 @@ impl App {
     pub fn new(opc_provider: Arc<dyn OpcProvider>) -> Self {
         Self {
             host_input: "localhost".into(),
+            host_error: None,
             servers: Vec::new(),
+            server_host: None,
             tags: Vec::new(),
             selected_index: None,
             current_screen: CurrentScreen::Home,
+            screen_stack: vec![CurrentScreen::Home],
             opc_provider,
             messages: Vec::new(),
+            last_message_count: 0,
             list_state: ListState::default(),
             table_state: TableState::default(), // Initialize
             browse_progress: Arc::new(AtomicUsize::new(0)),
+            estimated_total_tags: None,
+            browse_estimates: BrowseEstimates::default(),
+            browse_estimates_path: None,
+            browse_depth: Arc::new(AtomicUsize::new(0)),
+            browse_depth_limit_hit: Arc::new(AtomicUsize::new(0)),
+            browse_tags_sink: Arc::new(std::sync::Mutex::new(Some(Vec::new()))),
             browse_result_rx: None,
+            browse_op_id: None,
+            public_group_browse: false,
             fetch_result_rx: None,
+            fetch_op_id: None,
+            probe_dcom: false,
+            dcom_probe: connectivity::probe_dcom_port_owned,
+            dcom_probe_rx: None,
             selected_tags: Vec::new(),
             tag_values: Vec::new(),
             read_result_rx: None,
+            read_op_id: None,
+            read_progress: Arc::new(AtomicUsize::new(0)),
+            read_total: 0,
+            read_batch_size: DEFAULT_READ_BATCH_SIZE,
+            read_chunks_done: Arc::new(AtomicUsize::new(0)),
+            read_chunk_total: 0,
             refresh_server: None,
             refresh_tag_ids: Vec::new(),
             last_read_time: None,
+            consecutive_read_errors: 0,
+            auto_reconnect: true,
+            reconnect_tag_ids: Vec::new(),
+            change_correlation: HashMap::new(),
+            change_counts: Vec::new(),
+            loading_tick: 0,
+            loading_since: None,
+            last_server_activity: None,
+            last_operation_failed: false,
+            stale_after: Some(std::time::Duration::from_secs(DEFAULT_STALE_AFTER_SECS)),
+            browse_time: None,
+            browse_partial: None,
+            export_path_input: None,
+            tag_columns: 1,
+            tag_column_offset: 0,
             search_mode: false,
             search_query: String::new(),
+            search_case_sensitive: false,
             search_matches: Vec::new(),
             search_match_index: 0,
+            search_history: VecDeque::new(),
+            search_history_index: None,
+            search_draft: String::new(),
+            search_history_path: None,
 
             write_tag_id: None,
             write_value_input: String::new(),
+            write_multiline: false,
+            retry_result_rx: None,
+            retry_op_id: None,
             write_result_rx: None,
+            write_op_id: None,
+            bulk_write_tags: Vec::new(),
+            bulk_write_rx: None,
+            write_error: None,
+            inline_edit: None,
             browsed_server: None,
+            server_latencies: Vec::new(),
+            servers_sorted_by_latency: false,
+            server_latency_rx: None,
+            last_fetch_start: None,
+            pending_fetch: false,
+            offline_snapshot: None,
+            session_log_path: None,
+            session_log: None,
+            record_mode: false,
+            recorder: None,
+            recordings_dir: PathBuf::from(recording::RECORDINGS_DIR),
+            live_mode: false,
+            show_vt_column: false,
+            show_leaf_names: false,
+            show_engineering_units: false,
+            engineering_units: HashMap::new(),
+            engineering_units_rx: None,
+            diagnostics_rx: None,
+            decimal_comma: false,
+            increment_step: DEFAULT_INCREMENT_STEP,
+            large_increment_step: DEFAULT_LARGE_INCREMENT_STEP,
+            browse_filter: AccessRightsFilter::Any,
+            theme: Theme::default(),
+            command_palette: None,
+            help_from_screen: CurrentScreen::Home,
+            help_scroll: 0,
+            worker_queue_depth: None,
+            queue_warning_shown: false,
+            refresh_paused: false,
+            worker_heartbeat: None,
+            last_worker_ping: None,
+            worker_ping_rx: None,
+            worker_unresponsive: false,
+            shutdown_requested: Arc::new(AtomicBool::new(false)),
+            active_sessions: Vec::new(),
+            current_session: 0,
+            opening_session: false,
+        }
+    }
+
+    /// Populate the Tag Values screen from a snapshot file without connecting.
+    pub fn load_from_snapshot(&mut self, path: &std::path::Path) -> anyhow::Result<()> {
+        let snapshot = ServerSnapshot::load(path)?;
+        tracing::info!(
+            path = %path.display(),
+            server = %snapshot.server,
+            count = snapshot.values.len(),
+            taken_at = %snapshot.taken_at,
+            "load_from_snapshot"
+        );
+
+        self.tag_values = snapshot.values.into_iter().map(TagValue::from).collect();
+        self.refresh_tag_ids = self.tag_values.iter().map(|tv| tv.tag_id.clone()).collect();
+        self.tags = self
+            .refresh_tag_ids
+            .iter()
+            .map(|id| BrowsedTag::from(id.as_str()))
+            .collect();
+        self.selected_tags = vec![true; self.tags.len()];
+        self.refresh_server = Some(snapshot.server.clone());
+        self.browsed_server = Some(snapshot.server);
+        self.last_read_time = None;
+        self.offline_snapshot = Some(snapshot.taken_at);
+
+        self.reset_screen_stack(CurrentScreen::TagValues);
+        let first = if self.tag_values.is_empty() {
+            None
+        } else {
+            Some(0)
+        };
+        self.selected_index = first;
+        self.table_state.select(first);
+        self.add_message(format!(
+            "Loaded {} values from snapshot {}",
+            self.tag_values.len(),
+            path.display()
+        ));
+        Ok(())
+    }
+
+    /// Banner shown while viewing a snapshot, e.g. `[OFFLINE — snapshot from ...]`.
+    pub fn offline_banner(&self) -> Option<String> {
+        self.offline_snapshot
+            .as_ref()
+            .map(|taken_at| format!("[OFFLINE — snapshot from {taken_at}]"))
+    }
+
+    /// Banner shown once the app has been idle for longer than
+    /// [`stale_after`](Self::stale_after), so the reconnect delay of the next
+    /// operation is not mistaken for a hang.
+    pub fn stale_connection_banner(&self) -> Option<&'static str> {
+        let idle = self.last_server_activity?.elapsed();
+        let stale =
+            self.offline_snapshot.is_none() && self.stale_after.is_some_and(|limit| idle >= limit);
+        stale.then_some("connection may be stale — next action will reconnect")
+    }
+
+    /// Whether the app is waiting on the server: the Loading screen is up or
+    /// a server list or browse is in flight.
+    pub fn is_loading(&self) -> bool {
+        self.current_screen == CurrentScreen::Loading
+            || self.fetch_result_rx.is_some()
+            || self.dcom_probe_rx.is_some()
+            || self.browse_result_rx.is_some()
+    }
+
+    /// Advance the spinner and track how long the current wait has lasted.
+    ///
+    /// Called once per main-loop iteration.
+    pub fn tick_loading(&mut self) {
+        self.loading_tick = self.loading_tick.wrapping_add(1);
+        if self.is_loading() {
+            self.loading_since
+                .get_or_insert_with(std::time::Instant::now);
+        } else {
+            self.loading_since = None;
+        }
+    }
+
+    /// Record that the server just answered an operation.
+    fn mark_server_activity(&mut self) {
+        self.last_server_activity = Some(std::time::Instant::now());
+        self.last_operation_failed = false;
+    }
+
+    /// Record that an operation on the server failed as a whole.
+    fn mark_server_error(&mut self) {
+        self.last_operation_failed = true;
+    }
+
+    /// State of the link to the current session's server, from the outcome
+    /// of its last operation, the reconnect logic and the worker heartbeat.
+    pub fn connection_state(&self) -> ConnectionState {
+        if self.offline_snapshot.is_some() {
+            ConnectionState::Idle
+        } else if !self.reconnect_tag_ids.is_empty() || self.consecutive_read_errors > 0 {
+            ConnectionState::Reconnecting
+        } else if self.last_operation_failed || self.worker_unresponsive {
+            ConnectionState::Disconnected
+        } else if self.last_server_activity.is_some() && self.stale_connection_banner().is_none() {
+            ConnectionState::Connected
+        } else {
+            ConnectionState::Idle
         }
     }
 
+    /// Reject an operation that needs the server while viewing a snapshot.
+    fn reject_if_offline(&mut self, action: &str) -> bool {
+        if self.offline_snapshot.is_some() {
+            self.add_message(format!("Offline mode: {action} is disabled"));
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Push a status message, keeping the last 10.
+    ///
+    /// A message identical to the previous one replaces it with a `[×N]`
+    /// repeat count instead, so a recurring error cannot flood the log. The
+    /// operation IDs of the two are ignored for the comparison, and the
+    /// newest one is kept.
     pub fn add_message(&mut self, message: String) {
+        self.append_session_log(&message);
+        let count = self.last_message_count;
+        let repeats_last = self.messages.last().is_some_and(|last| {
+            let base = last.strip_suffix(&format!(" [×{count}]")).unwrap_or(last);
+            without_op_suffix(base) == without_op_suffix(&message)
+        });
+        if repeats_last && let Some(last) = self.messages.last_mut() {
+            self.last_message_count += 1;
+            *last = format!("{message} [×{}]", self.last_message_count);
+            return;
+        }
+        self.last_message_count = 1;
         self.messages.push(message);
         if self.messages.len() > 10 {
             self.messages.remove(0);
         }
     }
 
+    /// Mirror a status message to the session log, opening it on first use.
+    ///
+    /// Logging failures are traced and then disable the session log; they
+    /// never interrupt the TUI.
+    fn append_session_log(&mut self, message: &str) {
+        if self.session_log.is_none() {
+            let Some(path) = &self.session_log_path else {
+                return;
+            };
+            if let Some(parent) = path.parent() {
+                let _ = std::fs::create_dir_all(parent);
+            }
+            match std::fs::OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(path)
+            {
+                Ok(file) => self.session_log = Some(file),
+                Err(e) => {
+                    tracing::warn!(error = %e, path = %path.display(), "Failed to open session log");
+                    self.session_log_path = None;
+                    return;
+                }
+            }
+        }
+
+        if let Some(file) = &mut self.session_log
+            && let Err(e) = writeln!(file, "{}", crate::session_log::format_line(message))
+        {
+            tracing::warn!(error = %e, "Failed to write session log");
+            self.session_log = None;
+            self.session_log_path = None;
+        }
+    }
+
+    fn fetch_debounce_active(&self) -> bool {
+        self.last_fetch_start
+            .is_some_and(|t| t.elapsed() < FETCH_DEBOUNCE)
+    }
+
     // Actions
     pub fn start_fetch_servers(&mut self) {
+        if let Err(e) = validate_host(&self.host_input) {
+            tracing::debug!(host = %self.host_input, error = %e, "start_fetch_servers: invalid host");
+            self.host_error = Some(e);
+            return;
+        }
+        self.host_error = None;
+        // Connecting to a server leaves the loaded snapshot behind
+        self.offline_snapshot = None;
+
+        if self.fetch_debounce_active() {
+            if self.fetch_result_rx.is_some() || self.dcom_probe_rx.is_some() {
+                tracing::debug!("start_fetch_servers: fetch already in flight, ignoring");
+            } else {
+                tracing::debug!("start_fetch_servers: debounced, queuing fetch");
+                self.pending_fetch = true;
+            }
+            return;
+        }
+
+        self.pending_fetch = false;
+        self.last_fetch_start = Some(std::time::Instant::now());
         let host = self.host_input.clone();
+
+        if self.probe_dcom && !connectivity::is_local_host(&host) {
+            self.add_message(format!(
+                "Checking {host} port {}...",
+                connectivity::DCOM_PORT
+            ));
+            let (tx, rx) = oneshot::channel();
+            let probe = (self.dcom_probe)(host.clone());
+            tokio::spawn(async move {
+                let reachable = probe.await;
+                let _ = tx.send((host, reachable));
+            });
+            self.dcom_probe_rx = Some(rx);
+            return;
+        }
+        self.spawn_list_servers(host);
+    }
+
+    /// Enter the Loading screen and list the servers on `host` in the background.
+    fn spawn_list_servers(&mut self, host: String) {
+        let op_id = OpId::start("list_servers");
+        let _entered = op_id.enter();
         self.current_screen = CurrentScreen::Loading;
-        self.add_message(format!("Connecting to {host}..."));
+        self.add_message(format!(
+            "Connecting to {host}...{}",
+            op_suffix(Some(&op_id))
+        ));
 
         let provider = Arc::clone(&self.opc_provider);
         let (tx, rx) = oneshot::channel();
 
-        tokio::spawn(async move {
+        let task = async move {
             let result = tokio::time::timeout(
                 std::time::Duration::from_secs(OPC_TIMEOUT_SECS),
                 provider.list_servers(&host),
@@ -141,17 +853,58 @@ impl App {
             });
 
             let _ = tx.send(final_result);
-        });
+        };
+        tokio::spawn(task.instrument(op_id.span().clone()));
 
         self.fetch_result_rx = Some(rx);
+        self.fetch_op_id = Some(op_id);
     }
 
     pub fn poll_fetch_result(&mut self) {
+        if self.pending_fetch
+            && self.fetch_result_rx.is_none()
+            && self.dcom_probe_rx.is_none()
+            && !self.fetch_debounce_active()
+        {
+            self.start_fetch_servers();
+        }
+
+        if let Some(rx) = &mut self.dcom_probe_rx {
+            match rx.try_recv() {
+                Err(oneshot::error::TryRecvError::Empty) => {}
+                Ok((host, false)) => {
+                    self.dcom_probe_rx = None;
+                    let msg = format!(
+                        "Port {} unreachable — check firewall",
+                        connectivity::DCOM_PORT
+                    );
+                    tracing::warn!(host = %host, "{msg}");
+                    self.add_message(format!("{host}: {msg}"));
+                    self.host_error = Some(msg);
+                }
+                Ok((host, true)) => {
+                    self.dcom_probe_rx = None;
+                    self.spawn_list_servers(host);
+                }
+                // A lost probe proves nothing, so carry on as if it passed
+                Err(oneshot::error::TryRecvError::Closed) => {
+                    self.dcom_probe_rx = None;
+                    self.spawn_list_servers(self.host_input.clone());
+                }
+            }
+        }
+
         if let Some(rx) = &mut self.fetch_result_rx {
+            let _entered = self.fetch_op_id.as_ref().map(OpId::enter);
             match rx.try_recv() {
                 Ok(Ok(servers)) => {
+                    self.mark_server_activity();
                     self.servers = servers;
-                    self.current_screen = CurrentScreen::ServerList;
+                    self.server_host = Some(normalize_host(&self.host_input));
+                    self.server_latencies = vec![None; self.servers.len()];
+                    self.servers_sorted_by_latency = false;
+                    self.server_latency_rx = None;
+                    self.push_screen(CurrentScreen::ServerList);
                     if self.servers.is_empty() {
                         self.selected_index = None;
                         self.list_state.select(None);
@@ -167,16 +920,27 @@ impl App {
                     self.fetch_result_rx = None;
                 }
                 Ok(Err(e)) => {
-                    self.current_screen = CurrentScreen::Home;
+                    self.push_screen(CurrentScreen::Home);
+                    self.mark_server_error();
                     tracing::error!(error = %e, "Failed to fetch servers");
-                    self.add_message(format!("Error fetching servers: {e}"));
+                    self.add_message(format!(
+                        "Error fetching servers: {e}{}",
+                        op_suffix(self.fetch_op_id.as_ref())
+                    ));
+                    if let Some(hint) = local_host_hint(&self.host_input, &e) {
+                        self.add_message(format!("Hint: {hint}"));
+                    }
+                    // Only the local registry can be inspected
+                    if connectivity::is_local_host(&self.host_input) {
+                        self.start_connection_diagnostics(OPC_ENUM_PROG_ID.to_string(), &e);
+                    }
                     self.fetch_result_rx = None;
                 }
                 Err(oneshot::error::TryRecvError::Empty) => {
                     // Still running
                 }
                 Err(oneshot::error::TryRecvError::Closed) => {
-                    self.current_screen = CurrentScreen::Home;
+                    self.push_screen(CurrentScreen::Home);
                     tracing::error!(
                         "Server listing background task terminated unexpectedly (sender dropped)"
                     );
@@ -185,15 +949,54 @@ impl App {
                 }
             }
         }
+        self.clamp_selection();
     }
 
-    pub fn select_next(&mut self) {
-        let count = match self.current_screen {
+    /// Length of the list shown on the current screen (0 off list screens).
+    fn current_list_len(&self) -> usize {
+        match self.current_screen {
             CurrentScreen::ServerList => self.servers.len(),
             CurrentScreen::TagList => self.tags.len(),
             CurrentScreen::TagValues => self.tag_values.len(),
             _ => 0,
+        }
+    }
+
+    /// Pull `selected_index` back inside the current list.
+    ///
+    /// The list under the cursor can shrink between frames (a refresh returning
+    /// fewer values, a re-browse), so every navigation method calls this before
+    /// indexing and every poll that replaces list data calls it afterwards.
+    /// Screens without a list are left alone so the cursor survives a detour
+    /// through, say, the write prompt.
+    pub fn clamp_selection(&mut self) {
+        if !matches!(
+            self.current_screen,
+            CurrentScreen::ServerList | CurrentScreen::TagList | CurrentScreen::TagValues
+        ) {
+            return;
+        }
+        let Some(idx) = self.selected_index else {
+            return;
         };
+        let clamped = self
+            .current_list_len()
+            .checked_sub(1)
+            .map(|last| idx.min(last));
+        if clamped == Some(idx) {
+            return;
+        }
+        tracing::debug!(from = idx, to = ?clamped, "clamp_selection");
+        self.selected_index = clamped;
+        self.list_state.select(clamped);
+        if self.current_screen == CurrentScreen::TagValues {
+            self.table_state.select(clamped);
+        }
+    }
+
+    pub fn select_next(&mut self) {
+        self.clamp_selection();
+        let count = self.current_list_len();
 
         if count == 0 {
             return;
@@ -215,6 +1018,7 @@ impl App {
     }
 
     pub fn select_prev(&mut self) {
+        self.clamp_selection();
         if let Some(idx) = self.selected_index
             && idx > 0
         {
@@ -227,20 +1031,27 @@ impl App {
         }
     }
 
+    /// Items skipped by [`page_down`](Self::page_down) and
+    /// [`page_up`](Self::page_up): a page of every column on the tag list.
+    fn page_size(&self) -> usize {
+        const PAGE_SIZE: usize = 20;
+        if self.current_screen == CurrentScreen::TagList {
+            PAGE_SIZE * usize::from(self.tag_columns)
+        } else {
+            PAGE_SIZE
+        }
+    }
+
     /// Jump forward by PAGE_SIZE items (clamped to end of list).
     pub fn page_down(&mut self) {
-        let count = match self.current_screen {
-            CurrentScreen::ServerList => self.servers.len(),
-            CurrentScreen::TagList => self.tags.len(),
-            CurrentScreen::TagValues => self.tag_values.len(),
-            _ => 0,
-        };
+        self.clamp_selection();
+        let count = self.current_list_len();
 
         if count == 0 {
             return;
         }
 
-        let page_size = 20;
+        let page_size = self.page_size();
         if let Some(idx) = self.selected_index {
             let new_idx = (idx + page_size).min(count - 1);
             self.selected_index = Some(new_idx);
@@ -259,7 +1070,8 @@ impl App {
 
     /// Jump backward by PAGE_SIZE items (clamped to start of list).
     pub fn page_up(&mut self) {
-        let page_size = 20;
+        self.clamp_selection();
+        let page_size = self.page_size();
         if let Some(idx) = self.selected_index {
             let new_idx = idx.saturating_sub(page_size);
             self.selected_index = Some(new_idx);
@@ -276,6 +1088,180 @@ impl App {
         }
     }
 
+    /// Cycle the tag list through 1 to [`MAX_TAG_COLUMNS`] columns (`Ctrl+W`).
+    pub fn cycle_tag_columns(&mut self) {
+        self.tag_columns = self.tag_columns % MAX_TAG_COLUMNS + 1;
+        self.tag_column_offset = 0;
+        tracing::debug!(columns = self.tag_columns, "cycle_tag_columns");
+    }
+
+    /// Rows in each column of the tag list; the last column may be shorter.
+    pub fn tag_column_rows(&self) -> usize {
+        self.tags
+            .len()
+            .div_ceil(usize::from(self.tag_columns.max(1)))
+            .max(1)
+    }
+
+    /// Scroll the multi-column tag list so the selected row is among the
+    /// `visible` rows on screen; all columns scroll together.
+    pub fn scroll_tag_columns(&mut self, visible: usize) {
+        let visible = visible.max(1);
+        let rows = self.tag_column_rows();
+        if let Some(row) = self.selected_index.map(|idx| idx % rows) {
+            if row < self.tag_column_offset {
+                self.tag_column_offset = row;
+            } else if row >= self.tag_column_offset + visible {
+                self.tag_column_offset = row + 1 - visible;
+            }
+        }
+        self.tag_column_offset = self.tag_column_offset.min(rows.saturating_sub(visible));
+    }
+
+    /// Move to the same row of the next tag list column (`Right`).
+    ///
+    /// From the last column the selection wraps to the next row of the first.
+    pub fn select_next_column(&mut self) {
+        if self.current_screen != CurrentScreen::TagList || self.tag_columns < 2 {
+            return;
+        }
+        self.clamp_selection();
+        let rows = self.tag_column_rows();
+        let target = match self.selected_index {
+            None => Some(0),
+            Some(idx) if idx + rows < self.tags.len() => Some(idx + rows),
+            Some(idx) => Some(idx % rows + 1).filter(|&row| row < rows),
+        };
+        if let Some(idx) = target.filter(|&idx| idx < self.tags.len()) {
+            self.selected_index = Some(idx);
+            self.list_state.select(Some(idx));
+        }
+    }
+
+    /// Move to the same row of the previous tag list column (`Left`).
+    ///
+    /// From the first column the selection wraps to the previous row of the
+    /// last column that has one.
+    pub fn select_prev_column(&mut self) {
+        if self.current_screen != CurrentScreen::TagList || self.tag_columns < 2 {
+            return;
+        }
+        self.clamp_selection();
+        let rows = self.tag_column_rows();
+        let target = match self.selected_index {
+            Some(idx) if idx >= rows => Some(idx - rows),
+            Some(idx) if idx > 0 => {
+                let row = idx - 1;
+                let last_column = (self.tags.len() - 1 - row) / rows;
+                Some(last_column * rows + row)
+            }
+            _ => None,
+        };
+        if let Some(idx) = target {
+            self.selected_index = Some(idx);
+            self.list_state.select(Some(idx));
+        }
+    }
+
+    /// Measure how long each listed server takes to connect, then sort the
+    /// list by it.
+    ///
+    /// Servers are connected one after another through
+    /// [`OpcProvider::get_server_metadata`]; one that fails or takes longer
+    /// than [`LATENCY_PROBE_TIMEOUT`] is left unmeasured.
+    pub fn measure_server_latencies(&mut self) {
+        if self.current_screen != CurrentScreen::ServerList
+            || self.servers.is_empty()
+            || self.server_latency_rx.is_some()
+        {
+            return;
+        }
+
+        self.add_message(format!(
+            "Measuring connect latency of {} servers...",
+            self.servers.len()
+        ));
+        let provider = Arc::clone(&self.opc_provider);
+        let servers = self.servers.clone();
+        let (tx, rx) = oneshot::channel();
+        tokio::spawn(async move {
+            let mut latencies = Vec::with_capacity(servers.len());
+            for server in servers {
+                let started = tokio::time::Instant::now();
+                let latency = match tokio::time::timeout(
+                    LATENCY_PROBE_TIMEOUT,
+                    provider.get_server_metadata(&server),
+                )
+                .await
+                {
+                    Ok(Ok(_)) => Some(started.elapsed()),
+                    Ok(Err(e)) => {
+                        tracing::debug!(server = %server, error = %e, "Latency probe failed");
+                        None
+                    }
+                    Err(_) => None,
+                };
+                latencies.push((server, latency));
+            }
+            let _ = tx.send(latencies);
+        });
+        self.server_latency_rx = Some(rx);
+    }
+
+    /// Check if the latency measurement has completed and, if so, sort the
+    /// server list by it.
+    pub fn poll_server_latencies(&mut self) {
+        let Some(rx) = &mut self.server_latency_rx else {
+            return;
+        };
+        let measured = match rx.try_recv() {
+            Ok(measured) => measured,
+            Err(oneshot::error::TryRecvError::Empty) => return,
+            Err(oneshot::error::TryRecvError::Closed) => {
+                self.server_latency_rx = None;
+                return;
+            }
+        };
+        self.server_latency_rx = None;
+
+        let by_server: HashMap<_, _> = measured.into_iter().collect();
+        self.server_latencies = self
+            .servers
+            .iter()
+            .map(|server| by_server.get(server).copied().flatten())
+            .collect();
+        let count = self.server_latencies.iter().flatten().count();
+        self.add_message(format!(
+            "Measured latency of {count}/{} servers",
+            self.servers.len()
+        ));
+        self.sort_servers_by_latency();
+    }
+
+    /// Reorder `servers` (and `server_latencies` alongside) by ascending latency.
+    ///
+    /// Servers with a measured latency come first, fastest at the top; servers
+    /// without one (unmeasured or timed out) keep their relative order at the end.
+    pub fn sort_servers_by_latency(&mut self) {
+        if self.current_screen != CurrentScreen::ServerList || self.servers.is_empty() {
+            return;
+        }
+
+        self.server_latencies.resize(self.servers.len(), None);
+        let mut paired: Vec<(String, Option<std::time::Duration>)> = self
+            .servers
+            .drain(..)
+            .zip(self.server_latencies.drain(..))
+            .collect();
+        paired.sort_by_key(|(_, latency)| (latency.is_none(), *latency));
+        (self.servers, self.server_latencies) = paired.into_iter().unzip();
+
+        self.servers_sorted_by_latency = true;
+        self.selected_index = Some(0);
+        self.list_state.select(Some(0));
+        tracing::debug!(count = self.servers.len(), "sort_servers_by_latency");
+    }
+
     pub fn start_browse_tags(&mut self) {
         if self.current_screen != CurrentScreen::ServerList {
             return;
@@ -289,105 +1275,322 @@ impl App {
             Some(s) => s.clone(),
             None => return,
         };
+        self.browse_server(server);
+    }
 
+    /// Start browsing the namespace of `server`, showing the loading screen.
+    fn browse_server(&mut self, server: String) {
+        let op_id = OpId::start("browse_tags");
+        let _entered = op_id.enter();
+        let op = op_suffix(Some(&op_id));
         self.browsed_server = Some(server.clone());
+        self.offline_snapshot = None;
 
         self.current_screen = CurrentScreen::Loading;
+        self.public_group_browse = false;
         self.browse_progress = Arc::new(AtomicUsize::new(0));
-        self.add_message(format!("Browsing tags on {server}..."));
+        self.estimated_total_tags = self
+            .browse_estimates
+            .get(&self.browse_estimate_key(&server));
+        self.browse_depth = Arc::new(AtomicUsize::new(0));
+        self.browse_depth_limit_hit = Arc::new(AtomicUsize::new(0));
+        if self.browse_filter == AccessRightsFilter::Any {
+            self.add_message(format!("Browsing tags on {server}...{op}"));
+        } else {
+            self.add_message(format!(
+                "Browsing {} tags on {server}...{op}",
+                self.browse_filter
+            ));
+        }
 
         let provider = Arc::clone(&self.opc_provider);
-        let progress = Arc::clone(&self.browse_progress);
-        let tags_sink = Arc::new(std::sync::Mutex::new(Vec::new()));
-        let sink_for_task = Arc::clone(&tags_sink);
+        let access = self.browse_filter;
+        self.browse_tags_sink = Arc::new(std::sync::Mutex::new(Some(Vec::new())));
+        let tags_sink = Arc::clone(&self.browse_tags_sink);
+        let progress = BrowseProgress {
+            tags_found: Arc::clone(&self.browse_progress),
+            depth: Arc::clone(&self.browse_depth),
+            depth_limit_hit: Arc::clone(&self.browse_depth_limit_hit),
+            tags_sink: Arc::clone(&tags_sink),
+        };
 
         let (tx, rx) = oneshot::channel();
 
-        tokio::spawn(async move {
+        let task = async move {
             let timeout_duration = std::time::Duration::from_secs(OPC_TIMEOUT_SECS);
             let result = tokio::time::timeout(
                 timeout_duration,
-                provider.browse_tags(&server, MAX_BROWSE_TAGS, progress, sink_for_task),
+                provider.browse_tags(&server, MAX_BROWSE_TAGS, access, progress),
             )
             .await;
 
             let final_result = match result {
-                Ok(inner) => inner,
-                Err(_) => {
-                    // Timeout occurred. Harvest partial results from sink.
-                    let partial_tags = if let Ok(sink) = tags_sink.lock() {
-                        sink.clone()
-                    } else {
-                        Vec::new()
-                    };
-
-                    if !partial_tags.is_empty() {
-                        tracing::warn!(
-                            server = %server,
-                            count = partial_tags.len(),
-                            timeout_secs = OPC_TIMEOUT_SECS,
-                            "Browse tags timed out; returning partial results"
-                        );
-                        Ok(partial_tags)
-                    } else {
-                        tracing::error!(
-                            server = %server,
-                            timeout_secs = OPC_TIMEOUT_SECS,
-                            "Browse tags timed out with zero tags found"
-                        );
-                        Err(OpcError::Internal(format!(
-                            "Browse timed out ({OPC_TIMEOUT_SECS}s) for '{server}' with no tags found"
-                        )))
-                    }
-                }
+                Ok(inner) => inner.map(BrowseResult::from),
+                Err(_) => harvest_timed_out_browse(&tags_sink, &server),
             };
 
             let _ = tx.send(final_result);
-        });
+        };
+        tokio::spawn(task.instrument(op_id.span().clone()));
 
         self.browse_result_rx = Some(rx);
+        self.browse_op_id = Some(op_id);
     }
 
-    pub fn poll_browse_result(&mut self) {
-        if let Some(rx) = &mut self.browse_result_rx {
-            match rx.try_recv() {
-                Ok(Ok(tags)) => {
-                    self.tags = tags;
-                    self.selected_tags = vec![false; self.tags.len()];
-                    self.current_screen = CurrentScreen::TagList;
-                    if self.tags.is_empty() {
-                        self.selected_index = None;
-                        self.list_state.select(None);
-                    } else {
-                        self.selected_index = Some(0);
-                        self.list_state.select(Some(0));
-                    }
-                    self.add_message(format!("Found {} tags", self.tags.len()));
-                    self.browse_result_rx = None;
-                }
-                Ok(Err(e)) => {
-                    self.current_screen = CurrentScreen::ServerList;
-                    tracing::error!(error = %e, error_chain = ?e, "Browse tags failed");
-                    let hint = friendly_com_hint(&e);
-                    let msg = match hint {
-                        Some(h) => format!("Error: {} ({})", h, e),
-                        None => format!("Error: {:#}", e),
-                    };
-                    self.add_message(msg);
-                    self.browse_result_rx = None;
-                }
-                Err(oneshot::error::TryRecvError::Empty) => {
-                    // Still running
-                }
-                Err(oneshot::error::TryRecvError::Closed) => {
-                    self.current_screen = CurrentScreen::ServerList;
-                    tracing::error!(
-                        "Browse background task terminated unexpectedly (sender dropped)"
-                    );
-                    self.add_message("Browse task terminated unexpectedly".into());
+    /// Browse the items of the selected server's public groups instead of
+    /// its namespace. Each item is listed with its group as the branch.
+    pub fn start_browse_public_groups(&mut self) {
+        if self.current_screen != CurrentScreen::ServerList {
+            return;
+        }
+        let Some(server) = self
+            .selected_index
+            .and_then(|idx| self.servers.get(idx))
+            .cloned()
+        else {
+            return;
+        };
+
+        let op_id = OpId::start("browse_public_groups");
+        let _entered = op_id.enter();
+        self.browsed_server = Some(server.clone());
+        self.current_screen = CurrentScreen::Loading;
+        self.public_group_browse = true;
+        self.browse_progress = Arc::new(AtomicUsize::new(0));
+        self.estimated_total_tags = None;
+        self.browse_depth = Arc::new(AtomicUsize::new(0));
+        self.browse_depth_limit_hit = Arc::new(AtomicUsize::new(0));
+        self.browse_tags_sink = Arc::new(std::sync::Mutex::new(Some(Vec::new())));
+        self.add_message(format!(
+            "Reading public groups on {server}...{}",
+            op_suffix(Some(&op_id))
+        ));
+
+        let provider = Arc::clone(&self.opc_provider);
+        let progress = Arc::clone(&self.browse_progress);
+        let (tx, rx) = oneshot::channel();
+
+        let task = async move {
+            let result = tokio::time::timeout(
+                std::time::Duration::from_secs(OPC_TIMEOUT_SECS),
+                browse_public_groups(provider.as_ref(), &server, &progress),
+            )
+            .await
+            .map(|inner| inner.map(BrowseResult::from))
+            .unwrap_or_else(|_| {
+                tracing::error!(server = %server, "Public group browse timed out ({OPC_TIMEOUT_SECS}s)");
+                Err(OpcError::Timeout(format!(
+                    "public groups of '{server}' after {OPC_TIMEOUT_SECS}s"
+                )))
+            });
+            let _ = tx.send(result);
+        };
+        tokio::spawn(task.instrument(op_id.span().clone()));
+
+        self.browse_result_rx = Some(rx);
+        self.browse_op_id = Some(op_id);
+    }
+
+    pub fn poll_browse_result(&mut self) {
+        if let Some(rx) = &mut self.browse_result_rx {
+            let _entered = self.browse_op_id.as_ref().map(OpId::enter);
+            match rx.try_recv() {
+                Ok(Ok(result)) => {
+                    self.mark_server_activity();
+                    self.show_browsed_tags(result.tags, result.partial);
+                    if result.partial {
+                        self.add_message(format!(
+                            "Warning: browse timed out; showing {} tags found so far",
+                            self.tags.len()
+                        ));
+                    } else {
+                        self.add_message(format!("Found {} tags", self.tags.len()));
+                        if !self.public_group_browse {
+                            self.record_browse_estimate();
+                        }
+                    }
+                    let depth_limit = self.browse_depth_limit_hit.load(Ordering::Relaxed);
+                    if depth_limit > 0 {
+                        self.add_message(format!(
+                            "Warning: namespace deeper than {depth_limit} levels — some tags omitted"
+                        ));
+                    }
+                    self.browse_result_rx = None;
+                    self.finish_reconnect();
+                }
+                Ok(Err(e)) => {
+                    let op = op_suffix(self.browse_op_id.as_ref());
+                    self.push_screen(CurrentScreen::ServerList);
+                    self.mark_server_error();
+                    if !self.reconnect_tag_ids.is_empty() {
+                        self.reconnect_tag_ids.clear();
+                        self.add_message("Reconnect failed: could not browse the server".into());
+                    }
+                    tracing::error!(error = %e, error_chain = ?e, "Browse tags failed");
+                    if self.public_group_browse && matches!(e, OpcError::NotImplemented(_)) {
+                        self.add_message(format!(
+                            "This server does not support public groups; press Enter to browse its namespace{op}"
+                        ));
+                    } else if let Some((problem, remedy)) = browse_error_guidance(&e) {
+                        self.add_message(format!("Error: {problem} ({e}){op}"));
+                        self.add_message(format!("Hint: {remedy}"));
+                    } else {
+                        let msg = match friendly_com_hint(&e) {
+                            Some(h) => format!("Error: {} ({}){op}", h, e),
+                            None => format!("Error: {:#}{op}", e),
+                        };
+                        self.add_message(msg);
+                    }
+                    if let Some(hint) = local_host_hint(&self.host_input, &e) {
+                        self.add_message(format!("Hint: {hint}"));
+                    }
+                    if let Some(server) = self.browsed_server.clone() {
+                        self.start_connection_diagnostics(server, &e);
+                    }
                     self.browse_result_rx = None;
                 }
+                Err(oneshot::error::TryRecvError::Empty) => {
+                    // Still running
+                }
+                Err(oneshot::error::TryRecvError::Closed) => {
+                    // The task died without replying (a panic); keep whatever
+                    // it had already pushed to the shared sink, closing the
+                    // sink so a walk still running cannot add to it later.
+                    let partial = self
+                        .browse_tags_sink
+                        .lock()
+                        .unwrap_or_else(std::sync::PoisonError::into_inner)
+                        .take()
+                        .unwrap_or_default();
+                    if partial.is_empty() {
+                        self.push_screen(CurrentScreen::ServerList);
+                        tracing::error!(
+                            "Browse background task terminated unexpectedly (sender dropped)"
+                        );
+                        self.add_message("Browse task terminated unexpectedly".into());
+                    } else {
+                        tracing::warn!(
+                            count = partial.len(),
+                            "Browse background task terminated unexpectedly; showing partial results"
+                        );
+                        self.show_browsed_tags(partial, true);
+                        self.add_message(format!(
+                            "Warning: browse terminated unexpectedly; showing {} tags found so far",
+                            self.tags.len()
+                        ));
+                    }
+                    self.browse_result_rx = None;
+                    self.finish_reconnect();
+                }
+            }
+        }
+        self.clamp_selection();
+    }
+
+    /// Check the registration of `prog_id` after `error`, so the status log
+    /// can list what to fix instead of a bare HRESULT.
+    ///
+    /// Only COM errors are diagnosed; the findings arrive through
+    /// [`poll_connection_diagnostics`](Self::poll_connection_diagnostics).
+    fn start_connection_diagnostics(&mut self, prog_id: String, error: &OpcError) {
+        if error.hresult().is_none() || self.diagnostics_rx.is_some() {
+            return;
+        }
+        tracing::info!(server = %prog_id, "Diagnosing connection failure");
+        let provider = Arc::clone(&self.opc_provider);
+        let (tx, rx) = oneshot::channel();
+        tokio::spawn(async move {
+            let result = tokio::time::timeout(
+                std::time::Duration::from_secs(OPC_TIMEOUT_SECS),
+                provider.connect_with_diagnostics(&prog_id),
+            )
+            .await
+            .unwrap_or_else(|_| {
+                Err(OpcError::Timeout(format!(
+                    "diagnosing '{prog_id}' after {OPC_TIMEOUT_SECS}s"
+                )))
+            });
+            let _ = tx.send(result);
+        });
+        self.diagnostics_rx = Some(rx);
+    }
+
+    /// Check if connection diagnostics have completed and list the
+    /// checklist in the status log.
+    pub fn poll_connection_diagnostics(&mut self) {
+        let Some(rx) = &mut self.diagnostics_rx else {
+            return;
+        };
+        match rx.try_recv() {
+            Ok(Err(OpcError::Diagnosed { diagnostics, .. })) => {
+                self.add_message(format!("Checklist for {}:", diagnostics.prog_id));
+                for (n, item) in diagnostics.checklist().iter().enumerate() {
+                    self.add_message(format!("  {}. {item}", n + 1));
+                }
+                self.diagnostics_rx = None;
+            }
+            Ok(Ok(metadata)) => {
+                self.add_message(format!("{} connects now; try again", metadata.server));
+                self.diagnostics_rx = None;
             }
+            Ok(Err(e)) => {
+                tracing::debug!(error = %e, "No connection diagnostics available");
+                self.diagnostics_rx = None;
+            }
+            Err(oneshot::error::TryRecvError::Empty) => {}
+            Err(oneshot::error::TryRecvError::Closed) => {
+                self.diagnostics_rx = None;
+            }
+        }
+    }
+
+    /// Key of the browse estimate for `server` on the current host with the
+    /// current access filter, which changes how many tags are found.
+    fn browse_estimate_key(&self, server: &str) -> String {
+        let host = self.server_host.as_deref().unwrap_or("localhost");
+        format!("{host}/{server} ({})", self.browse_filter)
+    }
+
+    /// How far the running browse is, as a percentage of
+    /// [`estimated_total_tags`](Self::estimated_total_tags), capped at 100.
+    pub fn browse_estimated_percent(&self) -> Option<u8> {
+        let estimated = self.estimated_total_tags.filter(|&total| total > 0)?;
+        let actual = self.browse_progress.load(Ordering::Relaxed);
+        let percent = (actual.saturating_mul(100) / estimated).min(100);
+        Some(u8::try_from(percent).unwrap_or(100))
+    }
+
+    /// Remember how many tags the browse that just completed found, as the
+    /// estimate for the next browse of the same server.
+    fn record_browse_estimate(&mut self) {
+        let Some(server) = self.browsed_server.clone() else {
+            return;
+        };
+        let key = self.browse_estimate_key(&server);
+        self.browse_estimates.record(key, self.tags.len());
+        if let Some(path) = &self.browse_estimates_path
+            && let Err(e) = self.browse_estimates.save(path)
+        {
+            tracing::warn!(error = %e, "Failed to save browse estimates");
+        }
+    }
+
+    /// Switch to the tag list showing `tags` from a finished browse.
+    ///
+    /// `partial` marks results of a browse that did not run to completion.
+    fn show_browsed_tags(&mut self, tags: Vec<BrowsedTag>, partial: bool) {
+        self.register_browsed_session();
+        self.tags = tags;
+        self.browse_partial = partial.then(|| Arc::clone(&self.browse_progress));
+        self.browse_time = Some(chrono::Local::now());
+        self.selected_tags = vec![false; self.tags.len()];
+        self.push_screen(CurrentScreen::TagList);
+        if self.tags.is_empty() {
+            self.selected_index = None;
+            self.list_state.select(None);
+        } else {
+            self.selected_index = Some(0);
+            self.list_state.select(Some(0));
         }
     }
 
@@ -396,39 +1599,109 @@ impl App {
         if self.current_screen != CurrentScreen::TagList {
             return;
         }
+        self.clamp_selection();
         if let Some(idx) = self.selected_index
             && idx < self.selected_tags.len()
             && let Some(tag) = self.tags.get(idx)
         {
             self.selected_tags[idx] = !self.selected_tags[idx];
             tracing::debug!(
-                tag = %tag,
+                tag = %tag.tag_id,
                 selected = self.selected_tags[idx],
                 "toggle_tag_selection"
             );
         }
     }
 
-    /// Start reading values for selected tags.
-    pub fn start_read_values(&mut self) {
+    /// Invert the tag selection.
+    ///
+    /// While a search query is active only the matching tags are inverted;
+    /// the selection of every other tag is left alone.
+    pub fn invert_tag_selection(&mut self) {
         if self.current_screen != CurrentScreen::TagList {
             return;
         }
+        if self.search_mode && !self.search_query.is_empty() {
+            for &idx in &self.search_matches {
+                if let Some(selected) = self.selected_tags.get_mut(idx) {
+                    *selected = !*selected;
+                }
+            }
+        } else {
+            for selected in &mut self.selected_tags {
+                *selected = !*selected;
+            }
+        }
+        let count = self.selected_tags.iter().filter(|&&s| s).count();
+        tracing::debug!(selected = count, "invert_tag_selection");
+        self.add_message(format!("Selection inverted: {count} tags selected"));
+    }
 
-        // Gather selected tag IDs
-        let selected_tag_ids: Vec<String> = self
-            .tags
+    /// IDs of the tags selected on the Tag List, in browse order.
+    fn selected_tag_ids(&self) -> Vec<String> {
+        self.tags
             .iter()
             .enumerate()
-            .filter_map(|(idx, tag_id)| {
+            .filter_map(|(idx, tag)| {
                 if self.selected_tags.get(idx).copied().unwrap_or(false) {
-                    Some(tag_id.clone())
+                    Some(tag.tag_id.clone())
                 } else {
                     None
                 }
             })
-            .collect();
+            .collect()
+    }
+
+    /// Stop tracking `tag_id`: unselect it on the Tag List, drop it from the
+    /// auto-refresh set and remove its row from the values table.
+    ///
+    /// Any read still in flight is discarded and the next auto-refresh fires
+    /// right away, so only the remaining tags are read.
+    pub fn deselect_tag(&mut self, tag_id: &str) {
+        if let Some(idx) = self.tags.iter().position(|t| t.tag_id == tag_id)
+            && let Some(selected) = self.selected_tags.get_mut(idx)
+        {
+            *selected = false;
+        }
+        self.refresh_tag_ids.retain(|id| id != tag_id);
+        self.tag_values.retain(|tv| tv.tag_id != tag_id);
+        self.read_result_rx = None;
+        // Backdate the last read so the next auto-refresh is immediate
+        let now = std::time::Instant::now();
+        self.last_read_time = Some(now.checked_sub(AUTO_REFRESH_INTERVAL).unwrap_or(now));
+        self.clamp_selection();
+        tracing::debug!(
+            tag = %tag_id,
+            remaining = self.refresh_tag_ids.len(),
+            "deselect_tag"
+        );
+        self.add_message(format!("Stopped tracking '{tag_id}'"));
+    }
+
+    /// Deselect the highlighted Tag Values row (`Space`).
+    pub fn deselect_highlighted_tag(&mut self) {
+        if self.current_screen != CurrentScreen::TagValues {
+            return;
+        }
+        let Some(tag_id) = self
+            .table_state
+            .selected()
+            .and_then(|idx| self.tag_values.get(idx))
+            .map(|tv| tv.tag_id.clone())
+        else {
+            self.add_message("No tag selected.".into());
+            return;
+        };
+        self.deselect_tag(&tag_id);
+    }
+
+    /// Start reading values for selected tags.
+    pub fn start_read_values(&mut self) {
+        if self.current_screen != CurrentScreen::TagList || self.reject_if_offline("reading") {
+            return;
+        }
 
+        let selected_tag_ids = self.selected_tag_ids();
         if selected_tag_ids.is_empty() {
             tracing::debug!("start_read_values: no tags selected");
             self.add_message("No tags selected. Press Space to select tags.".into());
@@ -447,6 +1720,8 @@ impl App {
         self.refresh_server = Some(server.clone());
         self.refresh_tag_ids.clone_from(&selected_tag_ids);
 
+        let op_id = OpId::start("read_tag_values");
+        let _entered = op_id.enter();
         tracing::info!(
             server = %server,
             count = selected_tag_ids.len(),
@@ -454,15 +1729,33 @@ impl App {
             "start_read_values: sending tags to backend"
         );
         self.current_screen = CurrentScreen::Loading;
-        self.add_message(format!("Reading {} tag values...", selected_tag_ids.len()));
+        self.add_message(format!(
+            "Reading {} tag values...{}",
+            selected_tag_ids.len(),
+            op_suffix(Some(&op_id))
+        ));
+        self.read_progress = Arc::new(AtomicUsize::new(0));
+        self.read_total = selected_tag_ids.len();
+        let batch_size = self.read_batch_size.max(1);
+        self.read_chunks_done = Arc::new(AtomicUsize::new(0));
+        self.read_chunk_total = selected_tag_ids.len().div_ceil(batch_size);
 
         let provider = Arc::clone(&self.opc_provider);
+        let progress = Arc::clone(&self.read_progress);
+        let chunks_done = Arc::clone(&self.read_chunks_done);
         let (tx, rx) = oneshot::channel();
 
-        tokio::spawn(async move {
+        let task = async move {
             let result = tokio::time::timeout(
                 std::time::Duration::from_secs(OPC_TIMEOUT_SECS),
-                provider.read_tag_values(&server, selected_tag_ids),
+                read_in_batches(
+                    provider.as_ref(),
+                    &server,
+                    &selected_tag_ids,
+                    batch_size,
+                    &progress,
+                    &chunks_done,
+                ),
             )
             .await;
 
@@ -477,17 +1770,27 @@ impl App {
             };
 
             let _ = tx.send(final_result);
-        });
+        };
+        tokio::spawn(task.instrument(op_id.span().clone()));
 
         self.read_result_rx = Some(rx);
+        self.read_op_id = Some(op_id);
     }
 
     pub fn poll_read_result(&mut self) {
         if let Some(rx) = &mut self.read_result_rx {
+            let _entered = self.read_op_id.as_ref().map(OpId::enter);
             match rx.try_recv() {
                 Ok(Ok(values)) => {
+                    self.record_value_changes(&values);
+                    self.record_read(&values);
                     self.tag_values = values;
-                    self.current_screen = CurrentScreen::TagValues;
+                    // Live values replace a loaded snapshot
+                    self.offline_snapshot = None;
+                    // A refresh landing under the correlation view leaves it open
+                    if self.current_screen != CurrentScreen::Correlation {
+                        self.push_screen(CurrentScreen::TagValues);
+                    }
                     if self.tag_values.is_empty() {
                         self.selected_index = None;
                         self.table_state.select(None);
@@ -505,7 +1808,7 @@ impl App {
                     let error_count = self
                         .tag_values
                         .iter()
-                        .filter(|tv| tv.value == "Error")
+                        .filter(|tv| tv.status != ReadStatus::Ok)
                         .count();
 
                     if error_count > 0 {
@@ -519,30 +1822,204 @@ impl App {
                     }
 
                     self.last_read_time = Some(std::time::Instant::now());
+                    self.consecutive_read_errors = 0;
+                    self.mark_server_activity();
+                    self.read_result_rx = None;
+                    self.read_total = 0;
+                    self.request_engineering_units();
+                }
+                Ok(Err(e))
+                    if self.auto_reconnect
+                        && self.current_screen == CurrentScreen::TagValues
+                        && matches!(e, OpcError::Com { .. }) =>
+                {
                     self.read_result_rx = None;
+                    self.read_total = 0;
+                    self.consecutive_read_errors += 1;
+                    tracing::warn!(
+                        error = %e,
+                        consecutive = self.consecutive_read_errors,
+                        "Auto-refresh read failed"
+                    );
+                    if self.consecutive_read_errors >= RECONNECT_AFTER_READ_ERRORS {
+                        self.reconnect();
+                    } else {
+                        self.add_message(format!(
+                            "Error reading values ({}/{RECONNECT_AFTER_READ_ERRORS}): {e}{}",
+                            self.consecutive_read_errors,
+                            op_suffix(self.read_op_id.as_ref())
+                        ));
+                        // Try again on the next cycle rather than right away
+                        self.last_read_time = Some(std::time::Instant::now());
+                    }
                 }
                 Ok(Err(e)) => {
-                    self.current_screen = CurrentScreen::TagList;
+                    self.consecutive_read_errors = 0;
+                    self.mark_server_error();
+                    self.push_screen(CurrentScreen::TagList);
                     tracing::error!(error = %e, error_chain = ?e, "Read tag values failed");
                     let hint = friendly_com_hint(&e);
+                    let op = op_suffix(self.read_op_id.as_ref());
                     let msg = match hint {
-                        Some(h) => format!("Error reading values: {} ({})", h, e),
-                        None => format!("Error reading values: {:#}", e),
+                        Some(h) => format!("Error reading values: {} ({}){op}", h, e),
+                        None => format!("Error reading values: {:#}{op}", e),
                     };
                     self.add_message(msg);
                     self.read_result_rx = None;
+                    self.read_total = 0;
                 }
                 Err(oneshot::error::TryRecvError::Empty) => {
                     // Still running
                 }
                 Err(oneshot::error::TryRecvError::Closed) => {
-                    self.current_screen = CurrentScreen::TagList;
+                    self.push_screen(CurrentScreen::TagList);
                     tracing::error!(
                         "Read values background task terminated unexpectedly (sender dropped)"
                     );
                     self.add_message("Read task terminated unexpectedly".into());
                     self.read_result_rx = None;
+                    self.read_total = 0;
+                }
+            }
+        }
+        self.clamp_selection();
+    }
+
+    /// Recover from a server that stopped answering reads: leave Tag Values,
+    /// browse the same server again and, once that completes, read the tags
+    /// that were being watched ([`finish_reconnect`](Self::finish_reconnect)).
+    fn reconnect(&mut self) {
+        let Some(server) = self.refresh_server.clone() else {
+            return;
+        };
+        tracing::warn!(
+            server = %server,
+            errors = self.consecutive_read_errors,
+            "Reconnecting after consecutive read errors"
+        );
+        self.add_message(format!(
+            "{} reads failed in a row; reconnecting to {server}...",
+            self.consecutive_read_errors
+        ));
+        self.consecutive_read_errors = 0;
+        self.reconnect_tag_ids = std::mem::take(&mut self.refresh_tag_ids);
+        self.go_back();
+        self.browse_server(server);
+    }
+
+    /// Select the tags saved by [`reconnect`](Self::reconnect) in the fresh
+    /// browse and read them. Tags the server no longer has are reported.
+    fn finish_reconnect(&mut self) {
+        let wanted: HashSet<String> = std::mem::take(&mut self.reconnect_tag_ids)
+            .into_iter()
+            .collect();
+        if wanted.is_empty() || self.current_screen != CurrentScreen::TagList {
+            return;
+        }
+        let mut found = 0;
+        for (tag, selected) in self.tags.iter().zip(&mut self.selected_tags) {
+            *selected = wanted.contains(&tag.tag_id);
+            found += usize::from(*selected);
+        }
+        if found < wanted.len() {
+            self.add_message(format!(
+                "Reconnected; {} tags are no longer on the server",
+                wanted.len() - found
+            ));
+        }
+        if found > 0 {
+            self.start_read_values();
+        }
+    }
+
+    /// Re-read only the tags whose last read failed.
+    ///
+    /// Triggered by `r` on TagValues. The results are merged back into the
+    /// table by tag ID; rows that read fine are left untouched.
+    pub fn retry_failed_reads(&mut self) {
+        if self.current_screen != CurrentScreen::TagValues || self.reject_if_offline("retrying") {
+            return;
+        }
+        if self.retry_result_rx.is_some() {
+            return; // Retry already in-flight
+        }
+        let Some(server_name) = self.refresh_server.clone() else {
+            return;
+        };
+        let tag_ids: Vec<String> = self
+            .tag_values
+            .iter()
+            .filter(|tv| tv.status != ReadStatus::Ok)
+            .map(|tv| tv.tag_id.clone())
+            .collect();
+        if tag_ids.is_empty() {
+            self.add_message("No failed tags to retry".into());
+            return;
+        }
+
+        let op_id = OpId::start("retry_failed_reads");
+        let _entered = op_id.enter();
+        tracing::info!(tag_count = tag_ids.len(), "Retrying failed tag reads");
+        self.add_message(format!(
+            "Retrying {} failed tags...{}",
+            tag_ids.len(),
+            op_suffix(Some(&op_id))
+        ));
+        self.retry_result_rx = Some(spawn_refresh_read(
+            Arc::clone(&self.opc_provider),
+            server_name,
+            tag_ids,
+            false,
+        ));
+        self.retry_op_id = Some(op_id);
+    }
+
+    /// Check if the retry of failed tags has completed.
+    ///
+    /// Called on every tick; merges the re-read values into `tag_values`.
+    pub fn poll_retry_result(&mut self) {
+        let Some(rx) = &mut self.retry_result_rx else {
+            return;
+        };
+        let _entered = self.retry_op_id.as_ref().map(OpId::enter);
+        match rx.try_recv() {
+            Ok(Ok(values)) => {
+                self.mark_server_activity();
+                let retried = values.len();
+                let mut recovered = 0;
+                for value in values {
+                    if let Some(row) = self
+                        .tag_values
+                        .iter_mut()
+                        .find(|tv| tv.tag_id == value.tag_id)
+                    {
+                        if value.status == ReadStatus::Ok {
+                            recovered += 1;
+                        }
+                        *row = value;
+                    }
                 }
+                self.add_message(format!(
+                    "Retried {retried} failed tags: {recovered} recovered"
+                ));
+                self.retry_result_rx = None;
+            }
+            Ok(Err(e)) => {
+                self.mark_server_error();
+                tracing::error!(error = %e, error_chain = ?e, "Retry of failed tags failed");
+                self.add_message(format!(
+                    "Error retrying failed tags: {e:#}{}",
+                    op_suffix(self.retry_op_id.as_ref())
+                ));
+                self.retry_result_rx = None;
+            }
+            Err(oneshot::error::TryRecvError::Empty) => {
+                // Still running
+            }
+            Err(oneshot::error::TryRecvError::Closed) => {
+                tracing::error!("Retry background task terminated unexpectedly (sender dropped)");
+                self.add_message("Retry task terminated unexpectedly".into());
+                self.retry_result_rx = None;
             }
         }
     }
@@ -552,9 +2029,10 @@ impl App {
     /// Triggered from TagValues. If only one tag is displayed, it is auto-selected.
     /// If multiple are displayed, the currently highlighted row is used.
     pub fn enter_write_mode(&mut self) {
-        if self.current_screen != CurrentScreen::TagValues {
+        if self.current_screen != CurrentScreen::TagValues || self.reject_if_offline("writing") {
             return;
         }
+        self.clamp_selection();
 
         let tag_id = if self.tag_values.len() == 1 {
             // Auto-select the only tag
@@ -570,915 +2048,5176 @@ impl App {
             tracing::debug!(tag_id = %id, "enter_write_mode: entering write mode for tag");
             self.write_tag_id = Some(id);
             self.write_value_input.clear();
-            self.current_screen = CurrentScreen::WriteInput;
+            self.write_multiline = false;
+            self.write_error = None;
+            self.push_screen(CurrentScreen::WriteInput);
         } else {
             tracing::debug!("enter_write_mode: no tag selected");
             self.add_message("No tag selected to write.".into());
         }
     }
 
-    /// Start writing a value to the selected tag.
-    pub fn start_write_value(&mut self) {
-        let tag_id = match &self.write_tag_id {
-            Some(t) => t.clone(),
-            None => return,
-        };
-        let value_str = self.write_value_input.trim().to_string();
-        if value_str.is_empty() {
-            self.add_message("Value cannot be empty.".into());
+    /// Prompt for one value to write to every selected tag (`w` on the Tag List).
+    pub fn enter_bulk_write_mode(&mut self) {
+        if self.current_screen != CurrentScreen::TagList || self.reject_if_offline("writing") {
+            return;
+        }
+        let targets = self.selected_tag_ids();
+        if targets.is_empty() {
+            self.add_message("No tags selected. Press Space to select tags.".into());
             return;
         }
+        tracing::debug!(
+            count = targets.len(),
+            "enter_bulk_write_mode: writing to selected tags"
+        );
+        self.bulk_write_tags = targets;
+        self.write_tag_id = None;
+        self.write_value_input.clear();
+        self.write_multiline = false;
+        self.write_error = None;
+        self.push_screen(CurrentScreen::WriteInput);
+    }
 
-        // Parse the value string into OpcValue (try int -> float -> bool -> string)
-        let opc_value = parse_opc_value(&value_str);
+    /// Switch the write editor between a single-line value and multi-line JSON.
+    pub fn toggle_write_multiline(&mut self) {
+        if self.current_screen != CurrentScreen::WriteInput {
+            return;
+        }
+        self.write_multiline = !self.write_multiline;
+        tracing::debug!(multiline = self.write_multiline, "toggle_write_multiline");
+    }
 
-        tracing::info!(tag = %tag_id, value = %value_str, parsed_type = ?opc_value, "start_write_value: initiating write");
+    /// Toggle the VARIANT type column on the Tag Values screen.
+    pub fn toggle_vt_column(&mut self) {
+        self.show_vt_column = !self.show_vt_column;
+        tracing::debug!(show = self.show_vt_column, "toggle_vt_column");
+    }
 
-        let server = match &self.refresh_server {
-            Some(s) => s.clone(),
-            None => {
-                self.add_message("No server context for write.".into());
-                return;
-            }
-        };
+    /// Toggle between full tag IDs and leaf names on the tag list and the
+    /// Tag Values screen.
+    pub fn toggle_leaf_names(&mut self) {
+        self.show_leaf_names = !self.show_leaf_names;
+        tracing::debug!(leaf = self.show_leaf_names, "toggle_leaf_names");
+    }
 
-        self.current_screen = CurrentScreen::Loading;
-        self.add_message(format!("Writing '{value_str}' to {tag_id}..."));
+    /// Toggle showing values with their engineering units, e.g.
+    /// `73.2 °C (range 0–200)`.
+    ///
+    /// Units come from the items' OPC properties and are fetched once per tag.
+    pub fn toggle_engineering_units(&mut self) {
+        if self.current_screen != CurrentScreen::TagValues {
+            return;
+        }
+        self.show_engineering_units = !self.show_engineering_units;
+        tracing::debug!(
+            show = self.show_engineering_units,
+            "toggle_engineering_units"
+        );
+        self.request_engineering_units();
+    }
 
-        let provider = Arc::clone(&self.opc_provider);
-        let (tx, rx) = oneshot::channel();
+    /// Engineering units of `tag_id` on the current server, if they are
+    /// being shown and the server reports any.
+    pub fn engineering_units_for(&self, tag_id: &str) -> Option<&EngineeringUnits> {
+        if !self.show_engineering_units {
+            return None;
+        }
+        let server = self.refresh_server.clone()?;
+        self.engineering_units
+            .get(&(server, tag_id.to_string()))?
+            .as_ref()
+    }
 
-        // Use a consistent timeout
-        const OPC_TIMEOUT_SECS_WRITE: u64 = 10;
+    /// Fetch engineering units for displayed tags that are not cached yet.
+    fn request_engineering_units(&mut self) {
+        if !self.show_engineering_units
+            || self.engineering_units_rx.is_some()
+            || self.offline_snapshot.is_some()
+        {
+            return;
+        }
+        let Some(server) = self.refresh_server.clone() else {
+            return;
+        };
+        let tag_ids: Vec<String> = self
+            .tag_values
+            .iter()
+            .filter(|tv| {
+                !self
+                    .engineering_units
+                    .contains_key(&(server.clone(), tv.tag_id.clone()))
+            })
+            .map(|tv| tv.tag_id.clone())
+            .collect();
+        if tag_ids.is_empty() {
+            return;
+        }
 
+        tracing::debug!(tag_count = tag_ids.len(), "Fetching engineering units");
+        let provider = Arc::clone(&self.opc_provider);
+        let (tx, rx) = oneshot::channel();
         tokio::spawn(async move {
             let result = tokio::time::timeout(
-                std::time::Duration::from_secs(OPC_TIMEOUT_SECS_WRITE),
-                provider.write_tag_value(&server, &tag_id, opc_value),
+                std::time::Duration::from_secs(OPC_TIMEOUT_SECS),
+                provider.get_engineering_units(&server, tag_ids.clone()),
             )
-            .await;
-
-            let final_result = match result {
-                Ok(inner) => inner,
-                Err(_) => {
-                    tracing::error!("Write tag value timed out ({OPC_TIMEOUT_SECS_WRITE}s)");
-                    Err(OpcError::Internal(format!(
-                        "Write timed out ({OPC_TIMEOUT_SECS_WRITE}s)"
-                    )))
-                }
-            };
-            let _ = tx.send(final_result);
+            .await
+            .unwrap_or_else(|_| {
+                Err(OpcError::Timeout(format!(
+                    "engineering units after {OPC_TIMEOUT_SECS}s"
+                )))
+            })
+            .map(|units| {
+                tag_ids
+                    .into_iter()
+                    .map(|tag_id| (server.clone(), tag_id))
+                    .zip(units)
+                    .collect()
+            });
+            let _ = tx.send(result);
         });
-
-        self.write_result_rx = Some(rx);
+        self.engineering_units_rx = Some(rx);
     }
 
-    /// Poll for the result of the background write operation.
-    pub fn poll_write_result(&mut self) {
-        if let Some(rx) = &mut self.write_result_rx {
-            match rx.try_recv() {
-                Ok(Ok(result)) => {
-                    if result.success {
-                        tracing::info!(tag = %result.tag_id, "poll_write_result: write succeeded");
-                        self.add_message(format!("✓ Write to '{}' succeeded", result.tag_id));
-                    } else {
-                        let err_msg = result.error.unwrap_or_default();
-                        self.add_message(format!(
-                            "✗ Write to '{}' failed: {}",
-                            result.tag_id, err_msg
-                        ));
-                    }
-                    self.current_screen = CurrentScreen::TagValues;
-                    self.write_result_rx = None;
-                    // Trigger a refresh to show the new value
-                    self.start_read_values();
-                }
-                Ok(Err(e)) => {
-                    tracing::error!(error = %e, "Write tag values failed");
-                    self.add_message(format!("Browse error: {e:#}"));
-                    self.current_screen = CurrentScreen::TagValues;
-                    self.write_result_rx = None;
-                }
-                Err(oneshot::error::TryRecvError::Empty) => {}
-                Err(oneshot::error::TryRecvError::Closed) => {
-                    self.current_screen = CurrentScreen::TagValues;
-                    tracing::error!("Write background task terminated unexpectedly");
-                    self.add_message("Write task terminated unexpectedly".into());
-                    self.write_result_rx = None;
-                }
+    /// Check if the engineering units fetch has completed.
+    ///
+    /// If the server cannot report them, the option is switched off again
+    /// and the raw values are shown.
+    pub fn poll_engineering_units(&mut self) {
+        let Some(rx) = &mut self.engineering_units_rx else {
+            return;
+        };
+        match rx.try_recv() {
+            Ok(Ok(batch)) => {
+                self.engineering_units.extend(batch);
+                self.engineering_units_rx = None;
+            }
+            Ok(Err(e)) => {
+                tracing::warn!(error = %e, "Engineering units unavailable");
+                let reason = if matches!(e, OpcError::NotImplemented(_)) {
+                    "server does not support item properties".to_string()
+                } else {
+                    format!("{e:#}")
+                };
+                self.add_message(format!(
+                    "Engineering units unavailable ({reason}); showing raw values"
+                ));
+                self.show_engineering_units = false;
+                self.engineering_units_rx = None;
+            }
+            Err(oneshot::error::TryRecvError::Empty) => {}
+            Err(oneshot::error::TryRecvError::Closed) => {
+                self.engineering_units_rx = None;
             }
         }
     }
 
-    pub fn maybe_auto_refresh(&mut self) {
-        if self.current_screen != CurrentScreen::TagValues {
+    /// Toggle live mode on the Tag Values screen.
+    ///
+    /// In live mode auto-refresh reads from one persistent active group
+    /// instead of re-adding items every cycle; turning it off releases that
+    /// group on the server.
+    pub fn toggle_live_mode(&mut self) {
+        if self.current_screen != CurrentScreen::TagValues || self.reject_if_offline("live mode") {
             return;
         }
-        if self.read_result_rx.is_some() {
-            return; // Read already in-flight
-        }
-        let elapsed = match self.last_read_time {
-            Some(t) => t.elapsed(),
-            None => return,
-        };
-        if elapsed < std::time::Duration::from_secs(1) {
-            return;
+        self.live_mode = !self.live_mode;
+        tracing::info!(live = self.live_mode, "toggle_live_mode");
+        if self.live_mode {
+            self.add_message("Live mode on: reading from a persistent group".into());
+        } else {
+            self.stop_live_reads();
+            self.add_message("Live mode off".into());
         }
+    }
 
-        let server_name = match &self.refresh_server {
-            Some(s) => s.clone(),
-            None => return,
-        };
-        let tag_ids = self.refresh_tag_ids.clone();
-        if tag_ids.is_empty() {
+    /// Release the server-side live group in the background.
+    fn stop_live_reads(&self) {
+        let Some(server) = self.refresh_server.clone() else {
             return;
-        }
-
-        tracing::debug!(tag_count = tag_ids.len(), "Auto-refreshing tag values");
+        };
         let provider = Arc::clone(&self.opc_provider);
-        let (tx, rx) = oneshot::channel();
-
         tokio::spawn(async move {
             let result = tokio::time::timeout(
                 std::time::Duration::from_secs(OPC_TIMEOUT_SECS),
-                provider.read_tag_values(&server_name, tag_ids),
+                provider.stop_live_reads(&server),
             )
             .await;
-
-            let final_result = match result {
-                Ok(inner) => inner,
-                Err(_) => {
-                    tracing::error!("Auto-refresh timed out ({OPC_TIMEOUT_SECS}s)");
-                    Err(OpcError::Internal(format!(
-                        "Auto-refresh timed out ({OPC_TIMEOUT_SECS}s)"
-                    )))
+            match result {
+                Ok(Ok(())) => tracing::debug!(server = %server, "Live group released"),
+                Ok(Err(e)) => {
+                    tracing::warn!(server = %server, error = %e, "Failed to release live group")
                 }
-            };
-
-            let _ = tx.send(final_result);
+                Err(_) => tracing::warn!(server = %server, "Releasing live group timed out"),
+            }
         });
+    }
 
-        self.read_result_rx = Some(rx);
+    /// Render the Tag Values table as tab-separated values, header row first.
+    ///
+    /// Columns and row order match what is currently displayed, so the
+    /// result pastes straight into a spreadsheet.
+    pub fn table_tsv(&self) -> String {
+        let mut header = vec!["Tag ID", "Data Type", "Value", "Quality", "Timestamp"];
+        if self.show_vt_column {
+            header.insert(3, "Type");
+        }
+        let mut out = header.join("\t");
+        out.push('\n');
+
+        for tv in &self.tag_values {
+            let mut cells = vec![
+                tsv_field(&tv.tag_id),
+                data_type_symbol(tv.canonical_data_type),
+                tsv_field(&tv.value),
+                tsv_field(&tv.quality),
+                tsv_field(&tv.timestamp),
+            ];
+            if self.show_vt_column {
+                cells.insert(3, tv.vt.map_or_else(|| "—".to_string(), vt_to_string));
+            }
+            out.push_str(&cells.join("\t"));
+            out.push('\n');
+        }
+        out
     }
 
-    /// Enter search mode, clearing any previous query.
-    pub fn enter_search_mode(&mut self) {
-        if self.current_screen != CurrentScreen::TagList {
+    /// Copy the whole Tag Values table to the system clipboard as TSV.
+    pub fn copy_table_tsv(&mut self) {
+        if self.current_screen != CurrentScreen::TagValues {
+            return;
+        }
+        if self.tag_values.is_empty() {
+            self.add_message("Nothing to copy".into());
             return;
         }
-        self.search_mode = true;
-        self.search_query.clear();
-        self.search_matches.clear();
-        self.search_match_index = 0;
-    }
 
-    /// Exit search mode, keeping cursor position.
-    pub fn exit_search_mode(&mut self) {
-        self.search_mode = false;
-        // Keep Query string so user sees what they searched for if they enter again?
-        // Actually, the plan said "clear any previous query" on enter, so it's fine.
+        let tsv = self.table_tsv();
+        let rows = self.tag_values.len();
+        match arboard::Clipboard::new().and_then(|mut cb| cb.set_text(tsv)) {
+            Ok(()) => {
+                tracing::info!(rows, "copy_table_tsv");
+                self.add_message(format!("Copied {rows} rows to clipboard (TSV)"));
+            }
+            Err(e) => {
+                tracing::warn!(error = %e, "copy_table_tsv: clipboard unavailable");
+                self.add_message(format!("Copy failed: {e}"));
+            }
+        }
     }
 
-    /// Update the search query and recompute matches.
-    pub fn update_search_query(&mut self, c: char) {
-        self.search_query.push(c);
-        self.recompute_search_matches();
+    /// Count which rows of `tag_values` change in the read that returned
+    /// `values`, alone and in pairs. A read of a different set of tags
+    /// starts the counts over.
+    fn record_value_changes(&mut self, values: &[TagValue]) {
+        let same_tags = self.change_counts.len() == values.len()
+            && self.tag_values.len() == values.len()
+            && self
+                .tag_values
+                .iter()
+                .zip(values)
+                .all(|(old, new)| old.tag_id == new.tag_id);
+        if !same_tags {
+            self.change_correlation.clear();
+            self.change_counts = vec![0; values.len()];
+            return;
+        }
+        if values.len() > MAX_CORRELATION_TAGS {
+            return;
+        }
+
+        let changed: Vec<usize> = self
+            .tag_values
+            .iter()
+            .zip(values)
+            .enumerate()
+            .filter(|(_, (old, new))| old.value != new.value)
+            .map(|(idx, _)| idx)
+            .collect();
+        for (n, &i) in changed.iter().enumerate() {
+            self.change_counts[i] += 1;
+            for &j in &changed[n + 1..] {
+                *self.change_correlation.entry((i, j)).or_insert(0) += 1;
+            }
+        }
     }
 
-    /// Delete last character from search query and recompute.
-    pub fn search_backspace(&mut self) {
-        self.search_query.pop();
-        self.recompute_search_matches();
+    /// Share of the reads changing row `i` or row `j` that changed both,
+    /// from `0.0` (never together) to `1.0` (always together).
+    pub fn change_correlation_of(&self, i: usize, j: usize) -> f64 {
+        let key = (i.min(j), i.max(j));
+        let both = self.change_correlation.get(&key).copied().unwrap_or(0);
+        let count = |idx: usize| self.change_counts.get(idx).copied().unwrap_or(0);
+        let either = count(i) + count(j) - both;
+        if either == 0 {
+            0.0
+        } else {
+            f64::from(both) / f64::from(either)
+        }
     }
 
-    fn recompute_search_matches(&mut self) {
-        let query = self.search_query.to_lowercase();
-        self.search_matches = self
-            .tags
-            .iter()
+    /// Each row of `tag_values` that changes together with others more than
+    /// [`CORRELATION_THRESHOLD`] of the time, with those rows and their
+    /// correlation, strongest first.
+    pub fn correlated_tags(&self) -> Vec<(usize, Vec<(usize, f64)>)> {
+        let mut adjacency: Vec<Vec<(usize, f64)>> = vec![Vec::new(); self.change_counts.len()];
+        for &(i, j) in self.change_correlation.keys() {
+            let correlation = self.change_correlation_of(i, j);
+            if correlation > CORRELATION_THRESHOLD {
+                adjacency[i].push((j, correlation));
+                adjacency[j].push((i, correlation));
+            }
+        }
+        adjacency
+            .into_iter()
             .enumerate()
-            .filter_map(|(idx, tag)| {
-                if tag.to_lowercase().contains(&query) {
-                    Some(idx)
-                } else {
-                    None
-                }
+            .filter(|(_, partners)| !partners.is_empty())
+            .map(|(idx, mut partners)| {
+                partners.sort_by(|a, b| b.1.total_cmp(&a.1).then(a.0.cmp(&b.0)));
+                (idx, partners)
             })
-            .collect();
+            .collect()
+    }
 
-        self.search_match_index = 0;
-        if let Some(&first_match) = self.search_matches.first() {
-            self.selected_index = Some(first_match);
-            self.list_state.select(Some(first_match));
+    /// Show which tags change together (`g` on Tag Values).
+    pub fn open_correlation_view(&mut self) {
+        if self.current_screen != CurrentScreen::TagValues {
+            return;
         }
+        tracing::debug!(
+            pairs = self.change_correlation.len(),
+            "open_correlation_view"
+        );
+        self.push_screen(CurrentScreen::Correlation);
     }
 
-    /// Jump to the next search match.
-    pub fn next_search_match(&mut self) {
-        if self.search_matches.is_empty() {
+    /// Write the browsed tag IDs to `path`, one per line, sorted alphabetically.
+    ///
+    /// The file starts with a `# Server: …  Browsed: …` comment and is UTF-8
+    /// with `\n` line endings. A `.csv` or `.json` file name instead writes
+    /// records with the tag ID and its branch, in the same format as
+    /// `--read --format`. Returns the number of tags written.
+    ///
+    /// # Errors
+    /// Returns `Err` if the file cannot be written.
+    pub fn export_tag_list(&self, path: &std::path::Path) -> anyhow::Result<usize> {
+        if let Some(format) = OutputFormat::from_path(path) {
+            let mut tags: Vec<&BrowsedTag> = self.tags.iter().collect();
+            tags.sort_unstable_by(|a, b| a.tag_id.cmp(&b.tag_id));
+            let rows: Vec<Vec<String>> = tags
+                .iter()
+                .map(|t| vec![t.tag_id.clone(), t.parent_branch.clone()])
+                .collect();
+            let mut out = Vec::new();
+            format
+                .writer()
+                .write_records(&mut out, &["tag_id", "branch"], &rows)?;
+            std::fs::write(path, out)
+                .with_context(|| format!("Failed to write tag list to {}", path.display()))?;
+            tracing::info!(path = %path.display(), count = rows.len(), %format, "export_tag_list");
+            return Ok(rows.len());
+        }
+
+        let mut tag_ids: Vec<&str> = self.tags.iter().map(|t| t.tag_id.as_str()).collect();
+        tag_ids.sort_unstable();
+
+        let server = self.browsed_server.as_deref().unwrap_or("unknown");
+        let browsed = self.browse_time.map_or_else(
+            || "unknown".to_string(),
+            |t| t.format("%Y-%m-%dT%H:%M:%S").to_string(),
+        );
+        let mut out = format!("# Server: {server}  Browsed: {browsed}\n");
+        for id in &tag_ids {
+            out.push_str(id);
+            out.push('\n');
+        }
+        std::fs::write(path, out)
+            .with_context(|| format!("Failed to write tag list to {}", path.display()))?;
+        tracing::info!(path = %path.display(), count = tag_ids.len(), "export_tag_list");
+        Ok(tag_ids.len())
+    }
+
+    /// Open the file name prompt for exporting the tag list (`Ctrl+L`).
+    pub fn start_export_prompt(&mut self) {
+        if self.current_screen != CurrentScreen::TagList || self.search_mode {
             return;
         }
-        self.search_match_index = (self.search_match_index + 1) % self.search_matches.len();
-        if let Some(&next_idx) = self.search_matches.get(self.search_match_index) {
-            self.selected_index = Some(next_idx);
-            self.list_state.select(Some(next_idx));
+        if self.tags.is_empty() {
+            self.add_message("No tags to export".into());
+            return;
         }
+        let name = self
+            .browsed_server
+            .as_deref()
+            .map_or_else(|| "tags.txt".to_string(), |s| format!("{s}.tags.txt"));
+        self.export_path_input = Some(name);
     }
 
-    /// Jump to the previous search match.
-    pub fn prev_search_match(&mut self) {
-        if self.search_matches.is_empty() {
+    /// Export the tag list to the file named in the prompt and close it.
+    pub fn confirm_export(&mut self) {
+        let Some(input) = self.export_path_input.take() else {
+            return;
+        };
+        let path = input.trim();
+        if path.is_empty() {
+            self.add_message("Export cancelled: no file name".into());
             return;
         }
-        if self.search_match_index == 0 {
-            self.search_match_index = self.search_matches.len() - 1;
-        } else {
-            self.search_match_index -= 1;
+        match self.export_tag_list(std::path::Path::new(path)) {
+            Ok(count) => self.add_message(format!("Exported {count} tags to {path}")),
+            Err(e) => {
+                tracing::warn!(error = ?e, "export_tag_list failed");
+                self.add_message(format!("Export failed: {e:#}"));
+            }
         }
-        if let Some(&prev_idx) = self.search_matches.get(self.search_match_index) {
-            self.selected_index = Some(prev_idx);
-            self.list_state.select(Some(prev_idx));
+    }
+
+    /// Write the highlighted row's value adjusted by one step, skipping the write screen.
+    ///
+    /// `increment` selects the direction and `large` selects
+    /// `large_increment_step` over `increment_step`. Non-numeric values are
+    /// left untouched.
+    pub fn nudge_selected_value(&mut self, increment: bool, large: bool) {
+        if self.current_screen != CurrentScreen::TagValues || self.reject_if_offline("writing") {
+            return;
+        }
+        if self.write_result_rx.is_some() {
+            self.add_message("A write is already in progress.".into());
+            return;
         }
+
+        let Some(tv) = self
+            .table_state
+            .selected()
+            .and_then(|idx| self.tag_values.get(idx))
+        else {
+            self.add_message("No tag selected to adjust.".into());
+            return;
+        };
+
+        let step = if large {
+            self.large_increment_step
+        } else {
+            self.increment_step
+        };
+        let delta = if increment { step } else { -step };
+
+        let Some(new_value) = nudge_value(tv.value.trim(), delta) else {
+            let msg = format!(
+                "'{}' is not numeric — cannot adjust {}",
+                tv.value, tv.tag_id
+            );
+            self.add_message(msg);
+            return;
+        };
+
+        tracing::debug!(tag_id = %tv.tag_id, old = %tv.value, new = %new_value, "nudge_selected_value");
+        self.write_tag_id = Some(tv.tag_id.clone());
+        self.write_value_input = new_value;
+        self.start_write_value();
     }
 
-    pub fn go_back(&mut self) {
-        match self.current_screen {
-            CurrentScreen::ServerList => {
-                self.current_screen = CurrentScreen::Home;
-                self.servers.clear();
-                self.selected_index = None;
-                self.list_state.select(None);
-            }
-            CurrentScreen::TagList => {
-                self.current_screen = CurrentScreen::ServerList;
-                self.tags.clear();
-                // Restore selection to the previous server if possible
-                if !self.servers.is_empty() {
-                    self.selected_index = Some(0); // Simple fallback for now
-                    self.list_state.select(Some(0));
-                }
-            }
-            CurrentScreen::TagValues => {
-                self.current_screen = CurrentScreen::TagList;
-                self.tag_values.clear();
-                self.refresh_server = None;
-                self.refresh_tag_ids.clear();
-                self.last_read_time = None;
-                // Restore selection to tags list
-                if !self.tags.is_empty() {
-                    self.selected_index = Some(0);
-                    self.list_state.select(Some(0));
+    /// Start editing the highlighted row's value in place (`F2`), starting
+    /// from the value last read with the cursor after it.
+    pub fn begin_inline_edit(&mut self) {
+        if self.current_screen != CurrentScreen::TagValues || self.reject_if_offline("writing") {
+            return;
+        }
+        self.clamp_selection();
+        match self.table_state.selected() {
+            Some(row) if row < self.tag_values.len() => {
+                let tv = &self.tag_values[row];
+                tracing::debug!(row, tag_id = %tv.tag_id, "begin_inline_edit");
+                // A failed read has no value worth editing
+                let value = if tv.status == ReadStatus::Ok {
+                    tv.value.clone()
                 } else {
-                    self.selected_index = None;
-                    self.list_state.select(None);
-                }
-            }
-            CurrentScreen::WriteInput => {
-                self.current_screen = CurrentScreen::TagValues;
-                self.write_tag_id = None;
-                self.write_value_input.clear();
+                    String::new()
+                };
+                let cursor = value.chars().count();
+                self.inline_edit = Some((row, value, cursor));
             }
-            _ => {}
+            _ => self.add_message("No tag selected to write.".into()),
+        }
+    }
+
+    /// Insert `c` at the inline edit cursor.
+    pub fn inline_edit_insert(&mut self, c: char) {
+        if let Some((_, value, cursor)) = &mut self.inline_edit {
+            value.insert(char_to_byte(value, *cursor), c);
+            *cursor += 1;
+        }
+    }
+
+    /// Delete the character before the inline edit cursor.
+    pub fn inline_edit_backspace(&mut self) {
+        if let Some((_, value, cursor)) = &mut self.inline_edit
+            && *cursor > 0
+        {
+            *cursor -= 1;
+            value.remove(char_to_byte(value, *cursor));
+        }
+    }
+
+    /// Move the inline edit cursor one character left or right.
+    pub fn inline_edit_move(&mut self, right: bool) {
+        if let Some((_, value, cursor)) = &mut self.inline_edit {
+            *cursor = if right {
+                (*cursor + 1).min(value.chars().count())
+            } else {
+                cursor.saturating_sub(1)
+            };
+        }
+    }
+
+    /// Leave inline editing without writing.
+    pub fn cancel_inline_edit(&mut self) {
+        self.inline_edit = None;
+    }
+
+    /// Write the inline edit's value to its row's tag.
+    pub fn commit_inline_edit(&mut self) {
+        let Some((row, value, _)) = self.inline_edit.take() else {
+            return;
+        };
+        let Some(tag_id) = self.tag_values.get(row).map(|tv| tv.tag_id.clone()) else {
+            self.add_message("The edited row is no longer displayed.".into());
+            return;
+        };
+        self.write_tag_id = Some(tag_id);
+        self.write_value_input = value;
+        self.start_write_value();
+    }
+
+    /// Start writing a value to the selected tag, or to every tag of a bulk write.
+    pub fn start_write_value(&mut self) {
+        if self.reject_if_offline("writing") {
+            return;
+        }
+        if !self.bulk_write_tags.is_empty() {
+            self.start_bulk_write();
+            return;
+        }
+        let Some(tag_id) = self.write_tag_id.clone() else {
+            tracing::warn!("start_write_value: no target tag");
+            self.add_message("No tag selected to write.".into());
+            return;
+        };
+        let Some(server) = self.refresh_server.clone() else {
+            tracing::warn!(tag = %tag_id, "start_write_value: no server context");
+            let msg = "No server context for write — read the tag's value first.".to_string();
+            self.add_message(format!("Write to {tag_id} not sent: {msg}"));
+            self.fail_write(msg);
+            return;
+        };
+        let Some((opc_value, value_str)) = self.parse_write_input(&tag_id) else {
+            return;
+        };
+
+        let op_id = OpId::start("write_tag_value");
+        let _entered = op_id.enter();
+        tracing::info!(tag = %tag_id, value = %value_str, parsed_type = ?opc_value, "start_write_value: initiating write");
+
+        self.current_screen = CurrentScreen::Loading;
+        self.write_error = None;
+        self.add_message(format!(
+            "Writing '{value_str}' to {tag_id}...{}",
+            op_suffix(Some(&op_id))
+        ));
+
+        let provider = Arc::clone(&self.opc_provider);
+        let (tx, rx) = oneshot::channel();
+
+        // Use a consistent timeout
+        const OPC_TIMEOUT_SECS_WRITE: u64 = 10;
+
+        let task = async move {
+            let result = tokio::time::timeout(
+                std::time::Duration::from_secs(OPC_TIMEOUT_SECS_WRITE),
+                provider.write_tag_value(&server, &tag_id, opc_value),
+            )
+            .await;
+
+            let final_result = match result {
+                Ok(inner) => inner,
+                Err(_) => {
+                    tracing::error!("Write tag value timed out ({OPC_TIMEOUT_SECS_WRITE}s)");
+                    Err(OpcError::Internal(format!(
+                        "Write timed out ({OPC_TIMEOUT_SECS_WRITE}s)"
+                    )))
+                }
+            };
+            let _ = tx.send(final_result);
+        };
+        tokio::spawn(task.instrument(op_id.span().clone()));
+
+        self.write_result_rx = Some(rx);
+        self.write_op_id = Some(op_id);
+    }
+
+    /// Parse the write editor's input, reporting problems against `target`.
+    ///
+    /// Returns the value to send and its text for the status log.
+    fn parse_write_input(&mut self, target: &str) -> Option<(OpcValue, String)> {
+        let value_str = self.write_value_input.trim().to_string();
+        if value_str.is_empty() {
+            self.add_message("Value cannot be empty.".into());
+            return None;
+        }
+
+        if self.write_multiline {
+            match parse_json_opc_value(&value_str) {
+                // Flatten the editor's line breaks for the status log
+                Ok(value) => Some((
+                    value,
+                    value_str.split_whitespace().collect::<Vec<_>>().join(" "),
+                )),
+                Err(e) => {
+                    tracing::warn!(tag = %target, error = %e, "start_write_value: invalid JSON");
+                    self.add_message(format!("Write to {target} not sent: {e}"));
+                    self.fail_write(e);
+                    None
+                }
+            }
+        } else {
+            // Parse the value string into OpcValue (try int -> float -> bool -> string)
+            Some((parse_opc_value(&value_str, self.decimal_comma), value_str))
+        }
+    }
+
+    /// Write the entered value to every tag in `bulk_write_tags` with one batch write.
+    fn start_bulk_write(&mut self) {
+        let target = format!("{} tags", self.bulk_write_tags.len());
+        let Some(server) = self.browsed_server.clone() else {
+            tracing::warn!("start_bulk_write: no server context");
+            let msg = "No server context for write — browse a server first.".to_string();
+            self.add_message(format!("Write to {target} not sent: {msg}"));
+            self.fail_write(msg);
+            return;
+        };
+        let Some((opc_value, value_str)) = self.parse_write_input(&target) else {
+            return;
+        };
+
+        let op_id = OpId::start("write_tag_values");
+        let _entered = op_id.enter();
+        tracing::info!(
+            count = self.bulk_write_tags.len(),
+            value = %value_str,
+            parsed_type = ?opc_value,
+            "start_bulk_write: initiating batch write"
+        );
+
+        self.current_screen = CurrentScreen::Loading;
+        self.write_error = None;
+        self.add_message(format!(
+            "Writing '{value_str}' to {target}...{}",
+            op_suffix(Some(&op_id))
+        ));
+
+        let writes = self
+            .bulk_write_tags
+            .iter()
+            .map(|tag_id| (tag_id.clone(), opc_value.clone()))
+            .collect();
+        let provider = Arc::clone(&self.opc_provider);
+        let (tx, rx) = oneshot::channel();
+
+        let task = async move {
+            let result = tokio::time::timeout(
+                std::time::Duration::from_secs(OPC_TIMEOUT_SECS),
+                provider.write_tag_values(&server, writes),
+            )
+            .await;
+
+            let final_result = match result {
+                Ok(inner) => inner,
+                Err(_) => {
+                    tracing::error!("Bulk write timed out ({OPC_TIMEOUT_SECS}s)");
+                    Err(OpcError::Internal(format!(
+                        "Write timed out ({OPC_TIMEOUT_SECS}s)"
+                    )))
+                }
+            };
+            let _ = tx.send(final_result);
+        };
+        tokio::spawn(task.instrument(op_id.span().clone()));
+
+        self.bulk_write_rx = Some(rx);
+        self.write_op_id = Some(op_id);
+    }
+
+    /// Poll for the result of a bulk write.
+    ///
+    /// Logs one line per tag and a summary, then returns to the Tag List. If
+    /// the batch could not be sent at all, the write screen is shown again
+    /// with the error.
+    pub fn poll_bulk_write_result(&mut self) {
+        let Some(rx) = &mut self.bulk_write_rx else {
+            return;
+        };
+        let _entered = self.write_op_id.as_ref().map(OpId::enter);
+        let result = match rx.try_recv() {
+            Ok(result) => result,
+            Err(oneshot::error::TryRecvError::Empty) => return,
+            Err(oneshot::error::TryRecvError::Closed) => {
+                tracing::error!("Bulk write background task terminated unexpectedly");
+                Err(OpcError::Internal(
+                    "Write task terminated unexpectedly".into(),
+                ))
+            }
+        };
+        self.bulk_write_rx = None;
+
+        match result {
+            Ok(results) => {
+                self.mark_server_activity();
+                for result in &results {
+                    if result.success {
+                        self.add_message(format!(
+                            "  ✓ {}{}",
+                            result.tag_id,
+                            sent_as(result.sent_vt)
+                        ));
+                    } else {
+                        self.add_message(format!(
+                            "  ✗ {}: {}",
+                            result.tag_id,
+                            result.error.as_deref().unwrap_or("unknown error")
+                        ));
+                    }
+                }
+                let succeeded = results.iter().filter(|r| r.success).count();
+                tracing::info!(
+                    succeeded,
+                    total = results.len(),
+                    "poll_bulk_write_result: batch write finished"
+                );
+                self.add_message(format!(
+                    "Bulk write: {succeeded}/{} tags succeeded",
+                    results.len()
+                ));
+                self.bulk_write_tags.clear();
+                self.write_value_input.clear();
+                self.push_screen(CurrentScreen::TagList);
+            }
+            Err(e) => {
+                self.mark_server_error();
+                tracing::error!(error = %e, "Bulk write failed");
+                self.add_message(format!(
+                    "Write error: {e:#}{}",
+                    op_suffix(self.write_op_id.as_ref())
+                ));
+                self.fail_write(e.to_string());
+            }
+        }
+    }
+
+    /// Poll for the result of the background write operation.
+    ///
+    /// On failure the app stays on (or returns to) the write screen with the
+    /// attempted value still in the input, so it can be corrected and resubmitted.
+    pub fn poll_write_result(&mut self) {
+        if let Some(rx) = &mut self.write_result_rx {
+            let _entered = self.write_op_id.as_ref().map(OpId::enter);
+            match rx.try_recv() {
+                Ok(Ok(result)) => {
+                    self.mark_server_activity();
+                    self.write_result_rx = None;
+                    if result.success {
+                        tracing::info!(tag = %result.tag_id, attempts = result.attempts, "poll_write_result: write succeeded");
+                        let retried = if result.attempts > 1 {
+                            format!(" after {} attempts", result.attempts)
+                        } else {
+                            String::new()
+                        };
+                        self.add_message(format!(
+                            "✓ Write to '{}' succeeded{retried}{}",
+                            result.tag_id,
+                            sent_as(result.sent_vt)
+                        ));
+                        self.push_screen(CurrentScreen::TagValues);
+                        // Trigger a refresh to show the new value
+                        self.start_read_values();
+                    } else {
+                        let err_msg = result.error.unwrap_or_default();
+                        self.add_message(format!(
+                            "✗ Write to '{}'{} failed: {}{}",
+                            result.tag_id,
+                            sent_as(result.sent_vt),
+                            err_msg,
+                            op_suffix(self.write_op_id.as_ref())
+                        ));
+                        self.fail_write(err_msg);
+                    }
+                }
+                Ok(Err(e)) => {
+                    self.mark_server_error();
+                    tracing::error!(error = %e, "Write tag values failed");
+                    self.add_message(format!(
+                        "Write error: {e:#}{}",
+                        op_suffix(self.write_op_id.as_ref())
+                    ));
+                    self.write_result_rx = None;
+                    self.fail_write(e.to_string());
+                }
+                Err(oneshot::error::TryRecvError::Empty) => {}
+                Err(oneshot::error::TryRecvError::Closed) => {
+                    tracing::error!("Write background task terminated unexpectedly");
+                    self.add_message("Write task terminated unexpectedly".into());
+                    self.write_result_rx = None;
+                    self.fail_write("Write task terminated unexpectedly".into());
+                }
+            }
+        }
+    }
+
+    /// Return to the write screen with `error` displayed and the attempted value kept.
+    fn fail_write(&mut self, error: String) {
+        self.write_error = Some(error);
+        self.push_screen(
+            if self.write_tag_id.is_some() || !self.bulk_write_tags.is_empty() {
+                CurrentScreen::WriteInput
+            } else {
+                CurrentScreen::TagValues
+            },
+        );
+    }
+
+    /// Switch to `Exiting` once a shutdown signal has been received, so the
+    /// event loop ends and the terminal and COM teardown run as on `q`.
+    pub fn check_shutdown_requested(&mut self) {
+        if self.shutdown_requested.load(Ordering::SeqCst)
+            && self.current_screen != CurrentScreen::Exiting
+        {
+            tracing::info!("check_shutdown_requested: exiting on shutdown signal");
+            self.current_screen = CurrentScreen::Exiting;
+        }
+    }
+
+    /// Ping the OPC worker every [`WORKER_PING_INTERVAL`] and warn when it does
+    /// not answer in time; called once per render cycle.
+    ///
+    /// Pings are skipped while a request is running, since the worker
+    /// answers them only after finishing it.
+    pub fn check_worker_health(&mut self) {
+        if self.worker_heartbeat.is_none() {
+            return;
+        }
+        if let Some(rx) = &mut self.worker_ping_rx {
+            let alive = match rx.try_recv() {
+                Ok(alive) => alive,
+                Err(oneshot::error::TryRecvError::Empty) => return,
+                Err(oneshot::error::TryRecvError::Closed) => false,
+            };
+            self.worker_ping_rx = None;
+            self.worker_unresponsive = !alive;
+            if !alive {
+                tracing::warn!("check_worker_health: OPC worker did not answer a ping");
+                self.add_message(format!(
+                    "Warning: OPC worker did not answer a health check within {} ms",
+                    opc_da_client::com_worker::PING_TIMEOUT.as_millis()
+                ));
+            }
+            return;
+        }
+
+        let busy = self.current_screen == CurrentScreen::Loading
+            || self.read_result_rx.is_some()
+            || self.retry_result_rx.is_some()
+            || self.engineering_units_rx.is_some()
+            || self
+                .active_sessions
+                .iter()
+                .any(|s| s.read_result_rx.is_some());
+        if busy
+            || self
+                .last_worker_ping
+                .is_some_and(|t| t.elapsed() < WORKER_PING_INTERVAL)
+        {
+            return;
+        }
+        let Some(worker) = self.worker_heartbeat.clone() else {
+            return;
+        };
+        self.last_worker_ping = Some(std::time::Instant::now());
+        let (tx, rx) = oneshot::channel();
+        tokio::spawn(async move {
+            let _ = tx.send(worker.is_alive().await);
+        });
+        self.worker_ping_rx = Some(rx);
+    }
+
+    /// Check the OPC worker queue for backpressure; called once per render cycle.
+    ///
+    /// Warns once when the queue passes 75% of its capacity, pauses
+    /// auto-refresh when it is full, and resumes it once the queue drains
+    /// below half.
+    pub fn check_worker_queue(&mut self) {
+        let Some(depth) = self
+            .worker_queue_depth
+            .as_ref()
+            .map(|d| d.load(Ordering::Relaxed))
+        else {
+            return;
+        };
+
+        if depth > QUEUE_WARN_DEPTH {
+            if !self.queue_warning_shown {
+                self.queue_warning_shown = true;
+                tracing::warn!(depth, "OPC worker queue nearly full");
+                self.add_message(
+                    "Warning: OPC worker queue nearly full — consider reducing refresh rate".into(),
+                );
+            }
+        } else {
+            self.queue_warning_shown = false;
+        }
+
+        if depth >= QUEUE_PAUSE_DEPTH && !self.refresh_paused {
+            self.refresh_paused = true;
+            self.add_message("Auto-refresh paused until the OPC worker catches up".into());
+        } else if depth < QUEUE_RESUME_DEPTH && self.refresh_paused {
+            self.refresh_paused = false;
+            self.add_message("Auto-refresh resumed".into());
+        }
+    }
+
+    pub fn maybe_auto_refresh(&mut self) {
+        if self.current_screen != CurrentScreen::TagValues || self.offline_snapshot.is_some() {
+            return;
+        }
+        if self.read_result_rx.is_some() {
+            return; // Read already in-flight
+        }
+        if self.refresh_paused {
+            return; // Worker queue is full
+        }
+        let elapsed = match self.last_read_time {
+            Some(t) => t.elapsed(),
+            None => return,
+        };
+        if elapsed < AUTO_REFRESH_INTERVAL {
+            return;
+        }
+
+        let server_name = match &self.refresh_server {
+            Some(s) => s.clone(),
+            None => return,
+        };
+        let tag_ids = self.refresh_tag_ids.clone();
+        if tag_ids.is_empty() {
+            return;
+        }
+
+        let live = self.live_mode;
+        let op_id = OpId::start("refresh_tag_values");
+        let _entered = op_id.enter();
+        tracing::debug!(
+            tag_count = tag_ids.len(),
+            live,
+            "Auto-refreshing tag values"
+        );
+        self.read_result_rx = Some(spawn_refresh_read(
+            Arc::clone(&self.opc_provider),
+            server_name,
+            tag_ids,
+            live,
+        ));
+        self.read_op_id = Some(op_id);
+    }
+
+    /// Auto-refresh background sessions, each on its own cycle.
+    ///
+    /// Results are stored in the session; reads that fail are logged and
+    /// retried on the next cycle.
+    pub fn refresh_background_sessions(&mut self) {
+        if self.offline_snapshot.is_some() {
+            return;
+        }
+        for (idx, session) in self.active_sessions.iter_mut().enumerate() {
+            // While another server is being opened, the current session is parked too
+            if idx == self.current_session && !self.opening_session {
+                continue;
+            }
+            if let Some(rx) = &mut session.read_result_rx {
+                match rx.try_recv() {
+                    Ok(Ok(values)) => {
+                        session.tag_values = values;
+                        session.consecutive_read_errors = 0;
+                        session.last_server_activity = Some(std::time::Instant::now());
+                        session.last_operation_failed = false;
+                    }
+                    Ok(Err(e)) => {
+                        session.last_operation_failed = true;
+                        if matches!(e, OpcError::Com { .. }) {
+                            session.consecutive_read_errors =
+                                session.consecutive_read_errors.saturating_add(1);
+                        }
+                        tracing::warn!(server = %session.server, error = %e, "Background refresh failed");
+                    }
+                    Err(oneshot::error::TryRecvError::Empty) => continue,
+                    Err(oneshot::error::TryRecvError::Closed) => {
+                        tracing::warn!(server = %session.server, "Background refresh task terminated");
+                    }
+                }
+                session.read_result_rx = None;
+                session.last_read_time = Some(std::time::Instant::now());
+                continue;
+            }
+            if self.refresh_paused
+                || session.refresh_tag_ids.is_empty()
+                || session
+                    .last_read_time
+                    .is_some_and(|t| t.elapsed() < AUTO_REFRESH_INTERVAL)
+            {
+                continue;
+            }
+            session.read_result_rx = Some(spawn_refresh_read(
+                Arc::clone(&self.opc_provider),
+                session.server.clone(),
+                session.refresh_tag_ids.clone(),
+                false,
+            ));
+        }
+    }
+
+    /// Keep the current server open in the background and pick another one.
+    pub fn open_new_session(&mut self) {
+        if self.current_screen != CurrentScreen::TagValues
+            || self.reject_if_offline("opening another server")
+        {
+            return;
+        }
+        if self.active_sessions.len() >= MAX_SESSIONS {
+            self.add_message(format!("Up to {MAX_SESSIONS} servers can be open at once"));
+            return;
+        }
+        self.park_current_session();
+        self.opening_session = true;
+        self.browsed_server = None;
+        self.push_screen(CurrentScreen::ServerList);
+        let first = if self.servers.is_empty() {
+            None
+        } else {
+            Some(0)
+        };
+        self.selected_index = first;
+        self.list_state.select(first);
+        self.add_message("Select another server to open (Esc to cancel)".into());
+    }
+
+    /// Show the session at `idx` (`Alt+1..4`).
+    pub fn switch_session(&mut self, idx: usize) {
+        if !matches!(
+            self.current_screen,
+            CurrentScreen::TagList | CurrentScreen::TagValues
+        ) || idx == self.current_session
+            || idx >= self.active_sessions.len()
+        {
+            return;
+        }
+        self.park_current_session();
+        self.restore_session(idx);
+    }
+
+    /// Record the server just browsed as a session: a new one when opening
+    /// another server (or for the first browse), otherwise the current one.
+    fn register_browsed_session(&mut self) {
+        let Some(server) = self.browsed_server.clone() else {
+            return;
+        };
+        let host = self.server_host.clone().unwrap_or_default();
+        if self.opening_session || self.active_sessions.is_empty() {
+            self.opening_session = false;
+            self.active_sessions.push(ServerSession {
+                host,
+                server,
+                ..ServerSession::default()
+            });
+            self.current_session = self.active_sessions.len() - 1;
+        } else if let Some(session) = self.active_sessions.get_mut(self.current_session) {
+            session.host = host;
+            session.server = server;
+        }
+    }
+
+    /// Move the current session's state out of `App` into its slot.
+    fn park_current_session(&mut self) {
+        self.inline_edit = None;
+        if self.live_mode {
+            self.live_mode = false;
+            self.stop_live_reads();
+        }
+        self.refresh_server = None;
+        let Some(session) = self.active_sessions.get_mut(self.current_session) else {
+            return;
+        };
+        session.tags = std::mem::take(&mut self.tags);
+        session.browse_time = self.browse_time.take();
+        session.browse_partial = self.browse_partial.take();
+        session.selected_tags = std::mem::take(&mut self.selected_tags);
+        session.tag_values = std::mem::take(&mut self.tag_values);
+        session.list_state = std::mem::take(&mut self.list_state);
+        session.table_state = std::mem::take(&mut self.table_state);
+        session.selected_index = self.selected_index.take();
+        session.refresh_tag_ids = std::mem::take(&mut self.refresh_tag_ids);
+        session.last_read_time = self.last_read_time.take();
+        session.read_result_rx = self.read_result_rx.take();
+        session.consecutive_read_errors = std::mem::take(&mut self.consecutive_read_errors);
+        session.reconnect_tag_ids = std::mem::take(&mut self.reconnect_tag_ids);
+        session.last_server_activity = self.last_server_activity.take();
+        session.last_operation_failed = std::mem::take(&mut self.last_operation_failed);
+    }
+
+    /// Load the session at `idx` into `App` and show it.
+    fn restore_session(&mut self, idx: usize) {
+        let Some(session) = self.active_sessions.get_mut(idx) else {
+            return;
+        };
+        self.current_session = idx;
+        self.tags = std::mem::take(&mut session.tags);
+        self.browse_time = session.browse_time.take();
+        self.browse_partial = session.browse_partial.take();
+        self.selected_tags = std::mem::take(&mut session.selected_tags);
+        self.tag_values = std::mem::take(&mut session.tag_values);
+        self.list_state = std::mem::take(&mut session.list_state);
+        self.table_state = std::mem::take(&mut session.table_state);
+        self.selected_index = session.selected_index.take();
+        self.refresh_tag_ids = std::mem::take(&mut session.refresh_tag_ids);
+        self.last_read_time = session.last_read_time.take();
+        self.read_result_rx = session.read_result_rx.take();
+        self.consecutive_read_errors = std::mem::take(&mut session.consecutive_read_errors);
+        self.reconnect_tag_ids = std::mem::take(&mut session.reconnect_tag_ids);
+        self.last_server_activity = session.last_server_activity.take();
+        self.last_operation_failed = std::mem::take(&mut session.last_operation_failed);
+        self.browsed_server = Some(session.server.clone());
+        self.refresh_server = if self.refresh_tag_ids.is_empty() {
+            None
+        } else {
+            Some(session.server.clone())
+        };
+        self.reset_screen_stack(if self.tag_values.is_empty() {
+            CurrentScreen::TagList
+        } else {
+            CurrentScreen::TagValues
+        });
+    }
+
+    /// Remove the current session, showing the previous one if any remain.
+    ///
+    /// Returns `false` when no other session is left to show.
+    fn close_current_session(&mut self) -> bool {
+        if self.current_session >= self.active_sessions.len() {
+            return false;
+        }
+        let closed = self.active_sessions.remove(self.current_session);
+        if self.active_sessions.is_empty() {
+            self.current_session = 0;
+            return false;
+        }
+        self.add_message(format!("Closed {}", closed.display_name()));
+        self.restore_session(self.current_session.saturating_sub(1));
+        true
+    }
+
+    /// Enter search mode, clearing any previous query.
+    pub fn enter_search_mode(&mut self) {
+        if self.current_screen != CurrentScreen::TagList {
+            return;
+        }
+        self.search_mode = true;
+        self.search_query.clear();
+        self.search_matches.clear();
+        self.search_match_index = 0;
+        self.search_history_index = None;
+    }
+
+    /// Exit search mode, keeping cursor position. A non-empty query becomes
+    /// the newest entry of the search history.
+    pub fn exit_search_mode(&mut self) {
+        self.search_mode = false;
+        self.search_history_index = None;
+        if self.search_query.is_empty() {
+            return;
+        }
+        self.search_history.retain(|q| *q != self.search_query);
+        self.search_history.push_front(self.search_query.clone());
+        self.search_history.truncate(SEARCH_HISTORY_LEN);
+        if let Some(path) = &self.search_history_path
+            && let Err(e) = config::save_search_history(&self.search_history, path)
+        {
+            tracing::warn!(error = %e, "Failed to save search history");
+        }
+    }
+
+    /// Update the search query and recompute matches. Typing after browsing
+    /// the history edits the recalled query.
+    pub fn update_search_query(&mut self, c: char) {
+        self.search_history_index = None;
+        self.search_query.push(c);
+        self.recompute_search_matches();
+    }
+
+    /// Delete last character from search query and recompute.
+    pub fn search_backspace(&mut self) {
+        self.search_history_index = None;
+        self.search_query.pop();
+        self.recompute_search_matches();
+    }
+
+    /// Recall the next older query of the search history (Up), staying on
+    /// the oldest.
+    pub fn search_history_prev(&mut self) {
+        if self.search_history.is_empty() {
+            return;
+        }
+        let index = self
+            .search_history_index
+            .map_or(0, |i| (i + 1).min(self.search_history.len() - 1));
+        self.recall_search(Some(index));
+    }
+
+    /// Recall the next newer query of the search history (Down). Going past
+    /// the newest leaves the history with the query typed before it.
+    pub fn search_history_next(&mut self) {
+        match self.search_history_index {
+            None => {}
+            Some(0) => self.recall_search(None),
+            Some(i) => self.recall_search(Some(i - 1)),
+        }
+    }
+
+    /// Show history entry `index` (or, for `None`, the query typed before
+    /// browsing the history) and its matches.
+    fn recall_search(&mut self, index: Option<usize>) {
+        if self.search_history_index.is_none() {
+            self.search_draft = std::mem::take(&mut self.search_query);
+        }
+        self.search_history_index = index;
+        self.search_query = match index.and_then(|i| self.search_history.get(i)) {
+            Some(query) => query.clone(),
+            None => std::mem::take(&mut self.search_draft),
+        };
+        self.recompute_search_matches();
+    }
+
+    /// Switch between case-sensitive and case-insensitive search and
+    /// recompute the matches of the current query.
+    pub fn toggle_search_case(&mut self) {
+        self.search_case_sensitive = !self.search_case_sensitive;
+        self.recompute_search_matches();
+    }
+
+    fn recompute_search_matches(&mut self) {
+        let case_sensitive = self.search_case_sensitive;
+        let query = if case_sensitive {
+            self.search_query.clone()
+        } else {
+            self.search_query.to_lowercase()
+        };
+        self.search_matches = self
+            .tags
+            .iter()
+            .enumerate()
+            .filter_map(|(idx, tag)| {
+                let found = if case_sensitive {
+                    tag.tag_id.contains(&query)
+                } else {
+                    tag.tag_id.to_lowercase().contains(&query)
+                };
+                if found { Some(idx) } else { None }
+            })
+            .collect();
+
+        self.search_match_index = 0;
+        if let Some(&first_match) = self.search_matches.first() {
+            self.selected_index = Some(first_match);
+            self.list_state.select(Some(first_match));
+        }
+    }
+
+    /// Jump to the next search match.
+    pub fn next_search_match(&mut self) {
+        if self.search_matches.is_empty() {
+            return;
+        }
+        self.search_match_index = (self.search_match_index + 1) % self.search_matches.len();
+        if let Some(&next_idx) = self.search_matches.get(self.search_match_index) {
+            self.selected_index = Some(next_idx);
+            self.list_state.select(Some(next_idx));
+        }
+    }
+
+    /// Jump to the previous search match.
+    pub fn prev_search_match(&mut self) {
+        if self.search_matches.is_empty() {
+            return;
+        }
+        if self.search_match_index == 0 {
+            self.search_match_index = self.search_matches.len() - 1;
+        } else {
+            self.search_match_index -= 1;
+        }
+        if let Some(&prev_idx) = self.search_matches.get(self.search_match_index) {
+            self.selected_index = Some(prev_idx);
+            self.list_state.select(Some(prev_idx));
+        }
+    }
+
+    /// Switch between the dark and light themes (`Alt+T`). Any other theme,
+    /// including config overrides, is replaced by the dark one.
+    pub fn toggle_theme(&mut self) {
+        self.theme = self.theme.toggled_dark_light();
+        let name = if self.theme == Theme::dark() {
+            "dark"
+        } else {
+            "light"
+        };
+        tracing::debug!(theme = name, "toggle_theme");
+        self.add_message(format!("Theme: {name}"));
+    }
+
+    /// Start or stop recording read results to a new file under
+    /// `recordings_dir` (`Ctrl+R`), for replaying later with `--replay`.
+    pub fn toggle_record_mode(&mut self) {
+        if self.record_mode {
+            self.record_mode = false;
+            if let Some(recorder) = self.recorder.take() {
+                self.add_message(format!(
+                    "Recording stopped: {} reads in {}",
+                    recorder.count(),
+                    recorder.path().display()
+                ));
+            }
+            return;
+        }
+
+        let path = recording::new_recording_path(&self.recordings_dir);
+        match Recorder::create(&path) {
+            Ok(recorder) => {
+                tracing::info!(path = %path.display(), "toggle_record_mode: recording");
+                self.add_message(format!("Recording reads to {}", path.display()));
+                self.recorder = Some(recorder);
+                self.record_mode = true;
+            }
+            Err(e) => {
+                tracing::warn!(error = %e, "toggle_record_mode: failed to start");
+                self.add_message(format!("Error starting recording: {e:#}"));
+            }
+        }
+    }
+
+    /// Append a read result to the recording, if one is running. A failed
+    /// write stops the recording rather than interrupting reads.
+    fn record_read(&mut self, values: &[TagValue]) {
+        let Some(recorder) = &mut self.recorder else {
+            return;
+        };
+        let server = self.refresh_server.as_deref().unwrap_or_default();
+        if let Err(e) = recorder.record(server, values, std::time::Instant::now()) {
+            tracing::warn!(error = %e, "record_read: stopping recording");
+            self.recorder = None;
+            self.record_mode = false;
+            self.add_message(format!("Error recording reads, recording stopped: {e:#}"));
+        }
+    }
+
+    /// Open the command palette over the current screen.
+    ///
+    /// Not available while loading, when a background result is about to
+    /// replace the screen underneath.
+    pub fn open_command_palette(&mut self) {
+        if matches!(
+            self.current_screen,
+            CurrentScreen::Loading | CurrentScreen::CommandPalette | CurrentScreen::Exiting
+        ) {
+            return;
+        }
+        self.command_palette = Some(CommandPalette::new(self.current_screen));
+        self.current_screen = CurrentScreen::CommandPalette;
+    }
+
+    /// Show the keybinding help for the current screen (`F1`).
+    pub fn open_help(&mut self) {
+        if matches!(
+            self.current_screen,
+            CurrentScreen::Help | CurrentScreen::Exiting
+        ) {
+            return;
+        }
+        self.help_from_screen = self.current_screen;
+        self.help_scroll = 0;
+        self.current_screen = CurrentScreen::Help;
+    }
+
+    /// Scroll the help text by `lines`, down if positive.
+    pub fn scroll_help(&mut self, lines: i16) {
+        let lines_total =
+            Self::help_text(self.help_from_screen).lines().count() + HELP_GLOBAL.lines().count();
+        let max = lines_total.saturating_sub(1);
+        let max = u16::try_from(max).unwrap_or(u16::MAX);
+        self.help_scroll = self.help_scroll.saturating_add_signed(lines).min(max);
+    }
+
+    /// Every key specific to `screen`, one per line. The keys that work
+    /// everywhere are listed separately in [`HELP_GLOBAL`].
+    pub const fn help_text(screen: CurrentScreen) -> &'static str {
+        match screen {
+            CurrentScreen::Home => HELP_HOME,
+            CurrentScreen::ServerList => HELP_SERVER_LIST,
+            CurrentScreen::TagList => HELP_TAG_LIST,
+            CurrentScreen::TagValues => HELP_TAG_VALUES,
+            CurrentScreen::WriteInput => HELP_WRITE_INPUT,
+            CurrentScreen::Correlation => HELP_CORRELATION,
+            CurrentScreen::Loading => HELP_LOADING,
+            CurrentScreen::CommandPalette => HELP_COMMAND_PALETTE,
+            CurrentScreen::Help | CurrentScreen::Exiting => "",
+        }
+    }
+
+    /// Close the command palette, returning to the screen it was opened from.
+    pub fn close_command_palette(&mut self) {
+        if let Some(palette) = self.command_palette.take() {
+            self.current_screen = palette.origin;
+        }
+    }
+
+    /// Append `c` to the palette query, highlighting the best match.
+    pub fn palette_input(&mut self, c: char) {
+        if let Some(palette) = &mut self.command_palette {
+            palette.input_query.push(c);
+            palette.selected = 0;
+        }
+    }
+
+    /// Delete the last character of the palette query.
+    pub fn palette_backspace(&mut self) {
+        if let Some(palette) = &mut self.command_palette {
+            palette.input_query.pop();
+            palette.selected = 0;
+        }
+    }
+
+    /// Move the palette highlight down (`forward`) or up, wrapping around.
+    pub fn palette_move(&mut self, forward: bool) {
+        if let Some(palette) = &mut self.command_palette {
+            let len = palette.matches().len();
+            if len == 0 {
+                return;
+            }
+            palette.selected = if forward {
+                (palette.selected + 1) % len
+            } else {
+                (palette.selected + len - 1) % len
+            };
+        }
+    }
+
+    /// Close the palette and run the highlighted action.
+    pub fn execute_palette_selection(&mut self) {
+        let Some(action) = self
+            .command_palette
+            .as_ref()
+            .and_then(CommandPalette::selected_action)
+        else {
+            return;
+        };
+        self.close_command_palette();
+        tracing::debug!(action = action.label, "execute_palette_selection");
+        (action.action)(self);
+    }
+
+    /// Show `screen`, recording it as the newest breadcrumb.
+    ///
+    /// Returning to a screen already on the stack drops the breadcrumbs above
+    /// it, so re-reading values or recovering from a failed load never stacks
+    /// duplicates.
+    pub fn push_screen(&mut self, screen: CurrentScreen) {
+        if let Some(pos) = self.screen_stack.iter().position(|&s| s == screen) {
+            self.screen_stack.truncate(pos + 1);
+        } else {
+            self.screen_stack.push(screen);
+        }
+        self.current_screen = screen;
+    }
+
+    /// Show `screen` with the usual Home → `ServerList` → `TagList` → `TagValues`
+    /// breadcrumbs beneath it, for jumps that skip the normal navigation.
+    fn reset_screen_stack(&mut self, screen: CurrentScreen) {
+        let depth = match screen {
+            CurrentScreen::Home => 1,
+            CurrentScreen::ServerList => 2,
+            CurrentScreen::TagList => 3,
+            _ => 4,
+        };
+        self.screen_stack = [
+            CurrentScreen::Home,
+            CurrentScreen::ServerList,
+            CurrentScreen::TagList,
+            CurrentScreen::TagValues,
+        ][..depth]
+            .to_vec();
+        self.push_screen(screen);
+    }
+
+    /// Drop the current breadcrumb and show the one beneath it; `Home` is never popped.
+    fn pop_screen(&mut self) {
+        if self.screen_stack.len() > 1 {
+            self.screen_stack.pop();
+        }
+        self.current_screen = self
+            .screen_stack
+            .last()
+            .copied()
+            .unwrap_or(CurrentScreen::Home);
+    }
+
+    /// Leave the current screen, cleaning up its state and popping the screen stack.
+    ///
+    /// On `Home` there is nowhere left to go, so this exits.
+    pub fn go_back(&mut self) {
+        match self.current_screen {
+            CurrentScreen::Home => {
+                self.current_screen = CurrentScreen::Exiting;
+                return;
+            }
+            CurrentScreen::ServerList if self.opening_session => {
+                self.opening_session = false;
+                self.restore_session(self.current_session);
+                return;
+            }
+            CurrentScreen::ServerList => {
+                self.servers.clear();
+                self.server_host = None;
+                self.server_latencies.clear();
+                self.servers_sorted_by_latency = false;
+                self.server_latency_rx = None;
+                self.selected_index = None;
+                self.list_state.select(None);
+            }
+            CurrentScreen::TagList => {
+                if self.close_current_session() {
+                    return;
+                }
+                self.tags.clear();
+                // Restore selection to the previous server if possible
+                if !self.servers.is_empty() {
+                    self.selected_index = Some(0); // Simple fallback for now
+                    self.list_state.select(Some(0));
+                }
+            }
+            CurrentScreen::TagValues => {
+                if self.live_mode {
+                    self.live_mode = false;
+                    self.stop_live_reads();
+                }
+                self.tag_values.clear();
+                self.refresh_server = None;
+                self.refresh_tag_ids.clear();
+                self.last_read_time = None;
+                // Restore selection to tags list
+                if !self.tags.is_empty() {
+                    self.selected_index = Some(0);
+                    self.list_state.select(Some(0));
+                } else {
+                    self.selected_index = None;
+                    self.list_state.select(None);
+                }
+            }
+            CurrentScreen::Correlation => {}
+            CurrentScreen::WriteInput => {
+                // First Esc dismisses a pending write error, second leaves the screen.
+                if self.write_error.take().is_some() {
+                    return;
+                }
+                self.write_tag_id = None;
+                self.bulk_write_tags.clear();
+                self.write_value_input.clear();
+                self.write_multiline = false;
+            }
+            CurrentScreen::CommandPalette => {
+                self.close_command_palette();
+                return;
+            }
+            CurrentScreen::Help => {
+                self.current_screen = self.help_from_screen;
+                return;
+            }
+            CurrentScreen::Loading | CurrentScreen::Exiting => return,
+        }
+        self.pop_screen();
+    }
+}
+
+/// Keys that work on every screen, shown below each screen's help text.
+pub const HELP_GLOBAL: &str = "Everywhere
+  F1            Show this help; Esc or F1 closes it, Up/Down scroll
+  Ctrl+P        Command palette: type to filter actions, Enter runs one
+  Ctrl+R        Start or stop recording read results
+  Alt+T         Switch between the dark and light themes
+  Alt+1-4       Switch between open servers
+  Up/Down       Move through lists
+  PgUp/PgDn     Page through lists";
+
+const HELP_HOME: &str = "Home: connect to a host
+  Type          Enter a hostname or IP address (empty for this machine)
+  Backspace     Delete the last character
+  Enter         List the OPC DA servers on the host
+  Esc           Quit";
+
+const HELP_SERVER_LIST: &str = "Server List: pick a server
+  Enter         Browse the server's tags
+  g             Browse the items of its public groups instead
+  l             Measure connect latency, sort fastest first
+  Esc           Back to the host
+  q             Quit";
+
+const HELP_TAG_LIST: &str = "Tag List: select tags to read
+  Space         Select or unselect the highlighted tag
+  v             Invert the selection
+  Enter         Read the selected tags
+  s             Search: filter the list by substring
+  w             Write one value to every selected tag
+  f             Show leaf names instead of full tag IDs
+  Ctrl+L        Export the tag list (.csv and .json include branches)
+  Ctrl+W        Split the list into 1-4 columns; Left/Right move between them
+  Esc           Back to the server list
+  q             Quit
+
+While searching (after s)
+  Type          Narrow the matches
+  Backspace     Delete the last character of the query
+  Tab/Shift+Tab Next or previous match
+  Up/Down       Recall older or newer queries
+  Space         Select or unselect the highlighted tag
+  Ctrl+V        Invert the selection of the matches only
+  Ctrl+C        Toggle case-sensitive matching
+  Enter         Leave search and read the selected tags
+  Esc           Leave search
+
+While exporting (after Ctrl+L)
+  Type          Enter the file name
+  Backspace     Delete the last character of the name
+  Enter         Export to that file
+  Esc           Cancel the export";
+
+const HELP_TAG_VALUES: &str = "Tag Values: refreshed every second
+  w             Write a value to the highlighted tag
+  F2            Edit the value in place (Enter writes, Esc cancels)
+  +/-           Write the value +/- one step (Alt: large step)
+  Space         Stop tracking the highlighted tag
+  r             Re-read only the tags whose last read failed
+  l             Toggle live mode (keep one server-side group)
+  t             Toggle the VARIANT type column
+  u             Show engineering units and ranges
+  f             Show leaf names instead of full tag IDs
+  g             List tags that change together
+  c             Copy the table as TSV
+  n             Open another server alongside this one
+  Esc           Back to the tag list
+  q             Quit";
+
+const HELP_WRITE_INPUT: &str = "Write: enter a value
+  Type          Enter the value
+  Enter         Write it (or retry after an error)
+  Ctrl+E        Toggle the multi-line JSON editor for arrays
+  Ctrl+S        Write, in the multi-line editor (Enter adds a line there)
+  Esc           Dismiss an error, then cancel";
+
+const HELP_CORRELATION: &str = "Correlated tags
+  Esc           Back to the tag values
+  q             Quit";
+
+const HELP_LOADING: &str = "Loading: waiting for the server
+  Esc           Stop waiting and go back";
+
+const HELP_COMMAND_PALETTE: &str = "Command palette
+  Type          Filter the actions
+  Up/Down, Tab  Move the highlight
+  Enter         Run the highlighted action
+  Esc           Close the palette";
+
+/// Longest hostname DNS allows.
+const MAX_HOST_LEN: usize = 253;
+
+/// Check a host entered on the Home screen before connecting to it.
+///
+/// Accepts hostnames and IPv4/IPv6 addresses (bracketed or not, with an
+/// optional `%zone`); anything else would only fail later inside DCOM.
+fn validate_host(host: &str) -> Result<(), String> {
+    let host = host.trim();
+    if host.is_empty() {
+        return Err("Host must not be empty".into());
+    }
+    if host.len() > MAX_HOST_LEN {
+        return Err(format!("Host is longer than {MAX_HOST_LEN} characters"));
+    }
+    if let Some(c) = host
+        .chars()
+        .find(|c| !(c.is_ascii_alphanumeric() || "-._:[]%".contains(*c)))
+    {
+        return Err(format!("Host contains invalid character '{c}'"));
+    }
+    if host.contains([':', '[', ']', '%']) && !is_ipv6_literal(host) {
+        return Err(format!("'{host}' is not a valid IPv6 address"));
+    }
+    Ok(())
+}
+
+/// Start a background read for auto-refresh, bounded by [`OPC_TIMEOUT_SECS`].
+fn spawn_refresh_read(
+    provider: Arc<dyn OpcProvider>,
+    server: String,
+    tag_ids: Vec<String>,
+    live: bool,
+) -> oneshot::Receiver<Result<Vec<TagValue>, OpcError>> {
+    let (tx, rx) = oneshot::channel();
+
+    let task = async move {
+        let read = async {
+            if live {
+                provider.read_tag_values_live(&server, tag_ids).await
+            } else {
+                provider
+                    .read_tag_values(&server, tag_ids, Arc::default())
+                    .await
+            }
+        };
+        let result =
+            tokio::time::timeout(std::time::Duration::from_secs(OPC_TIMEOUT_SECS), read).await;
+
+        let final_result = match result {
+            Ok(inner) => inner,
+            Err(_) => {
+                tracing::error!("Background read timed out ({OPC_TIMEOUT_SECS}s)");
+                Err(OpcError::Internal(format!(
+                    "Background read timed out ({OPC_TIMEOUT_SECS}s)"
+                )))
+            }
+        };
+
+        let _ = tx.send(final_result);
+    };
+    // Keep the caller's operation span, if any, on the background read
+    tokio::spawn(task.instrument(tracing::Span::current()));
+
+    rx
+}
+
+/// ` (sent as VT_R8)` for a write that sent a VARIANT, or nothing.
+fn sent_as(sent_vt: Option<u16>) -> String {
+    sent_vt
+        .map(|vt| format!(" (sent as {})", vt_to_string(vt)))
+        .unwrap_or_default()
+}
+
+/// Partial results of a browse of `server` that timed out.
+///
+/// Takes the tags out of `tags_sink` and leaves it `None`, so the walk, which
+/// keeps running on the COM thread, stops writing to it. A sink poisoned by a
+/// panic is recovered: the tags in it are still valid.
+///
+/// # Errors
+/// Returns [`OpcError::Timeout`] if the browse found no tags before timing out.
+fn harvest_timed_out_browse(
+    tags_sink: &std::sync::Mutex<Option<Vec<BrowsedTag>>>,
+    server: &str,
+) -> Result<BrowseResult, OpcError> {
+    let partial_tags = tags_sink
+        .lock()
+        .unwrap_or_else(std::sync::PoisonError::into_inner)
+        .take()
+        .unwrap_or_default();
+
+    if partial_tags.is_empty() {
+        tracing::error!(
+            server = %server,
+            timeout_secs = OPC_TIMEOUT_SECS,
+            "Browse tags timed out with zero tags found"
+        );
+        Err(OpcError::Timeout(format!(
+            "browse of '{server}' after {OPC_TIMEOUT_SECS}s with no tags found"
+        )))
+    } else {
+        tracing::warn!(
+            server = %server,
+            count = partial_tags.len(),
+            timeout_secs = OPC_TIMEOUT_SECS,
+            "Browse tags timed out; returning partial results"
+        );
+        Ok(BrowseResult {
+            tags: partial_tags,
+            partial: true,
+        })
+    }
+}
+
+/// Items of every public group on `server`, each with its group as the
+/// parent branch. Groups that cannot be read are skipped.
+async fn browse_public_groups(
+    provider: &dyn OpcProvider,
+    server: &str,
+    progress: &AtomicUsize,
+) -> Result<Vec<BrowsedTag>, OpcError> {
+    let mut tags = Vec::new();
+    for group in provider.list_public_groups(server).await? {
+        match provider.read_public_group(server, &group).await {
+            Ok(values) => tags.extend(values.into_iter().map(|tv| BrowsedTag {
+                tag_id: tv.tag_id,
+                depth: 1,
+                parent_branch: group.clone(),
+            })),
+            Err(e) => {
+                tracing::warn!(group = %group, error = %e, "Skipping unreadable public group")
+            }
+        }
+        progress.store(tags.len(), Ordering::Relaxed);
+    }
+    Ok(tags)
+}
+
+/// Read `tag_ids` from `server` in batches of at most `batch_size` tags, one
+/// batch after another, and concatenate the values in order.
+///
+/// `progress` is handed to every batch's read, so it counts the tags read
+/// across all batches; `chunks_done` counts the batches completed so far.
+/// The first failing batch fails the whole read.
+async fn read_in_batches(
+    provider: &dyn OpcProvider,
+    server: &str,
+    tag_ids: &[String],
+    batch_size: usize,
+    progress: &Arc<AtomicUsize>,
+    chunks_done: &AtomicUsize,
+) -> Result<Vec<TagValue>, OpcError> {
+    let mut values = Vec::with_capacity(tag_ids.len());
+    for batch in tag_ids.chunks(batch_size.max(1)) {
+        let batch_values = provider
+            .read_tag_values(server, batch.to_vec(), Arc::clone(progress))
+            .await?;
+        // Catch up for providers that do not report progress themselves
+        values.extend(batch_values);
+        progress.fetch_max(values.len(), Ordering::Relaxed);
+        chunks_done.fetch_add(1, Ordering::Relaxed);
+    }
+    Ok(values)
+}
+
+/// Guidance for browse failures with a well-known cause: what went wrong and
+/// a one-line remediation.
+fn browse_error_guidance(error: &OpcError) -> Option<(&'static str, &'static str)> {
+    if matches!(error, OpcError::Timeout(_)) {
+        return Some((
+            "Browse timed out — the server may be overloaded",
+            "Retry when the server is less busy, or narrow the browse with --browse-filter",
+        ));
+    }
+    match error.hresult()?.0.cast_unsigned() {
+        // E_ACCESSDENIED
+        0x8007_0005 => Some((
+            "Access denied — check DCOM permissions on the remote machine",
+            "In dcomcnfg, grant this user Remote Launch, Remote Activation and Remote Access",
+        )),
+        // CO_E_SERVER_EXEC_FAILURE
+        0x8008_0005 => Some((
+            "Server registered but not responding",
+            "Check that the OPC server process starts and is not hung, then retry",
+        )),
+        // RPC_S_SERVER_UNAVAILABLE
+        0x8007_06BA => Some((
+            "Server not reachable — verify hostname and firewall",
+            "Allow TCP 135 and the DCOM dynamic port range through the firewall on both hosts",
+        )),
+        _ => None,
+    }
+}
+
+/// Byte offset of the `idx`-th character of `s` (its length past the end).
+fn char_to_byte(s: &str, idx: usize) -> usize {
+    s.char_indices().nth(idx).map_or(s.len(), |(i, _)| i)
+}
+
+/// Render a value's age compactly, e.g. `"2s ago"` or `"5m ago"`.
+pub fn format_age(age: std::time::Duration) -> String {
+    let secs = age.as_secs();
+    match secs {
+        0..60 => format!("{secs}s ago"),
+        60..3600 => format!("{}m ago", secs / 60),
+        3600..86_400 => format!("{}h ago", secs / 3600),
+        _ => format!("{}d ago", secs / 86_400),
+    }
+}
+
+/// Short symbol for an item's canonical data type, e.g. `"I4"` or `"BOOL"`;
+/// `"—"` for `VT_EMPTY`, which servers report when the type is unknown.
+pub fn data_type_symbol(vt: u16) -> String {
+    if vt == 0 {
+        "—".to_string()
+    } else {
+        vt_to_string(vt).replace("VT_", "")
+    }
+}
+
+/// Make a cell safe for TSV by flattening tabs and line breaks to spaces.
+fn tsv_field(s: &str) -> String {
+    s.replace(['\t', '\r', '\n'], " ")
+}
+
+/// Apply `delta` to a displayed numeric value, keeping integers integral where possible.
+fn nudge_value(current: &str, delta: f64) -> Option<String> {
+    if delta.fract() == 0.0
+        && let Ok(i) = current.parse::<i64>()
+    {
+        #[allow(clippy::cast_possible_truncation)]
+        return Some(i.saturating_add(delta as i64).to_string());
+    }
+    let f = current.parse::<f64>().ok().filter(|f| f.is_finite())?;
+    Some((f + delta).to_string())
+}
+
+/// Helper to parse a user string into a typed [`OpcValue`].
+fn parse_opc_value(s: &str, decimal_comma: bool) -> OpcValue {
+    if decimal_comma && let Some(normalized) = normalize_decimal_comma(s) {
+        return parse_opc_value(&normalized, false);
+    }
+    // Try integer first
+    if let Ok(i) = s.parse::<i32>() {
+        return OpcValue::Int(i);
+    }
+    // Then float
+    if let Ok(f) = s.parse::<f64>() {
+        return OpcValue::Float(f);
+    }
+    // Then boolean
+    match s.to_lowercase().as_str() {
+        "true" | "1" => return OpcValue::Bool(true),
+        "false" | "0" => return OpcValue::Bool(false),
+        _ => {}
+    }
+    // Default to string
+    let result = OpcValue::String(s.to_string());
+    tracing::debug!(input = %s, parsed = ?result, "parse_opc_value: detected type");
+    result
+}
+
+/// `s` with its decimal comma turned into a point, if it is a decimal number
+/// written with one (`-3,5` → `-3.5`); `None` for anything else.
+fn normalize_decimal_comma(s: &str) -> Option<String> {
+    let (int, frac) = s.split_once(',')?;
+    let is_digits = |part: &str| !part.is_empty() && part.bytes().all(|b| b.is_ascii_digit());
+    let unsigned = int.strip_prefix(['-', '+']).unwrap_or(int);
+    (is_digits(unsigned) && is_digits(frac)).then(|| format!("{int}.{frac}"))
+}
+
+/// Parse the multi-line write editor's text as JSON into an [`OpcValue`].
+///
+/// Arrays may nest and mix types; `null` and objects have no VARIANT form
+/// and are rejected.
+fn parse_json_opc_value(s: &str) -> Result<OpcValue, String> {
+    fn convert(value: serde_json::Value) -> Result<OpcValue, String> {
+        match value {
+            serde_json::Value::Bool(b) => Ok(OpcValue::Bool(b)),
+            serde_json::Value::Number(n) => {
+                Ok(n.as_i64().and_then(|i| i32::try_from(i).ok()).map_or_else(
+                    || OpcValue::Float(n.as_f64().unwrap_or(f64::NAN)),
+                    OpcValue::Int,
+                ))
+            }
+            serde_json::Value::String(s) => Ok(OpcValue::String(s)),
+            serde_json::Value::Array(items) => items
+                .into_iter()
+                .map(convert)
+                .collect::<Result<_, _>>()
+                .map(OpcValue::Array),
+            serde_json::Value::Null => Err("JSON null cannot be written".to_string()),
+            serde_json::Value::Object(_) => Err("JSON objects cannot be written".to_string()),
+        }
+    }
+
+    let value = serde_json::from_str(s).map_err(|e| format!("Invalid JSON: {e}"))?;
+    convert(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mockall::predicate::*;
+    use opc_da_client::{MockOpcProvider, OpcResult};
+
+    #[test]
+    fn test_poll_fetch_result_success() {
+        let (tx, rx) = oneshot::channel();
+        let mock = MockOpcProvider::new();
+        let mut app = App::new(Arc::new(mock));
+        app.current_screen = CurrentScreen::Loading;
+        app.fetch_result_rx = Some(rx);
+
+        tx.send(Ok(vec!["Server1".into(), "Server2".into()]))
+            .unwrap();
+        app.poll_fetch_result();
+
+        assert_eq!(app.current_screen, CurrentScreen::ServerList);
+        assert_eq!(app.servers.len(), 2);
+        assert_eq!(app.server_host.as_deref(), Some("localhost"));
+        assert_eq!(app.selected_index, Some(0));
+        assert!(app.fetch_result_rx.is_none());
+        assert!(app.messages.last().unwrap().contains("Found 2 servers"));
+    }
+
+    #[test]
+    fn test_zoned_ipv6_host_is_bracketed_for_display() {
+        let (tx, rx) = oneshot::channel();
+        let mut app = App::new(Arc::new(MockOpcProvider::new()));
+        app.host_input = "fe80::1%3".into();
+        app.current_screen = CurrentScreen::Loading;
+        app.fetch_result_rx = Some(rx);
+
+        tx.send(Ok(vec!["Server1".into()])).unwrap();
+        app.poll_fetch_result();
+        assert_eq!(app.server_host.as_deref(), Some("[fe80::1%3]"));
+
+        browse_into(&mut app, "Server1", &["Tag1"]);
+        assert_eq!(
+            app.active_sessions[0].display_name(),
+            "[fe80::1%3]: Server1"
+        );
+    }
+
+    #[test]
+    fn test_session_records_discovery_host() {
+        let mock = MockOpcProvider::new();
+        let mut app = App::new(Arc::new(mock));
+        app.server_host = Some("[::1]".into());
+        browse_into(&mut app, "Matrikon.OPC.Simulation", &["Tag1"]);
+
+        assert_eq!(app.active_sessions[0].host, "[::1]");
+        assert_eq!(
+            app.active_sessions[0].display_name(),
+            "[::1]: Matrikon.OPC.Simulation"
+        );
+
+        app.go_back();
+        assert_eq!(app.current_screen, CurrentScreen::ServerList);
+        app.go_back();
+        assert_eq!(app.server_host, None);
+    }
+
+    #[test]
+    fn test_poll_fetch_result_error() {
+        let (tx, rx) = oneshot::channel();
+        let mock = MockOpcProvider::new();
+        let mut app = App::new(Arc::new(mock));
+        app.current_screen = CurrentScreen::Loading;
+        app.fetch_result_rx = Some(rx);
+
+        tx.send(Err(OpcError::Internal("Connection failed".to_string())))
+            .unwrap();
+        app.poll_fetch_result();
+
+        assert_eq!(app.current_screen, CurrentScreen::Home);
+        assert!(app.fetch_result_rx.is_none());
+        assert_eq!(
+            app.messages.last().unwrap(),
+            "Error fetching servers: Internal error: Connection failed"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_unreachable_dcom_port_stays_on_home() {
+        let mock = MockOpcProvider::new();
+        let mut app = App::new(Arc::new(mock));
+        app.probe_dcom = true;
+        app.dcom_probe = |host| {
+            assert_eq!(host, "plc-gateway");
+            Box::pin(async { false })
+        };
+        app.host_input = "plc-gateway".into();
+
+        app.start_fetch_servers();
+        assert_eq!(app.current_screen, CurrentScreen::Home);
+        let probed = app.dcom_probe_rx.take().unwrap().await.unwrap();
+        let (tx, rx) = oneshot::channel();
+        tx.send(probed).unwrap();
+        app.dcom_probe_rx = Some(rx);
+        app.poll_fetch_result();
+
+        assert_eq!(app.current_screen, CurrentScreen::Home);
+        assert!(app.fetch_result_rx.is_none());
+        assert_eq!(
+            app.host_error.as_deref(),
+            Some("Port 135 unreachable — check firewall")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_reachable_dcom_port_lists_servers() {
+        let mut mock = MockOpcProvider::new();
+        mock.expect_list_servers()
+            .withf(|host| host == "plc-gateway")
+            .returning(|_| Ok(vec!["Vendor.Server.1".into()]));
+        let mut app = App::new(Arc::new(mock));
+        app.probe_dcom = true;
+        let (tx, rx) = oneshot::channel();
+        app.dcom_probe_rx = Some(rx);
+
+        tx.send(("plc-gateway".into(), true)).unwrap();
+        app.poll_fetch_result();
+
+        assert!(app.dcom_probe_rx.is_none());
+        assert_eq!(app.current_screen, CurrentScreen::Loading);
+        assert!(app.fetch_result_rx.is_some());
+        assert!(app.host_error.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_local_host_skips_dcom_probe() {
+        let mut mock = MockOpcProvider::new();
+        mock.expect_list_servers().returning(|_| Ok(vec![]));
+        let mut app = App::new(Arc::new(mock));
+        app.probe_dcom = true;
+
+        app.start_fetch_servers();
+
+        assert!(app.dcom_probe_rx.is_none());
+        assert_eq!(app.current_screen, CurrentScreen::Loading);
+    }
+
+    #[test]
+    fn test_poll_fetch_result_empty_servers() {
+        let (tx, rx) = oneshot::channel();
+        let mock = MockOpcProvider::new();
+        let mut app = App::new(Arc::new(mock));
+        app.current_screen = CurrentScreen::Loading;
+        app.fetch_result_rx = Some(rx);
+
+        tx.send(Ok(vec![])).unwrap();
+        app.poll_fetch_result();
+
+        assert_eq!(app.current_screen, CurrentScreen::ServerList);
+        assert!(app.servers.is_empty());
+        assert_eq!(app.selected_index, None);
+        assert!(app.messages.last().unwrap().contains("Found 0 servers"));
+    }
+
+    #[test]
+    fn test_poll_fetch_result_closed() {
+        let (tx, rx) = oneshot::channel::<OpcResult<Vec<String>>>();
+        let mock = MockOpcProvider::new();
+        let mut app = App::new(Arc::new(mock));
+        app.current_screen = CurrentScreen::Loading;
+        app.fetch_result_rx = Some(rx);
+
+        // Drop the sender
+        drop(tx);
+        app.poll_fetch_result();
+
+        assert_eq!(app.current_screen, CurrentScreen::Home);
+        assert!(
+            app.messages
+                .last()
+                .unwrap()
+                .contains("terminated unexpectedly")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_start_fetch_servers_sets_loading() {
+        let mut mock = MockOpcProvider::new();
+        mock.expect_list_servers()
+            .returning(|_| Ok(vec!["S1".into()]));
+
+        let mut app = App::new(Arc::new(mock));
+        app.start_fetch_servers();
+
+        assert_eq!(app.current_screen, CurrentScreen::Loading);
+        assert!(app.fetch_result_rx.is_some());
+    }
+
+    #[test]
+    fn test_server_navigation() {
+        let mock = MockOpcProvider::new();
+        let mut app = App::new(Arc::new(mock));
+        app.servers = vec!["S1".into(), "S2".into()];
+        app.selected_index = Some(0);
+        app.current_screen = CurrentScreen::ServerList;
+        app.list_state.select(Some(0));
+
+        app.select_next();
+        assert_eq!(app.selected_index, Some(1));
+
+        app.select_next(); // Should stay at 1
+        assert_eq!(app.selected_index, Some(1));
+
+        app.select_prev();
+        assert_eq!(app.selected_index, Some(0));
+
+        app.select_prev(); // Should stay at 0
+        assert_eq!(app.selected_index, Some(0));
+    }
+
+    #[test]
+    fn test_tag_navigation_logic() {
+        let mock = MockOpcProvider::new();
+        let mut app = App::new(Arc::new(mock));
+        app.servers = vec!["S1".into()];
+        app.selected_index = Some(0);
+        app.tags = vec!["T1".into(), "T2".into()];
+        app.current_screen = CurrentScreen::TagList;
+        app.list_state.select(Some(0));
+
+        // Test boundary check against tags (2), not servers (1)
+        app.select_next();
+        assert_eq!(app.selected_index, Some(1));
+        assert_eq!(app.list_state.selected(), Some(1));
+
+        app.select_next(); // Should stay at 1
+        assert_eq!(app.selected_index, Some(1));
+    }
+
+    #[tokio::test]
+    async fn test_enter_selected_server_navigation() {
+        let mut mock = MockOpcProvider::new();
+        mock.expect_browse_tags()
+            .with(
+                eq("S1"),
+                eq(MAX_BROWSE_TAGS),
+                eq(AccessRightsFilter::Writable),
+                always(),
+            )
+            .returning(|_, _, _, _| Ok(vec!["T1".into()]));
+
+        let mut app = App::new(Arc::new(mock));
+        app.servers = vec!["S1".into()];
+        app.selected_index = Some(0);
+        app.current_screen = CurrentScreen::ServerList;
+        app.list_state.select(Some(0));
+        app.browse_filter = AccessRightsFilter::Writable;
+
+        app.start_browse_tags();
+        // Wait briefly for the spawned task
+        tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+        app.poll_browse_result();
+
+        assert!(matches!(app.current_screen, CurrentScreen::TagList));
+        assert_eq!(app.tags.len(), 1);
+        assert_eq!(app.selected_index, Some(0));
+        assert!(!app.messages.iter().any(|m| m.contains("levels")));
+    }
+
+    #[tokio::test]
+    async fn test_browse_warns_when_depth_limit_hit() {
+        let mut mock = MockOpcProvider::new();
+        mock.expect_browse_tags().returning(|_, _, _, progress| {
+            progress.depth_limit_hit.store(50, Ordering::Relaxed);
+            Ok(vec!["T1".into()])
+        });
+        let mut app = App::new(Arc::new(mock));
+        app.servers = vec!["S1".into()];
+        app.selected_index = Some(0);
+        app.current_screen = CurrentScreen::ServerList;
+
+        app.start_browse_tags();
+        let result = app.browse_result_rx.take().unwrap().await.unwrap();
+        let (tx, rx) = oneshot::channel();
+        tx.send(result).unwrap();
+        app.browse_result_rx = Some(rx);
+        app.poll_browse_result();
+
+        assert_eq!(app.tags.len(), 1);
+        assert!(
+            app.messages
+                .last()
+                .is_some_and(|m| m.contains("namespace deeper than 50 levels — some tags omitted"))
+        );
+    }
+
+    #[test]
+    fn test_browse_estimated_percent() {
+        let mut app = App::new(Arc::new(MockOpcProvider::new()));
+        app.browse_progress.store(500, Ordering::Relaxed);
+        assert_eq!(app.browse_estimated_percent(), None);
+
+        app.estimated_total_tags = Some(1000);
+        assert_eq!(app.browse_estimated_percent(), Some(50));
+
+        // The server may have grown since the estimate was taken
+        app.browse_progress.store(1500, Ordering::Relaxed);
+        assert_eq!(app.browse_estimated_percent(), Some(100));
+
+        app.estimated_total_tags = Some(0);
+        assert_eq!(app.browse_estimated_percent(), None);
+    }
+
+    #[tokio::test]
+    async fn test_complete_browse_becomes_next_estimate() {
+        let mut mock = MockOpcProvider::new();
+        mock.expect_browse_tags()
+            .returning(|_, _, _, _| Ok(vec!["T1".into(), "T2".into(), "T3".into()]));
+        let mut app = App::new(Arc::new(mock));
+        app.servers = vec!["S1".into()];
+        app.selected_index = Some(0);
+        app.reset_screen_stack(CurrentScreen::ServerList);
+
+        app.start_browse_tags();
+        assert_eq!(app.estimated_total_tags, None);
+        wait_for_browse(&mut app).await;
+        assert_eq!(app.tags.len(), 3);
+
+        app.go_back();
+        app.selected_index = Some(0);
+        app.start_browse_tags();
+        assert_eq!(app.estimated_total_tags, Some(3));
+        wait_for_browse(&mut app).await;
+
+        // Estimates are kept per access filter
+        app.go_back();
+        app.selected_index = Some(0);
+        app.browse_filter = AccessRightsFilter::Writable;
+        app.start_browse_tags();
+        assert_eq!(app.estimated_total_tags, None);
+    }
+
+    #[test]
+    fn test_go_back_navigation() {
+        let mock = MockOpcProvider::new();
+        let mut app = App::new(Arc::new(mock));
+        app.servers = vec!["S1".into()];
+        app.selected_index = Some(0);
+        app.tags = vec!["T1".into()];
+        app.reset_screen_stack(CurrentScreen::TagList);
+        app.list_state.select(Some(0));
+
+        // TagList -> ServerList
+        app.go_back();
+        assert!(matches!(app.current_screen, CurrentScreen::ServerList));
+        assert!(app.tags.is_empty());
+        assert_eq!(app.selected_index, Some(0));
+
+        // ServerList -> Home
+        app.go_back();
+        assert!(matches!(app.current_screen, CurrentScreen::Home));
+        assert!(app.servers.is_empty());
+        assert_eq!(app.selected_index, None);
+    }
+
+    #[test]
+    fn test_screen_stack_multi_level_navigation() {
+        let mut app = App::new(Arc::new(MockOpcProvider::new()));
+        app.push_screen(CurrentScreen::ServerList);
+        app.push_screen(CurrentScreen::TagList);
+        app.push_screen(CurrentScreen::TagValues);
+        // Re-reading values must not stack a second TagValues breadcrumb
+        app.push_screen(CurrentScreen::TagValues);
+        assert_eq!(
+            app.screen_stack,
+            [
+                CurrentScreen::Home,
+                CurrentScreen::ServerList,
+                CurrentScreen::TagList,
+                CurrentScreen::TagValues
+            ]
+        );
+
+        app.go_back();
+        assert_eq!(app.current_screen, CurrentScreen::TagList);
+        app.go_back();
+        assert_eq!(app.current_screen, CurrentScreen::ServerList);
+        app.go_back();
+        assert_eq!(app.current_screen, CurrentScreen::Home);
+        assert_eq!(app.screen_stack, [CurrentScreen::Home]);
+    }
+
+    #[test]
+    fn test_shutdown_signal_exits() {
+        let mut app = App::new(Arc::new(MockOpcProvider::new()));
+        app.push_screen(CurrentScreen::ServerList);
+        app.check_shutdown_requested();
+        assert_eq!(app.current_screen, CurrentScreen::ServerList);
+
+        app.shutdown_requested.store(true, Ordering::SeqCst);
+        app.check_shutdown_requested();
+        assert_eq!(app.current_screen, CurrentScreen::Exiting);
+    }
+
+    #[test]
+    fn test_go_back_on_home_exits_without_popping_home() {
+        let mut app = App::new(Arc::new(MockOpcProvider::new()));
+        assert_eq!(app.screen_stack, [CurrentScreen::Home]);
+
+        app.go_back();
+        assert_eq!(app.current_screen, CurrentScreen::Exiting);
+        assert_eq!(app.screen_stack, [CurrentScreen::Home]);
+    }
+
+    #[tokio::test]
+    async fn test_loading_transition() {
+        let mock = MockOpcProvider::new();
+        let mut app = App::new(Arc::new(mock));
+        app.start_fetch_servers();
+        assert_eq!(app.current_screen, CurrentScreen::Loading);
+        assert!(app.messages.iter().any(|m| m.contains("Connecting to")));
+    }
+
+    #[tokio::test]
+    async fn test_tui_navigation_flow() {
+        let (tx, rx) = oneshot::channel();
+        let mock = MockOpcProvider::new();
+        let mut app = App::new(Arc::new(mock));
+
+        // 1. Initial State: Home
+        assert!(matches!(app.current_screen, CurrentScreen::Home));
+        assert_eq!(app.host_input, "localhost");
+
+        // 2. Start fetch
+        app.start_fetch_servers();
+        assert_eq!(app.current_screen, CurrentScreen::Loading);
+        app.fetch_result_rx = Some(rx);
+
+        // 3. Complete fetch
+        tx.send(Ok(vec!["Server1".into()])).unwrap();
+        app.poll_fetch_result();
+
+        assert!(matches!(app.current_screen, CurrentScreen::ServerList));
+        assert_eq!(app.servers.len(), 1);
+        assert_eq!(app.selected_index, Some(0));
+        assert_eq!(app.list_state.selected(), Some(0));
+
+        // 4. User goes back to Home
+        app.go_back();
+        assert!(matches!(app.current_screen, CurrentScreen::Home));
+        assert!(app.servers.is_empty());
+        assert_eq!(app.selected_index, None);
+        assert_eq!(app.list_state.selected(), None);
+    }
+
+    #[tokio::test]
+    async fn test_poll_browse_result_error_shows_message() {
+        let (tx, rx) = oneshot::channel();
+        let mock = MockOpcProvider::new();
+        let mut app = App::new(Arc::new(mock));
+        app.current_screen = CurrentScreen::Loading;
+        app.browse_result_rx = Some(rx);
+
+        // Simulate provider returning a descriptive error
+        tx.send(Err(OpcError::Internal(
+            "DCOM access denied on remote host".to_string(),
+        )))
+        .unwrap();
+
+        app.poll_browse_result();
+
+        assert_eq!(app.current_screen, CurrentScreen::ServerList);
+        assert!(app.browse_result_rx.is_none());
+        let last_msg = app.messages.last().unwrap();
+        assert!(last_msg.contains("Error: "));
+        assert!(last_msg.contains("DCOM access denied")); // Error context preserved
+    }
+
+    #[test]
+    fn test_poll_browse_result_guided_messages() {
+        let com = |code: u32| OpcError::Com {
+            source: windows::core::Error::from_hresult(windows::core::HRESULT(code.cast_signed())),
+        };
+        let cases = [
+            (com(0x8007_0005), "check DCOM permissions"),
+            (
+                OpcError::Timeout("browse of 'S1' after 30s".into()),
+                "the server may be overloaded",
+            ),
+            (com(0x8008_0005), "registered but not responding"),
+            (com(0x8007_06BA), "verify hostname and firewall"),
+        ];
+
+        for (error, guidance) in cases {
+            let (tx, rx) = oneshot::channel();
+            let mut app = App::new(Arc::new(MockOpcProvider::new()));
+            app.current_screen = CurrentScreen::Loading;
+            app.browse_result_rx = Some(rx);
+            tx.send(Err(error)).unwrap();
+
+            app.poll_browse_result();
+
+            assert_eq!(app.current_screen, CurrentScreen::ServerList);
+            let error_msg = app
+                .messages
+                .iter()
+                .find(|m| m.starts_with("Error: "))
+                .unwrap();
+            assert!(error_msg.contains(guidance), "{error_msg}");
+            assert!(app.messages.iter().any(|m| m.starts_with("Hint: ")));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_browse_com_error_lists_registration_checklist() {
+        let mut mock = MockOpcProvider::new();
+        mock.expect_connect_with_diagnostics()
+            .withf(|server| server == "Missing.Server")
+            .returning(|server| {
+                Err(OpcError::Diagnosed {
+                    source: Box::new(OpcError::Com {
+                        source: windows::core::Error::from_hresult(windows::core::HRESULT(
+                            0x8004_0154_u32.cast_signed(),
+                        )),
+                    }),
+                    diagnostics: opc_da_client::ConnectionDiagnostics {
+                        prog_id: server.to_string(),
+                        ..Default::default()
+                    },
+                })
+            });
+        let mut app = App::new(Arc::new(mock));
+        app.current_screen = CurrentScreen::Loading;
+        app.browsed_server = Some("Missing.Server".into());
+        let (tx, rx) = oneshot::channel();
+        app.browse_result_rx = Some(rx);
+        tx.send(Err(OpcError::Com {
+            source: windows::core::Error::from_hresult(windows::core::HRESULT(
+                0x8004_0154_u32.cast_signed(),
+            )),
+        }))
+        .unwrap();
+        app.poll_browse_result();
+
+        let diagnosed = app.diagnostics_rx.take().unwrap().await.unwrap();
+        let (tx, rx) = oneshot::channel();
+        tx.send(diagnosed).unwrap();
+        app.diagnostics_rx = Some(rx);
+        app.poll_connection_diagnostics();
+
+        assert!(app.diagnostics_rx.is_none());
+        let checklist = app
+            .messages
+            .iter()
+            .position(|m| m == "Checklist for Missing.Server:")
+            .unwrap();
+        assert!(app.messages[checklist + 1].contains("is not registered on this machine"));
+    }
+
+    #[test]
+    fn test_poll_browse_result_keeps_branch_metadata() {
+        let (tx, rx) = oneshot::channel();
+        let mock = MockOpcProvider::new();
+        let mut app = App::new(Arc::new(mock));
+        app.current_screen = CurrentScreen::Loading;
+        app.browse_result_rx = Some(rx);
+
+        let tag = BrowsedTag {
+            tag_id: "Channel1.Device1.Tag1".into(),
+            depth: 2,
+            parent_branch: "Channel1/Device1".into(),
+        };
+        tx.send(Ok(vec![tag.clone()].into())).unwrap();
+        app.poll_browse_result();
+
+        assert_eq!(app.current_screen, CurrentScreen::TagList);
+        assert_eq!(app.tags, vec![tag]);
+    }
+
+    #[tokio::test]
+    async fn test_poll_browse_result_closed_shows_message() {
+        let (tx, rx) = oneshot::channel();
+        let mock = MockOpcProvider::new();
+        let mut app = App::new(Arc::new(mock));
+        app.current_screen = CurrentScreen::Loading;
+        app.browse_result_rx = Some(rx);
+
+        // Drop sender without sending — simulates task panic
+        drop(tx);
+
+        app.poll_browse_result();
+
+        assert_eq!(app.current_screen, CurrentScreen::ServerList);
+        assert!(app.browse_result_rx.is_none());
+        let last_msg = app.messages.last().unwrap();
+        assert!(last_msg.contains("terminated unexpectedly"));
+    }
+
+    #[tokio::test]
+    async fn test_poll_browse_result_closed_harvests_partial_tags() {
+        let (tx, rx) = oneshot::channel();
+        let mock = MockOpcProvider::new();
+        let mut app = App::new(Arc::new(mock));
+        app.current_screen = CurrentScreen::Loading;
+        app.browse_result_rx = Some(rx);
+        app.browse_tags_sink
+            .lock()
+            .unwrap()
+            .as_mut()
+            .unwrap()
+            .extend(["Tag1", "Tag2"].map(BrowsedTag::from));
+
+        // The browse panicked after finding two tags.
+        drop(tx);
+        app.poll_browse_result();
+
+        assert_eq!(app.current_screen, CurrentScreen::TagList);
+        assert!(app.browse_result_rx.is_none());
+        assert_eq!(app.tags.len(), 2);
+        assert_eq!(app.selected_tags, vec![false, false]);
+        assert_eq!(app.selected_index, Some(0));
+        let last_msg = app.messages.last().unwrap();
+        assert!(last_msg.starts_with("Warning: "), "{last_msg}");
+        assert!(last_msg.contains("2 tags found so far"), "{last_msg}");
+        assert!(app.browse_tags_sink.lock().unwrap().is_none());
+        assert!(app.browse_partial.is_some());
+    }
+
+    #[test]
+    fn test_harvest_timed_out_browse_closes_sink() {
+        let sink = std::sync::Mutex::new(Some(vec![BrowsedTag::from("Tag1")]));
+
+        let result = harvest_timed_out_browse(&sink, "S1").unwrap();
+
+        assert!(result.partial);
+        assert_eq!(result.tags, vec![BrowsedTag::from("Tag1")]);
+        // The walk is still running; the closed sink takes no more tags.
+        assert!(sink.lock().unwrap().is_none());
+        assert!(matches!(
+            harvest_timed_out_browse(&sink, "S1"),
+            Err(OpcError::Timeout(_))
+        ));
+    }
+
+    #[test]
+    fn test_poll_browse_result_partial_marks_tag_list() {
+        let (tx, rx) = oneshot::channel();
+        let mut app = App::new(Arc::new(MockOpcProvider::new()));
+        app.current_screen = CurrentScreen::Loading;
+        app.browse_result_rx = Some(rx);
+        app.browse_progress.store(5, Ordering::Relaxed);
+
+        tx.send(Ok(BrowseResult {
+            tags: vec![BrowsedTag::from("Tag1")],
+            partial: true,
+        }))
+        .unwrap();
+        app.poll_browse_result();
+
+        assert_eq!(app.current_screen, CurrentScreen::TagList);
+        let found = app.browse_partial.as_ref().unwrap();
+        assert_eq!(found.load(Ordering::Relaxed), 5);
+        assert!(app.messages.last().unwrap().contains("browse timed out"));
+    }
+
+    #[tokio::test]
+    async fn test_poll_browse_result_empty_tags() {
+        let (tx, rx) = oneshot::channel();
+        let mock = MockOpcProvider::new();
+        let mut app = App::new(Arc::new(mock));
+        app.current_screen = CurrentScreen::Loading;
+        app.browse_result_rx = Some(rx);
+
+        tx.send(Ok(Vec::new().into())).unwrap();
+
+        app.poll_browse_result();
+
+        assert_eq!(app.current_screen, CurrentScreen::TagList);
+        assert!(app.tags.is_empty());
+        assert_eq!(app.selected_index, None);
+        assert_eq!(app.list_state.selected(), None);
+        assert!(app.messages.last().unwrap().contains("Found 0 tags"));
+    }
+
+    /// Poll the browse until it leaves the Loading screen.
+    async fn wait_for_browse(app: &mut App) {
+        for _ in 0..100 {
+            app.poll_browse_result();
+            if app.current_screen != CurrentScreen::Loading {
+                return;
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+        }
+        panic!("browse did not finish");
+    }
+
+    #[tokio::test]
+    async fn test_browse_public_groups_lists_group_items() {
+        let mut mock = MockOpcProvider::new();
+        mock.expect_list_public_groups()
+            .returning(|_| Ok(vec!["Plant".into(), "Broken".into()]));
+        mock.expect_read_public_group().returning(|_, group| {
+            if group != "Plant" {
+                return Err(OpcError::InvalidState("no such group".into()));
+            }
+            Ok(["Line1.Speed", "Line1.Running"]
+                .into_iter()
+                .map(|tag_id| TagValue {
+                    tag_id: tag_id.into(),
+                    value: "0".into(),
+                    quality: "Good".into(),
+                    timestamp: String::new(),
+                    raw_timestamp: None,
+                    vt: Some(5),
+                    access_rights: 1,
+                    canonical_data_type: 5,
+                    status: ReadStatus::Ok,
+                })
+                .collect())
+        });
+
+        let mut app = App::new(Arc::new(mock));
+        app.current_screen = CurrentScreen::ServerList;
+        app.servers = vec!["S1".into()];
+        app.selected_index = Some(0);
+
+        app.start_browse_public_groups();
+        assert_eq!(app.current_screen, CurrentScreen::Loading);
+        wait_for_browse(&mut app).await;
+
+        assert_eq!(app.current_screen, CurrentScreen::TagList);
+        let tags: Vec<_> = app
+            .tags
+            .iter()
+            .map(|t| (t.tag_id.as_str(), t.parent_branch.as_str()))
+            .collect();
+        assert_eq!(tags, [("Line1.Speed", "Plant"), ("Line1.Running", "Plant")]);
+        assert_eq!(app.browsed_server.as_deref(), Some("S1"));
+    }
+
+    #[tokio::test]
+    async fn test_browse_public_groups_unsupported() {
+        let mut mock = MockOpcProvider::new();
+        mock.expect_list_public_groups()
+            .returning(|_| Err(OpcError::NotImplemented("public groups".into())));
+
+        let mut app = App::new(Arc::new(mock));
+        app.current_screen = CurrentScreen::ServerList;
+        app.servers = vec!["S1".into()];
+        app.selected_index = Some(0);
+
+        app.start_browse_public_groups();
+        wait_for_browse(&mut app).await;
+
+        assert_eq!(app.current_screen, CurrentScreen::ServerList);
+        assert!(
+            app.messages
+                .last()
+                .unwrap()
+                .contains("does not support public groups")
+        );
+    }
+
+    #[test]
+    fn test_start_browse_no_selection() {
+        let mock = MockOpcProvider::new();
+        let mut app = App::new(Arc::new(mock));
+        app.current_screen = CurrentScreen::ServerList;
+        app.servers = vec!["S1".into()];
+        app.selected_index = None; // No selection
+
+        app.start_browse_tags();
+
+        // Should remain on ServerList — no crash, no Loading transition
+        assert_eq!(app.current_screen, CurrentScreen::ServerList);
+        assert!(app.browse_result_rx.is_none());
+    }
+
+    #[test]
+    fn test_start_browse_wrong_screen() {
+        let mock = MockOpcProvider::new();
+        let mut app = App::new(Arc::new(mock));
+        app.current_screen = CurrentScreen::Home; // Wrong screen
+        app.servers = vec!["S1".into()];
+        app.selected_index = Some(0);
+
+        app.start_browse_tags();
+
+        assert_eq!(app.current_screen, CurrentScreen::Home); // Unchanged
+        assert!(app.browse_result_rx.is_none());
+    }
+
+    #[test]
+    fn test_poll_fetch_result_timeout() {
+        let (tx, rx) = oneshot::channel();
+        let mock = MockOpcProvider::new();
+        let mut app = App::new(Arc::new(mock));
+        app.current_screen = CurrentScreen::Loading;
+        app.fetch_result_rx = Some(rx);
+
+        tx.send(Err(OpcError::Internal(
+            "Connection timed out (30s)".to_string(),
+        )))
+        .unwrap();
+        app.poll_fetch_result();
+
+        assert_eq!(app.current_screen, CurrentScreen::Home);
+        assert!(app.messages.last().unwrap().contains("timed out"));
+    }
+
+    #[test]
+    fn test_start_fetch_servers_rejects_invalid_host() {
+        let mut mock = MockOpcProvider::new();
+        mock.expect_list_servers().times(0);
+        let mut app = App::new(Arc::new(mock));
+
+        app.host_input = "plant pc".into();
+        app.start_fetch_servers();
+        assert_eq!(app.current_screen, CurrentScreen::Home);
+        assert!(app.fetch_result_rx.is_none());
+        assert_eq!(
+            app.host_error.as_deref(),
+            Some("Host contains invalid character ' '")
+        );
+
+        app.host_input = "a".repeat(254);
+        app.start_fetch_servers();
+        assert!(app.host_error.as_deref().unwrap().contains("253"));
+
+        assert!(validate_host("::1").is_ok());
+        assert!(validate_host("[2001:db8::1]").is_ok());
+        assert!(validate_host("plant-pc.example.com").is_ok());
+        assert!(validate_host("fe80::1%3").is_ok());
+        assert!(validate_host("[fe80::1%eth0]").is_ok());
+        assert!(validate_host("").is_err());
+        assert_eq!(
+            validate_host("fe80::1%"),
+            Err("'fe80::1%' is not a valid IPv6 address".into())
+        );
+        assert!(validate_host("plant%pc").is_err());
+        assert!(validate_host("::1]").is_err());
+    }
+
+    #[test]
+    fn test_add_message_ring_buffer() {
+        let mock = MockOpcProvider::new();
+        let mut app = App::new(Arc::new(mock));
+
+        for i in 0..15 {
+            app.add_message(format!("msg-{}", i));
+        }
+
+        assert_eq!(app.messages.len(), 10); // Capped at 10
+        assert_eq!(app.messages[0], "msg-5"); // Oldest surviving
+        assert_eq!(app.messages[9], "msg-14"); // Latest
+    }
+
+    #[test]
+    fn test_add_message_collapses_repeats() {
+        let mock = MockOpcProvider::new();
+        let mut app = App::new(Arc::new(mock));
+
+        for _ in 0..5 {
+            app.add_message("Error reading values: busy".into());
+        }
+        assert_eq!(app.messages.len(), 1);
+        assert_eq!(app.messages[0], "Error reading values: busy [×5]");
+        assert_eq!(app.last_message_count, 5);
+
+        app.add_message("Connected".into());
+        assert_eq!(app.messages.len(), 2);
+        assert_eq!(app.messages[1], "Connected");
+        assert_eq!(app.last_message_count, 1);
+
+        // A repeat after something else starts a new run
+        app.add_message("Error reading values: busy".into());
+        app.add_message("Error reading values: busy".into());
+        assert_eq!(app.messages.len(), 3);
+        assert_eq!(app.messages[2], "Error reading values: busy [×2]");
+
+        // Repeats of different operations collapse too, keeping the last ID
+        let ops = [OpId::start("read"), OpId::start("read")];
+        for op in &ops {
+            app.add_message(format!("Error: timeout{}", op_suffix(Some(op))));
+        }
+        assert_eq!(app.messages.len(), 4);
+        assert_eq!(
+            app.messages[3],
+            format!("Error: timeout{} [×2]", op_suffix(Some(&ops[1])))
+        );
+    }
+
+    #[test]
+    fn test_select_on_empty_list() {
+        let mock = MockOpcProvider::new();
+        let mut app = App::new(Arc::new(mock));
+        app.current_screen = CurrentScreen::ServerList;
+        app.servers = vec![]; // Empty
+
+        app.select_next();
+        assert_eq!(app.selected_index, None);
+
+        app.select_prev();
+        assert_eq!(app.selected_index, None);
+    }
+
+    #[test]
+    fn test_poll_browse_result_no_task() {
+        let mock = MockOpcProvider::new();
+        let mut app = App::new(Arc::new(mock));
+        app.current_screen = CurrentScreen::ServerList;
+
+        // No browse_result_rx set — should not panic
+        app.poll_browse_result();
+
+        assert_eq!(app.current_screen, CurrentScreen::ServerList);
+    }
+
+    #[test]
+    fn test_toggle_tag_selection() {
+        let mock = MockOpcProvider::new();
+        let mut app = App::new(Arc::new(mock));
+        app.current_screen = CurrentScreen::TagList;
+        app.tags = vec!["Tag1".into(), "Tag2".into()];
+        app.selected_tags = vec![false, false];
+        app.selected_index = Some(1);
+
+        app.toggle_tag_selection();
+        assert_eq!(app.selected_tags, vec![false, true]);
+
+        app.toggle_tag_selection();
+        assert_eq!(app.selected_tags, vec![false, false]);
+    }
+
+    #[test]
+    fn test_invert_tag_selection() {
+        let mock = MockOpcProvider::new();
+        let mut app = App::new(Arc::new(mock));
+        app.current_screen = CurrentScreen::TagList;
+        app.tags = vec!["Flow1".into(), "Temp1".into(), "Flow2".into()];
+        app.selected_tags = vec![true, false, false];
+
+        app.invert_tag_selection();
+        assert_eq!(app.selected_tags, vec![false, true, true]);
+
+        // With a search filter only the matches are inverted
+        app.enter_search_mode();
+        for c in "flow".chars() {
+            app.update_search_query(c);
+        }
+        app.invert_tag_selection();
+        assert_eq!(app.selected_tags, vec![true, true, false]);
+        assert_eq!(
+            app.messages.last().unwrap(),
+            "Selection inverted: 2 tags selected"
+        );
+    }
+
+    #[test]
+    fn test_clamp_selection_after_list_shrinks() {
+        let mock = MockOpcProvider::new();
+        let mut app = App::new(Arc::new(mock));
+        app.current_screen = CurrentScreen::TagList;
+        app.tags = vec!["Tag1".into(), "Tag2".into(), "Tag3".into()];
+        app.selected_tags = vec![false; 3];
+        app.selected_index = Some(99);
+        app.list_state.select(Some(99));
+
+        app.clamp_selection();
+        assert_eq!(app.selected_index, Some(2));
+        assert_eq!(app.list_state.selected(), Some(2));
+
+        // Navigation and toggling clamp first instead of indexing past the end
+        app.selected_index = Some(99);
+        app.toggle_tag_selection();
+        assert_eq!(app.selected_tags, vec![false, false, true]);
+        app.selected_index = Some(99);
+        app.select_prev();
+        assert_eq!(app.selected_index, Some(1));
+
+        app.tags.clear();
+        app.clamp_selection();
+        assert_eq!(app.selected_index, None);
+        assert_eq!(app.list_state.selected(), None);
+    }
+
+    #[test]
+    fn test_start_read_values_no_selection() {
+        let mock = MockOpcProvider::new();
+        let mut app = App::new(Arc::new(mock));
+        app.current_screen = CurrentScreen::TagList;
+        app.tags = vec!["Tag1".into()];
+        app.selected_tags = vec![false];
+
+        app.start_read_values();
+
+        assert_eq!(app.current_screen, CurrentScreen::TagList);
+        assert!(app.messages.last().unwrap().contains("No tags selected"));
+        assert!(app.read_result_rx.is_none());
+    }
+
+    #[test]
+    fn test_start_read_values_wrong_screen() {
+        let mock = MockOpcProvider::new();
+        let mut app = App::new(Arc::new(mock));
+        app.current_screen = CurrentScreen::ServerList;
+
+        app.start_read_values();
+
+        assert_eq!(app.current_screen, CurrentScreen::ServerList);
+        assert!(app.read_result_rx.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_start_read_values_success() {
+        use mockall::predicate::eq;
+        let mut mock = MockOpcProvider::new();
+        mock.expect_read_tag_values()
+            .with(eq("TestServer"), eq(vec!["Tag1".to_string()]), always())
+            .returning(|_, _, _| Ok(vec![]));
+
+        let mut app = App::new(Arc::new(mock));
+        app.current_screen = CurrentScreen::TagList;
+        app.tags = vec!["Tag1".into()];
+        app.selected_tags = vec![true];
+        app.browsed_server = Some("TestServer".into());
+
+        app.start_read_values();
+
+        assert_eq!(app.current_screen, CurrentScreen::Loading);
+        assert!(app.read_result_rx.is_some());
+        assert_eq!(app.refresh_server, Some("TestServer".into()));
+    }
+
+    #[test]
+    fn test_start_read_values_no_browsed_server() {
+        let mock = MockOpcProvider::new();
+        let mut app = App::new(Arc::new(mock));
+        app.current_screen = CurrentScreen::TagList;
+        app.tags = vec!["Tag1".into()];
+        app.selected_tags = vec![true];
+        app.browsed_server = None; // Simulate missing context
+
+        app.start_read_values();
+
+        assert_eq!(app.current_screen, CurrentScreen::TagList); // Should not transition
+        assert!(app.read_result_rx.is_none());
+        assert!(app.messages.last().unwrap().contains("No server context"));
+    }
+
+    #[test]
+    fn test_poll_read_result_success() {
+        let (tx, rx) = oneshot::channel();
+        let mock = MockOpcProvider::new();
+        let mut app = App::new(Arc::new(mock));
+        app.current_screen = CurrentScreen::Loading;
+        app.read_result_rx = Some(rx);
+
+        let values = vec![TagValue {
+            tag_id: "Tag1".into(),
+            value: "123".into(),
+            quality: "Good".into(),
+            timestamp: "Today".into(),
+            raw_timestamp: None,
+            vt: None,
+            access_rights: 0,
+            canonical_data_type: 0,
+            status: ReadStatus::Ok,
+        }];
+
+        tx.send(Ok(values)).unwrap();
+        app.poll_read_result();
+
+        assert_eq!(app.current_screen, CurrentScreen::TagValues);
+        assert_eq!(app.tag_values.len(), 1);
+        assert_eq!(app.tag_values[0].value, "123");
+        assert!(app.read_result_rx.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_recorded_reads_replay_in_order() {
+        let dir = std::env::temp_dir().join(format!("opc-cli-{}-recordings", std::process::id()));
+        let mut app = App::new(Arc::new(MockOpcProvider::new()));
+        app.recordings_dir = dir.clone();
+        app.refresh_server = Some("Server1".into());
+        app.toggle_record_mode();
+        assert!(app.record_mode);
+
+        let reads: Vec<Vec<TagValue>> = (1..=3)
+            .map(|n| {
+                ["Tag1", "Tag2"]
+                    .iter()
+                    .map(|tag| TagValue {
+                        tag_id: (*tag).into(),
+                        value: format!("{tag}-{n}"),
+                        quality: "Good".into(),
+                        timestamp: format!("2024-01-01 12:00:0{n}"),
+                        raw_timestamp: None,
+                        vt: Some(8),
+                        access_rights: 0,
+                        canonical_data_type: 0,
+                        status: ReadStatus::Ok,
+                    })
+                    .collect()
+            })
+            .collect();
+        for values in &reads {
+            let (tx, rx) = oneshot::channel();
+            app.read_result_rx = Some(rx);
+            tx.send(Ok(values.clone())).unwrap();
+            app.poll_read_result();
+        }
+        let path = app.recorder.as_ref().unwrap().path().to_path_buf();
+        app.toggle_record_mode();
+        assert!(!app.record_mode);
+        assert!(app.messages.last().unwrap().contains("3 reads"));
+
+        let replay = crate::recording::ReplayOpcProvider::load(&path).unwrap();
+        let _ = std::fs::remove_dir_all(&dir);
+        let tags = replay
+            .browse_tags(
+                "Server1",
+                usize::MAX,
+                AccessRightsFilter::Any,
+                Arc::default(),
+                Arc::default(),
+                Arc::default(),
+                Arc::default(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(
+            tags,
+            vec![BrowsedTag::from("Tag1"), BrowsedTag::from("Tag2")]
+        );
+        for values in &reads {
+            let replayed = replay
+                .read_tag_values("Server1", vec![], Arc::default())
+                .await
+                .unwrap();
+            assert_eq!(&replayed, values);
+        }
+    }
+
+    #[test]
+    fn test_stale_connection_banner_after_idle() {
+        let (tx, rx) = oneshot::channel();
+        let mut app = App::new(Arc::new(MockOpcProvider::new()));
+        app.stale_after = Some(std::time::Duration::ZERO);
+        assert!(app.stale_connection_banner().is_none(), "nothing done yet");
+
+        app.read_result_rx = Some(rx);
+        tx.send(Ok(Vec::new())).unwrap();
+        app.poll_read_result();
+        assert!(app.last_server_activity.is_some());
+        assert!(app.stale_connection_banner().is_some());
+
+        app.stale_after = Some(std::time::Duration::from_secs(60));
+        assert!(app.stale_connection_banner().is_none());
+
+        app.stale_after = Some(std::time::Duration::ZERO);
+        app.offline_snapshot = Some("2024-01-01 12:00:00".into());
+        assert!(app.stale_connection_banner().is_none());
+    }
+
+    #[test]
+    fn test_connection_state_follows_operation_outcomes() {
+        let mut app = App::new(Arc::new(MockOpcProvider::new()));
+        assert_eq!(app.connection_state(), ConnectionState::Idle);
+
+        deliver_read(&mut app, vec![tag_value("A", "1".into())]);
+        assert_eq!(app.connection_state(), ConnectionState::Connected);
+
+        let (tx, rx) = oneshot::channel();
+        app.read_result_rx = Some(rx);
+        tx.send(Err(OpcError::Internal("Read failed".into())))
+            .unwrap();
+        app.poll_read_result();
+        assert_eq!(app.connection_state(), ConnectionState::Disconnected);
+
+        deliver_read(&mut app, vec![tag_value("A", "2".into())]);
+        assert_eq!(app.connection_state(), ConnectionState::Connected);
+
+        app.reconnect_tag_ids = vec!["A".into()];
+        assert_eq!(app.connection_state(), ConnectionState::Reconnecting);
+        app.reconnect_tag_ids.clear();
+
+        app.worker_unresponsive = true;
+        assert_eq!(app.connection_state(), ConnectionState::Disconnected);
+        app.worker_unresponsive = false;
+
+        app.stale_after = Some(std::time::Duration::ZERO);
+        assert_eq!(app.connection_state(), ConnectionState::Idle);
+    }
+
+    #[test]
+    fn test_tick_loading_advances_and_times_the_wait() {
+        let (_tx, rx) = oneshot::channel();
+        let mut app = App::new(Arc::new(MockOpcProvider::new()));
+
+        for _ in 0..10 {
+            app.tick_loading();
+        }
+        assert_eq!(app.loading_tick, 10);
+        assert!(app.loading_since.is_none());
+
+        app.browse_result_rx = Some(rx);
+        app.tick_loading();
+        let since = app.loading_since.unwrap();
+        app.tick_loading();
+        assert_eq!(app.loading_since, Some(since), "kept for the whole wait");
+
+        app.browse_result_rx = None;
+        app.tick_loading();
+        assert!(app.loading_since.is_none());
+
+        app.loading_tick = u8::MAX;
+        app.tick_loading();
+        assert_eq!(app.loading_tick, 0);
+    }
+
+    #[test]
+    fn test_poll_read_result_error() {
+        let (tx, rx) = oneshot::channel();
+        let mock = MockOpcProvider::new();
+        let mut app = App::new(Arc::new(mock));
+        app.current_screen = CurrentScreen::Loading;
+        app.read_result_rx = Some(rx);
+
+        tx.send(Err(OpcError::Internal("Read failed".to_string())))
+            .unwrap();
+        app.poll_read_result();
+
+        assert_eq!(app.current_screen, CurrentScreen::TagList);
+        assert!(app.read_result_rx.is_none());
+        assert!(
+            app.messages
+                .last()
+                .unwrap()
+                .contains("Error reading values")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_read_messages_share_an_operation_id() {
+        let mut mock = MockOpcProvider::new();
+        mock.expect_read_tag_values()
+            .returning(|_, _, _| Err(OpcError::Internal("Read failed".into())));
+        let mut app = App::new(Arc::new(mock));
+        app.current_screen = CurrentScreen::TagList;
+        app.tags = vec!["Tag1".into()];
+        app.selected_tags = vec![true];
+        app.browsed_server = Some("TestServer".into());
+
+        app.start_read_values();
+        let op_id = app.read_op_id.clone().expect("read has an ID").to_string();
+        assert!(
+            app.messages
+                .last()
+                .unwrap()
+                .ends_with(&format!("... [op {op_id}]"))
+        );
+
+        for _ in 0..100 {
+            app.poll_read_result();
+            if app.current_screen != CurrentScreen::Loading {
+                break;
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+        }
+        let error = app.messages.last().unwrap();
+        assert!(error.starts_with("Error reading values"), "{error}");
+        assert!(error.ends_with(&format!("[op {op_id}]")), "{error}");
+
+        app.start_read_values();
+        assert_ne!(
+            app.read_op_id.as_ref().unwrap().to_string(),
+            op_id,
+            "each action gets its own ID"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_reconnect_after_consecutive_com_errors() {
+        const RPC_E_DISCONNECTED: u32 = 0x8001_0108;
+        let mut mock = MockOpcProvider::new();
+        let mut failures = 0;
+        mock.expect_read_tag_values()
+            .times(6)
+            .returning(move |_, tag_ids, _| {
+                if failures < 5 {
+                    failures += 1;
+                    return Err(OpcError::Com {
+                        source: windows::core::Error::from_hresult(windows::core::HRESULT(
+                            RPC_E_DISCONNECTED.cast_signed(),
+                        )),
+                    });
+                }
+                Ok(tag_ids
+                    .into_iter()
+                    .map(|tag_id| tag_value(&tag_id, "fresh".into()))
+                    .collect())
+            });
+        mock.expect_browse_tags()
+            .times(1)
+            .returning(|_, _, _, _| Ok(vec!["A".into(), "B".into(), "C".into()]));
+        let mut app = App::new(Arc::new(mock));
+        app.browsed_server = Some("Server".into());
+        app.tags = vec!["A".into(), "B".into(), "C".into()];
+        app.selected_tags = vec![true, false, true];
+        app.reset_screen_stack(CurrentScreen::TagValues);
+        app.refresh_server = Some("Server".into());
+        app.refresh_tag_ids = vec!["A".into(), "C".into()];
+        app.tag_values = vec![tag_value("A", "old".into()), tag_value("C", "old".into())];
+
+        for attempt in 1..=4 {
+            app.last_read_time = Some(std::time::Instant::now() - AUTO_REFRESH_INTERVAL);
+            app.maybe_auto_refresh();
+            redeliver_read(&mut app).await;
+            assert_eq!(app.current_screen, CurrentScreen::TagValues);
+            assert_eq!(app.consecutive_read_errors, attempt);
+        }
+
+        app.last_read_time = Some(std::time::Instant::now() - AUTO_REFRESH_INTERVAL);
+        app.maybe_auto_refresh();
+        redeliver_read(&mut app).await;
+        assert_eq!(app.current_screen, CurrentScreen::Loading);
+        assert_eq!(app.consecutive_read_errors, 0);
+        assert!(app.refresh_tag_ids.is_empty());
+
+        let browse = app.browse_result_rx.take().unwrap().await.unwrap();
+        let (tx, rx) = oneshot::channel();
+        tx.send(browse).unwrap();
+        app.browse_result_rx = Some(rx);
+        app.poll_browse_result();
+        assert_eq!(app.current_screen, CurrentScreen::Loading, "reading again");
+        assert_eq!(app.selected_tags, [true, false, true]);
+        redeliver_read(&mut app).await;
+
+        assert_eq!(app.current_screen, CurrentScreen::TagValues);
+        assert_eq!(app.refresh_tag_ids, ["A", "C"]);
+        assert_eq!(app.tag_values.len(), 2);
+        assert!(app.tag_values.iter().all(|tv| tv.value == "fresh"));
+    }
+
+    #[tokio::test]
+    async fn test_no_reconnect_when_disabled() {
+        let mut app = App::new(Arc::new(MockOpcProvider::new()));
+        app.auto_reconnect = false;
+        app.reset_screen_stack(CurrentScreen::TagValues);
+        app.refresh_server = Some("Server".into());
+        app.refresh_tag_ids = vec!["A".into()];
+        let (tx, rx) = oneshot::channel();
+        app.read_result_rx = Some(rx);
+        tx.send(Err(OpcError::Com {
+            source: windows::core::Error::empty(),
+        }))
+        .unwrap();
+        app.poll_read_result();
+
+        assert_eq!(app.current_screen, CurrentScreen::TagList);
+        assert_eq!(app.consecutive_read_errors, 0);
+    }
+
+    /// Wait for the read in flight and hand its result to `poll_read_result`.
+    async fn redeliver_read(app: &mut App) {
+        let result = app.read_result_rx.take().unwrap().await.unwrap();
+        let (tx, rx) = oneshot::channel();
+        tx.send(result).unwrap();
+        app.read_result_rx = Some(rx);
+        app.poll_read_result();
+    }
+
+    #[test]
+    fn test_help_text_lists_screen_keys() {
+        let tag_list = App::help_text(CurrentScreen::TagList);
+        assert!(tag_list.contains("Space"));
+        assert!(tag_list.contains("  s "));
+        assert!(tag_list.contains("While searching"), "search keys separate");
+        for screen in [
+            CurrentScreen::Home,
+            CurrentScreen::ServerList,
+            CurrentScreen::TagValues,
+            CurrentScreen::WriteInput,
+            CurrentScreen::Loading,
+        ] {
+            assert!(App::help_text(screen).contains("Esc"), "{screen:?}");
+        }
+    }
+
+    #[test]
+    fn test_go_back_from_tag_values() {
+        let mock = MockOpcProvider::new();
+        let mut app = App::new(Arc::new(mock));
+        app.reset_screen_stack(CurrentScreen::TagValues);
+        app.tags = vec!["Tag1".into()];
+        app.tag_values = vec![TagValue {
+            tag_id: "Tag1".into(),
+            value: "100".into(),
+            quality: "Good".into(),
+            timestamp: String::new(),
+            raw_timestamp: None,
+            vt: None,
+            access_rights: 0,
+            canonical_data_type: 0,
+            status: ReadStatus::Ok,
+        }];
+
+        app.go_back();
+
+        assert_eq!(app.current_screen, CurrentScreen::TagList);
+        assert!(app.tag_values.is_empty());
+        assert_eq!(app.tags.len(), 1); // Tags preserved
+    }
+
+    #[test]
+    fn test_select_next_on_tag_values() {
+        let mock = MockOpcProvider::new();
+        let mut app = App::new(Arc::new(mock));
+        app.current_screen = CurrentScreen::TagValues;
+        app.tag_values = vec![
+            TagValue {
+                tag_id: "T1".into(),
+                value: "V1".into(),
+                quality: "Q".into(),
+                timestamp: "T".into(),
+                raw_timestamp: None,
+                vt: None,
+                access_rights: 0,
+                canonical_data_type: 0,
+                status: ReadStatus::Ok,
+            },
+            TagValue {
+                tag_id: "T2".into(),
+                value: "V2".into(),
+                quality: "Q".into(),
+                timestamp: "T".into(),
+                raw_timestamp: None,
+                vt: None,
+                access_rights: 0,
+                canonical_data_type: 0,
+                status: ReadStatus::Ok,
+            },
+        ];
+        app.selected_index = Some(0);
+
+        app.select_next();
+        assert_eq!(app.selected_index, Some(1));
+
+        app.select_next(); // Should stay at 1
+        assert_eq!(app.selected_index, Some(1));
+    }
+
+    #[test]
+    fn test_page_down_basic() {
+        let mock = MockOpcProvider::new();
+        let mut app = App::new(Arc::new(mock));
+        app.current_screen = CurrentScreen::TagList;
+        app.tags = (0..50)
+            .map(|i| BrowsedTag::from(format!("T{}", i)))
+            .collect();
+        app.selected_index = Some(0);
+
+        app.page_down();
+        assert_eq!(app.selected_index, Some(20));
+
+        app.page_down();
+        assert_eq!(app.selected_index, Some(40));
+
+        app.page_down(); // Should clamp to 49
+        assert_eq!(app.selected_index, Some(49));
+    }
+
+    #[test]
+    fn test_page_up_basic() {
+        let mock = MockOpcProvider::new();
+        let mut app = App::new(Arc::new(mock));
+        app.current_screen = CurrentScreen::TagList;
+        app.tags = (0..50)
+            .map(|i| BrowsedTag::from(format!("T{}", i)))
+            .collect();
+        app.selected_index = Some(49);
+
+        app.page_up();
+        assert_eq!(app.selected_index, Some(29));
+
+        app.page_up();
+        assert_eq!(app.selected_index, Some(9));
+
+        app.page_up(); // Should clamp to 0
+        assert_eq!(app.selected_index, Some(0));
+    }
+
+    #[test]
+    fn test_tag_columns_navigation_and_paging() {
+        let mut app = App::new(Arc::new(MockOpcProvider::new()));
+        app.current_screen = CurrentScreen::TagList;
+        app.tags = (0..50).map(|i| BrowsedTag::from(format!("T{i}"))).collect();
+        app.selected_index = Some(3);
+
+        // One column: Left/Right do nothing.
+        app.select_next_column();
+        assert_eq!(app.selected_index, Some(3));
+
+        app.cycle_tag_columns();
+        assert_eq!(app.tag_columns, 2);
+        assert_eq!(app.tag_column_rows(), 25);
+
+        app.select_next_column();
+        assert_eq!(app.selected_index, Some(28));
+        // From the last column, wrap to the next row of the first.
+        app.select_next_column();
+        assert_eq!(app.selected_index, Some(4));
+        // And back again.
+        app.select_prev_column();
+        assert_eq!(app.selected_index, Some(28));
+        app.select_prev_column();
+        assert_eq!(app.selected_index, Some(3));
+
+        app.selected_index = Some(0);
+        app.select_prev_column();
+        assert_eq!(app.selected_index, Some(0));
+
+        // A page covers every column.
+        app.page_down();
+        assert_eq!(app.selected_index, Some(40));
+        app.page_up();
+        assert_eq!(app.selected_index, Some(0));
+
+        for _ in 0..3 {
+            app.cycle_tag_columns();
+        }
+        assert_eq!(app.tag_columns, 1);
+    }
+
+    #[test]
+    fn test_search_basic_matching() {
+        let mock = MockOpcProvider::new();
+        let mut app = App::new(Arc::new(mock));
+        app.current_screen = CurrentScreen::TagList;
+        app.tags = vec![
+            "System.Cpu".into(),
+            "System.Mem".into(),
+            "User.Data".into(),
+            "User.Settings".into(),
+        ];
+        app.selected_tags = vec![false; 4];
+
+        app.enter_search_mode();
+        assert!(app.search_mode);
+
+        app.update_search_query('s');
+        app.update_search_query('y');
+        app.update_search_query('s'); // Query: "sys"
+
+        assert_eq!(app.search_matches.len(), 2);
+        assert_eq!(app.search_matches[0], 0); // System.Cpu
+        assert_eq!(app.search_matches[1], 1); // System.Mem
+        assert_eq!(app.selected_index, Some(0));
+
+        app.next_search_match();
+        assert_eq!(app.selected_index, Some(1));
+
+        app.next_search_match(); // Should wrap
+        assert_eq!(app.selected_index, Some(0));
+
+        app.search_backspace(); // Query: "sy"
+        assert_eq!(app.search_matches.len(), 2);
+
+        app.exit_search_mode();
+        assert!(!app.search_mode);
+    }
+
+    #[test]
+    fn test_case_sensitive_search() {
+        let mock = MockOpcProvider::new();
+        let mut app = App::new(Arc::new(mock));
+        app.current_screen = CurrentScreen::TagList;
+        app.tags = vec!["Tag.A".into(), "tag.a".into()];
+        app.selected_tags = vec![false; 2];
+
+        app.enter_search_mode();
+        for c in "Tag.A".chars() {
+            app.update_search_query(c);
         }
+        assert_eq!(app.search_matches, [0, 1], "case ignored by default");
+
+        app.toggle_search_case();
+        assert!(app.search_case_sensitive);
+        assert_eq!(
+            app.search_matches,
+            [0],
+            "recomputed without another keystroke"
+        );
+
+        app.search_backspace();
+        app.update_search_query('A');
+        assert_eq!(app.search_matches, [0], "editing keeps the case mode");
+
+        app.toggle_search_case();
+        assert!(!app.search_case_sensitive);
+        assert_eq!(app.search_matches, [0, 1]);
     }
-}
 
-/// Helper to parse a user string into a typed [`OpcValue`].
-fn parse_opc_value(s: &str) -> OpcValue {
-    // Try integer first
-    if let Ok(i) = s.parse::<i32>() {
-        return OpcValue::Int(i);
+    /// Run one search for `query` and leave search mode.
+    fn search_for(app: &mut App, query: &str) {
+        app.enter_search_mode();
+        for c in query.chars() {
+            app.update_search_query(c);
+        }
+        app.exit_search_mode();
     }
-    // Then float
-    if let Ok(f) = s.parse::<f64>() {
-        return OpcValue::Float(f);
+
+    #[test]
+    fn test_search_history_cycles_through_queries() {
+        let mut app = App::new(Arc::new(MockOpcProvider::new()));
+        app.current_screen = CurrentScreen::TagList;
+        app.tags = vec!["Pump.Speed".into(), "Tank.Level".into(), "Valve".into()];
+        app.selected_tags = vec![false; 3];
+        for query in ["pump", "tank", "valve"] {
+            search_for(&mut app, query);
+        }
+
+        app.enter_search_mode();
+        app.search_history_prev();
+        assert_eq!(app.search_query, "valve");
+        assert_eq!(app.search_matches, [2], "matches follow the recalled query");
+        app.search_history_prev();
+        assert_eq!(app.search_query, "tank");
+        app.search_history_prev();
+        assert_eq!(app.search_query, "pump");
+        app.search_history_prev();
+        assert_eq!(app.search_query, "pump", "stays on the oldest");
+        assert_eq!(app.search_history_index, Some(2));
+
+        app.search_history_next();
+        assert_eq!(app.search_query, "tank");
+        app.search_history_next();
+        assert_eq!(app.search_query, "valve");
+        app.search_history_next();
+        assert_eq!(app.search_query, "");
+        assert_eq!(app.search_history_index, None);
+        assert_eq!(app.search_matches, [0, 1, 2]);
     }
-    // Then boolean
-    match s.to_lowercase().as_str() {
-        "true" | "1" => return OpcValue::Bool(true),
-        "false" | "0" => return OpcValue::Bool(false),
-        _ => {}
+
+    #[test]
+    fn test_search_history_deduplicates() {
+        let mut app = App::new(Arc::new(MockOpcProvider::new()));
+        app.current_screen = CurrentScreen::TagList;
+        for query in ["pump", "tank", "pump", ""] {
+            search_for(&mut app, query);
+        }
+        assert_eq!(app.search_history, ["pump", "tank"]);
+
+        for i in 0..30 {
+            search_for(&mut app, &format!("q{i}"));
+        }
+        assert_eq!(app.search_history.len(), SEARCH_HISTORY_LEN);
+        assert_eq!(app.search_history[0], "q29");
     }
-    // Default to string
-    let result = OpcValue::String(s.to_string());
-    tracing::debug!(input = %s, parsed = ?result, "parse_opc_value: detected type");
-    result
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use mockall::predicate::*;
-    use opc_da_client::{MockOpcProvider, OpcResult};
+    #[test]
+    fn test_typing_after_history_edits_recalled_query() {
+        let mut app = App::new(Arc::new(MockOpcProvider::new()));
+        app.current_screen = CurrentScreen::TagList;
+        app.tags = vec!["Tank.Level".into(), "Tank.Temp".into()];
+        app.selected_tags = vec![false; 2];
+        search_for(&mut app, "tank");
+
+        app.enter_search_mode();
+        app.search_history_prev();
+        app.update_search_query('.');
+        app.update_search_query('t');
+        assert_eq!(app.search_query, "tank.t");
+        assert_eq!(app.search_history_index, None);
+        assert_eq!(app.search_matches, [1]);
+
+        app.exit_search_mode();
+        assert_eq!(app.search_history, ["tank.t", "tank"]);
+    }
 
     #[test]
-    fn test_poll_fetch_result_success() {
-        let (tx, rx) = oneshot::channel();
+    fn test_search_history_restores_typed_query() {
+        let mut app = App::new(Arc::new(MockOpcProvider::new()));
+        app.current_screen = CurrentScreen::TagList;
+        app.tags = vec!["Tank.Level".into(), "Valve".into()];
+        app.selected_tags = vec![false; 2];
+        search_for(&mut app, "tank");
+        search_for(&mut app, "level");
+
+        app.enter_search_mode();
+        app.update_search_query('v');
+        app.update_search_query('a');
+        app.search_history_prev();
+        app.search_history_prev();
+        assert_eq!(app.search_query, "tank");
+
+        app.search_history_next();
+        app.search_history_next();
+        assert_eq!(app.search_query, "va");
+        assert_eq!(app.search_history_index, None);
+        assert_eq!(app.search_matches, [1]);
+    }
+
+    #[test]
+    fn test_sort_servers_by_latency() {
+        use std::time::Duration;
+
         let mock = MockOpcProvider::new();
         let mut app = App::new(Arc::new(mock));
-        app.current_screen = CurrentScreen::Loading;
-        app.fetch_result_rx = Some(rx);
+        app.current_screen = CurrentScreen::ServerList;
+        app.servers = vec![
+            "Slow".into(),
+            "Timeout".into(),
+            "Fast".into(),
+            "Medium".into(),
+            "Fastest".into(),
+        ];
+        app.server_latencies = vec![
+            Some(Duration::from_millis(300)),
+            None,
+            Some(Duration::from_millis(20)),
+            Some(Duration::from_millis(120)),
+            Some(Duration::from_millis(5)),
+        ];
+        app.selected_index = Some(3);
+        app.list_state.select(Some(3));
 
-        tx.send(Ok(vec!["Server1".into(), "Server2".into()]))
-            .unwrap();
-        app.poll_fetch_result();
+        app.sort_servers_by_latency();
 
-        assert_eq!(app.current_screen, CurrentScreen::ServerList);
-        assert_eq!(app.servers.len(), 2);
+        assert_eq!(
+            app.servers,
+            vec!["Fastest", "Fast", "Medium", "Slow", "Timeout"]
+        );
+        assert_eq!(
+            app.server_latencies,
+            vec![
+                Some(Duration::from_millis(5)),
+                Some(Duration::from_millis(20)),
+                Some(Duration::from_millis(120)),
+                Some(Duration::from_millis(300)),
+                None,
+            ]
+        );
+        assert!(app.servers_sorted_by_latency);
         assert_eq!(app.selected_index, Some(0));
-        assert!(app.fetch_result_rx.is_none());
-        assert!(app.messages.last().unwrap().contains("Found 2 servers"));
+        assert_eq!(app.list_state.selected(), Some(0));
     }
 
     #[test]
-    fn test_poll_fetch_result_error() {
-        let (tx, rx) = oneshot::channel();
+    fn test_sort_servers_by_latency_unmeasured_keeps_order() {
         let mock = MockOpcProvider::new();
         let mut app = App::new(Arc::new(mock));
-        app.current_screen = CurrentScreen::Loading;
-        app.fetch_result_rx = Some(rx);
+        app.current_screen = CurrentScreen::ServerList;
+        app.servers = vec!["B".into(), "A".into()];
+        app.server_latencies = Vec::new(); // No pings yet
 
-        tx.send(Err(OpcError::Internal("Connection failed".to_string())))
-            .unwrap();
-        app.poll_fetch_result();
+        app.sort_servers_by_latency();
 
-        assert_eq!(app.current_screen, CurrentScreen::Home);
-        assert!(app.fetch_result_rx.is_none());
-        assert!(app.messages.last().unwrap().contains("Error"));
+        assert_eq!(app.servers, vec!["B", "A"]);
+        assert_eq!(app.server_latencies, vec![None, None]);
     }
 
-    #[test]
-    fn test_poll_fetch_result_empty_servers() {
-        let (tx, rx) = oneshot::channel();
-        let mock = MockOpcProvider::new();
+    #[tokio::test]
+    async fn test_measure_server_latencies_sorts_unreachable_last() {
+        let mut mock = MockOpcProvider::new();
+        mock.expect_get_server_metadata().returning(|server| {
+            if server == "Down" {
+                Err(OpcError::Connection("unreachable".into()))
+            } else {
+                Ok(opc_da_client::ServerMetadata {
+                    server: server.to_string(),
+                    status: None,
+                    namespace_type: None,
+                    interfaces: Vec::new(),
+                    da_version: None,
+                })
+            }
+        });
         let mut app = App::new(Arc::new(mock));
-        app.current_screen = CurrentScreen::Loading;
-        app.fetch_result_rx = Some(rx);
-
-        tx.send(Ok(vec![])).unwrap();
-        app.poll_fetch_result();
+        app.current_screen = CurrentScreen::ServerList;
+        app.servers = vec!["Down".into(), "Up".into()];
+        app.server_latencies = vec![None, None];
 
-        assert_eq!(app.current_screen, CurrentScreen::ServerList);
-        assert!(app.servers.is_empty());
-        assert_eq!(app.selected_index, None);
-        assert!(app.messages.last().unwrap().contains("Found 0 servers"));
+        app.measure_server_latencies();
+        let measured = app.server_latency_rx.take().unwrap().await.unwrap();
+        let (tx, rx) = oneshot::channel();
+        tx.send(measured).unwrap();
+        app.server_latency_rx = Some(rx);
+        app.poll_server_latencies();
+
+        assert_eq!(app.servers, vec!["Up", "Down"]);
+        assert!(app.server_latencies[0].is_some());
+        assert_eq!(app.server_latencies[1], None);
+        assert!(app.servers_sorted_by_latency);
+        assert!(app.server_latency_rx.is_none());
     }
 
     #[test]
-    fn test_poll_fetch_result_closed() {
-        let (tx, rx) = oneshot::channel::<OpcResult<Vec<String>>>();
-        let mock = MockOpcProvider::new();
-        let mut app = App::new(Arc::new(mock));
-        app.current_screen = CurrentScreen::Loading;
-        app.fetch_result_rx = Some(rx);
+    fn test_data_type_symbol() {
+        assert_eq!(data_type_symbol(3), "I4");
+        assert_eq!(data_type_symbol(5), "R8");
+        assert_eq!(data_type_symbol(11), "BOOL");
+        assert_eq!(data_type_symbol(0x2005), "ARRAY|R8");
+        assert_eq!(data_type_symbol(0), "—");
+    }
 
-        // Drop the sender
-        drop(tx);
-        app.poll_fetch_result();
+    #[test]
+    fn test_format_age() {
+        use std::time::Duration;
+
+        assert_eq!(format_age(Duration::from_millis(2400)), "2s ago");
+        assert_eq!(format_age(Duration::from_secs(59)), "59s ago");
+        assert_eq!(format_age(Duration::from_secs(300)), "5m ago");
+        assert_eq!(format_age(Duration::from_secs(7200)), "2h ago");
+        assert_eq!(format_age(Duration::from_secs(3 * 86_400)), "3d ago");
+
+        let tv = TagValue {
+            tag_id: "T".into(),
+            value: "1".into(),
+            quality: "Good".into(),
+            timestamp: String::new(),
+            raw_timestamp: Some(std::time::SystemTime::now() - Duration::from_secs(2)),
+            vt: None,
+            access_rights: 0,
+            canonical_data_type: 0,
+            status: ReadStatus::Ok,
+        };
+        assert!(tv.age().is_some_and(|age| age >= Duration::from_secs(2)));
+    }
 
-        assert_eq!(app.current_screen, CurrentScreen::Home);
-        assert!(
-            app.messages
-                .last()
-                .unwrap()
-                .contains("terminated unexpectedly")
-        );
+    #[test]
+    fn test_nudge_value() {
+        assert_eq!(nudge_value("41", 1.0), Some("42".to_string()));
+        assert_eq!(nudge_value("5", -10.0), Some("-5".to_string()));
+        assert_eq!(nudge_value("2.5", 1.0), Some("3.5".to_string()));
+        assert_eq!(nudge_value("2", 0.5), Some("2.5".to_string()));
+        assert_eq!(nudge_value("Error", 1.0), None);
+        assert_eq!(nudge_value("NaN", 1.0), None);
     }
 
     #[tokio::test]
-    async fn test_start_fetch_servers_sets_loading() {
+    async fn test_nudge_selected_value_writes() {
         let mut mock = MockOpcProvider::new();
-        mock.expect_list_servers()
-            .returning(|_| Ok(vec!["S1".into()]));
+        mock.expect_write_tag_value()
+            .with(eq("TestServer"), eq("Tag1"), eq(OpcValue::Int(52)))
+            .returning(|_, tag_id, _| {
+                Ok(WriteResult {
+                    tag_id: tag_id.to_string(),
+                    success: true,
+                    error: None,
+                    attempts: 1,
+                    sent_vt: None,
+                })
+            });
 
         let mut app = App::new(Arc::new(mock));
-        app.start_fetch_servers();
+        app.current_screen = CurrentScreen::TagValues;
+        app.refresh_server = Some("TestServer".into());
+        app.tag_values = vec![TagValue {
+            tag_id: "Tag1".into(),
+            value: "42".into(),
+            quality: "Good".into(),
+            timestamp: String::new(),
+            raw_timestamp: None,
+            vt: None,
+            access_rights: 0,
+            canonical_data_type: 0,
+            status: ReadStatus::Ok,
+        }];
+        app.table_state.select(Some(0));
+
+        app.nudge_selected_value(true, true);
 
         assert_eq!(app.current_screen, CurrentScreen::Loading);
-        assert!(app.fetch_result_rx.is_some());
+        assert_eq!(app.write_value_input, "52");
+        assert!(app.write_result_rx.is_some());
     }
 
     #[test]
-    fn test_server_navigation() {
+    fn test_nudge_selected_value_non_numeric() {
         let mock = MockOpcProvider::new();
         let mut app = App::new(Arc::new(mock));
-        app.servers = vec!["S1".into(), "S2".into()];
-        app.selected_index = Some(0);
-        app.current_screen = CurrentScreen::ServerList;
-        app.list_state.select(Some(0));
+        app.current_screen = CurrentScreen::TagValues;
+        app.refresh_server = Some("TestServer".into());
+        app.tag_values = vec![TagValue {
+            tag_id: "Tag1".into(),
+            value: "Running".into(),
+            quality: "Good".into(),
+            timestamp: String::new(),
+            raw_timestamp: None,
+            vt: None,
+            access_rights: 0,
+            canonical_data_type: 0,
+            status: ReadStatus::Ok,
+        }];
+        app.table_state.select(Some(0));
 
-        app.select_next();
-        assert_eq!(app.selected_index, Some(1));
+        app.nudge_selected_value(false, false);
 
-        app.select_next(); // Should stay at 1
-        assert_eq!(app.selected_index, Some(1));
+        assert_eq!(app.current_screen, CurrentScreen::TagValues);
+        assert!(app.write_result_rx.is_none());
+        assert!(app.messages.last().unwrap().contains("not numeric"));
+    }
 
-        app.select_prev();
-        assert_eq!(app.selected_index, Some(0));
+    #[test]
+    fn test_poll_write_result_error_stays_on_write_input() {
+        let (tx, rx) = oneshot::channel();
+        let mock = MockOpcProvider::new();
+        let mut app = App::new(Arc::new(mock));
+        app.reset_screen_stack(CurrentScreen::TagValues);
+        app.current_screen = CurrentScreen::Loading;
+        app.write_tag_id = Some("Tag1".into());
+        app.write_value_input = "12.5".into();
+        app.write_result_rx = Some(rx);
 
-        app.select_prev(); // Should stay at 0
-        assert_eq!(app.selected_index, Some(0));
+        tx.send(Err(OpcError::Internal("Access denied".into())))
+            .unwrap();
+        app.poll_write_result();
+
+        assert_eq!(app.current_screen, CurrentScreen::WriteInput);
+        assert_eq!(app.write_value_input, "12.5");
+        assert!(
+            app.write_error
+                .as_deref()
+                .unwrap()
+                .contains("Access denied")
+        );
+        assert!(app.write_result_rx.is_none());
+        assert!(app.messages.last().unwrap().contains("Write error"));
+
+        // First Esc clears the error, second navigates back
+        app.go_back();
+        assert_eq!(app.current_screen, CurrentScreen::WriteInput);
+        assert!(app.write_error.is_none());
+        assert_eq!(app.write_value_input, "12.5");
+
+        app.go_back();
+        assert_eq!(app.current_screen, CurrentScreen::TagValues);
+        assert!(app.write_value_input.is_empty());
     }
 
     #[test]
-    fn test_tag_navigation_logic() {
-        let mock = MockOpcProvider::new();
+    fn test_parse_json_opc_value() {
+        assert_eq!(
+            parse_json_opc_value("[1.0,\n 2.5,\n 3]"),
+            Ok(OpcValue::Array(vec![
+                OpcValue::Float(1.0),
+                OpcValue::Float(2.5),
+                OpcValue::Int(3),
+            ]))
+        );
+        assert_eq!(
+            parse_json_opc_value(r#"["a", [true]]"#),
+            Ok(OpcValue::Array(vec![
+                OpcValue::String("a".into()),
+                OpcValue::Array(vec![OpcValue::Bool(true)]),
+            ]))
+        );
+        assert_eq!(parse_json_opc_value("5000000000"), Ok(OpcValue::Float(5e9)));
+        assert!(parse_json_opc_value("[1, null]").is_err());
+        assert!(
+            parse_json_opc_value("[1, 2")
+                .unwrap_err()
+                .starts_with("Invalid JSON")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_multiline_write_sends_json_array() {
+        let mut mock = MockOpcProvider::new();
+        mock.expect_write_tag_value()
+            .with(
+                eq("TestServer"),
+                eq("Tag1"),
+                eq(OpcValue::Array(vec![
+                    OpcValue::Float(1.5),
+                    OpcValue::Float(2.5),
+                ])),
+            )
+            .returning(|_, tag_id, _| {
+                Ok(WriteResult {
+                    tag_id: tag_id.to_string(),
+                    success: true,
+                    error: None,
+                    attempts: 1,
+                    sent_vt: None,
+                })
+            });
+
         let mut app = App::new(Arc::new(mock));
-        app.servers = vec!["S1".into()];
-        app.selected_index = Some(0);
-        app.tags = vec!["T1".into(), "T2".into()];
-        app.current_screen = CurrentScreen::TagList;
-        app.list_state.select(Some(0));
+        app.current_screen = CurrentScreen::WriteInput;
+        app.refresh_server = Some("TestServer".into());
+        app.write_tag_id = Some("Tag1".into());
+        app.toggle_write_multiline();
+        assert!(app.write_multiline);
+
+        // Invalid JSON stays on the editor with the error shown
+        app.write_value_input = "[1.5,\n2.5".into();
+        app.start_write_value();
+        assert_eq!(app.current_screen, CurrentScreen::WriteInput);
+        assert!(
+            app.write_error
+                .as_deref()
+                .unwrap()
+                .starts_with("Invalid JSON")
+        );
 
-        // Test boundary check against tags (2), not servers (1)
-        app.select_next();
-        assert_eq!(app.selected_index, Some(1));
-        assert_eq!(app.list_state.selected(), Some(1));
+        app.write_value_input = "[1.5,\n2.5]".into();
+        app.start_write_value();
+        assert_eq!(app.current_screen, CurrentScreen::Loading);
+        assert!(app.messages.last().unwrap().contains("'[1.5, 2.5]'"));
+        let rx = app.write_result_rx.take().unwrap();
+        assert!(rx.await.unwrap().unwrap().success);
+    }
 
-        app.select_next(); // Should stay at 1
-        assert_eq!(app.selected_index, Some(1));
+    #[tokio::test]
+    async fn test_deselect_tag_drops_it_from_auto_refresh() {
+        let mut mock = MockOpcProvider::new();
+        mock.expect_read_tag_values()
+            .withf(|server, tag_ids, _| server == "TestServer" && *tag_ids == ["A", "C"])
+            .times(1)
+            .returning(|_, tag_ids, _| {
+                Ok(tag_ids.iter().map(|id| tag_value(id, "1".into())).collect())
+            });
+
+        let mut app = App::new(Arc::new(mock));
+        browse_into(&mut app, "TestServer", &["A", "B", "C"]);
+        app.selected_tags = vec![true; 3];
+        app.refresh_server = Some("TestServer".into());
+        app.refresh_tag_ids = vec!["A".into(), "B".into(), "C".into()];
+        show_values(&mut app, &["A", "B", "C"]);
+        app.last_read_time = Some(std::time::Instant::now());
+        app.selected_index = Some(1);
+        app.table_state.select(Some(1));
+
+        app.deselect_highlighted_tag();
+        assert_eq!(app.selected_tags, [true, false, true]);
+        assert_eq!(app.refresh_tag_ids, ["A", "C"]);
+        let rows: Vec<&str> = app.tag_values.iter().map(|tv| tv.tag_id.as_str()).collect();
+        assert_eq!(rows, ["A", "C"]);
+
+        // The next auto-refresh is due immediately and reads only the rest
+        app.maybe_auto_refresh();
+        let values = app.read_result_rx.take().unwrap().await.unwrap().unwrap();
+        assert_eq!(values.len(), 2);
+    }
+
+    #[test]
+    fn test_parse_opc_value_decimal_comma() {
+        assert_eq!(
+            parse_opc_value("3,5", false),
+            OpcValue::String("3,5".into())
+        );
+        assert_eq!(parse_opc_value("3,5", true), OpcValue::Float(3.5));
+        assert_eq!(parse_opc_value("-0,25", true), OpcValue::Float(-0.25));
+        assert_eq!(parse_opc_value("3.5", true), OpcValue::Float(3.5));
+        assert_eq!(parse_opc_value("42", true), OpcValue::Int(42));
+        // Only a lone decimal comma between digits is normalized
+        assert_eq!(
+            parse_opc_value("1,2,3", true),
+            OpcValue::String("1,2,3".into())
+        );
+        assert_eq!(parse_opc_value("a,b", true), OpcValue::String("a,b".into()));
     }
 
     #[tokio::test]
-    async fn test_enter_selected_server_navigation() {
+    async fn test_bulk_write_to_selected_tags() {
         let mut mock = MockOpcProvider::new();
-        mock.expect_browse_tags()
-            .with(eq("S1"), eq(MAX_BROWSE_TAGS), always(), always())
-            .returning(|_, _, _, _| Ok(vec!["T1".into()]));
+        mock.expect_write_tag_values()
+            .with(
+                eq("TestServer"),
+                eq(vec![
+                    ("Out1".to_string(), OpcValue::Int(0)),
+                    ("Out3".to_string(), OpcValue::Int(0)),
+                ]),
+            )
+            .times(1)
+            .returning(|_, writes| {
+                Ok(writes
+                    .into_iter()
+                    .map(|(tag_id, _)| {
+                        let success = tag_id == "Out1";
+                        WriteResult {
+                            tag_id,
+                            success,
+                            error: (!success).then(|| "Bad rights".to_string()),
+                            attempts: 1,
+                            sent_vt: Some(3),
+                        }
+                    })
+                    .collect())
+            });
 
         let mut app = App::new(Arc::new(mock));
-        app.servers = vec!["S1".into()];
-        app.selected_index = Some(0);
-        app.current_screen = CurrentScreen::ServerList;
-        app.list_state.select(Some(0));
+        browse_into(&mut app, "TestServer", &["Out1", "Out2", "Out3"]);
 
-        app.start_browse_tags();
-        // Wait briefly for the spawned task
-        tokio::time::sleep(std::time::Duration::from_millis(50)).await;
-        app.poll_browse_result();
+        app.enter_bulk_write_mode();
+        assert_eq!(app.current_screen, CurrentScreen::TagList);
+        assert!(app.messages.last().unwrap().contains("No tags selected"));
 
-        assert!(matches!(app.current_screen, CurrentScreen::TagList));
-        assert_eq!(app.tags.len(), 1);
-        assert_eq!(app.selected_index, Some(0));
+        app.selected_tags = vec![true, false, true];
+        app.enter_bulk_write_mode();
+        assert_eq!(app.current_screen, CurrentScreen::WriteInput);
+        assert_eq!(app.bulk_write_tags, ["Out1", "Out3"]);
+
+        app.write_value_input = "0".into();
+        app.start_write_value();
+        assert_eq!(app.current_screen, CurrentScreen::Loading);
+        assert!(app.messages.last().unwrap().contains("'0' to 2 tags"));
+
+        let result = app.bulk_write_rx.take().unwrap().await.unwrap();
+        let (tx, rx) = oneshot::channel();
+        tx.send(result).unwrap();
+        app.bulk_write_rx = Some(rx);
+        app.poll_bulk_write_result();
+
+        assert_eq!(app.current_screen, CurrentScreen::TagList);
+        assert!(app.bulk_write_tags.is_empty());
+        let log = &app.messages[app.messages.len() - 3..];
+        assert_eq!(log[0], "  ✓ Out1 (sent as VT_I4)");
+        assert_eq!(log[1], "  ✗ Out3: Bad rights");
+        assert_eq!(log[2], "Bulk write: 1/2 tags succeeded");
     }
 
     #[test]
-    fn test_go_back_navigation() {
+    fn test_poll_write_result_rejected_stays_on_write_input() {
+        let (tx, rx) = oneshot::channel();
         let mock = MockOpcProvider::new();
         let mut app = App::new(Arc::new(mock));
-        app.servers = vec!["S1".into()];
-        app.selected_index = Some(0);
-        app.tags = vec!["T1".into()];
-        app.current_screen = CurrentScreen::TagList;
-        app.list_state.select(Some(0));
+        app.current_screen = CurrentScreen::Loading;
+        app.write_tag_id = Some("Tag1".into());
+        app.write_value_input = "abc".into();
+        app.write_result_rx = Some(rx);
 
-        // TagList -> ServerList
-        app.go_back();
-        assert!(matches!(app.current_screen, CurrentScreen::ServerList));
-        assert!(app.tags.is_empty());
-        assert_eq!(app.selected_index, Some(0));
+        tx.send(Ok(WriteResult {
+            tag_id: "Tag1".into(),
+            success: false,
+            error: Some("Type mismatch".into()),
+            attempts: 1,
+            sent_vt: None,
+        }))
+        .unwrap();
+        app.poll_write_result();
 
-        // ServerList -> Home
-        app.go_back();
-        assert!(matches!(app.current_screen, CurrentScreen::Home));
-        assert!(app.servers.is_empty());
-        assert_eq!(app.selected_index, None);
+        assert_eq!(app.current_screen, CurrentScreen::WriteInput);
+        assert_eq!(app.write_value_input, "abc");
+        assert_eq!(app.write_error.as_deref(), Some("Type mismatch"));
     }
 
     #[tokio::test]
-    async fn test_loading_transition() {
-        let mock = MockOpcProvider::new();
+    async fn test_start_fetch_servers_debounce_in_flight() {
+        use std::sync::atomic::Ordering;
+
+        let calls = Arc::new(AtomicUsize::new(0));
+        let calls_in_mock = Arc::clone(&calls);
+        let mut mock = MockOpcProvider::new();
+        mock.expect_list_servers().returning(move |_| {
+            calls_in_mock.fetch_add(1, Ordering::SeqCst);
+            Ok(vec!["S1".into()])
+        });
+
         let mut app = App::new(Arc::new(mock));
         app.start_fetch_servers();
+        app.start_fetch_servers();
+        app.start_fetch_servers();
+        tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+        assert!(!app.pending_fetch);
+
+        // Once the window has passed, a new press fetches normally
+        app.poll_fetch_result();
+        assert_eq!(app.current_screen, CurrentScreen::ServerList);
+        app.last_fetch_start =
+            Some(std::time::Instant::now() - std::time::Duration::from_millis(600));
+        app.start_fetch_servers();
+        tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
         assert_eq!(app.current_screen, CurrentScreen::Loading);
-        assert!(app.messages.iter().any(|m| m.contains("Connecting to")));
     }
 
     #[tokio::test]
-    async fn test_tui_navigation_flow() {
-        let (tx, rx) = oneshot::channel();
-        let mock = MockOpcProvider::new();
-        let mut app = App::new(Arc::new(mock));
+    async fn test_start_fetch_servers_debounce_queues_pending() {
+        let mut mock = MockOpcProvider::new();
+        mock.expect_list_servers()
+            .returning(|_| Ok(vec!["S1".into()]));
 
-        // 1. Initial State: Home
-        assert!(matches!(app.current_screen, CurrentScreen::Home));
-        assert_eq!(app.host_input, "localhost");
+        let mut app = App::new(Arc::new(mock));
+        app.last_fetch_start = Some(std::time::Instant::now());
 
-        // 2. Start fetch
         app.start_fetch_servers();
-        assert_eq!(app.current_screen, CurrentScreen::Loading);
-        app.fetch_result_rx = Some(rx);
+        assert!(app.pending_fetch);
+        assert!(app.fetch_result_rx.is_none());
 
-        // 3. Complete fetch
-        tx.send(Ok(vec!["Server1".into()])).unwrap();
+        app.last_fetch_start =
+            Some(std::time::Instant::now() - std::time::Duration::from_millis(600));
         app.poll_fetch_result();
-
-        assert!(matches!(app.current_screen, CurrentScreen::ServerList));
-        assert_eq!(app.servers.len(), 1);
-        assert_eq!(app.selected_index, Some(0));
-        assert_eq!(app.list_state.selected(), Some(0));
-
-        // 4. User goes back to Home
-        app.go_back();
-        assert!(matches!(app.current_screen, CurrentScreen::Home));
-        assert!(app.servers.is_empty());
-        assert_eq!(app.selected_index, None);
-        assert_eq!(app.list_state.selected(), None);
+        assert!(!app.pending_fetch);
+        assert!(app.fetch_result_rx.is_some());
+        assert_eq!(app.current_screen, CurrentScreen::Loading);
     }
 
-    #[tokio::test]
-    async fn test_poll_browse_result_error_shows_message() {
+    #[test]
+    fn test_poll_write_result_reports_retries() {
         let (tx, rx) = oneshot::channel();
         let mock = MockOpcProvider::new();
         let mut app = App::new(Arc::new(mock));
         app.current_screen = CurrentScreen::Loading;
-        app.browse_result_rx = Some(rx);
+        app.write_result_rx = Some(rx);
 
-        // Simulate provider returning a descriptive error
-        tx.send(Err(OpcError::Internal(
-            "DCOM access denied on remote host".to_string(),
-        )))
+        tx.send(Ok(WriteResult {
+            tag_id: "Tag1".into(),
+            success: true,
+            error: None,
+            attempts: 3,
+            sent_vt: Some(5),
+        }))
         .unwrap();
+        app.poll_write_result();
 
-        app.poll_browse_result();
+        assert_eq!(app.current_screen, CurrentScreen::TagValues);
+        assert!(
+            app.messages
+                .iter()
+                .any(|m| m.contains("succeeded after 3 attempts (sent as VT_R8)"))
+        );
+    }
 
-        assert_eq!(app.current_screen, CurrentScreen::ServerList);
-        assert!(app.browse_result_rx.is_none());
-        let last_msg = app.messages.last().unwrap();
-        assert!(last_msg.contains("Error: "));
-        assert!(last_msg.contains("DCOM access denied")); // Error context preserved
+    fn write_snapshot_file(name: &str, json: &str) -> std::path::PathBuf {
+        let path = std::env::temp_dir().join(format!("opc-cli-{}-{name}", std::process::id()));
+        std::fs::write(&path, json).unwrap();
+        path
     }
 
-    #[tokio::test]
-    async fn test_poll_browse_result_closed_shows_message() {
-        let (tx, rx) = oneshot::channel();
+    #[test]
+    fn test_load_from_snapshot() {
+        let path = write_snapshot_file(
+            "snapshot.json",
+            r#"{
+                "server": "Matrikon.OPC.Simulation.1",
+                "taken_at": "2024-01-01 12:00:00",
+                "values": [
+                    {"tag_id": "Random.Int4", "value": "42", "quality": "Good", "timestamp": "2024-01-01 11:59:59", "vt": 3},
+                    {"tag_id": "Random.Real8", "value": "1.5", "quality": "Good", "timestamp": "2024-01-01 11:59:58"}
+                ]
+            }"#,
+        );
         let mock = MockOpcProvider::new();
         let mut app = App::new(Arc::new(mock));
-        app.current_screen = CurrentScreen::Loading;
-        app.browse_result_rx = Some(rx);
-
-        // Drop sender without sending — simulates task panic
-        drop(tx);
 
-        app.poll_browse_result();
+        app.load_from_snapshot(&path).unwrap();
+        std::fs::remove_file(&path).ok();
 
-        assert_eq!(app.current_screen, CurrentScreen::ServerList);
-        assert!(app.browse_result_rx.is_none());
-        let last_msg = app.messages.last().unwrap();
-        assert!(last_msg.contains("terminated unexpectedly"));
+        assert_eq!(app.current_screen, CurrentScreen::TagValues);
+        assert_eq!(app.tag_values.len(), 2);
+        assert_eq!(app.tag_values[0].value, "42");
+        assert_eq!(app.tag_values[0].vt, Some(3));
+        assert_eq!(app.tag_values[1].vt, None);
+        let tag_ids: Vec<&str> = app.tags.iter().map(|t| t.tag_id.as_str()).collect();
+        assert_eq!(tag_ids, vec!["Random.Int4", "Random.Real8"]);
+        assert_eq!(
+            app.refresh_server.as_deref(),
+            Some("Matrikon.OPC.Simulation.1")
+        );
+        assert_eq!(
+            app.offline_banner().as_deref(),
+            Some("[OFFLINE — snapshot from 2024-01-01 12:00:00]")
+        );
     }
 
-    #[tokio::test]
-    async fn test_poll_browse_result_empty_tags() {
-        let (tx, rx) = oneshot::channel();
+    #[test]
+    fn test_snapshot_mode_blocks_writes() {
+        let path = write_snapshot_file(
+            "offline.json",
+            r#"{"server": "S", "taken_at": "2024-01-01 12:00:00",
+                "values": [{"tag_id": "T", "value": "1", "quality": "Good", "timestamp": ""}]}"#,
+        );
+        // No expectations: any provider call would panic
         let mock = MockOpcProvider::new();
         let mut app = App::new(Arc::new(mock));
-        app.current_screen = CurrentScreen::Loading;
-        app.browse_result_rx = Some(rx);
+        app.load_from_snapshot(&path).unwrap();
+        std::fs::remove_file(&path).ok();
 
-        tx.send(Ok(vec![])).unwrap();
+        app.enter_write_mode();
+        assert_eq!(app.current_screen, CurrentScreen::TagValues);
+        assert!(app.messages.last().unwrap().contains("Offline mode"));
 
-        app.poll_browse_result();
+        app.nudge_selected_value(true, false);
+        assert!(app.write_result_rx.is_none());
+        assert!(app.messages.last().unwrap().contains("Offline mode"));
 
-        assert_eq!(app.current_screen, CurrentScreen::TagList);
-        assert!(app.tags.is_empty());
-        assert_eq!(app.selected_index, None);
-        assert_eq!(app.list_state.selected(), None);
-        assert!(app.messages.last().unwrap().contains("Found 0 tags"));
+        app.last_read_time = Some(std::time::Instant::now() - std::time::Duration::from_secs(5));
+        app.maybe_auto_refresh();
+        assert!(app.read_result_rx.is_none());
     }
 
-    #[test]
-    fn test_start_browse_no_selection() {
-        let mock = MockOpcProvider::new();
+    #[tokio::test]
+    async fn test_live_read_leaves_snapshot_mode() {
+        let path = write_snapshot_file(
+            "leave-offline.json",
+            r#"{"server": "S", "taken_at": "2024-01-01 12:00:00",
+                "values": [{"tag_id": "T", "value": "1", "quality": "Good", "timestamp": ""}]}"#,
+        );
+        let mut mock = MockOpcProvider::new();
+        mock.expect_list_servers()
+            .returning(|_| Ok(vec!["S".into()]));
+        mock.expect_browse_tags()
+            .returning(|_, _, _, _| Ok(vec![BrowsedTag::from("T")]));
+        mock.expect_read_tag_values()
+            .times(1)
+            .returning(|_, _, _| Ok(vec![tag_value("T", "2".into())]));
         let mut app = App::new(Arc::new(mock));
-        app.current_screen = CurrentScreen::ServerList;
-        app.servers = vec!["S1".into()];
-        app.selected_index = None; // No selection
+        app.load_from_snapshot(&path).unwrap();
+        std::fs::remove_file(&path).ok();
+        assert!(app.offline_banner().is_some());
+
+        // Back out of the snapshot to Home and connect again
+        while app.current_screen != CurrentScreen::Home {
+            app.go_back();
+        }
+        app.probe_dcom = false;
+        app.host_input = "localhost".into();
+        app.start_fetch_servers();
+        assert!(app.offline_banner().is_none());
+        for _ in 0..100 {
+            app.poll_fetch_result();
+            if app.fetch_result_rx.is_none() {
+                break;
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+        }
+        assert_eq!(app.current_screen, CurrentScreen::ServerList);
 
         app.start_browse_tags();
+        wait_for_browse(&mut app).await;
+        assert_eq!(app.current_screen, CurrentScreen::TagList);
 
-        // Should remain on ServerList — no crash, no Loading transition
-        assert_eq!(app.current_screen, CurrentScreen::ServerList);
-        assert!(app.browse_result_rx.is_none());
+        app.selected_tags = vec![true; app.tags.len()];
+        app.start_read_values();
+        let values = app.read_result_rx.take().unwrap().await.unwrap().unwrap();
+        deliver_read(&mut app, values);
+        assert_eq!(app.current_screen, CurrentScreen::TagValues);
+        assert_eq!(app.tag_values[0].value, "2");
+        assert!(app.offline_snapshot.is_none());
+        assert_eq!(app.connection_state(), ConnectionState::Connected);
     }
 
-    #[test]
-    fn test_start_browse_wrong_screen() {
-        let mock = MockOpcProvider::new();
-        let mut app = App::new(Arc::new(mock));
-        app.current_screen = CurrentScreen::Home; // Wrong screen
-        app.servers = vec!["S1".into()];
-        app.selected_index = Some(0);
+    #[tokio::test]
+    async fn test_worker_backpressure_pauses_auto_refresh() {
+        let mut mock = MockOpcProvider::new();
+        // Only the read after the queue drains may reach the provider
+        mock.expect_read_tag_values()
+            .times(1)
+            .returning(|_, _, _| Ok(vec![]));
 
-        app.start_browse_tags();
+        let depth = Arc::new(AtomicUsize::new(0));
+        let mut app = App::new(Arc::new(mock));
+        app.worker_queue_depth = Some(Arc::clone(&depth));
+        app.current_screen = CurrentScreen::TagValues;
+        app.refresh_server = Some("Server1".into());
+        app.refresh_tag_ids = vec!["Tag1".into()];
+        app.last_read_time = Some(std::time::Instant::now() - std::time::Duration::from_secs(2));
+
+        depth.store(25, Ordering::Relaxed);
+        app.check_worker_queue();
+        app.check_worker_queue();
+        let warnings = app
+            .messages
+            .iter()
+            .filter(|m| m.contains("queue nearly full"))
+            .count();
+        assert_eq!(warnings, 1, "warning is shown once per backlog");
+        assert!(!app.refresh_paused);
+
+        depth.store(32, Ordering::Relaxed);
+        app.check_worker_queue();
+        assert!(app.refresh_paused);
+        app.maybe_auto_refresh();
+        assert!(app.read_result_rx.is_none());
 
-        assert_eq!(app.current_screen, CurrentScreen::Home); // Unchanged
-        assert!(app.browse_result_rx.is_none());
+        // Still above the resume threshold
+        depth.store(20, Ordering::Relaxed);
+        app.check_worker_queue();
+        assert!(app.refresh_paused);
+
+        depth.store(3, Ordering::Relaxed);
+        app.check_worker_queue();
+        assert!(!app.refresh_paused);
+        assert_eq!(app.messages.last().unwrap(), "Auto-refresh resumed");
+        app.maybe_auto_refresh();
+        let rx = app.read_result_rx.as_mut().unwrap();
+        assert!(rx.await.unwrap().is_ok());
     }
 
-    #[test]
-    fn test_poll_fetch_result_timeout() {
+    /// Wait for the ping `check_worker_health` sent and hand its answer back.
+    async fn settle_worker_ping(app: &mut App) -> bool {
+        let alive = app.worker_ping_rx.take().unwrap().await.unwrap();
         let (tx, rx) = oneshot::channel();
-        let mock = MockOpcProvider::new();
-        let mut app = App::new(Arc::new(mock));
-        app.current_screen = CurrentScreen::Loading;
-        app.fetch_result_rx = Some(rx);
+        tx.send(alive).unwrap();
+        app.worker_ping_rx = Some(rx);
+        app.check_worker_health();
+        alive
+    }
 
-        tx.send(Err(OpcError::Internal(
-            "Connection timed out (30s)".to_string(),
-        )))
-        .unwrap();
-        app.poll_fetch_result();
+    #[tokio::test(start_paused = true)]
+    async fn test_worker_heartbeat_warns_when_ping_goes_unanswered() {
+        use opc_da_client::com_worker::{ComRequest, REQUEST_QUEUE_CAPACITY};
 
-        assert_eq!(app.current_screen, CurrentScreen::Home);
-        assert!(app.messages.last().unwrap().contains("timed out"));
+        let (sender, mut requests) = tokio::sync::mpsc::channel(REQUEST_QUEUE_CAPACITY);
+        let mut app = App::new(Arc::new(MockOpcProvider::new()));
+        app.worker_heartbeat = Some(Arc::new(ComWorker::detached(sender)));
+
+        // A healthy worker answers without a warning
+        app.check_worker_health();
+        let Some(ComRequest::Ping { reply }) = requests.recv().await else {
+            panic!("expected a ping");
+        };
+        reply.send(()).unwrap();
+        assert!(settle_worker_ping(&mut app).await);
+        assert!(app.worker_ping_rx.is_none());
+        assert!(app.messages.iter().all(|m| !m.contains("health check")));
+
+        // No second ping within the interval, nor while a read is running
+        app.check_worker_health();
+        app.last_worker_ping = Some(std::time::Instant::now() - WORKER_PING_INTERVAL);
+        let (_read_tx, read_rx) = oneshot::channel();
+        app.read_result_rx = Some(read_rx);
+        app.check_worker_health();
+        assert!(app.worker_ping_rx.is_none());
+        assert!(requests.try_recv().is_err());
+
+        // A stuck worker leaves the ping unanswered past PING_TIMEOUT
+        app.read_result_rx = None;
+        app.check_worker_health();
+        assert!(!settle_worker_ping(&mut app).await);
+        assert!(matches!(requests.try_recv(), Ok(ComRequest::Ping { .. })));
+        assert_eq!(
+            app.messages.last().unwrap(),
+            "Warning: OPC worker did not answer a health check within 100 ms"
+        );
+    }
+
+    fn browse_into(app: &mut App, server: &str, tags: &[&str]) {
+        app.push_screen(CurrentScreen::ServerList);
+        app.browsed_server = Some(server.into());
+        let (tx, rx) = oneshot::channel();
+        let tags: Vec<BrowsedTag> = tags.iter().map(|t| BrowsedTag::from(*t)).collect();
+        tx.send(Ok(tags.into())).unwrap();
+        app.browse_result_rx = Some(rx);
+        app.poll_browse_result();
+    }
+
+    fn show_values(app: &mut App, tag_ids: &[&str]) {
+        app.tag_values = tag_ids
+            .iter()
+            .map(|id| TagValue {
+                tag_id: (*id).into(),
+                value: "1".into(),
+                quality: "Good".into(),
+                timestamp: String::new(),
+                raw_timestamp: None,
+                vt: None,
+                access_rights: 0,
+                canonical_data_type: 0,
+                status: ReadStatus::Ok,
+            })
+            .collect();
+        app.push_screen(CurrentScreen::TagValues);
     }
 
     #[test]
-    fn test_add_message_ring_buffer() {
-        let mock = MockOpcProvider::new();
-        let mut app = App::new(Arc::new(mock));
+    fn test_sessions_keep_independent_selections() {
+        let mut app = App::new(Arc::new(MockOpcProvider::new()));
+        app.servers = vec!["ServerA".into(), "ServerB".into()];
 
-        for i in 0..15 {
-            app.add_message(format!("msg-{}", i));
-        }
+        browse_into(&mut app, "ServerA", &["A1", "A2"]);
+        app.toggle_tag_selection();
+        show_values(&mut app, &["A1"]);
 
-        assert_eq!(app.messages.len(), 10); // Capped at 10
-        assert_eq!(app.messages[0], "msg-5"); // Oldest surviving
-        assert_eq!(app.messages[9], "msg-14"); // Latest
+        app.open_new_session();
+        assert_eq!(app.current_screen, CurrentScreen::ServerList);
+        browse_into(&mut app, "ServerB", &["B1", "B2", "B3"]);
+        assert_eq!(app.active_sessions.len(), 2);
+        assert_eq!(app.current_session, 1);
+        app.select_next();
+        app.toggle_tag_selection();
+        assert_eq!(app.selected_tags, vec![false, true, false]);
+
+        app.switch_session(0);
+        assert_eq!(app.browsed_server.as_deref(), Some("ServerA"));
+        assert_eq!(app.current_screen, CurrentScreen::TagValues);
+        assert_eq!(app.selected_tags, vec![true, false]);
+        assert_eq!(app.tag_values[0].tag_id, "A1");
+
+        app.switch_session(1);
+        assert_eq!(app.browsed_server.as_deref(), Some("ServerB"));
+        assert_eq!(app.current_screen, CurrentScreen::TagList);
+        assert_eq!(app.selected_tags, vec![false, true, false]);
+
+        // Closing B from its tag list returns to A
+        app.go_back();
+        assert_eq!(app.active_sessions.len(), 1);
+        assert_eq!(app.browsed_server.as_deref(), Some("ServerA"));
+        assert_eq!(app.current_screen, CurrentScreen::TagValues);
     }
 
     #[test]
-    fn test_select_on_empty_list() {
-        let mock = MockOpcProvider::new();
-        let mut app = App::new(Arc::new(mock));
-        app.current_screen = CurrentScreen::ServerList;
-        app.servers = vec![]; // Empty
+    fn test_sessions_keep_their_own_read_error_count() {
+        let mut app = App::new(Arc::new(MockOpcProvider::new()));
+        app.servers = vec!["ServerA".into(), "ServerB".into()];
+
+        browse_into(&mut app, "ServerA", &["A1"]);
+        show_values(&mut app, &["A1"]);
+        app.consecutive_read_errors = 3;
+
+        app.open_new_session();
+        browse_into(&mut app, "ServerB", &["B1"]);
+        show_values(&mut app, &["B1"]);
+        assert_eq!(app.consecutive_read_errors, 0);
+        app.consecutive_read_errors = 1;
+
+        app.switch_session(0);
+        assert_eq!(app.consecutive_read_errors, 3);
+        assert_eq!(app.active_sessions[1].consecutive_read_errors, 1);
+
+        app.switch_session(1);
+        assert_eq!(app.consecutive_read_errors, 1);
+    }
 
-        app.select_next();
-        assert_eq!(app.selected_index, None);
+    #[test]
+    fn test_sessions_keep_their_own_connection_state() {
+        let mut app = App::new(Arc::new(MockOpcProvider::new()));
+        app.servers = vec!["ServerA".into(), "ServerB".into()];
+
+        browse_into(&mut app, "ServerA", &["A1"]);
+        show_values(&mut app, &["A1"]);
+        app.mark_server_error();
+        assert_eq!(app.connection_state(), ConnectionState::Disconnected);
+
+        app.open_new_session();
+        browse_into(&mut app, "ServerB", &["B1"]);
+        show_values(&mut app, &["B1"]);
+        app.mark_server_activity();
+        assert_eq!(app.connection_state(), ConnectionState::Connected);
+
+        app.switch_session(0);
+        assert_eq!(app.connection_state(), ConnectionState::Disconnected);
+
+        app.switch_session(1);
+        assert_eq!(app.connection_state(), ConnectionState::Connected);
+    }
 
-        app.select_prev();
-        assert_eq!(app.selected_index, None);
+    #[test]
+    fn test_switching_sessions_preserves_scroll_position() {
+        let mut app = App::new(Arc::new(MockOpcProvider::new()));
+        app.servers = vec!["ServerA".into(), "ServerB".into()];
+        let many: Vec<String> = (0..60).map(|i| format!("Tag{i}")).collect();
+        let many: Vec<&str> = many.iter().map(String::as_str).collect();
+
+        browse_into(&mut app, "ServerA", &many);
+        app.list_state.select(Some(42));
+        *app.list_state.offset_mut() = 30;
+        show_values(&mut app, &["Tag42"]);
+
+        app.open_new_session();
+        browse_into(&mut app, "ServerB", &many);
+        app.list_state.select(Some(7));
+        show_values(&mut app, &["Tag7"]);
+
+        app.switch_session(0);
+        assert_eq!(app.list_state.selected(), Some(42));
+        assert_eq!(app.list_state.offset(), 30);
+
+        app.switch_session(1);
+        assert_eq!(app.list_state.selected(), Some(7));
+        assert_eq!(app.list_state.offset(), 0);
     }
 
     #[test]
-    fn test_poll_browse_result_no_task() {
-        let mock = MockOpcProvider::new();
+    fn test_open_new_session_limit_and_cancel() {
+        let mut app = App::new(Arc::new(MockOpcProvider::new()));
+        app.servers = vec!["S".into()];
+        browse_into(&mut app, "S", &["T"]);
+        show_values(&mut app, &["T"]);
+
+        // Cancelling returns to the parked session
+        app.open_new_session();
+        assert_eq!(app.current_screen, CurrentScreen::ServerList);
+        app.go_back();
+        assert_eq!(app.current_screen, CurrentScreen::TagValues);
+        assert_eq!(app.tag_values[0].tag_id, "T");
+        assert_eq!(app.active_sessions.len(), 1);
+
+        for _ in 1..MAX_SESSIONS {
+            app.open_new_session();
+            browse_into(&mut app, "S", &["T"]);
+            show_values(&mut app, &["T"]);
+        }
+        assert_eq!(app.active_sessions.len(), MAX_SESSIONS);
+        app.open_new_session();
+        assert_eq!(app.current_screen, CurrentScreen::TagValues);
+        assert!(app.messages.last().unwrap().starts_with("Up to 4 servers"));
+    }
+
+    #[tokio::test]
+    async fn test_background_session_auto_refreshes() {
+        let mut mock = MockOpcProvider::new();
+        mock.expect_read_tag_values()
+            .with(eq("ServerA"), eq(vec!["A1".to_string()]), always())
+            .times(1)
+            .returning(|_, _, _| {
+                Ok(vec![TagValue {
+                    tag_id: "A1".into(),
+                    value: "99".into(),
+                    quality: "Good".into(),
+                    timestamp: String::new(),
+                    raw_timestamp: None,
+                    vt: None,
+                    access_rights: 0,
+                    canonical_data_type: 0,
+                    status: ReadStatus::Ok,
+                }])
+            });
         let mut app = App::new(Arc::new(mock));
-        app.current_screen = CurrentScreen::ServerList;
+        app.active_sessions = vec![
+            ServerSession {
+                server: "ServerA".into(),
+                refresh_tag_ids: vec!["A1".into()],
+                last_read_time: Some(std::time::Instant::now() - std::time::Duration::from_secs(2)),
+                ..ServerSession::default()
+            },
+            ServerSession::default(),
+        ];
+        app.current_session = 1;
 
-        // No browse_result_rx set — should not panic
-        app.poll_browse_result();
+        app.refresh_background_sessions();
+        let rx = app.active_sessions[0].read_result_rx.take().unwrap();
+        let values = rx.await.unwrap().unwrap();
+        assert_eq!(values[0].value, "99");
+    }
+
+    #[tokio::test]
+    async fn test_retry_failed_reads_rereads_only_failed_tags() {
+        let row = |tag_id: &str, value: &str, status: ReadStatus| TagValue {
+            tag_id: tag_id.into(),
+            value: value.into(),
+            quality: "Good".into(),
+            timestamp: String::new(),
+            raw_timestamp: None,
+            vt: None,
+            access_rights: 0,
+            canonical_data_type: 0,
+            status,
+        };
+        let mut mock = MockOpcProvider::new();
+        mock.expect_read_tag_values()
+            .with(
+                eq("Server"),
+                eq(vec!["B".to_string(), "C".to_string()]),
+                always(),
+            )
+            .times(1)
+            .returning(move |_, _, _| {
+                Ok(vec![
+                    row("B", "2", ReadStatus::Ok),
+                    row("C", "Error", ReadStatus::ReadError),
+                ])
+            });
+        let mut app = App::new(Arc::new(mock));
+        app.current_screen = CurrentScreen::TagValues;
+        app.refresh_server = Some("Server".into());
+        app.tag_values = vec![
+            row("A", "1", ReadStatus::Ok),
+            row("B", "Error", ReadStatus::ReadError),
+            row("C", "Error", ReadStatus::NotAdded),
+        ];
 
-        assert_eq!(app.current_screen, CurrentScreen::ServerList);
+        app.retry_failed_reads();
+        let rx = app.retry_result_rx.take().unwrap();
+        let result = rx.await.unwrap();
+        let (tx, rx) = oneshot::channel();
+        tx.send(result).unwrap();
+        app.retry_result_rx = Some(rx);
+        app.poll_retry_result();
+
+        let values: Vec<_> = app.tag_values.iter().map(|tv| tv.value.as_str()).collect();
+        assert_eq!(values, ["1", "2", "Error"]);
+        assert_eq!(app.tag_values[2].status, ReadStatus::ReadError);
+        assert!(app.retry_result_rx.is_none());
+        assert!(
+            app.messages
+                .last()
+                .is_some_and(|m| m.contains("2 failed tags: 1 recovered"))
+        );
     }
 
-    #[test]
-    fn test_toggle_tag_selection() {
-        let mock = MockOpcProvider::new();
-        let mut app = App::new(Arc::new(mock));
-        app.current_screen = CurrentScreen::TagList;
-        app.tags = vec!["Tag1".into(), "Tag2".into()];
-        app.selected_tags = vec![false, false];
-        app.selected_index = Some(1);
-
-        app.toggle_tag_selection();
-        assert_eq!(app.selected_tags, vec![false, true]);
+    /// Feed `values` to `app` as if a read had just completed.
+    fn deliver_read(app: &mut App, values: Vec<TagValue>) {
+        let (tx, rx) = oneshot::channel();
+        tx.send(Ok(values)).unwrap();
+        app.read_result_rx = Some(rx);
+        app.poll_read_result();
+    }
 
-        app.toggle_tag_selection();
-        assert_eq!(app.selected_tags, vec![false, false]);
+    fn tag_value(tag_id: &str, value: String) -> TagValue {
+        TagValue {
+            tag_id: tag_id.into(),
+            value,
+            quality: "Good".into(),
+            timestamp: String::new(),
+            raw_timestamp: None,
+            vt: None,
+            access_rights: 0,
+            canonical_data_type: 0,
+            status: ReadStatus::Ok,
+        }
     }
 
     #[test]
-    fn test_start_read_values_no_selection() {
-        let mock = MockOpcProvider::new();
-        let mut app = App::new(Arc::new(mock));
-        app.current_screen = CurrentScreen::TagList;
-        app.tags = vec!["Tag1".into()];
-        app.selected_tags = vec![false];
+    fn test_change_correlation_tracks_tags_changing_together() {
+        let mut app = App::new(Arc::new(MockOpcProvider::new()));
+        app.current_screen = CurrentScreen::TagValues;
+        // Tags 0 and 2 change on every even cycle; tag 1 on every third.
+        for cycle in 0..=12 {
+            deliver_read(
+                &mut app,
+                vec![
+                    tag_value("Pump.Speed", (cycle / 2).to_string()),
+                    tag_value("Tank.Level", (cycle / 3).to_string()),
+                    tag_value("Pump.Current", format!("{}.5", cycle / 2)),
+                ],
+            );
+        }
 
-        app.start_read_values();
+        assert_eq!(app.change_counts, [6, 4, 6]);
+        assert!(app.change_correlation_of(0, 2) > 0.8);
+        assert!(app.change_correlation_of(2, 0) > 0.8);
+        assert!(app.change_correlation_of(0, 1) < 0.3);
+        assert_eq!(
+            app.correlated_tags(),
+            [(0, vec![(2, 1.0)]), (2, vec![(0, 1.0)])]
+        );
 
-        assert_eq!(app.current_screen, CurrentScreen::TagList);
-        assert!(app.messages.last().unwrap().contains("No tags selected"));
-        assert!(app.read_result_rx.is_none());
+        app.open_correlation_view();
+        assert_eq!(app.current_screen, CurrentScreen::Correlation);
+        deliver_read(
+            &mut app,
+            vec![
+                tag_value("Pump.Speed", "7".into()),
+                tag_value("Tank.Level", "5".into()),
+                tag_value("Pump.Current", "7.5".into()),
+            ],
+        );
+        assert_eq!(app.current_screen, CurrentScreen::Correlation);
+        assert_eq!(app.change_counts, [7, 5, 7]);
+        app.go_back();
+        assert_eq!(app.current_screen, CurrentScreen::TagValues);
     }
 
     #[test]
-    fn test_start_read_values_wrong_screen() {
-        let mock = MockOpcProvider::new();
-        let mut app = App::new(Arc::new(mock));
-        app.current_screen = CurrentScreen::ServerList;
+    fn test_change_correlation_resets_when_tags_change() {
+        let mut app = App::new(Arc::new(MockOpcProvider::new()));
+        for cycle in 0..3 {
+            let value = cycle.to_string();
+            deliver_read(
+                &mut app,
+                vec![tag_value("A", value.clone()), tag_value("B", value)],
+            );
+        }
+        assert_eq!(app.change_correlation.get(&(0, 1)), Some(&2));
 
-        app.start_read_values();
+        deliver_read(&mut app, vec![tag_value("C", "0".into())]);
 
-        assert_eq!(app.current_screen, CurrentScreen::ServerList);
-        assert!(app.read_result_rx.is_none());
+        assert!(app.change_correlation.is_empty());
+        assert_eq!(app.change_counts, [0]);
     }
 
     #[tokio::test]
-    async fn test_start_read_values_success() {
-        use mockall::predicate::eq;
+    async fn test_start_read_values_reads_in_batches() {
+        const OPC_E_INVALIDHANDLE: u32 = 0xC004_0001;
+        let progress_seen = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let seen = Arc::clone(&progress_seen);
         let mut mock = MockOpcProvider::new();
         mock.expect_read_tag_values()
-            .with(eq("TestServer"), eq(vec!["Tag1".to_string()]))
-            .returning(|_, _| Ok(vec![]));
-
+            .times(3)
+            .returning(move |_, tag_ids, progress| {
+                seen.lock().unwrap().push(progress.load(Ordering::Relaxed));
+                progress.fetch_add(tag_ids.len(), Ordering::Relaxed);
+                if tag_ids.len() > 50 {
+                    return Err(OpcError::Com {
+                        source: windows::core::Error::from_hresult(windows::core::HRESULT(
+                            OPC_E_INVALIDHANDLE.cast_signed(),
+                        )),
+                    });
+                }
+                Ok(tag_ids
+                    .into_iter()
+                    .map(|tag_id| TagValue {
+                        tag_id,
+                        value: "1".into(),
+                        quality: "Good".into(),
+                        timestamp: String::new(),
+                        raw_timestamp: None,
+                        vt: None,
+                        access_rights: 0,
+                        canonical_data_type: 0,
+                        status: ReadStatus::Ok,
+                    })
+                    .collect())
+            });
         let mut app = App::new(Arc::new(mock));
         app.current_screen = CurrentScreen::TagList;
-        app.tags = vec!["Tag1".into()];
-        app.selected_tags = vec![true];
-        app.browsed_server = Some("TestServer".into());
+        app.browsed_server = Some("Server".into());
+        app.tags = (0..120)
+            .map(|i| BrowsedTag {
+                tag_id: format!("Tag{i}"),
+                depth: 0,
+                parent_branch: String::new(),
+            })
+            .collect();
+        app.selected_tags = vec![true; 120];
 
         app.start_read_values();
-
-        assert_eq!(app.current_screen, CurrentScreen::Loading);
-        assert!(app.read_result_rx.is_some());
-        assert_eq!(app.refresh_server, Some("TestServer".into()));
+        assert_eq!(app.read_chunk_total, 3);
+        let values = app.read_result_rx.take().unwrap().await.unwrap().unwrap();
+
+        assert_eq!(values.len(), 120);
+        assert_eq!(values[119].tag_id, "Tag119");
+        assert_eq!(app.read_progress.load(Ordering::Relaxed), 120);
+        assert_eq!(app.read_chunks_done.load(Ordering::Relaxed), 3);
+        assert_eq!(
+            *progress_seen.lock().unwrap(),
+            [0, 50, 100],
+            "each batch continues the shared progress count"
+        );
     }
 
-    #[test]
-    fn test_start_read_values_no_browsed_server() {
-        let mock = MockOpcProvider::new();
+    #[tokio::test]
+    async fn test_engineering_units_fetched_once_per_tag() {
+        let mut mock = MockOpcProvider::new();
+        mock.expect_get_engineering_units()
+            .with(
+                eq("Server"),
+                eq(vec!["Temp".to_string(), "Flag".to_string()]),
+            )
+            .times(1)
+            .returning(|_, _| {
+                Ok(vec![
+                    Some(EngineeringUnits {
+                        units: Some("°C".into()),
+                        low: Some(0.0),
+                        high: Some(200.0),
+                    }),
+                    None,
+                ])
+            });
         let mut app = App::new(Arc::new(mock));
-        app.current_screen = CurrentScreen::TagList;
-        app.tags = vec!["Tag1".into()];
-        app.selected_tags = vec![true];
-        app.browsed_server = None; // Simulate missing context
-
-        app.start_read_values();
-
-        assert_eq!(app.current_screen, CurrentScreen::TagList); // Should not transition
-        assert!(app.read_result_rx.is_none());
-        assert!(app.messages.last().unwrap().contains("No server context"));
-    }
+        app.current_screen = CurrentScreen::TagValues;
+        app.refresh_server = Some("Server".into());
+        show_values(&mut app, &["Temp", "Flag"]);
 
-    #[test]
-    fn test_poll_read_result_success() {
+        app.toggle_engineering_units();
+        let result = app.engineering_units_rx.take().unwrap().await.unwrap();
         let (tx, rx) = oneshot::channel();
-        let mock = MockOpcProvider::new();
-        let mut app = App::new(Arc::new(mock));
-        app.current_screen = CurrentScreen::Loading;
-        app.read_result_rx = Some(rx);
+        tx.send(result).unwrap();
+        app.engineering_units_rx = Some(rx);
+        app.poll_engineering_units();
 
-        let values = vec![TagValue {
-            tag_id: "Tag1".into(),
-            value: "123".into(),
-            quality: "Good".into(),
-            timestamp: "Today".into(),
-        }];
+        let temp = app.engineering_units_for("Temp").unwrap();
+        assert_eq!(temp.annotate("73.2"), "73.2 °C (range 0–200)");
+        assert!(app.engineering_units_for("Flag").is_none());
 
-        tx.send(Ok(values)).unwrap();
-        app.poll_read_result();
+        // Both tags are cached, so toggling again fetches nothing
+        app.toggle_engineering_units();
+        app.toggle_engineering_units();
+        assert!(app.engineering_units_rx.is_none());
 
-        assert_eq!(app.current_screen, CurrentScreen::TagValues);
-        assert_eq!(app.tag_values.len(), 1);
-        assert_eq!(app.tag_values[0].value, "123");
-        assert!(app.read_result_rx.is_none());
+        app.toggle_engineering_units();
+        assert!(
+            app.engineering_units_for("Temp").is_none(),
+            "hidden when off"
+        );
     }
 
     #[test]
-    fn test_poll_read_result_error() {
+    fn test_engineering_units_unsupported_falls_back_to_raw_values() {
         let (tx, rx) = oneshot::channel();
-        let mock = MockOpcProvider::new();
-        let mut app = App::new(Arc::new(mock));
-        app.current_screen = CurrentScreen::Loading;
-        app.read_result_rx = Some(rx);
+        let mut app = App::new(Arc::new(MockOpcProvider::new()));
+        app.show_engineering_units = true;
+        app.engineering_units_rx = Some(rx);
 
-        tx.send(Err(OpcError::Internal("Read failed".to_string())))
+        tx.send(Err(OpcError::NotImplemented("item properties".into())))
             .unwrap();
-        app.poll_read_result();
+        app.poll_engineering_units();
 
-        assert_eq!(app.current_screen, CurrentScreen::TagList);
-        assert!(app.read_result_rx.is_none());
+        assert!(!app.show_engineering_units);
+        assert!(app.engineering_units_rx.is_none());
         assert!(
             app.messages
                 .last()
                 .unwrap()
-                .contains("Error reading values")
+                .contains("server does not support item properties")
         );
     }
 
     #[test]
-    fn test_go_back_from_tag_values() {
+    fn test_retry_failed_reads_without_failures() {
         let mock = MockOpcProvider::new();
         let mut app = App::new(Arc::new(mock));
         app.current_screen = CurrentScreen::TagValues;
-        app.tags = vec!["Tag1".into()];
-        app.tag_values = vec![TagValue {
-            tag_id: "Tag1".into(),
-            value: "100".into(),
-            quality: "Good".into(),
-            timestamp: String::new(),
-        }];
+        app.refresh_server = Some("Server".into());
 
-        app.go_back();
+        app.retry_failed_reads();
 
-        assert_eq!(app.current_screen, CurrentScreen::TagList);
-        assert!(app.tag_values.is_empty());
-        assert_eq!(app.tags.len(), 1); // Tags preserved
+        assert!(app.retry_result_rx.is_none());
+        assert_eq!(app.messages.last().unwrap(), "No failed tags to retry");
     }
 
     #[test]
-    fn test_select_next_on_tag_values() {
+    fn test_load_from_snapshot_invalid_file() {
+        let path = write_snapshot_file("invalid.json", "not json");
+        let mock = MockOpcProvider::new();
+        let mut app = App::new(Arc::new(mock));
+
+        assert!(app.load_from_snapshot(&path).is_err());
+        std::fs::remove_file(&path).ok();
+        assert_eq!(app.current_screen, CurrentScreen::Home);
+    }
+
+    #[test]
+    fn test_table_tsv() {
         let mock = MockOpcProvider::new();
         let mut app = App::new(Arc::new(mock));
-        app.current_screen = CurrentScreen::TagValues;
         app.tag_values = vec![
             TagValue {
-                tag_id: "T1".into(),
-                value: "V1".into(),
-                quality: "Q".into(),
-                timestamp: "T".into(),
+                tag_id: "Tag.A".into(),
+                value: "1.5".into(),
+                quality: "Good".into(),
+                timestamp: "2024-01-01 12:00:00".into(),
+                raw_timestamp: None,
+                vt: Some(5),
+                access_rights: 0,
+                canonical_data_type: 3, // VT_I4
+                status: ReadStatus::Ok,
             },
             TagValue {
-                tag_id: "T2".into(),
-                value: "V2".into(),
-                quality: "Q".into(),
-                timestamp: "T".into(),
+                tag_id: "Tag.B".into(),
+                value: "line1\nline2\tend".into(),
+                quality: "Bad".into(),
+                timestamp: String::new(),
+                raw_timestamp: None,
+                vt: None,
+                access_rights: 0,
+                canonical_data_type: 0,
+                status: ReadStatus::Ok,
             },
         ];
-        app.selected_index = Some(0);
 
-        app.select_next();
-        assert_eq!(app.selected_index, Some(1));
+        assert_eq!(
+            app.table_tsv(),
+            "Tag ID\tData Type\tValue\tQuality\tTimestamp\n\
+             Tag.A\tI4\t1.5\tGood\t2024-01-01 12:00:00\n\
+             Tag.B\t—\tline1 line2 end\tBad\t\n"
+        );
 
-        app.select_next(); // Should stay at 1
-        assert_eq!(app.selected_index, Some(1));
+        app.show_vt_column = true;
+        let tsv = app.table_tsv();
+        let mut lines = tsv.lines();
+        assert_eq!(
+            lines.next(),
+            Some("Tag ID\tData Type\tValue\tType\tQuality\tTimestamp")
+        );
+        assert_eq!(
+            lines.next(),
+            Some("Tag.A\tI4\t1.5\tVT_R8\tGood\t2024-01-01 12:00:00")
+        );
+        assert_eq!(lines.next(), Some("Tag.B\t—\tline1 line2 end\t—\tBad\t"));
     }
 
     #[test]
-    fn test_page_down_basic() {
+    fn test_copy_table_tsv_empty() {
         let mock = MockOpcProvider::new();
         let mut app = App::new(Arc::new(mock));
-        app.current_screen = CurrentScreen::TagList;
-        app.tags = (0..50).map(|i| format!("T{}", i)).collect();
-        app.selected_index = Some(0);
+        app.current_screen = CurrentScreen::TagValues;
 
-        app.page_down();
-        assert_eq!(app.selected_index, Some(20));
+        app.copy_table_tsv();
+        assert_eq!(app.messages.last().unwrap(), "Nothing to copy");
+    }
 
-        app.page_down();
-        assert_eq!(app.selected_index, Some(40));
+    #[test]
+    fn test_session_log_mirrors_messages() {
+        let path =
+            std::env::temp_dir().join(format!("opc-cli-{}-session-test.log", std::process::id()));
+        std::fs::remove_file(&path).ok();
+        let mock = MockOpcProvider::new();
+        let mut app = App::new(Arc::new(mock));
+        app.session_log_path = Some(path.clone());
+        assert!(app.session_log.is_none()); // opened lazily
 
-        app.page_down(); // Should clamp to 49
-        assert_eq!(app.selected_index, Some(49));
+        for i in 0..5 {
+            app.add_message(format!("message {i}"));
+        }
+        assert!(app.session_log.is_some());
+        drop(app);
+
+        let text = std::fs::read_to_string(&path).unwrap();
+        std::fs::remove_file(&path).ok();
+        let lines: Vec<&str> = text.lines().filter(|l| !l.is_empty()).collect();
+        assert_eq!(lines.len(), 5);
+        for (i, line) in lines.iter().enumerate() {
+            // "YYYY-MM-DD HH:MM:SS [INFO] message N"
+            let (stamp, rest) = line.split_at(19);
+            assert!(
+                chrono::NaiveDateTime::parse_from_str(stamp, "%Y-%m-%d %H:%M:%S").is_ok(),
+                "bad timestamp in {line:?}"
+            );
+            assert_eq!(rest, format!(" [INFO] message {i}"));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_live_mode_uses_live_reads() {
+        let mut mock = MockOpcProvider::new();
+        mock.expect_read_tag_values_live()
+            .with(eq("Server1"), eq(vec!["Tag1".to_string()]))
+            .times(1)
+            .returning(|_, _| {
+                Ok(vec![TagValue {
+                    tag_id: "Tag1".into(),
+                    value: "7".into(),
+                    quality: "Good".into(),
+                    timestamp: String::new(),
+                    raw_timestamp: None,
+                    vt: None,
+                    access_rights: 0,
+                    canonical_data_type: 0,
+                    status: ReadStatus::Ok,
+                }])
+            });
+        mock.expect_read_tag_values().times(0);
+
+        let mut app = App::new(Arc::new(mock));
+        app.current_screen = CurrentScreen::TagValues;
+        app.refresh_server = Some("Server1".into());
+        app.refresh_tag_ids = vec!["Tag1".into()];
+        app.last_read_time = Some(std::time::Instant::now() - std::time::Duration::from_secs(2));
+
+        app.toggle_live_mode();
+        assert!(app.live_mode);
+        app.maybe_auto_refresh();
+
+        let rx = app.read_result_rx.as_mut().unwrap();
+        let result = rx.await.unwrap().unwrap();
+        assert_eq!(result[0].value, "7");
+    }
+
+    #[tokio::test]
+    async fn test_leaving_live_mode_releases_group() {
+        let (tx, rx) = oneshot::channel();
+        let tx = std::sync::Mutex::new(Some(tx));
+        let mut mock = MockOpcProvider::new();
+        mock.expect_stop_live_reads()
+            .with(eq("Server1"))
+            .times(1)
+            .returning(move |_| {
+                if let Some(tx) = tx.lock().unwrap().take() {
+                    let _ = tx.send(());
+                }
+                Ok(())
+            });
+
+        let mut app = App::new(Arc::new(mock));
+        app.reset_screen_stack(CurrentScreen::TagValues);
+        app.refresh_server = Some("Server1".into());
+        app.live_mode = true;
+
+        app.go_back();
+        assert!(!app.live_mode);
+        assert_eq!(app.current_screen, CurrentScreen::TagList);
+        tokio::time::timeout(std::time::Duration::from_secs(1), rx)
+            .await
+            .expect("stop_live_reads was not called")
+            .unwrap();
     }
 
     #[test]
-    fn test_page_up_basic() {
+    fn test_start_write_value_without_server_context() {
+        // No expectations: the provider must not be called
         let mock = MockOpcProvider::new();
         let mut app = App::new(Arc::new(mock));
-        app.current_screen = CurrentScreen::TagList;
-        app.tags = (0..50).map(|i| format!("T{}", i)).collect();
-        app.selected_index = Some(49);
+        app.current_screen = CurrentScreen::WriteInput;
+        app.write_tag_id = Some("Tag1".into());
+        app.write_value_input = "42".into();
+        app.refresh_server = None;
 
-        app.page_up();
-        assert_eq!(app.selected_index, Some(29));
+        app.start_write_value();
 
-        app.page_up();
-        assert_eq!(app.selected_index, Some(9));
+        assert!(app.write_result_rx.is_none());
+        assert_eq!(app.current_screen, CurrentScreen::WriteInput);
+        assert_eq!(app.write_value_input, "42", "input kept for retry");
+        assert!(
+            app.write_error
+                .as_deref()
+                .is_some_and(|e| e.contains("No server context"))
+        );
+        assert!(
+            app.messages
+                .last()
+                .unwrap()
+                .contains("Write to Tag1 not sent")
+        );
+    }
 
-        app.page_up(); // Should clamp to 0
-        assert_eq!(app.selected_index, Some(0));
+    #[test]
+    fn test_start_write_value_without_tag() {
+        let mock = MockOpcProvider::new();
+        let mut app = App::new(Arc::new(mock));
+        app.current_screen = CurrentScreen::WriteInput;
+        app.refresh_server = Some("Server1".into());
+        app.write_value_input = "42".into();
+
+        app.start_write_value();
+
+        assert!(app.write_result_rx.is_none());
+        assert_eq!(app.messages.last().unwrap(), "No tag selected to write.");
     }
 
     #[test]
-    fn test_search_basic_matching() {
+    fn test_export_tag_list_sorted_with_header() {
         let mock = MockOpcProvider::new();
         let mut app = App::new(Arc::new(mock));
-        app.current_screen = CurrentScreen::TagList;
+        app.browsed_server = Some("Server1".into());
         app.tags = vec![
-            "System.Cpu".into(),
-            "System.Mem".into(),
-            "User.Data".into(),
-            "User.Settings".into(),
+            "Zone.Δp".into(),
+            "Boiler.Température".into(),
+            "Alarm".into(),
         ];
-        app.selected_tags = vec![false; 4];
-
-        app.enter_search_mode();
-        assert!(app.search_mode);
-
-        app.update_search_query('s');
-        app.update_search_query('y');
-        app.update_search_query('s'); // Query: "sys"
+        let path = std::env::temp_dir().join(format!("opc-cli-{}-tags.txt", std::process::id()));
 
-        assert_eq!(app.search_matches.len(), 2);
-        assert_eq!(app.search_matches[0], 0); // System.Cpu
-        assert_eq!(app.search_matches[1], 1); // System.Mem
-        assert_eq!(app.selected_index, Some(0));
+        let count = app.export_tag_list(&path).unwrap();
+        let text = std::fs::read_to_string(&path).unwrap();
+        let _ = std::fs::remove_file(&path);
 
-        app.next_search_match();
-        assert_eq!(app.selected_index, Some(1));
+        assert_eq!(count, 3);
+        let mut lines = text.lines();
+        assert!(
+            lines
+                .next()
+                .unwrap()
+                .starts_with("# Server: Server1  Browsed: ")
+        );
+        assert_eq!(
+            lines.collect::<Vec<_>>(),
+            ["Alarm", "Boiler.Température", "Zone.Δp"]
+        );
+    }
 
-        app.next_search_match(); // Should wrap
-        assert_eq!(app.selected_index, Some(0));
+    #[test]
+    fn test_export_tag_list_as_csv() {
+        let mock = MockOpcProvider::new();
+        let mut app = App::new(Arc::new(mock));
+        app.tags = vec![
+            BrowsedTag {
+                tag_id: "Plant.Pump, 2".into(),
+                depth: 1,
+                parent_branch: "Plant".into(),
+            },
+            "Alarm".into(),
+        ];
+        let path = std::env::temp_dir().join(format!("opc-cli-{}-tags.csv", std::process::id()));
 
-        app.search_backspace(); // Query: "sy"
-        assert_eq!(app.search_matches.len(), 2);
+        let count = app.export_tag_list(&path).unwrap();
+        let text = std::fs::read_to_string(&path).unwrap();
+        let _ = std::fs::remove_file(&path);
 
-        app.exit_search_mode();
-        assert!(!app.search_mode);
+        assert_eq!(count, 2);
+        assert_eq!(
+            text,
+            "tag_id,branch\r\nAlarm,\r\n\"Plant.Pump, 2\",Plant\r\n"
+        );
     }
 }
@@ -0,0 +1,305 @@
+//! # bench
+//!
+//! Headless read-performance benchmark (`--bench`).
+//!
+//! ## Overview
+//!
+//! [`run`] browses up to `count` tags on a server, performs one untimed
+//! warm-up read (which also establishes the connection), then reads the same
+//! tags `iterations` times and reports latency statistics and throughput.
+//! Running it once with and once without `--live` compares per-read groups
+//! against a reused group read from the server cache.
+
+use anyhow::{Context, Result, bail};
+use opc_da_client::{AccessRightsFilter, BrowseProgress, OpcProvider};
+use serde::Serialize;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// What to benchmark.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BenchConfig {
+    /// Server `ProgID` to read from.
+    pub server: String,
+    /// Number of tags to read on each iteration.
+    pub count: usize,
+    /// Number of timed reads.
+    pub iterations: usize,
+    /// Read through a persistent group's cache instead of a new group per read.
+    pub live: bool,
+}
+
+/// Latency statistics and throughput for one benchmark run.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct BenchReport {
+    pub server: String,
+    /// `"device"` for a new group per read, `"live"` for the reused cached group.
+    pub mode: &'static str,
+    pub tag_count: usize,
+    /// Reads that completed successfully and were timed.
+    pub iterations: usize,
+    /// Reads that returned an error; excluded from the statistics.
+    pub failures: usize,
+    pub min_ms: f64,
+    pub avg_ms: f64,
+    pub p95_ms: f64,
+    pub max_ms: f64,
+    /// Tag values read per second over all successful reads.
+    pub tags_per_sec: f64,
+}
+
+impl BenchReport {
+    /// Human-readable summary table.
+    pub fn to_table(&self) -> String {
+        format!(
+            "Read benchmark: {} — {} tags × {} reads ({})\n\
+             {:<12}{:>10.2} ms\n\
+             {:<12}{:>10.2} ms\n\
+             {:<12}{:>10.2} ms\n\
+             {:<12}{:>10.2} ms\n\
+             {:<12}{:>10.1} tags/s\n\
+             {:<12}{:>10}",
+            self.server,
+            self.tag_count,
+            self.iterations,
+            self.mode,
+            "min",
+            self.min_ms,
+            "avg",
+            self.avg_ms,
+            "p95",
+            self.p95_ms,
+            "max",
+            self.max_ms,
+            "throughput",
+            self.tags_per_sec,
+            "failures",
+            self.failures,
+        )
+    }
+}
+
+/// Browse `config.count` tags on the server and time repeated reads of them.
+///
+/// # Errors
+/// Returns `Err` if the browse fails, finds no tags, the warm-up read fails,
+/// or every timed read fails.
+pub async fn run(provider: &dyn OpcProvider, config: &BenchConfig) -> Result<BenchReport> {
+    let tags = provider
+        .browse_tags(
+            &config.server,
+            config.count,
+            AccessRightsFilter::Any,
+            BrowseProgress::default(),
+        )
+        .await
+        .with_context(|| format!("Failed to browse tags on {}", config.server))?;
+    let tag_ids: Vec<String> = tags
+        .into_iter()
+        .take(config.count)
+        .map(|t| t.tag_id)
+        .collect();
+    if tag_ids.is_empty() {
+        bail!("No tags found on {}", config.server);
+    }
+    tracing::info!(
+        server = %config.server,
+        tags = tag_ids.len(),
+        iterations = config.iterations,
+        live = config.live,
+        "Starting read benchmark"
+    );
+
+    let read = |ids: Vec<String>| async move {
+        if config.live {
+            provider.read_tag_values_live(&config.server, ids).await
+        } else {
+            provider
+                .read_tag_values(&config.server, ids, Arc::default())
+                .await
+        }
+    };
+
+    read(tag_ids.clone()).await.context("Warm-up read failed")?;
+
+    let mut samples = Vec::with_capacity(config.iterations);
+    let mut failures = 0;
+    for _ in 0..config.iterations {
+        let start = Instant::now();
+        match read(tag_ids.clone()).await {
+            Ok(_) => samples.push(start.elapsed()),
+            Err(e) => {
+                tracing::warn!(error = %e, "Benchmark read failed");
+                failures += 1;
+            }
+        }
+    }
+
+    if config.live
+        && let Err(e) = provider.stop_live_reads(&config.server).await
+    {
+        tracing::warn!(error = %e, "Failed to release live group after benchmark");
+    }
+
+    let Some(stats) = LatencyStats::from_samples(&samples) else {
+        bail!("All {failures} benchmark reads failed");
+    };
+    let total_tags = tag_ids.len().saturating_mul(samples.len());
+    Ok(BenchReport {
+        server: config.server.clone(),
+        mode: if config.live { "live" } else { "device" },
+        tag_count: tag_ids.len(),
+        iterations: samples.len(),
+        failures,
+        min_ms: millis(stats.min),
+        avg_ms: millis(stats.avg),
+        p95_ms: millis(stats.p95),
+        max_ms: millis(stats.max),
+        tags_per_sec: f64::from(u32::try_from(total_tags).unwrap_or(u32::MAX))
+            / stats.total.as_secs_f64().max(f64::EPSILON),
+    })
+}
+
+/// Summary of a set of read latencies.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct LatencyStats {
+    min: Duration,
+    avg: Duration,
+    /// Nearest-rank 95th percentile.
+    p95: Duration,
+    max: Duration,
+    total: Duration,
+}
+
+impl LatencyStats {
+    /// `None` if there are no samples.
+    fn from_samples(samples: &[Duration]) -> Option<Self> {
+        let mut sorted = samples.to_vec();
+        sorted.sort_unstable();
+        let total: Duration = sorted.iter().sum();
+        let count = u32::try_from(sorted.len()).ok()?;
+        let p95_rank = (sorted.len() * 95).div_ceil(100);
+        Some(Self {
+            min: *sorted.first()?,
+            avg: total / count,
+            p95: sorted[p95_rank.saturating_sub(1)],
+            max: *sorted.last()?,
+            total,
+        })
+    }
+}
+
+fn millis(d: Duration) -> f64 {
+    d.as_secs_f64() * 1000.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mockall::predicate::*;
+    use opc_da_client::{BrowsedTag, MockOpcProvider, OpcError};
+
+    fn ms(n: u64) -> Duration {
+        Duration::from_millis(n)
+    }
+
+    #[test]
+    fn test_latency_stats() {
+        let samples: Vec<Duration> = (1..=20).rev().map(ms).collect();
+        let stats = LatencyStats::from_samples(&samples).unwrap();
+        assert_eq!(stats.min, ms(1));
+        assert_eq!(stats.max, ms(20));
+        assert_eq!(stats.p95, ms(19));
+        assert_eq!(stats.total, ms(210));
+        assert_eq!(stats.avg, Duration::from_micros(10_500));
+
+        let single = LatencyStats::from_samples(&[ms(7)]).unwrap();
+        assert_eq!(single.p95, ms(7));
+        assert!(LatencyStats::from_samples(&[]).is_none());
+    }
+
+    #[tokio::test]
+    async fn test_run_reads_browsed_tags() {
+        let mut mock = MockOpcProvider::new();
+        mock.expect_browse_tags()
+            .with(eq("Server1"), eq(2), eq(AccessRightsFilter::Any), always())
+            .times(1)
+            .returning(|_, _, _, _| Ok(vec![BrowsedTag::from("A"), BrowsedTag::from("B")]));
+        // Warm-up read plus three timed reads, one of which fails
+        let mut calls = 0;
+        mock.expect_read_tag_values()
+            .with(
+                eq("Server1"),
+                eq(vec!["A".to_string(), "B".to_string()]),
+                always(),
+            )
+            .times(4)
+            .returning(move |_, _, _| {
+                calls += 1;
+                if calls == 3 {
+                    Err(OpcError::Internal("busy".into()))
+                } else {
+                    Ok(vec![])
+                }
+            });
+        mock.expect_read_tag_values_live().times(0);
+
+        let config = BenchConfig {
+            server: "Server1".into(),
+            count: 2,
+            iterations: 3,
+            live: false,
+        };
+        let report = run(&mock, &config).await.unwrap();
+        assert_eq!(report.mode, "device");
+        assert_eq!(report.tag_count, 2);
+        assert_eq!(report.iterations, 2);
+        assert_eq!(report.failures, 1);
+        assert!(report.min_ms <= report.p95_ms && report.p95_ms <= report.max_ms);
+        assert!(report.to_table().contains("2 tags × 2 reads (device)"));
+    }
+
+    #[tokio::test]
+    async fn test_run_live_releases_group() {
+        let mut mock = MockOpcProvider::new();
+        mock.expect_browse_tags()
+            .returning(|_, _, _, _| Ok(vec![BrowsedTag::from("A")]));
+        mock.expect_read_tag_values_live()
+            .times(3)
+            .returning(|_, _| Ok(vec![]));
+        mock.expect_stop_live_reads()
+            .with(eq("Server1"))
+            .times(1)
+            .returning(|_| Ok(()));
+
+        let config = BenchConfig {
+            server: "Server1".into(),
+            count: 10,
+            iterations: 2,
+            live: true,
+        };
+        let report = run(&mock, &config).await.unwrap();
+        assert_eq!(report.mode, "live");
+        assert_eq!(report.tag_count, 1);
+
+        let json = serde_json::to_value(&report).unwrap();
+        assert_eq!(json["mode"], "live");
+        assert_eq!(json["iterations"], 2);
+    }
+
+    #[tokio::test]
+    async fn test_run_without_tags_fails() {
+        let mut mock = MockOpcProvider::new();
+        mock.expect_browse_tags().returning(|_, _, _, _| Ok(vec![]));
+        mock.expect_read_tag_values().times(0);
+
+        let config = BenchConfig {
+            server: "Empty".into(),
+            count: 5,
+            iterations: 1,
+            live: false,
+        };
+        let err = run(&mock, &config).await.unwrap_err();
+        assert!(err.to_string().contains("No tags found"));
+    }
+}
@@ -0,0 +1,167 @@
+//! # config
+//!
+//! Optional JSON configuration file for the TUI.
+//!
+//! ## Overview
+//!
+//! Settings that outlive a single run live in `opc-cli.json` in the working
+//! directory, or the file given with `--config`. Every section is optional,
+//! so an empty object (or no file at all) means defaults throughout.
+//!
+//! What the TUI learns while running, such as how many tags each server
+//! had, is kept apart from the hand-written config in
+//! [`BROWSE_ESTIMATES_FILE`] and [`SEARCH_HISTORY_FILE`].
+
+use crate::theme::ThemeConfig;
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, VecDeque};
+use std::path::Path;
+
+/// Config file read when `--config` is not given.
+pub const DEFAULT_CONFIG_FILE: &str = "opc-cli.json";
+
+/// File caching the tag count of each server's last complete browse.
+pub const BROWSE_ESTIMATES_FILE: &str = "opc-cli-browse-estimates.json";
+
+/// File keeping the tag list's recent search queries, newest first.
+pub const SEARCH_HISTORY_FILE: &str = "opc-cli-search-history.json";
+
+/// Contents of the config file.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct CliConfig {
+    /// Colour theme; see [`crate::theme`].
+    pub theme: ThemeConfig,
+}
+
+impl CliConfig {
+    /// Read and parse a config file.
+    pub fn load(path: &Path) -> Result<Self> {
+        let text = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read config {}", path.display()))?;
+        serde_json::from_str(&text)
+            .with_context(|| format!("Invalid config file {}", path.display()))
+    }
+
+    /// Read [`DEFAULT_CONFIG_FILE`] if it exists, defaults otherwise.
+    pub fn load_default() -> Result<Self> {
+        let path = Path::new(DEFAULT_CONFIG_FILE);
+        if path.exists() {
+            Self::load(path)
+        } else {
+            Ok(Self::default())
+        }
+    }
+}
+
+/// Tag count of the last complete browse of each server, used to show how
+/// far along the next browse of it is.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct BrowseEstimates(BTreeMap<String, usize>);
+
+impl BrowseEstimates {
+    /// Read the cache at `path`. A missing or unreadable cache means no
+    /// estimates: they only refine a progress display.
+    pub fn load(path: &Path) -> Self {
+        let Ok(text) = std::fs::read_to_string(path) else {
+            return Self::default();
+        };
+        serde_json::from_str(&text).unwrap_or_else(|e| {
+            tracing::warn!(path = %path.display(), error = %e, "Ignoring invalid browse estimates");
+            Self::default()
+        })
+    }
+
+    /// Write the cache to `path`.
+    pub fn save(&self, path: &Path) -> Result<()> {
+        let text = serde_json::to_string_pretty(self)?;
+        std::fs::write(path, text)
+            .with_context(|| format!("Failed to write browse estimates {}", path.display()))
+    }
+
+    /// Tag count last recorded for `key`.
+    pub fn get(&self, key: &str) -> Option<usize> {
+        self.0.get(key).copied()
+    }
+
+    /// Record `count` tags for `key`, replacing any earlier count.
+    pub fn record(&mut self, key: String, count: usize) {
+        self.0.insert(key, count);
+    }
+}
+
+/// Read the search history at `path`. A missing or unreadable file means
+/// an empty history.
+pub fn load_search_history(path: &Path) -> VecDeque<String> {
+    let Ok(text) = std::fs::read_to_string(path) else {
+        return VecDeque::new();
+    };
+    serde_json::from_str(&text).unwrap_or_else(|e| {
+        tracing::warn!(path = %path.display(), error = %e, "Ignoring invalid search history");
+        VecDeque::new()
+    })
+}
+
+/// Write the search history to `path`.
+pub fn save_search_history(history: &VecDeque<String>, path: &Path) -> Result<()> {
+    let text = serde_json::to_string_pretty(history)?;
+    std::fs::write(path, text)
+        .with_context(|| format!("Failed to write search history {}", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_theme_section() {
+        let config: CliConfig = serde_json::from_str(
+            r#"{"theme": {"name": "high-contrast", "colors": {"header": "cyan"}}}"#,
+        )
+        .unwrap();
+        assert_eq!(config.theme.name.as_deref(), Some("high-contrast"));
+        assert_eq!(config.theme.colors["header"], "cyan");
+
+        assert_eq!(
+            serde_json::from_str::<CliConfig>("{}").unwrap(),
+            CliConfig::default()
+        );
+        assert!(serde_json::from_str::<CliConfig>(r#"{"colour": {}}"#).is_err());
+    }
+
+    #[test]
+    fn test_browse_estimates_round_trip() {
+        let dir = std::env::temp_dir().join(format!("opc-cli-estimates-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join(BROWSE_ESTIMATES_FILE);
+
+        assert_eq!(BrowseEstimates::load(&path), BrowseEstimates::default());
+
+        let mut estimates = BrowseEstimates::default();
+        estimates.record("localhost/Server.A (any)".into(), 1234);
+        estimates.save(&path).unwrap();
+        let loaded = BrowseEstimates::load(&path);
+        assert_eq!(loaded.get("localhost/Server.A (any)"), Some(1234));
+        assert_eq!(loaded.get("localhost/Server.B (any)"), None);
+
+        std::fs::write(&path, "not json").unwrap();
+        assert_eq!(BrowseEstimates::load(&path), BrowseEstimates::default());
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_search_history_round_trip() {
+        let path = std::env::temp_dir().join(format!(
+            "opc-cli-{}-{SEARCH_HISTORY_FILE}",
+            std::process::id()
+        ));
+        assert!(load_search_history(&path).is_empty());
+
+        let history = VecDeque::from(["Pump".to_string(), "Tank.Level".to_string()]);
+        save_search_history(&history, &path).unwrap();
+        assert_eq!(load_search_history(&path), history);
+        std::fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,95 @@
+//! # connectivity
+//!
+//! Quick network checks run before talking DCOM to a remote host.
+//!
+//! ## Overview
+//!
+//! DCOM first contacts the RPC endpoint mapper on TCP port 135. When a
+//! firewall drops that port, `list_servers` only fails after the full COM
+//! timeout with an unhelpful `RPC_S_SERVER_UNAVAILABLE`. A plain TCP
+//! connect with a short timeout detects the same problem in about a second.
+
+use std::future::Future;
+use std::pin::Pin;
+use std::time::Duration;
+use tokio::net::TcpStream;
+
+/// Reachability check of a host's DCOM port, such as [`probe_dcom_port_owned`].
+pub type DcomProbe = fn(String) -> Pin<Box<dyn Future<Output = bool> + Send>>;
+
+/// TCP port of the RPC endpoint mapper that DCOM connects to first.
+pub const DCOM_PORT: u16 = 135;
+
+/// How long [`probe_dcom_port`] waits for the connection.
+pub const PROBE_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// Whether `host` accepts a TCP connection on the DCOM endpoint mapper port
+/// within [`PROBE_TIMEOUT`].
+pub async fn probe_dcom_port(host: &str) -> bool {
+    probe_port(host, DCOM_PORT, PROBE_TIMEOUT).await
+}
+
+/// [`probe_dcom_port`] taking ownership of `host`, as a [`DcomProbe`].
+pub fn probe_dcom_port_owned(host: String) -> Pin<Box<dyn Future<Output = bool> + Send>> {
+    Box::pin(async move { probe_dcom_port(&host).await })
+}
+
+/// Whether `host` accepts a TCP connection on `port` within `timeout`.
+///
+/// `host` may be a hostname or an IPv4/IPv6 literal, with or without
+/// brackets. Resolution failures count as unreachable.
+pub async fn probe_port(host: &str, port: u16, timeout: Duration) -> bool {
+    let host = host.trim().trim_start_matches('[').trim_end_matches(']');
+    let reachable = matches!(
+        tokio::time::timeout(timeout, TcpStream::connect((host, port))).await,
+        Ok(Ok(_))
+    );
+    tracing::debug!(host, port, reachable, "probe_port");
+    reachable
+}
+
+/// Whether `host` names this machine, where COM does not go through the
+/// endpoint mapper on TCP and a probe would prove nothing.
+pub fn is_local_host(host: &str) -> bool {
+    let host = host.trim();
+    host.is_empty()
+        || host == "."
+        || host.eq_ignore_ascii_case("localhost")
+        || host == "127.0.0.1"
+        || host == "::1"
+        || host == "[::1]"
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::net::TcpListener;
+
+    #[tokio::test]
+    async fn test_probe_port_reachable() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+
+        assert!(probe_port("127.0.0.1", port, PROBE_TIMEOUT).await);
+    }
+
+    #[tokio::test]
+    async fn test_probe_port_unreachable() {
+        // Bind and release a port so nothing is listening on it
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        drop(listener);
+
+        assert!(!probe_port("127.0.0.1", port, PROBE_TIMEOUT).await);
+        assert!(!probe_port("host.invalid", port, PROBE_TIMEOUT).await);
+    }
+
+    #[test]
+    fn test_is_local_host() {
+        for host in ["", ".", "LocalHost", "127.0.0.1", "::1", "[::1]"] {
+            assert!(is_local_host(host), "{host:?}");
+        }
+        assert!(!is_local_host("plc-gateway"));
+        assert!(!is_local_host("10.0.0.5"));
+    }
+}
@@ -11,22 +11,243 @@
 //! and runs the primary input-event and render loops.
 
 mod app;
+mod bench;
+mod config;
+mod connectivity;
+mod op_id;
+mod output;
+mod palette;
+mod recording;
+mod session_log;
+mod snapshot;
+mod theme;
 mod ui;
+mod watch;
 
-use crate::app::{App, CurrentScreen};
+use crate::app::{App, CurrentScreen, DEFAULT_READ_BATCH_SIZE};
+use crate::config::{BROWSE_ESTIMATES_FILE, BrowseEstimates, CliConfig, SEARCH_HISTORY_FILE};
+use crate::output::OutputFormat;
+use crate::recording::ReplayOpcProvider;
+use crate::theme::Theme;
 use anyhow::Result;
+use clap::Parser;
 use crossterm::{
-    event::{self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode},
+    event::{self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode, KeyModifiers},
     execute,
     terminal::{EnterAlternateScreen, LeaveAlternateScreen, disable_raw_mode, enable_raw_mode},
 };
-use opc_da_client::{ComConnector, OpcDaClient};
+use opc_da_client::{
+    AccessRightsFilter, ComApartment, ComConnector, OpcDaClient, OpcDaConfig, OpcProvider,
+    SimulatedOpcProvider, vt_from_str,
+};
 use ratatui::{Terminal, backend::CrosstermBackend};
-use std::{io, sync::Arc, time::Duration};
+use std::{
+    io,
+    path::{Path, PathBuf},
+    sync::{
+        Arc,
+        atomic::{AtomicBool, Ordering},
+    },
+    time::Duration,
+};
 use tracing_subscriber::{EnvFilter, fmt, prelude::*};
 
+/// Command-line arguments.
+#[derive(Debug, Parser)]
+#[command(version, about)]
+struct Cli {
+    /// Open a saved tag-value snapshot (JSON) in offline mode instead of connecting.
+    #[arg(long, value_name = "FILE")]
+    snapshot: Option<PathBuf>,
+
+    /// Show the previous run's status messages before starting the TUI.
+    #[arg(long)]
+    show_last_session: bool,
+
+    /// Replay a recording made with `Ctrl+R` instead of connecting: reads
+    /// return the recorded values in order, at their original intervals.
+    #[arg(long, value_name = "FILE", conflicts_with_all = ["snapshot", "simulate"])]
+    replay: Option<PathBuf>,
+
+    /// Run against a built-in simulated server with changing synthetic values
+    /// instead of OPC DA, for trying out the UI without a server.
+    #[arg(long, conflicts_with = "snapshot")]
+    simulate: bool,
+
+    /// Benchmark repeated reads against `--server` and exit (no TUI).
+    #[arg(long, requires = "server", conflicts_with = "snapshot")]
+    bench: bool,
+
+    /// Read `--tags` from `--server` once, print the values in `--format`
+    /// and exit (no TUI).
+    #[arg(long, requires_all = ["server", "tags"], conflicts_with_all = ["snapshot", "bench"])]
+    read: bool,
+
+    /// Comma-separated tag IDs for `--read`.
+    #[arg(long, value_name = "TAGS", value_delimiter = ',')]
+    tags: Vec<String>,
+
+    /// Poll the tags listed in FILE (one per line) from `--server` every
+    /// `--interval` seconds and write their values to `--watch-out` until
+    /// Ctrl+C (no TUI).
+    #[arg(
+        long,
+        value_name = "FILE",
+        requires_all = ["server", "watch_out"],
+        conflicts_with_all = ["snapshot", "bench", "read"]
+    )]
+    watch_in: Option<PathBuf>,
+
+    /// File `--watch-in` writes to. A `.csv` file is appended to each round;
+    /// `.json` and other files are replaced with the latest values.
+    #[arg(long, value_name = "FILE", requires = "watch_in")]
+    watch_out: Option<PathBuf>,
+
+    /// Seconds between `--watch-in` reads.
+    #[arg(long, value_name = "SECS", default_value_t = 5, value_parser = clap::value_parser!(u64).range(1..))]
+    interval: u64,
+
+    /// How `--read` prints values: `table` (default), `csv` or `json`.
+    #[arg(long, value_name = "FORMAT", default_value_t = OutputFormat::Table)]
+    format: OutputFormat,
+
+    /// Server `ProgID` to benchmark or read from.
+    #[arg(long, value_name = "PROGID")]
+    server: Option<String>,
+
+    /// Number of tags to read per benchmark iteration.
+    #[arg(long, value_name = "N", default_value_t = 100)]
+    count: usize,
+
+    /// Number of timed benchmark reads.
+    #[arg(long, value_name = "M", default_value_t = 20)]
+    iterations: usize,
+
+    /// Benchmark reads from a reused, cached group instead of a new group per read.
+    #[arg(long)]
+    live: bool,
+
+    /// Print the benchmark report as JSON.
+    #[arg(long)]
+    json: bool,
+
+    /// COM apartment model for the OPC worker thread: `mta` (default) or `sta`
+    /// for legacy in-process servers that require a single-threaded apartment.
+    #[arg(long, value_name = "MODEL", default_value_t = ComApartment::MultiThreaded)]
+    apartment: ComApartment,
+
+    /// Show values of unrecognised VARIANT types as a hex dump of their first 8 bytes.
+    #[arg(long)]
+    hex_unknown_types: bool,
+
+    /// Read every element of array values instead of eliding after the first
+    /// 20, so TUI exports hold the complete arrays. Always on for `--read`
+    /// and `--watch-in`.
+    #[arg(long)]
+    full_arrays: bool,
+
+    /// On connecting to a server, remove groups a previous run left behind
+    /// (any group named `opc-da-client-*`).
+    #[arg(long)]
+    cleanup_groups: bool,
+
+    /// On connecting to a server, check its reported state and refuse to use
+    /// it while it is failed, suspended or in a communication fault.
+    #[arg(long)]
+    probe_on_connect: bool,
+
+    /// Convert each written value to the tag's canonical data type before
+    /// sending it, for servers that reject writes of a mismatched type.
+    #[arg(long)]
+    type_safe_writes: bool,
+
+    /// Check that the server returns read results in the order the tags were
+    /// requested, and fail the read instead of showing values against the
+    /// wrong tags if it does not.
+    #[arg(long)]
+    strict_client_handles: bool,
+
+    /// Read a tag as a specific VARIANT type, e.g. `Tank.Level=VT_R8`, for
+    /// servers that can convert it. Repeat for more tags.
+    #[arg(long, value_name = "TAG=TYPE", value_parser = parse_request_type)]
+    request_type: Vec<(String, u16)>,
+
+    /// Accept `,` as the decimal separator when typing write values, so
+    /// `3,5` is written as the number 3.5 rather than as text.
+    #[arg(long)]
+    decimal_comma: bool,
+
+    /// Largest number of tags per read request and OPC group; bigger reads are
+    /// split into batches of this size. Lower it for servers that fail or
+    /// time out on large reads.
+    ///
+    /// The TUI batches its first read of a selection itself, to show
+    /// progress per batch; auto-refresh and `--read` leave the splitting to
+    /// the provider's `max_items_per_group`, which is set to the same value.
+    #[arg(long, value_name = "N", default_value_t = DEFAULT_READ_BATCH_SIZE)]
+    read_batch_size: usize,
+
+    /// Deepest branch level walked when browsing a hierarchical namespace;
+    /// tags below it are omitted with a warning.
+    #[arg(long, value_name = "LEVELS", default_value_t = OpcDaConfig::default().max_browse_depth)]
+    max_browse_depth: usize,
+
+    /// Seconds without a server response after which the status log warns
+    /// that the connection may be stale and the next action will reconnect
+    /// (0 = never).
+    #[arg(long, value_name = "SECS", default_value_t = app::DEFAULT_STALE_AFTER_SECS)]
+    stale_after: u64,
+
+    /// Amount `+`/`-` add to or subtract from a numeric value on the Tag
+    /// Values screen.
+    #[arg(long, value_name = "N", default_value_t = app::DEFAULT_INCREMENT_STEP, value_parser = parse_step)]
+    step: f64,
+
+    /// Amount `Alt` + `+`/`-` add to or subtract from a numeric value.
+    #[arg(long, value_name = "N", default_value_t = app::DEFAULT_LARGE_INCREMENT_STEP, value_parser = parse_step)]
+    large_step: f64,
+
+    /// Browse only tags with these access rights: `any` (default), `readable`
+    /// or `writable`. Filtering happens on the server.
+    #[arg(long, value_name = "ACCESS", default_value_t = AccessRightsFilter::Any)]
+    browse_filter: AccessRightsFilter,
+
+    /// Skip the check that a remote host answers on TCP port 135 before
+    /// listing its servers, for networks where the port is filtered but
+    /// DCOM still gets through.
+    #[arg(long)]
+    no_probe: bool,
+
+    /// Go back to the tag list on the first failed auto-refresh read,
+    /// instead of retrying and, after five COM errors in a row, browsing the
+    /// server again and re-reading the same tags.
+    #[arg(long)]
+    no_reconnect: bool,
+
+    /// JSON config file (default: `opc-cli.json` in the working directory, if present).
+    #[arg(long, value_name = "FILE")]
+    config: Option<PathBuf>,
+
+    /// Colour theme: `default`, `high-contrast`, `monochrome`, `dark` or `light`. Overrides the
+    /// config file's theme name; its colour overrides still apply.
+    #[arg(long, value_name = "NAME")]
+    theme: Option<String>,
+}
+
 #[tokio::main]
 async fn main() -> Result<()> {
+    let cli = Cli::parse();
+
+    // Load the config BEFORE anything else so a broken file fails fast
+    let mut config = match &cli.config {
+        Some(path) => CliConfig::load(path)?,
+        None => CliConfig::load_default()?,
+    };
+    if let Some(name) = &cli.theme {
+        config.theme.name = Some(name.clone());
+    }
+    let theme = Theme::from_config(&config.theme)?;
+
     // Initialize logging
     let file_appender = tracing_appender_localtime::rolling::daily("logs", "opc-cli.log");
     let (non_blocking, _guard) = tracing_appender_localtime::non_blocking(file_appender);
@@ -44,10 +265,91 @@ async fn main() -> Result<()> {
 
     tracing::info!("Starting OPC CLI");
 
-    // COM initialization is handled transparently by the OpcDaClient worker thread.
+    // COM initialization is handled transparently by the OpcDaClient worker
+    // thread, in the apartment chosen with --apartment.
+    tracing::info!(apartment = %cli.apartment, "COM apartment model");
 
     // Create OPC client BEFORE entering TUI mode so init errors are visible
-    let opc_wrapper = Arc::new(OpcDaClient::new(ComConnector)?);
+    let opc_config = OpcDaConfig::default()
+        .with_apartment(cli.apartment)
+        .with_hex_dump_unknown_vt(cli.hex_unknown_types)
+        .with_full_array_values(cli.full_array_values())
+        .with_cleanup_groups_on_connect(cli.cleanup_groups)
+        .with_probe_on_connect(cli.probe_on_connect)
+        .with_type_safe_writes(cli.type_safe_writes)
+        .with_strict_client_handles(cli.strict_client_handles)
+        // Same limit as App::read_batch_size, so an App batch is never split
+        // again and provider-side splitting uses the same size
+        .with_max_items_per_group(cli.read_batch_size)
+        .with_max_browse_depth(cli.max_browse_depth);
+    let opc_config = cli
+        .request_type
+        .iter()
+        .fold(opc_config, |config, (tag_id, vt)| {
+            config.with_requested_data_type(tag_id.clone(), *vt)
+        });
+    let (opc_wrapper, worker): (Arc<dyn OpcProvider>, _) = if cli.simulate {
+        tracing::info!("Using simulated OPC provider");
+        (Arc::new(SimulatedOpcProvider::new()), None)
+    } else if let Some(path) = &cli.replay {
+        tracing::info!(path = %path.display(), "Replaying recorded reads");
+        (Arc::new(ReplayOpcProvider::load(path)?), None)
+    } else {
+        let client = OpcDaClient::with_config(ComConnector, opc_config)?;
+        let worker = Arc::clone(&client.worker);
+        (Arc::new(client), Some(worker))
+    };
+
+    if cli.bench {
+        return run_bench(opc_wrapper.as_ref(), &cli).await;
+    }
+    if cli.read {
+        return run_read(opc_wrapper.as_ref(), &cli).await;
+    }
+    if let (Some(input), Some(output)) = (&cli.watch_in, &cli.watch_out) {
+        let config = watch::WatchConfig {
+            server: cli.server.clone().unwrap_or_default(),
+            input: input.clone(),
+            output: output.clone(),
+            // The file name wins, so `--watch-out values.csv` needs no `--format`
+            format: OutputFormat::from_path(output).unwrap_or(cli.format),
+            interval: Duration::from_secs(cli.interval),
+        };
+        return watch::run(opc_wrapper.as_ref(), &config).await;
+    }
+
+    let log_dir = Path::new(session_log::SESSION_LOG_DIR);
+    // Read before this run's own log can be created
+    let last_session = if cli.show_last_session {
+        Some(session_log::read_last_session(log_dir)?)
+    } else {
+        None
+    };
+
+    // Load the snapshot BEFORE entering TUI mode so file errors are visible
+    let mut app = App::new(opc_wrapper);
+    app.worker_queue_depth = worker.as_ref().map(|w| w.queue_depth_handle());
+    app.worker_heartbeat = worker;
+    app.browse_filter = cli.browse_filter;
+    app.read_batch_size = cli.read_batch_size;
+    app.decimal_comma = cli.decimal_comma;
+    app.probe_dcom = !cli.no_probe;
+    app.auto_reconnect = !cli.no_reconnect;
+    app.stale_after = (cli.stale_after > 0).then(|| Duration::from_secs(cli.stale_after));
+    app.increment_step = cli.step;
+    app.large_increment_step = cli.large_step;
+    app.theme = theme;
+    app.session_log_path = Some(session_log::new_session_path(log_dir));
+    let estimates_path = PathBuf::from(BROWSE_ESTIMATES_FILE);
+    app.browse_estimates = BrowseEstimates::load(&estimates_path);
+    app.browse_estimates_path = Some(estimates_path);
+    let history_path = PathBuf::from(SEARCH_HISTORY_FILE);
+    app.search_history = config::load_search_history(&history_path);
+    app.search_history_path = Some(history_path);
+    if let Some(path) = &cli.snapshot {
+        app.load_from_snapshot(path)?;
+    }
+    spawn_shutdown_listener(Arc::clone(&app.shutdown_requested));
 
     // Setup terminal
     enable_raw_mode()?;
@@ -56,9 +358,12 @@ async fn main() -> Result<()> {
     let backend = CrosstermBackend::new(stdout);
     let mut terminal = Terminal::new(backend)?;
 
-    // Create app and run it
-    let mut app = App::new(opc_wrapper);
-    let res = run_app(&mut terminal, &mut app);
+    // Run the app
+    let res = match &last_session {
+        Some((path, lines)) => view_session_log(&mut terminal, path, lines, &app.theme),
+        None => Ok(()),
+    }
+    .and_then(|()| run_app(&mut terminal, &mut app));
 
     // Restore terminal
     disable_raw_mode()?;
@@ -76,6 +381,112 @@ async fn main() -> Result<()> {
     Ok(())
 }
 
+/// Parse a `--request-type` value: a tag ID, `=`, and a `VARTYPE` name.
+impl Cli {
+    /// Whether array values are read in full: asked for with `--full-arrays`,
+    /// and always for the headless modes, whose output is an export.
+    const fn full_array_values(&self) -> bool {
+        self.full_arrays || self.read || self.watch_in.is_some()
+    }
+}
+
+fn parse_request_type(arg: &str) -> Result<(String, u16), String> {
+    let (tag_id, vt) = arg
+        .rsplit_once('=')
+        .ok_or_else(|| format!("expected TAG=TYPE, got '{arg}'"))?;
+    let vt = vt_from_str(vt)
+        .ok_or_else(|| format!("unknown VARIANT type '{vt}' (e.g. VT_R8, VT_I4, VT_BSTR)"))?;
+    Ok((tag_id.trim().to_string(), vt))
+}
+
+/// Parse a `--step` or `--large-step` value: a finite number above zero.
+fn parse_step(arg: &str) -> Result<f64, String> {
+    match arg.trim().parse::<f64>() {
+        Ok(step) if step.is_finite() && step > 0.0 => Ok(step),
+        _ => Err(format!("expected a number above zero, got '{arg}'")),
+    }
+}
+
+/// Headless `--bench` mode: print the report to stdout.
+async fn run_bench(provider: &dyn OpcProvider, cli: &Cli) -> Result<()> {
+    let config = bench::BenchConfig {
+        server: cli.server.clone().unwrap_or_default(),
+        count: cli.count,
+        iterations: cli.iterations,
+        live: cli.live,
+    };
+    let report = bench::run(provider, &config).await?;
+    if cli.json {
+        println!("{}", serde_json::to_string_pretty(&report)?);
+    } else {
+        println!("{}", report.to_table());
+    }
+    Ok(())
+}
+
+/// Headless `--read` mode: read the tags once and print them to stdout.
+async fn run_read(provider: &dyn OpcProvider, cli: &Cli) -> Result<()> {
+    let server = cli.server.as_deref().unwrap_or_default();
+    let values = provider
+        .read_tag_values(server, cli.tags.clone(), Arc::default())
+        .await?;
+    let mut stdout = io::stdout().lock();
+    cli.format.writer().write_records(
+        &mut stdout,
+        &output::TAG_VALUE_COLUMNS,
+        &output::tag_value_rows(&values),
+    )?;
+    Ok(())
+}
+
+/// Set `flag` when the process is asked to stop from outside the TUI, so
+/// `run_app` leaves through the normal teardown path: the terminal is
+/// restored and the COM worker uninitializes on drop.
+fn spawn_shutdown_listener(flag: Arc<AtomicBool>) {
+    tokio::spawn(async move {
+        match wait_for_shutdown_signal().await {
+            Ok(signal) => {
+                tracing::info!(signal, "Shutdown signal received");
+                flag.store(true, Ordering::SeqCst);
+            }
+            Err(e) => tracing::warn!(error = %e, "Cannot listen for shutdown signals"),
+        }
+    });
+}
+
+/// Wait for Ctrl+C, Ctrl+Break, the console window closing, logoff or
+/// system shutdown.
+#[cfg(windows)]
+async fn wait_for_shutdown_signal() -> io::Result<&'static str> {
+    use tokio::signal::windows;
+
+    let mut ctrl_break = windows::ctrl_break()?;
+    let mut close = windows::ctrl_close()?;
+    let mut logoff = windows::ctrl_logoff()?;
+    let mut shutdown = windows::ctrl_shutdown()?;
+    Ok(tokio::select! {
+        _ = tokio::signal::ctrl_c() => "Ctrl+C",
+        _ = ctrl_break.recv() => "Ctrl+Break",
+        _ = close.recv() => "console closed",
+        _ = logoff.recv() => "logoff",
+        _ = shutdown.recv() => "system shutdown",
+    })
+}
+
+/// Wait for SIGINT, SIGTERM, or SIGHUP (the terminal or SSH session went away).
+#[cfg(unix)]
+async fn wait_for_shutdown_signal() -> io::Result<&'static str> {
+    use tokio::signal::unix::{SignalKind, signal};
+
+    let mut terminate = signal(SignalKind::terminate())?;
+    let mut hangup = signal(SignalKind::hangup())?;
+    Ok(tokio::select! {
+        _ = tokio::signal::ctrl_c() => "SIGINT",
+        _ = terminate.recv() => "SIGTERM",
+        _ = hangup.recv() => "SIGHUP",
+    })
+}
+
 fn run_app<B: ratatui::backend::Backend>(
     terminal: &mut Terminal<B>,
     app: &mut App,
@@ -89,8 +500,18 @@ fn run_app<B: ratatui::backend::Backend>(
         app.poll_fetch_result();
         app.poll_browse_result();
         app.poll_read_result();
+        app.poll_retry_result();
+        app.poll_engineering_units();
+        app.poll_server_latencies();
+        app.poll_connection_diagnostics();
         app.poll_write_result();
+        app.poll_bulk_write_result();
+        app.check_worker_queue();
+        app.check_worker_health();
         app.maybe_auto_refresh();
+        app.refresh_background_sessions();
+        app.tick_loading();
+        app.check_shutdown_requested();
 
         terminal.draw(|f| ui::render(f, app))?;
 
@@ -106,11 +527,70 @@ fn run_app<B: ratatui::backend::Backend>(
     }
 }
 
+/// Read-only, scrollable view of a previous session log; any of
+/// `Enter`/`Esc`/`q` continues into the TUI.
+fn view_session_log<B: ratatui::backend::Backend>(
+    terminal: &mut Terminal<B>,
+    path: &Path,
+    lines: &[String],
+    theme: &Theme,
+) -> io::Result<()> {
+    let max_scroll = lines.len().saturating_sub(1);
+    let mut scroll = 0usize;
+    loop {
+        terminal.draw(|f| ui::render_session_log(f, path, lines, scroll, theme))?;
+
+        if event::poll(Duration::from_millis(100))?
+            && let Event::Key(key) = event::read()?
+            && key.kind == event::KeyEventKind::Press
+        {
+            match key.code {
+                KeyCode::Down => scroll = (scroll + 1).min(max_scroll),
+                KeyCode::Up => scroll = scroll.saturating_sub(1),
+                KeyCode::PageDown => scroll = (scroll + 20).min(max_scroll),
+                KeyCode::PageUp => scroll = scroll.saturating_sub(20),
+                KeyCode::Enter | KeyCode::Esc | KeyCode::Char('q' | 'Q') => return Ok(()),
+                _ => {}
+            }
+        }
+    }
+}
+
 fn handle_key_event(app: &mut App, key: event::KeyEvent) {
     if key.kind != event::KeyEventKind::Press {
         return;
     }
 
+    // Alt+1..4 switches between open server sessions
+    if key.modifiers.contains(KeyModifiers::ALT)
+        && let KeyCode::Char(c @ '1'..='4') = key.code
+        && let Some(n) = c.to_digit(10).and_then(|d| usize::try_from(d).ok())
+    {
+        app.switch_session(n - 1);
+        return;
+    }
+
+    if key.modifiers.contains(KeyModifiers::CONTROL) && key.code == KeyCode::Char('p') {
+        app.open_command_palette();
+        return;
+    }
+    if key.code == KeyCode::F(1) {
+        if app.current_screen == CurrentScreen::Help {
+            app.go_back();
+        } else {
+            app.open_help();
+        }
+        return;
+    }
+    if key.modifiers.contains(KeyModifiers::CONTROL) && key.code == KeyCode::Char('r') {
+        app.toggle_record_mode();
+        return;
+    }
+    if key.modifiers.contains(KeyModifiers::ALT) && matches!(key.code, KeyCode::Char('t' | 'T')) {
+        app.toggle_theme();
+        return;
+    }
+
     match app.current_screen {
         CurrentScreen::Home => match key.code {
             KeyCode::Enter => {
@@ -118,13 +598,13 @@ fn handle_key_event(app: &mut App, key: event::KeyEvent) {
             }
             KeyCode::Char(c) => {
                 app.host_input.push(c);
+                app.host_error = None;
             }
             KeyCode::Backspace => {
                 app.host_input.pop();
+                app.host_error = None;
             }
-            KeyCode::Esc => {
-                app.current_screen = CurrentScreen::Exiting;
-            }
+            KeyCode::Esc => app.go_back(),
             _ => {}
         },
         CurrentScreen::ServerList => match key.code {
@@ -133,6 +613,8 @@ fn handle_key_event(app: &mut App, key: event::KeyEvent) {
             KeyCode::PageUp => app.page_up(),
             KeyCode::Down => app.select_next(),
             KeyCode::Up => app.select_prev(),
+            KeyCode::Char('l' | 'L') => app.measure_server_latencies(),
+            KeyCode::Char('g' | 'G') => app.start_browse_public_groups(),
             KeyCode::Enter => {
                 app.start_browse_tags();
             }
@@ -141,6 +623,15 @@ fn handle_key_event(app: &mut App, key: event::KeyEvent) {
             }
             _ => {}
         },
+        CurrentScreen::TagList if app.export_path_input.is_some() => match key.code {
+            KeyCode::Enter => app.confirm_export(),
+            KeyCode::Esc => app.export_path_input = None,
+            KeyCode::Char(c) => app.export_path_input.get_or_insert_default().push(c),
+            KeyCode::Backspace => {
+                app.export_path_input.get_or_insert_default().pop();
+            }
+            _ => {}
+        },
         CurrentScreen::TagList => {
             if app.search_mode {
                 match key.code {
@@ -148,7 +639,15 @@ fn handle_key_event(app: &mut App, key: event::KeyEvent) {
                     KeyCode::Backspace => app.search_backspace(),
                     KeyCode::Tab => app.next_search_match(),
                     KeyCode::BackTab => app.prev_search_match(),
+                    KeyCode::Up => app.search_history_prev(),
+                    KeyCode::Down => app.search_history_next(),
                     KeyCode::Char(' ') => app.toggle_tag_selection(),
+                    KeyCode::Char('v') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                        app.invert_tag_selection();
+                    }
+                    KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                        app.toggle_search_case();
+                    }
                     KeyCode::Enter => {
                         app.exit_search_mode();
                         app.start_read_values();
@@ -163,8 +662,19 @@ fn handle_key_event(app: &mut App, key: event::KeyEvent) {
                     KeyCode::PageUp => app.page_up(),
                     KeyCode::Down => app.select_next(),
                     KeyCode::Up => app.select_prev(),
+                    KeyCode::Right => app.select_next_column(),
+                    KeyCode::Left => app.select_prev_column(),
                     KeyCode::Char(' ') => app.toggle_tag_selection(),
+                    KeyCode::Char('l') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                        app.start_export_prompt();
+                    }
+                    KeyCode::Char('w') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                        app.cycle_tag_columns();
+                    }
                     KeyCode::Char('s' | 'S') => app.enter_search_mode(),
+                    KeyCode::Char('f' | 'F') => app.toggle_leaf_names(),
+                    KeyCode::Char('v' | 'V') => app.invert_tag_selection(),
+                    KeyCode::Char('w' | 'W') => app.enter_bulk_write_mode(),
                     KeyCode::Enter => app.start_read_values(),
                     KeyCode::Char('q' | 'Q') => {
                         app.current_screen = CurrentScreen::Exiting;
@@ -173,19 +683,60 @@ fn handle_key_event(app: &mut App, key: event::KeyEvent) {
                 }
             }
         }
+        CurrentScreen::TagValues if app.inline_edit.is_some() => match key.code {
+            KeyCode::Enter => app.commit_inline_edit(),
+            KeyCode::Esc => app.cancel_inline_edit(),
+            KeyCode::Char(c) => app.inline_edit_insert(c),
+            KeyCode::Backspace => app.inline_edit_backspace(),
+            KeyCode::Left => app.inline_edit_move(false),
+            KeyCode::Right => app.inline_edit_move(true),
+            _ => {}
+        },
         CurrentScreen::TagValues => match key.code {
             KeyCode::Esc => app.go_back(),
+            KeyCode::F(2) => app.begin_inline_edit(),
             KeyCode::PageDown => app.page_down(),
             KeyCode::PageUp => app.page_up(),
             KeyCode::Down => app.select_next(),
             KeyCode::Up => app.select_prev(),
             KeyCode::Char('w' | 'W') => app.enter_write_mode(),
+            KeyCode::Char(' ') => app.deselect_highlighted_tag(),
+            KeyCode::Char('t' | 'T') => app.toggle_vt_column(),
+            KeyCode::Char('f' | 'F') => app.toggle_leaf_names(),
+            KeyCode::Char('c' | 'C') => app.copy_table_tsv(),
+            KeyCode::Char('g' | 'G') => app.open_correlation_view(),
+            KeyCode::Char('l' | 'L') => app.toggle_live_mode(),
+            KeyCode::Char('r' | 'R') => app.retry_failed_reads(),
+            KeyCode::Char('u' | 'U') => app.toggle_engineering_units(),
+            KeyCode::Char('n' | 'N') => app.open_new_session(),
+            KeyCode::Char(c @ ('+' | '-')) => {
+                // Not Shift: on many layouts '+' already needs it and '-' does not.
+                let large = key.modifiers.contains(KeyModifiers::ALT);
+                app.nudge_selected_value(c == '+', large);
+            }
+            KeyCode::Char('q' | 'Q') => {
+                app.current_screen = CurrentScreen::Exiting;
+            }
+            _ => {}
+        },
+        CurrentScreen::Correlation => match key.code {
+            KeyCode::Esc => app.go_back(),
             KeyCode::Char('q' | 'Q') => {
                 app.current_screen = CurrentScreen::Exiting;
             }
             _ => {}
         },
         CurrentScreen::WriteInput => match key.code {
+            // Not Ctrl+M: terminals send it as Enter.
+            KeyCode::Char('e') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                app.toggle_write_multiline();
+            }
+            KeyCode::Char('s')
+                if app.write_multiline && key.modifiers.contains(KeyModifiers::CONTROL) =>
+            {
+                app.start_write_value();
+            }
+            KeyCode::Enter if app.write_multiline => app.write_value_input.push('\n'),
             KeyCode::Enter => app.start_write_value(),
             KeyCode::Esc => app.go_back(),
             KeyCode::Char(c) => app.write_value_input.push(c),
@@ -194,6 +745,23 @@ fn handle_key_event(app: &mut App, key: event::KeyEvent) {
             }
             _ => {}
         },
+        CurrentScreen::CommandPalette => match key.code {
+            KeyCode::Esc => app.close_command_palette(),
+            KeyCode::Enter => app.execute_palette_selection(),
+            KeyCode::Down | KeyCode::Tab => app.palette_move(true),
+            KeyCode::Up | KeyCode::BackTab => app.palette_move(false),
+            KeyCode::Char(c) => app.palette_input(c),
+            KeyCode::Backspace => app.palette_backspace(),
+            _ => {}
+        },
+        CurrentScreen::Help => match key.code {
+            KeyCode::Esc | KeyCode::Char('q' | 'Q') => app.go_back(),
+            KeyCode::Down => app.scroll_help(1),
+            KeyCode::Up => app.scroll_help(-1),
+            KeyCode::PageDown => app.scroll_help(20),
+            KeyCode::PageUp => app.scroll_help(-20),
+            _ => {}
+        },
         CurrentScreen::Loading => {
             if key.code == KeyCode::Esc {
                 app.go_back();
@@ -206,7 +774,7 @@ fn handle_key_event(app: &mut App, key: event::KeyEvent) {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crossterm::event::{KeyEvent, KeyEventKind, KeyEventState, KeyModifiers};
+    use crossterm::event::{KeyEvent, KeyEventKind, KeyEventState};
     use opc_da_client::MockOpcProvider;
 
     #[test]
@@ -273,4 +841,329 @@ mod tests {
         handle_key_event(&mut app, quit_q);
         assert_eq!(app.current_screen, CurrentScreen::Exiting);
     }
+
+    #[test]
+    fn test_read_batch_size_flag() {
+        let cli = Cli::try_parse_from(["opc-cli"]).unwrap();
+        assert_eq!(cli.read_batch_size, DEFAULT_READ_BATCH_SIZE);
+        assert_eq!(cli.read_batch_size, 50);
+
+        let cli = Cli::try_parse_from(["opc-cli", "--read-batch-size", "25"]).unwrap();
+        assert_eq!(cli.read_batch_size, 25);
+    }
+
+    #[test]
+    fn test_step_flags() {
+        let cli = Cli::try_parse_from(["opc-cli"]).unwrap();
+        assert!((cli.step - app::DEFAULT_INCREMENT_STEP).abs() < f64::EPSILON);
+        assert!((cli.large_step - app::DEFAULT_LARGE_INCREMENT_STEP).abs() < f64::EPSILON);
+
+        let cli = Cli::try_parse_from(["opc-cli", "--step", "0.5", "--large-step", "25"]).unwrap();
+        assert!((cli.step - 0.5).abs() < f64::EPSILON);
+        assert!((cli.large_step - 25.0).abs() < f64::EPSILON);
+
+        for bad in ["0", "-1", "NaN", "x"] {
+            assert!(
+                Cli::try_parse_from(["opc-cli", "--step", bad]).is_err(),
+                "{bad}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_read_flags() {
+        let cli = Cli::try_parse_from([
+            "opc-cli", "--read", "--server", "S", "--tags", "A.B,C", "--format", "csv",
+        ])
+        .unwrap();
+        assert!(cli.read);
+        assert_eq!(cli.tags, ["A.B", "C"]);
+        assert_eq!(cli.format, OutputFormat::Csv);
+
+        assert!(Cli::try_parse_from(["opc-cli", "--read", "--server", "S"]).is_err());
+        assert!(Cli::try_parse_from(["opc-cli", "--format", "xml"]).is_err());
+    }
+
+    #[test]
+    fn test_full_array_values() {
+        let tui = Cli::try_parse_from(["opc-cli"]).unwrap();
+        assert!(!tui.full_array_values());
+
+        let tui = Cli::try_parse_from(["opc-cli", "--full-arrays"]).unwrap();
+        assert!(tui.full_array_values());
+
+        let read =
+            Cli::try_parse_from(["opc-cli", "--read", "--server", "S", "--tags", "A"]).unwrap();
+        assert!(read.full_array_values());
+
+        let watch = Cli::try_parse_from([
+            "opc-cli",
+            "--watch-in",
+            "tags.txt",
+            "--watch-out",
+            "values.csv",
+            "--server",
+            "S",
+        ])
+        .unwrap();
+        assert!(watch.full_array_values());
+    }
+
+    #[test]
+    fn test_request_type_flag() {
+        let cli = Cli::try_parse_from([
+            "opc-cli",
+            "--request-type",
+            "Tank.Level=VT_R8",
+            "--request-type",
+            "Pump.Run=bool",
+        ])
+        .unwrap();
+        assert_eq!(
+            cli.request_type,
+            [("Tank.Level".to_string(), 5), ("Pump.Run".to_string(), 11)]
+        );
+
+        assert!(Cli::try_parse_from(["opc-cli", "--request-type", "Tank.Level"]).is_err());
+        assert!(Cli::try_parse_from(["opc-cli", "--request-type", "Tank.Level=double"]).is_err());
+    }
+
+    #[test]
+    fn test_watch_flags() {
+        let cli = Cli::try_parse_from([
+            "opc-cli",
+            "--watch-in",
+            "tags.txt",
+            "--watch-out",
+            "values.csv",
+            "--server",
+            "S",
+        ])
+        .unwrap();
+        assert_eq!(cli.watch_in.as_deref(), Some(Path::new("tags.txt")));
+        assert_eq!(cli.interval, 5);
+
+        let missing_out = ["opc-cli", "--watch-in", "tags.txt", "--server", "S"];
+        assert!(Cli::try_parse_from(missing_out).is_err());
+        let zero_interval = [
+            "opc-cli",
+            "--watch-in",
+            "t",
+            "--watch-out",
+            "v",
+            "--server",
+            "S",
+            "--interval",
+            "0",
+        ];
+        assert!(Cli::try_parse_from(zero_interval).is_err());
+    }
+
+    #[test]
+    fn test_f1_opens_help_for_the_current_screen() {
+        let mut app = App::new(Arc::new(MockOpcProvider::new()));
+        app.push_screen(CurrentScreen::ServerList);
+        app.servers = vec!["Server.A".into()];
+
+        handle_key_event(&mut app, press(KeyCode::F(1)));
+        assert_eq!(app.current_screen, CurrentScreen::Help);
+        assert_eq!(app.help_from_screen, CurrentScreen::ServerList);
+
+        handle_key_event(&mut app, press(KeyCode::Down));
+        assert_eq!(app.help_scroll, 1);
+        handle_key_event(&mut app, press(KeyCode::Esc));
+        assert_eq!(app.current_screen, CurrentScreen::ServerList);
+        assert_eq!(app.servers, ["Server.A"], "the server list is kept");
+
+        handle_key_event(&mut app, press(KeyCode::F(1)));
+        handle_key_event(&mut app, press(KeyCode::F(1)));
+        assert_eq!(app.current_screen, CurrentScreen::ServerList);
+    }
+
+    #[test]
+    fn test_ctrl_c_toggles_case_sensitive_search() {
+        let mut app = App::new(Arc::new(MockOpcProvider::new()));
+        app.current_screen = CurrentScreen::TagList;
+        app.tags = vec!["Tag.A".into(), "tag.a".into()];
+        app.selected_tags = vec![false; 2];
+
+        handle_key_event(&mut app, press(KeyCode::Char('s')));
+        handle_key_event(&mut app, press(KeyCode::Char('T')));
+        assert_eq!(app.search_matches, [0, 1]);
+
+        let mut ctrl_c = press(KeyCode::Char('c'));
+        ctrl_c.modifiers = KeyModifiers::CONTROL;
+        handle_key_event(&mut app, ctrl_c);
+        assert!(app.search_case_sensitive);
+        assert_eq!(app.search_query, "T", "Ctrl+C is not typed into the query");
+        assert_eq!(app.search_matches, [0]);
+    }
+
+    fn press(code: KeyCode) -> KeyEvent {
+        KeyEvent {
+            code,
+            modifiers: KeyModifiers::empty(),
+            kind: KeyEventKind::Press,
+            state: KeyEventState::empty(),
+        }
+    }
+
+    fn tag_values_app(mock: MockOpcProvider) -> App {
+        let mut app = App::new(Arc::new(mock));
+        app.current_screen = CurrentScreen::TagValues;
+        app.refresh_server = Some("TestServer".into());
+        app.tag_values = vec![opc_da_client::TagValue {
+            tag_id: "Tag1".into(),
+            value: "42".into(),
+            quality: "Good".into(),
+            timestamp: String::new(),
+            raw_timestamp: None,
+            vt: None,
+            access_rights: 0,
+            canonical_data_type: 0,
+            status: opc_da_client::ReadStatus::Ok,
+        }];
+        app.selected_index = Some(0);
+        app.table_state.select(Some(0));
+        app
+    }
+
+    #[tokio::test]
+    async fn test_inline_edit_writes_on_enter() {
+        let mut mock = MockOpcProvider::new();
+        mock.expect_write_tag_value()
+            .withf(|server, tag_id, value| {
+                server == "TestServer"
+                    && tag_id == "Tag1"
+                    && *value == opc_da_client::OpcValue::Int(100)
+            })
+            .times(1)
+            .returning(|_, tag_id, _| {
+                Ok(opc_da_client::WriteResult {
+                    tag_id: tag_id.to_string(),
+                    success: true,
+                    error: None,
+                    attempts: 1,
+                    sent_vt: None,
+                })
+            });
+        let mut app = tag_values_app(mock);
+
+        handle_key_event(&mut app, press(KeyCode::F(2)));
+        // Starts from the current value
+        assert_eq!(app.inline_edit, Some((0, "42".to_string(), 2)));
+        handle_key_event(&mut app, press(KeyCode::Backspace));
+        handle_key_event(&mut app, press(KeyCode::Backspace));
+        for c in "10".chars() {
+            handle_key_event(&mut app, press(KeyCode::Char(c)));
+        }
+        // Cursor editing: "10" → "1|0" → "100"
+        handle_key_event(&mut app, press(KeyCode::Left));
+        handle_key_event(&mut app, press(KeyCode::Char('0')));
+        assert_eq!(app.inline_edit, Some((0, "100".to_string(), 2)));
+
+        handle_key_event(&mut app, press(KeyCode::Enter));
+        assert_eq!(app.inline_edit, None);
+        assert_eq!(app.write_value_input, "100");
+        assert_eq!(app.current_screen, CurrentScreen::Loading);
+
+        let rx = app.write_result_rx.take().unwrap();
+        assert!(rx.await.unwrap().unwrap().success);
+    }
+
+    #[tokio::test]
+    async fn test_alt_selects_large_step() {
+        let written = |expected: i32| {
+            let mut mock = MockOpcProvider::new();
+            mock.expect_write_tag_value()
+                .withf(move |_, _, value| *value == opc_da_client::OpcValue::Int(expected))
+                .times(1)
+                .returning(|_, tag_id, _| {
+                    Ok(opc_da_client::WriteResult {
+                        tag_id: tag_id.to_string(),
+                        success: true,
+                        error: None,
+                        attempts: 1,
+                        sent_vt: None,
+                    })
+                });
+            mock
+        };
+
+        // '+' typed with Shift, as on a US layout, still takes the small step.
+        let mut app = tag_values_app(written(45));
+        app.increment_step = 3.0;
+        let mut shift_plus = press(KeyCode::Char('+'));
+        shift_plus.modifiers = KeyModifiers::SHIFT;
+        handle_key_event(&mut app, shift_plus);
+        assert_eq!(app.write_value_input, "45");
+        app.write_result_rx.take().unwrap().await.unwrap().unwrap();
+
+        let mut app = tag_values_app(written(17));
+        app.large_increment_step = 25.0;
+        let mut alt_minus = press(KeyCode::Char('-'));
+        alt_minus.modifiers = KeyModifiers::ALT;
+        handle_key_event(&mut app, alt_minus);
+        assert_eq!(app.write_value_input, "17");
+        app.write_result_rx.take().unwrap().await.unwrap().unwrap();
+    }
+
+    #[test]
+    fn test_inline_edit_esc_cancels() {
+        let mut mock = MockOpcProvider::new();
+        mock.expect_write_tag_value().times(0);
+        let mut app = tag_values_app(mock);
+
+        handle_key_event(&mut app, press(KeyCode::F(2)));
+        handle_key_event(&mut app, press(KeyCode::Char('7')));
+        assert_eq!(app.inline_edit, Some((0, "427".to_string(), 3)));
+        // 'q' is input while editing, not quit
+        handle_key_event(&mut app, press(KeyCode::Char('q')));
+        handle_key_event(&mut app, press(KeyCode::Esc));
+
+        assert_eq!(app.inline_edit, None);
+        assert_eq!(app.current_screen, CurrentScreen::TagValues);
+        assert_eq!(app.tag_values[0].value, "42");
+        assert!(app.write_result_rx.is_none());
+    }
+
+    #[test]
+    fn test_inline_edit_starts_empty_after_failed_read() {
+        let mut app = tag_values_app(MockOpcProvider::new());
+        app.tag_values[0].value = "Error".into();
+        app.tag_values[0].status = opc_da_client::ReadStatus::ReadError;
+
+        handle_key_event(&mut app, press(KeyCode::F(2)));
+        assert_eq!(app.inline_edit, Some((0, String::new(), 0)));
+    }
+
+    #[test]
+    fn test_write_input_multiline_keys() {
+        let mut mock = MockOpcProvider::new();
+        mock.expect_write_tag_value().times(0);
+        let mut app = tag_values_app(mock);
+        handle_key_event(&mut app, press(KeyCode::Char('w')));
+        assert_eq!(app.current_screen, CurrentScreen::WriteInput);
+
+        let ctrl = |c| KeyEvent {
+            code: KeyCode::Char(c),
+            modifiers: KeyModifiers::CONTROL,
+            kind: KeyEventKind::Press,
+            state: KeyEventState::empty(),
+        };
+        handle_key_event(&mut app, ctrl('e'));
+        assert!(app.write_multiline);
+
+        // Enter adds a line instead of submitting
+        handle_key_event(&mut app, press(KeyCode::Char('[')));
+        handle_key_event(&mut app, press(KeyCode::Enter));
+        handle_key_event(&mut app, press(KeyCode::Char(']')));
+        assert_eq!(app.write_value_input, "[\n]");
+        assert_eq!(app.current_screen, CurrentScreen::WriteInput);
+
+        handle_key_event(&mut app, ctrl('e'));
+        assert!(!app.write_multiline);
+        handle_key_event(&mut app, press(KeyCode::Esc));
+        assert_eq!(app.current_screen, CurrentScreen::TagValues);
+    }
 }
@@ -0,0 +1,129 @@
+//! # op_id
+//!
+//! Short IDs tying a user action to its log lines.
+//!
+//! ## Overview
+//!
+//! Each server operation started from the TUI (listing servers, browsing,
+//! reading, retrying, writing) gets an [`OpId`]. It is recorded as the
+//! `op_id` field of the operation's tracing span and shown in its status
+//! messages, so a failure the user reports can be found in the log with a
+//! single grep.
+
+use std::fmt;
+use std::hash::{BuildHasher, Hasher};
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// Characters of an ID: lowercase Crockford base32, without the easily
+/// confused `i`, `l`, `o` and `u`.
+const ALPHABET: &[u8; 32] = b"0123456789abcdefghjkmnpqrstvwxyz";
+
+/// Number of characters in an ID.
+pub const OP_ID_LEN: usize = 6;
+
+/// Operations started so far, mixed into each ID so consecutive operations
+/// get different IDs. With 30 bits per ID, a repeat within a long run is
+/// unlikely but possible.
+static SEQUENCE: AtomicU64 = AtomicU64::new(0);
+
+/// A fresh ID such as `k3x9qz`. The per-process random seed of
+/// [`RandomState`] keeps IDs from different runs apart, so log files can be
+/// appended to.
+///
+/// [`RandomState`]: std::collections::hash_map::RandomState
+fn new_id() -> String {
+    let mut hasher = std::collections::hash_map::RandomState::new().build_hasher();
+    hasher.write_u64(SEQUENCE.fetch_add(1, Ordering::Relaxed));
+    let mut bits = hasher.finish();
+    (0..OP_ID_LEN)
+        .map(|_| {
+            let c = ALPHABET[usize::from(bits.to_le_bytes()[0] & 31)];
+            bits >>= 5;
+            char::from(c)
+        })
+        .collect()
+}
+
+/// The ID of one operation and the tracing span its log lines go in.
+#[derive(Debug, Clone)]
+pub struct OpId {
+    id: String,
+    span: tracing::Span,
+}
+
+impl OpId {
+    /// Start an operation: a fresh ID and an `op` span with `op_id` and
+    /// `action` fields. The span is a root, so an operation started while
+    /// handling the result of another is not logged as part of it.
+    pub fn start(action: &'static str) -> Self {
+        let id = new_id();
+        let span = tracing::info_span!(parent: None, "op", op_id = %id, action);
+        Self { id, span }
+    }
+
+    /// The operation's span, for instrumenting its background task.
+    pub const fn span(&self) -> &tracing::Span {
+        &self.span
+    }
+
+    /// Enter the operation's span until the guard is dropped, so log lines
+    /// written while starting it or handling its result carry its ID.
+    pub fn enter(&self) -> tracing::span::EnteredSpan {
+        self.span.clone().entered()
+    }
+}
+
+impl fmt::Display for OpId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.id)
+    }
+}
+
+/// ` [op k3x9qz]`, appended to status messages of the operation `id`, or
+/// nothing for an operation without one.
+pub fn op_suffix(id: Option<&OpId>) -> String {
+    id.map(|id| format!(" [op {id}]")).unwrap_or_default()
+}
+
+/// `message` without the [`op_suffix`] at its end, if it has one, so
+/// messages of different operations can be compared by their text.
+pub fn without_op_suffix(message: &str) -> &str {
+    message
+        .strip_suffix(']')
+        .and_then(|rest| rest.rsplit_once(" [op "))
+        .filter(|(_, id)| id.len() == OP_ID_LEN && id.bytes().all(|b| ALPHABET.contains(&b)))
+        .map_or(message, |(text, _)| text)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    #[test]
+    fn test_op_ids_are_short_and_distinct() {
+        let ids: HashSet<String> = (0..200).map(|_| new_id()).collect();
+        assert_eq!(ids.len(), 200);
+        for id in &ids {
+            assert_eq!(id.len(), OP_ID_LEN);
+            assert!(id.bytes().all(|b| ALPHABET.contains(&b)), "{id}");
+        }
+    }
+
+    #[test]
+    fn test_op_suffix() {
+        let op = OpId::start("read");
+        assert_eq!(op_suffix(Some(&op)), format!(" [op {op}]"));
+        assert_eq!(op.to_string().len(), OP_ID_LEN);
+        assert_eq!(op_suffix(None), "");
+    }
+
+    #[test]
+    fn test_without_op_suffix() {
+        let op = OpId::start("read");
+        let message = format!("Error reading values{}", op_suffix(Some(&op)));
+        assert_eq!(without_op_suffix(&message), "Error reading values");
+        assert_eq!(without_op_suffix("Read 3 tag values"), "Read 3 tag values");
+        assert_eq!(without_op_suffix("Tag [op 1]"), "Tag [op 1]");
+    }
+}
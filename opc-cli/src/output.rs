@@ -0,0 +1,293 @@
+//! # output
+//!
+//! Plain-text renderings of tabular results, shared by headless mode and
+//! the TUI's exports.
+//!
+//! ## Overview
+//!
+//! Results are handed over as records: a list of column names and one row
+//! of strings per record. An [`OutputFormat`] picks the [`RecordWriter`]
+//! that turns them into an aligned table, CSV or a JSON array of objects.
+
+use opc_da_client::TagValue;
+use std::fmt;
+use std::io::{self, Write};
+use std::path::Path;
+use std::str::FromStr;
+
+/// How headless results are written to stdout (`--format`).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum OutputFormat {
+    /// Columns padded to line up, for reading in a terminal.
+    #[default]
+    Table,
+    /// RFC 4180 CSV with a header row.
+    Csv,
+    /// A JSON array with one object per record.
+    Json,
+}
+
+impl fmt::Display for OutputFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Table => "table",
+            Self::Csv => "csv",
+            Self::Json => "json",
+        })
+    }
+}
+
+impl FromStr for OutputFormat {
+    type Err = String;
+
+    /// Parses `table`, `csv` or `json`, ignoring case.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "table" => Ok(Self::Table),
+            "csv" => Ok(Self::Csv),
+            "json" => Ok(Self::Json),
+            _ => Err(format!(
+                "unknown output format '{s}' (expected 'table', 'csv' or 'json')"
+            )),
+        }
+    }
+}
+
+impl OutputFormat {
+    /// The writer producing this format.
+    pub fn writer(self) -> Box<dyn RecordWriter> {
+        match self {
+            Self::Table => Box::new(TableWriter),
+            Self::Csv => Box::new(CsvWriter),
+            Self::Json => Box::new(JsonWriter),
+        }
+    }
+
+    /// The format a file name asks for by its extension (`.csv` or `.json`).
+    pub fn from_path(path: &Path) -> Option<Self> {
+        let extension = path.extension()?.to_str()?;
+        match extension.to_ascii_lowercase().as_str() {
+            "csv" => Some(Self::Csv),
+            "json" => Some(Self::Json),
+            _ => None,
+        }
+    }
+}
+
+/// Writes records, each a row of fields under `columns`, to `out`.
+pub trait RecordWriter {
+    /// Write `rows` under the header `columns`. Every row has one field per
+    /// column.
+    fn write_records(
+        &self,
+        out: &mut dyn Write,
+        columns: &[&str],
+        rows: &[Vec<String>],
+    ) -> io::Result<()>;
+}
+
+/// [`OutputFormat::Table`].
+pub struct TableWriter;
+
+impl RecordWriter for TableWriter {
+    fn write_records(
+        &self,
+        out: &mut dyn Write,
+        columns: &[&str],
+        rows: &[Vec<String>],
+    ) -> io::Result<()> {
+        let mut widths: Vec<usize> = columns.iter().map(|c| c.chars().count()).collect();
+        for row in rows {
+            for (width, field) in widths.iter_mut().zip(row) {
+                *width = (*width).max(field.chars().count());
+            }
+        }
+
+        let write_line = |out: &mut dyn Write, fields: &[&str]| -> io::Result<()> {
+            let line: Vec<String> = fields
+                .iter()
+                .zip(&widths)
+                .map(|(field, &width)| format!("{field:<width$}"))
+                .collect();
+            writeln!(out, "{}", line.join("  ").trim_end())
+        };
+
+        write_line(out, columns)?;
+        let rule: Vec<String> = widths.iter().map(|&w| "-".repeat(w)).collect();
+        writeln!(out, "{}", rule.join("  "))?;
+        for row in rows {
+            let fields: Vec<&str> = row.iter().map(String::as_str).collect();
+            write_line(out, &fields)?;
+        }
+        Ok(())
+    }
+}
+
+/// [`OutputFormat::Csv`].
+pub struct CsvWriter;
+
+impl CsvWriter {
+    /// `field`, quoted if it contains a delimiter, quote or line break.
+    fn escape(field: &str) -> std::borrow::Cow<'_, str> {
+        if field.contains([',', '"', '\n', '\r']) {
+            format!("\"{}\"", field.replace('"', "\"\"")).into()
+        } else {
+            field.into()
+        }
+    }
+
+    /// Write the header line alone, for starting a file that rows are
+    /// appended to later.
+    pub fn write_header(&self, out: &mut dyn Write, columns: &[&str]) -> io::Result<()> {
+        let header: Vec<_> = columns.iter().map(|c| Self::escape(c)).collect();
+        write!(out, "{}\r\n", header.join(","))
+    }
+
+    /// Write `rows` without a header.
+    pub fn write_rows(&self, out: &mut dyn Write, rows: &[Vec<String>]) -> io::Result<()> {
+        for row in rows {
+            let fields: Vec<_> = row.iter().map(|f| Self::escape(f)).collect();
+            write!(out, "{}\r\n", fields.join(","))?;
+        }
+        Ok(())
+    }
+}
+
+impl RecordWriter for CsvWriter {
+    fn write_records(
+        &self,
+        out: &mut dyn Write,
+        columns: &[&str],
+        rows: &[Vec<String>],
+    ) -> io::Result<()> {
+        self.write_header(out, columns)?;
+        self.write_rows(out, rows)
+    }
+}
+
+/// [`OutputFormat::Json`].
+pub struct JsonWriter;
+
+impl RecordWriter for JsonWriter {
+    fn write_records(
+        &self,
+        out: &mut dyn Write,
+        columns: &[&str],
+        rows: &[Vec<String>],
+    ) -> io::Result<()> {
+        // Built by hand rather than through `serde_json::Map` so the fields
+        // keep the column order.
+        writeln!(out, "[")?;
+        for (i, row) in rows.iter().enumerate() {
+            let fields: Vec<String> = columns
+                .iter()
+                .zip(row)
+                .map(|(column, field)| {
+                    format!(
+                        "{}: {}",
+                        serde_json::Value::from(*column),
+                        serde_json::Value::from(field.as_str())
+                    )
+                })
+                .collect();
+            let separator = if i + 1 < rows.len() { "," } else { "" };
+            writeln!(out, "  {{{}}}{separator}", fields.join(", "))?;
+        }
+        writeln!(out, "]")
+    }
+}
+
+/// Columns of [`tag_value_rows`].
+pub const TAG_VALUE_COLUMNS: [&str; 4] = ["tag_id", "value", "quality", "timestamp"];
+
+/// One row per value, under [`TAG_VALUE_COLUMNS`].
+pub fn tag_value_rows(values: &[TagValue]) -> Vec<Vec<String>> {
+    values
+        .iter()
+        .map(|v| {
+            vec![
+                v.tag_id.clone(),
+                v.value.clone(),
+                v.quality.clone(),
+                v.timestamp.clone(),
+            ]
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn render(format: OutputFormat, columns: &[&str], rows: &[Vec<String>]) -> String {
+        let mut out = Vec::new();
+        format
+            .writer()
+            .write_records(&mut out, columns, rows)
+            .unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    fn rows() -> Vec<Vec<String>> {
+        vec![
+            vec!["Pump.Speed".into(), "1,500.5".into()],
+            vec!["Tank.Name".into(), "Tank \"A\"".into()],
+        ]
+    }
+
+    #[test]
+    fn test_output_format_round_trips() {
+        for format in [OutputFormat::Table, OutputFormat::Csv, OutputFormat::Json] {
+            assert_eq!(format.to_string().parse::<OutputFormat>(), Ok(format));
+        }
+        assert_eq!("CSV".parse::<OutputFormat>(), Ok(OutputFormat::Csv));
+        assert!("xml".parse::<OutputFormat>().is_err());
+
+        assert_eq!(
+            OutputFormat::from_path(Path::new("tags.CSV")),
+            Some(OutputFormat::Csv)
+        );
+        assert_eq!(
+            OutputFormat::from_path(Path::new("tags.json")),
+            Some(OutputFormat::Json)
+        );
+        assert_eq!(OutputFormat::from_path(Path::new("tags.txt")), None);
+    }
+
+    #[test]
+    fn test_csv_quotes_special_fields() {
+        let csv = render(OutputFormat::Csv, &["tag_id", "value"], &rows());
+        assert_eq!(
+            csv,
+            "tag_id,value\r\nPump.Speed,\"1,500.5\"\r\nTank.Name,\"Tank \"\"A\"\"\"\r\n"
+        );
+    }
+
+    #[test]
+    fn test_json_keeps_column_order() {
+        let json = render(OutputFormat::Json, &["tag_id", "value"], &rows());
+        assert!(json.contains(r#"{"tag_id": "Pump.Speed", "value": "1,500.5"},"#));
+
+        let parsed: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed[1]["value"], "Tank \"A\"");
+        assert_eq!(
+            render(OutputFormat::Json, &["tag_id"], &[]),
+            "[\n]\n",
+            "empty output is still valid JSON"
+        );
+        assert_eq!(
+            serde_json::from_str::<serde_json::Value>("[\n]\n").unwrap(),
+            serde_json::json!([])
+        );
+    }
+
+    #[test]
+    fn test_table_aligns_columns() {
+        let table = render(OutputFormat::Table, &["tag_id", "value"], &rows());
+        let lines: Vec<&str> = table.lines().collect();
+        assert_eq!(lines[0], "tag_id      value");
+        assert_eq!(lines[1], "----------  --------");
+        assert_eq!(lines[2], "Pump.Speed  1,500.5");
+        assert_eq!(lines[3], "Tank.Name   Tank \"A\"");
+    }
+}
@@ -0,0 +1,265 @@
+//! # palette
+//!
+//! Command palette listing every keybinding-backed action.
+//!
+//! ## Overview
+//!
+//! `Ctrl+P` opens the palette over the current screen. Typing narrows
+//! [`PALETTE_ACTIONS`] by fuzzy match on the label; `Enter` closes the
+//! palette and runs the highlighted action on the screen it was opened from.
+//! Actions tied to a screen are only offered there.
+
+use crate::app::{App, CurrentScreen};
+use fuzzy_matcher::FuzzyMatcher;
+use fuzzy_matcher::skim::SkimMatcherV2;
+
+/// One action the palette can run.
+pub struct PaletteAction {
+    /// Name shown in the palette and matched against the query.
+    pub label: &'static str,
+    /// Screen the action works on; `None` for actions available everywhere.
+    pub screen_context: Option<CurrentScreen>,
+    /// Runs the action. The palette is closed first, so `app` is back on the
+    /// screen the palette was opened from.
+    pub action: fn(&mut App),
+}
+
+/// Every action offered by the palette, in display order.
+pub const PALETTE_ACTIONS: &[PaletteAction] = &[
+    PaletteAction {
+        label: "Connect to host",
+        screen_context: Some(CurrentScreen::Home),
+        action: App::start_fetch_servers,
+    },
+    PaletteAction {
+        label: "Start browse",
+        screen_context: Some(CurrentScreen::ServerList),
+        action: App::start_browse_tags,
+    },
+    PaletteAction {
+        label: "Browse public groups",
+        screen_context: Some(CurrentScreen::ServerList),
+        action: App::start_browse_public_groups,
+    },
+    PaletteAction {
+        label: "Sort servers by latency",
+        screen_context: Some(CurrentScreen::ServerList),
+        action: App::measure_server_latencies,
+    },
+    PaletteAction {
+        label: "Enter search mode",
+        screen_context: Some(CurrentScreen::TagList),
+        action: App::enter_search_mode,
+    },
+    PaletteAction {
+        label: "Toggle tag selection",
+        screen_context: Some(CurrentScreen::TagList),
+        action: App::toggle_tag_selection,
+    },
+    PaletteAction {
+        label: "Invert tag selection",
+        screen_context: Some(CurrentScreen::TagList),
+        action: App::invert_tag_selection,
+    },
+    PaletteAction {
+        label: "Export tag list",
+        screen_context: Some(CurrentScreen::TagList),
+        action: App::start_export_prompt,
+    },
+    PaletteAction {
+        label: "Change tag list columns",
+        screen_context: Some(CurrentScreen::TagList),
+        action: App::cycle_tag_columns,
+    },
+    PaletteAction {
+        label: "Read selected tags",
+        screen_context: Some(CurrentScreen::TagList),
+        action: App::start_read_values,
+    },
+    PaletteAction {
+        label: "Write value to selected tags",
+        screen_context: Some(CurrentScreen::TagList),
+        action: App::enter_bulk_write_mode,
+    },
+    PaletteAction {
+        label: "Write value",
+        screen_context: Some(CurrentScreen::TagValues),
+        action: App::enter_write_mode,
+    },
+    PaletteAction {
+        label: "Edit value in place",
+        screen_context: Some(CurrentScreen::TagValues),
+        action: App::begin_inline_edit,
+    },
+    PaletteAction {
+        label: "Toggle type column",
+        screen_context: Some(CurrentScreen::TagValues),
+        action: App::toggle_vt_column,
+    },
+    PaletteAction {
+        label: "Toggle engineering units",
+        screen_context: Some(CurrentScreen::TagValues),
+        action: App::toggle_engineering_units,
+    },
+    PaletteAction {
+        label: "Show correlated tags",
+        screen_context: Some(CurrentScreen::TagValues),
+        action: App::open_correlation_view,
+    },
+    PaletteAction {
+        label: "Toggle live mode",
+        screen_context: Some(CurrentScreen::TagValues),
+        action: App::toggle_live_mode,
+    },
+    PaletteAction {
+        label: "Retry failed reads",
+        screen_context: Some(CurrentScreen::TagValues),
+        action: App::retry_failed_reads,
+    },
+    PaletteAction {
+        label: "Copy table as TSV",
+        screen_context: Some(CurrentScreen::TagValues),
+        action: App::copy_table_tsv,
+    },
+    PaletteAction {
+        label: "Open another server",
+        screen_context: Some(CurrentScreen::TagValues),
+        action: App::open_new_session,
+    },
+    PaletteAction {
+        label: "Toggle full/leaf tag names",
+        screen_context: None,
+        action: App::toggle_leaf_names,
+    },
+    PaletteAction {
+        label: "Toggle dark/light theme",
+        screen_context: None,
+        action: App::toggle_theme,
+    },
+    PaletteAction {
+        label: "Start or stop recording reads",
+        screen_context: None,
+        action: App::toggle_record_mode,
+    },
+    PaletteAction {
+        label: "Show help for this screen",
+        screen_context: None,
+        action: App::open_help,
+    },
+    PaletteAction {
+        label: "Go back",
+        screen_context: None,
+        action: App::go_back,
+    },
+    PaletteAction {
+        label: "Quit",
+        screen_context: None,
+        action: |app| app.current_screen = CurrentScreen::Exiting,
+    },
+];
+
+/// State of the open command palette.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CommandPalette {
+    /// Text typed so far.
+    pub input_query: String,
+    /// Screen the palette was opened from, restored when it closes.
+    pub origin: CurrentScreen,
+    /// Index into [`matches`](Self::matches) of the highlighted action.
+    pub selected: usize,
+}
+
+impl CommandPalette {
+    pub const fn new(origin: CurrentScreen) -> Self {
+        Self {
+            input_query: String::new(),
+            origin,
+            selected: 0,
+        }
+    }
+
+    /// Actions available on the origin screen that match the query, best
+    /// match first; all of them, in declaration order, for an empty query.
+    pub fn matches(&self) -> Vec<&'static PaletteAction> {
+        let available = PALETTE_ACTIONS
+            .iter()
+            .filter(|a| a.screen_context.is_none_or(|s| s == self.origin));
+        if self.input_query.is_empty() {
+            return available.collect();
+        }
+
+        let matcher = SkimMatcherV2::default().ignore_case();
+        let mut scored: Vec<(i64, &'static PaletteAction)> = available
+            .filter_map(|a| {
+                matcher
+                    .fuzzy_match(a.label, &self.input_query)
+                    .map(|score| (score, a))
+            })
+            .collect();
+        // Stable, so equal scores keep declaration order.
+        scored.sort_by_key(|(score, _)| std::cmp::Reverse(*score));
+        scored.into_iter().map(|(_, a)| a).collect()
+    }
+
+    /// The highlighted action, if anything matches.
+    pub fn selected_action(&self) -> Option<&'static PaletteAction> {
+        self.matches().get(self.selected).copied()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use opc_da_client::MockOpcProvider;
+    use std::sync::Arc;
+
+    fn labels(palette: &CommandPalette) -> Vec<&'static str> {
+        palette.matches().iter().map(|a| a.label).collect()
+    }
+
+    #[test]
+    fn test_palette_filters_by_query() {
+        let mut palette = CommandPalette::new(CurrentScreen::ServerList);
+        palette.input_query = "bro".into();
+        let matches = labels(&palette);
+        assert!(!matches.is_empty());
+        assert!(
+            matches.iter().all(|l| l.to_lowercase().contains("browse")),
+            "{matches:?}"
+        );
+
+        palette.input_query = "zzz".into();
+        assert!(palette.matches().is_empty());
+        assert!(palette.selected_action().is_none());
+    }
+
+    #[test]
+    fn test_palette_hides_actions_of_other_screens() {
+        let palette = CommandPalette::new(CurrentScreen::ServerList);
+        let matches = labels(&palette);
+        assert!(matches.contains(&"Start browse"));
+        assert!(matches.contains(&"Quit"));
+        assert!(!matches.contains(&"Enter search mode"));
+
+        let palette = CommandPalette::new(CurrentScreen::TagList);
+        assert!(labels(&palette).contains(&"Enter search mode"));
+    }
+
+    #[test]
+    fn test_palette_executes_selected_action() {
+        let mut app = App::new(Arc::new(MockOpcProvider::new()));
+        app.current_screen = CurrentScreen::TagList;
+        app.tags = vec!["Tag1".into()];
+
+        app.open_command_palette();
+        assert_eq!(app.current_screen, CurrentScreen::CommandPalette);
+        for c in "search".chars() {
+            app.palette_input(c);
+        }
+        app.execute_palette_selection();
+
+        assert_eq!(app.current_screen, CurrentScreen::TagList);
+        assert!(app.command_palette.is_none());
+        assert!(app.search_mode);
+    }
+}
@@ -0,0 +1,507 @@
+//! # recording
+//!
+//! Read recordings for reproducing value sequences offline.
+//!
+//! ## Overview
+//!
+//! While record mode is on (`Ctrl+R`), every read result the TUI receives is
+//! appended to `recordings/session-{timestamp}.jsonl` as one
+//! [`RecordedRead`] per line. `--replay FILE` loads such a file into a
+//! [`ReplayOpcProvider`], which answers reads with the recorded values in
+//! order, spaced by the intervals they were originally read at.
+//!
+//! Like snapshots, recordings keep each value's display strings and
+//! `VARTYPE`, not its raw timestamp or item attributes.
+
+use crate::snapshot::SnapshotValue;
+use anyhow::{Context, Result, bail};
+use async_trait::async_trait;
+use opc_da_client::{
+    AccessRightsFilter, BrowseProgress, BrowsedTag, EngineeringUnits, OpcError, OpcProvider,
+    OpcResult, OpcValue, ReadStatus, ServerMetadata, TagValue, WriteResult,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, PoisonError};
+use std::time::{Duration, Instant};
+
+/// Directory new recordings are written to.
+pub const RECORDINGS_DIR: &str = "recordings";
+
+/// Path for a new recording in `dir`, stamped with the current local time.
+pub fn new_recording_path(dir: &Path) -> PathBuf {
+    let stamp = chrono::Local::now().format("%Y%m%d-%H%M%S");
+    dir.join(format!("session-{stamp}.jsonl"))
+}
+
+/// One read result, a line of a recording.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RecordedRead {
+    /// Milliseconds since the previous recorded read, 0 for the first.
+    pub delta_ms: u64,
+    /// The server `ProgID` the values were read from.
+    pub server: String,
+    pub values: Vec<SnapshotValue>,
+}
+
+/// Appends read results to a recording file.
+#[derive(Debug)]
+pub struct Recorder {
+    path: PathBuf,
+    file: std::fs::File,
+    last_read: Option<Instant>,
+    count: usize,
+}
+
+impl Recorder {
+    /// Create the recording file at `path`, and its directory if needed.
+    ///
+    /// # Errors
+    /// Returns `Err` if the file cannot be created.
+    pub fn create(path: &Path) -> Result<Self> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create {}", parent.display()))?;
+        }
+        let file = std::fs::File::create(path)
+            .with_context(|| format!("Failed to create recording {}", path.display()))?;
+        Ok(Self {
+            path: path.to_path_buf(),
+            file,
+            last_read: None,
+            count: 0,
+        })
+    }
+
+    /// Append one read of `values` from `server`, received `now`.
+    ///
+    /// # Errors
+    /// Returns `Err` if the line cannot be written.
+    pub fn record(&mut self, server: &str, values: &[TagValue], now: Instant) -> Result<()> {
+        let delta = self
+            .last_read
+            .map_or(Duration::ZERO, |last| now.saturating_duration_since(last));
+        let entry = RecordedRead {
+            delta_ms: u64::try_from(delta.as_millis()).unwrap_or(u64::MAX),
+            server: server.to_string(),
+            values: values.iter().map(SnapshotValue::from).collect(),
+        };
+        let line = serde_json::to_string(&entry)?;
+        writeln!(self.file, "{line}")
+            .with_context(|| format!("Failed to write recording {}", self.path.display()))?;
+        self.last_read = Some(now);
+        self.count += 1;
+        Ok(())
+    }
+
+    /// The file being written.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Number of reads recorded so far.
+    pub const fn count(&self) -> usize {
+        self.count
+    }
+}
+
+/// Read and parse a recording.
+///
+/// # Errors
+/// Returns `Err` if the file cannot be read, a line is not a
+/// [`RecordedRead`], or it holds no reads.
+pub fn load_recording(path: &Path) -> Result<Vec<RecordedRead>> {
+    let text = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read recording {}", path.display()))?;
+    let reads = text
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(i, line)| {
+            serde_json::from_str(line)
+                .with_context(|| format!("Invalid recording {} line {}", path.display(), i + 1))
+        })
+        .collect::<Result<Vec<RecordedRead>>>()?;
+    if reads.is_empty() {
+        bail!("Recording {} holds no reads", path.display());
+    }
+    Ok(reads)
+}
+
+/// [`OpcProvider`] answering reads from a recording instead of a server.
+///
+/// Lists the recorded server on every host, and browses to the tags of the
+/// first recorded read. Each read cycle returns the next recorded read, held
+/// back until its original interval since the previous one has passed; after
+/// the last, it keeps returning the last. Writes are refused.
+///
+/// A read returns the recorded values of the requested tags only, in request
+/// order. Reads of tags not yet served from the current recorded read, such
+/// as the batches of one large read, continue the same cycle; asking for a
+/// tag again starts the next.
+#[derive(Debug)]
+pub struct ReplayOpcProvider {
+    reads: Vec<RecordedRead>,
+    cursor: Mutex<ReplayCursor>,
+}
+
+/// Position of a replay in its recording.
+#[derive(Debug, Default)]
+struct ReplayCursor {
+    /// Index of the next read to replay.
+    next: usize,
+    /// Index of the read the current cycle is served from.
+    current: usize,
+    /// When the current read was served.
+    served: Option<tokio::time::Instant>,
+    /// Tags served from the current read so far.
+    served_tags: HashSet<String>,
+}
+
+impl ReplayOpcProvider {
+    /// Replays `reads`, which must not be empty.
+    pub fn new(reads: Vec<RecordedRead>) -> Self {
+        assert!(!reads.is_empty(), "a replay needs at least one read");
+        Self {
+            reads,
+            cursor: Mutex::new(ReplayCursor::default()),
+        }
+    }
+
+    /// Replays the recording at `path`.
+    ///
+    /// # Errors
+    /// Returns `Err` if the recording cannot be loaded.
+    pub fn load(path: &Path) -> Result<Self> {
+        load_recording(path).map(Self::new)
+    }
+
+    fn server(&self) -> &str {
+        &self.reads[0].server
+    }
+
+    fn check_server(&self, server: &str) -> OpcResult<()> {
+        if server == self.server() {
+            Ok(())
+        } else {
+            Err(OpcError::Connection(format!(
+                "server '{server}' is not in the recording (expected '{}')",
+                self.server()
+            )))
+        }
+    }
+
+    /// Take the read to serve `tag_ids` from and how long to wait before
+    /// returning it, moving to the next read when a new cycle starts.
+    fn advance(&self, tag_ids: &[String]) -> (&RecordedRead, Duration) {
+        let mut cursor = self.cursor.lock().unwrap_or_else(PoisonError::into_inner);
+        let new_cycle = cursor.served_tags.is_empty()
+            || tag_ids.iter().any(|id| cursor.served_tags.contains(id));
+        let mut wait = Duration::ZERO;
+        if new_cycle {
+            let index = cursor.next.min(self.reads.len() - 1);
+            if let Some(at) = cursor.served
+                && cursor.next < self.reads.len()
+            {
+                wait =
+                    Duration::from_millis(self.reads[index].delta_ms).saturating_sub(at.elapsed());
+            }
+            cursor.current = index;
+            cursor.next = (cursor.next + 1).min(self.reads.len());
+            cursor.served = Some(tokio::time::Instant::now() + wait);
+            cursor.served_tags.clear();
+        }
+        cursor.served_tags.extend(tag_ids.iter().cloned());
+        (&self.reads[cursor.current], wait)
+    }
+
+    async fn replay(&self, server: &str, tag_ids: &[String]) -> OpcResult<Vec<TagValue>> {
+        self.check_server(server)?;
+        let (read, wait) = self.advance(tag_ids);
+        let values = tag_ids
+            .iter()
+            .map(|id| {
+                read.values
+                    .iter()
+                    .find(|v| &v.tag_id == id)
+                    .cloned()
+                    .map_or_else(|| not_recorded(id), TagValue::from)
+            })
+            .collect();
+        tokio::time::sleep(wait).await;
+        Ok(values)
+    }
+
+    fn refuse_writes() -> OpcError {
+        OpcError::InvalidState("writes are disabled while replaying a recording".into())
+    }
+}
+
+/// Value of a requested tag the recorded read does not hold.
+fn not_recorded(tag_id: &str) -> TagValue {
+    TagValue {
+        tag_id: tag_id.to_string(),
+        value: "Error".to_string(),
+        quality: "Bad — not in recording".to_string(),
+        timestamp: String::new(),
+        raw_timestamp: None,
+        vt: None,
+        access_rights: 0,
+        canonical_data_type: 0,
+        status: ReadStatus::NotAdded,
+    }
+}
+
+#[async_trait]
+impl OpcProvider for ReplayOpcProvider {
+    async fn list_servers(&self, _host: &str) -> OpcResult<Vec<String>> {
+        Ok(vec![self.server().to_string()])
+    }
+
+    async fn browse_tags(
+        &self,
+        server: &str,
+        max_tags: usize,
+        _access: AccessRightsFilter,
+        progress: BrowseProgress,
+    ) -> OpcResult<Vec<BrowsedTag>> {
+        self.check_server(server)?;
+        let tags: Vec<BrowsedTag> = self.reads[0]
+            .values
+            .iter()
+            .take(max_tags)
+            .map(|v| BrowsedTag::from(v.tag_id.clone()))
+            .collect();
+        if let Some(sink) = progress
+            .tags_sink
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .as_mut()
+        {
+            sink.extend(tags.iter().cloned());
+        }
+        progress.tags_found.store(tags.len(), Ordering::Relaxed);
+        Ok(tags)
+    }
+
+    async fn read_tag_values(
+        &self,
+        server: &str,
+        tag_ids: Vec<String>,
+        progress: Arc<AtomicUsize>,
+    ) -> OpcResult<Vec<TagValue>> {
+        let values = self.replay(server, &tag_ids).await?;
+        progress.fetch_add(values.len(), Ordering::Relaxed);
+        Ok(values)
+    }
+
+    async fn write_tag_value(
+        &self,
+        _server: &str,
+        _tag_id: &str,
+        _value: OpcValue,
+    ) -> OpcResult<WriteResult> {
+        Err(Self::refuse_writes())
+    }
+
+    async fn write_tag_values(
+        &self,
+        _server: &str,
+        _writes: Vec<(String, OpcValue)>,
+    ) -> OpcResult<Vec<WriteResult>> {
+        Err(Self::refuse_writes())
+    }
+
+    /// The `VARTYPE` each tag had in the first recorded read.
+    async fn get_item_data_types(
+        &self,
+        server: &str,
+        tag_ids: Vec<String>,
+    ) -> OpcResult<Vec<Option<u16>>> {
+        self.check_server(server)?;
+        let first = &self.reads[0].values;
+        Ok(tag_ids
+            .iter()
+            .map(|id| first.iter().find(|v| &v.tag_id == id).and_then(|v| v.vt))
+            .collect())
+    }
+
+    async fn get_engineering_units(
+        &self,
+        server: &str,
+        tag_ids: Vec<String>,
+    ) -> OpcResult<Vec<Option<EngineeringUnits>>> {
+        self.check_server(server)?;
+        Ok(vec![None; tag_ids.len()])
+    }
+
+    async fn get_server_metadata(&self, server: &str) -> OpcResult<ServerMetadata> {
+        self.check_server(server)?;
+        Ok(ServerMetadata {
+            server: server.to_string(),
+            status: None,
+            namespace_type: Some("Flat".to_string()),
+            interfaces: Vec::new(),
+            da_version: None,
+        })
+    }
+
+    async fn read_tag_values_live(
+        &self,
+        server: &str,
+        tag_ids: Vec<String>,
+    ) -> OpcResult<Vec<TagValue>> {
+        self.replay(server, &tag_ids).await
+    }
+
+    async fn stop_live_reads(&self, server: &str) -> OpcResult<()> {
+        self.check_server(server)
+    }
+
+    async fn set_items_active(
+        &self,
+        server: &str,
+        _tag_ids: Vec<String>,
+        _active: bool,
+    ) -> OpcResult<usize> {
+        self.check_server(server)?;
+        Err(OpcError::NotImplemented(
+            "item active state in a recording".into(),
+        ))
+    }
+
+    async fn cleanup_orphaned_groups(&self, server: &str) -> OpcResult<usize> {
+        self.check_server(server)?;
+        Ok(0)
+    }
+
+    async fn list_public_groups(&self, server: &str) -> OpcResult<Vec<String>> {
+        self.check_server(server)?;
+        Ok(Vec::new())
+    }
+
+    async fn read_public_group(&self, server: &str, group: &str) -> OpcResult<Vec<TagValue>> {
+        self.check_server(server)?;
+        Err(OpcError::InvalidState(format!(
+            "a recording has no public group '{group}'"
+        )))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn read(delta_ms: u64, value: &str) -> RecordedRead {
+        RecordedRead {
+            delta_ms,
+            server: "Server1".into(),
+            values: vec![SnapshotValue {
+                tag_id: "Tag1".into(),
+                value: value.into(),
+                quality: "Good".into(),
+                timestamp: String::new(),
+                vt: Some(5),
+            }],
+        }
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_replay_keeps_original_spacing() {
+        let provider = ReplayOpcProvider::new(vec![read(0, "1"), read(2000, "2")]);
+
+        let start = tokio::time::Instant::now();
+        let tag1 = || vec!["Tag1".to_string()];
+        let first = provider.read_tag_values_live("Server1", tag1()).await;
+        assert_eq!(first.unwrap()[0].value, "1");
+        let second = provider.read_tag_values_live("Server1", tag1()).await;
+        assert_eq!(second.unwrap()[0].value, "2");
+        assert!(start.elapsed() >= Duration::from_millis(2000));
+
+        // Past the end, the last read repeats without waiting
+        let before = tokio::time::Instant::now();
+        let third = provider.read_tag_values_live("Server1", tag1()).await;
+        assert_eq!(third.unwrap()[0].value, "2");
+        assert_eq!(before.elapsed(), Duration::ZERO);
+    }
+
+    #[tokio::test]
+    async fn test_replay_serves_batched_reads_from_one_recorded_read() {
+        use crate::app::App;
+
+        let recorded = |value: &str| RecordedRead {
+            delta_ms: 0,
+            server: "Server1".into(),
+            values: ["Tag1", "Tag2", "Tag3"]
+                .into_iter()
+                .map(|tag_id| SnapshotValue {
+                    tag_id: tag_id.into(),
+                    value: format!("{tag_id}={value}"),
+                    quality: "Good".into(),
+                    timestamp: String::new(),
+                    vt: Some(5),
+                })
+                .collect(),
+        };
+        let provider = ReplayOpcProvider::new(vec![recorded("1"), recorded("2")]);
+        let mut app = App::new(Arc::new(provider));
+        app.read_batch_size = 2;
+        app.browsed_server = Some("Server1".into());
+        app.tags = ["Tag1", "Tag2", "Tag3"].map(BrowsedTag::from).to_vec();
+
+        assert_eq!(
+            read_selected(&mut app, vec![true; 3]).await,
+            ["Tag1=1", "Tag2=1", "Tag3=1"]
+        );
+        assert_eq!(
+            read_selected(&mut app, vec![true; 3]).await,
+            ["Tag1=2", "Tag2=2", "Tag3=2"]
+        );
+        // A subset gets only its own tags back
+        assert_eq!(
+            read_selected(&mut app, vec![false, true, false]).await,
+            ["Tag2=2"]
+        );
+    }
+
+    /// Read the `selected` tags of `app` as the TUI does, returning the values.
+    async fn read_selected(app: &mut crate::app::App, selected: Vec<bool>) -> Vec<String> {
+        app.selected_tags = selected;
+        app.current_screen = crate::app::CurrentScreen::TagList;
+        app.start_read_values();
+        let values = app.read_result_rx.take().unwrap().await.unwrap().unwrap();
+        values.into_iter().map(|v| v.value).collect()
+    }
+
+    #[tokio::test]
+    async fn test_replay_refuses_writes_and_other_servers() {
+        let provider = ReplayOpcProvider::new(vec![read(0, "1")]);
+        assert!(
+            provider
+                .write_tag_value("Server1", "Tag1", OpcValue::Int(1))
+                .await
+                .is_err()
+        );
+        assert!(
+            provider
+                .read_tag_values_live("Other", vec![])
+                .await
+                .is_err()
+        );
+        assert_eq!(provider.list_servers("anyhost").await.unwrap(), ["Server1"]);
+    }
+
+    #[test]
+    fn test_load_recording_rejects_empty_and_invalid_files() {
+        let path = std::env::temp_dir().join(format!("opc-cli-{}-bad.jsonl", std::process::id()));
+        std::fs::write(&path, "\n").unwrap();
+        assert!(load_recording(&path).is_err());
+        std::fs::write(&path, "{not json}\n").unwrap();
+        let err = load_recording(&path).unwrap_err();
+        let _ = std::fs::remove_file(&path);
+        assert!(format!("{err:#}").contains("line 1"));
+    }
+}
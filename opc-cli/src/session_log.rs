@@ -0,0 +1,105 @@
+//! # session_log
+//!
+//! Append-only per-run log of status messages.
+//!
+//! ## Overview
+//!
+//! Every message shown in the TUI status log is mirrored to
+//! `logs/session-{timestamp}.log`, one `{timestamp} [{level}] {message}` line
+//! per message, so the history survives a restart. `--show-last-session`
+//! reads the most recent file back.
+
+use anyhow::{Context, Result};
+use std::path::{Path, PathBuf};
+
+/// Directory holding session logs (shared with the tracing log).
+pub const SESSION_LOG_DIR: &str = "logs";
+
+const PREFIX: &str = "session-";
+const EXTENSION: &str = "log";
+
+/// Path for a new session log in `dir`, stamped with the current local time.
+pub fn new_session_path(dir: &Path) -> PathBuf {
+    let stamp = chrono::Local::now().format("%Y%m%d-%H%M%S");
+    dir.join(format!("{PREFIX}{stamp}.{EXTENSION}"))
+}
+
+/// The most recent session log in `dir`, if any.
+///
+/// The timestamp in the file name sorts lexicographically, so the greatest
+/// name is the newest file.
+pub fn latest_session_path(dir: &Path) -> Option<PathBuf> {
+    std::fs::read_dir(dir)
+        .ok()?
+        .filter_map(Result::ok)
+        .map(|entry| entry.path())
+        .filter(|path| {
+            path.extension().is_some_and(|ext| ext == EXTENSION)
+                && path
+                    .file_name()
+                    .and_then(|n| n.to_str())
+                    .is_some_and(|n| n.starts_with(PREFIX))
+        })
+        .max()
+}
+
+/// Read the lines of the most recent session log in `dir`.
+pub fn read_last_session(dir: &Path) -> Result<(PathBuf, Vec<String>)> {
+    let path = latest_session_path(dir)
+        .with_context(|| format!("No session log found in {}", dir.display()))?;
+    let text = std::fs::read_to_string(&path)
+        .with_context(|| format!("Failed to read session log {}", path.display()))?;
+    Ok((path, text.lines().map(str::to_owned).collect()))
+}
+
+/// Format one session log line as `{timestamp} [{level}] {message}`.
+pub fn format_line(message: &str) -> String {
+    let timestamp = chrono::Local::now().format("%Y-%m-%d %H:%M:%S");
+    format!("{timestamp} [{}] {message}", message_level(message))
+}
+
+/// Classify a status message; failures are logged at `ERROR`, warnings at
+/// `WARN`, the rest at `INFO`.
+fn message_level(message: &str) -> &'static str {
+    let lower = message.to_lowercase();
+    if lower.contains("error") || lower.contains("failed") {
+        "ERROR"
+    } else if lower.starts_with("warning") {
+        "WARN"
+    } else {
+        "INFO"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_line() {
+        let line = format_line("Write failed: access denied");
+        // "YYYY-MM-DD HH:MM:SS [ERROR] ..."
+        assert_eq!(line.as_bytes()[4], b'-');
+        assert_eq!(line.as_bytes()[13], b':');
+        assert!(line[19..].starts_with(" [ERROR] Write failed"));
+        assert!(format_line("Loaded 3 tags").contains(" [INFO] Loaded 3 tags"));
+        assert!(format_line("Warning: queue nearly full").contains(" [WARN] Warning:"));
+    }
+
+    #[test]
+    fn test_latest_session_path() {
+        let dir = std::env::temp_dir().join(format!("opc-cli-sessions-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        for name in [
+            "session-20240101-120000.log",
+            "session-20240102-080000.log",
+            "opc-cli.log.2024-01-03",
+        ] {
+            std::fs::write(dir.join(name), "x\n").unwrap();
+        }
+
+        let latest = latest_session_path(&dir).unwrap();
+        std::fs::remove_dir_all(&dir).ok();
+        assert_eq!(latest.file_name().unwrap(), "session-20240102-080000.log");
+    }
+}
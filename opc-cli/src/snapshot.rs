@@ -0,0 +1,80 @@
+//! # snapshot
+//!
+//! On-disk snapshots of tag values for offline inspection.
+//!
+//! ## Overview
+//!
+//! A [`ServerSnapshot`] is a JSON document recording the values read from one
+//! OPC server at a point in time. Loading one lets the TUI display production
+//! data on a machine with no connection to the server.
+
+use anyhow::{Context, Result};
+use opc_da_client::{ReadStatus, TagValue};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// Tag values captured from a single server.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ServerSnapshot {
+    /// The server `ProgID` the values were read from.
+    pub server: String,
+    /// When the snapshot was taken, as a local time string.
+    pub taken_at: String,
+    /// The captured values, in display order.
+    pub values: Vec<SnapshotValue>,
+}
+
+/// A single tag value inside a [`ServerSnapshot`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SnapshotValue {
+    pub tag_id: String,
+    pub value: String,
+    pub quality: String,
+    pub timestamp: String,
+    #[serde(default)]
+    pub vt: Option<u16>,
+}
+
+impl From<SnapshotValue> for TagValue {
+    fn from(v: SnapshotValue) -> Self {
+        // Snapshots keep only the display strings
+        let status = if v.value == "Error" {
+            ReadStatus::ReadError
+        } else {
+            ReadStatus::Ok
+        };
+        Self {
+            tag_id: v.tag_id,
+            value: v.value,
+            quality: v.quality,
+            timestamp: v.timestamp,
+            raw_timestamp: None,
+            vt: v.vt,
+            access_rights: 0,
+            canonical_data_type: 0,
+            status,
+        }
+    }
+}
+
+impl From<&TagValue> for SnapshotValue {
+    fn from(v: &TagValue) -> Self {
+        Self {
+            tag_id: v.tag_id.clone(),
+            value: v.value.clone(),
+            quality: v.quality.clone(),
+            timestamp: v.timestamp.clone(),
+            vt: v.vt,
+        }
+    }
+}
+
+impl ServerSnapshot {
+    /// Read and parse a snapshot file.
+    pub fn load(path: &Path) -> Result<Self> {
+        let text = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read snapshot {}", path.display()))?;
+        serde_json::from_str(&text)
+            .with_context(|| format!("Invalid snapshot file {}", path.display()))
+    }
+}
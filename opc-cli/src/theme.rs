@@ -0,0 +1,301 @@
+//! # theme
+//!
+//! Colour themes for the TUI.
+//!
+//! ## Overview
+//!
+//! Every colour drawn by [`ui`](crate::ui) comes from a [`Theme`], which maps
+//! semantic roles (selection, tag quality, headers, errors) to terminal
+//! colours. Five themes are built in — `default`, `high-contrast`,
+//! `monochrome`, `dark` and `light` — and the `theme` section of the config
+//! file can pick one by name and override individual roles:
+//!
+//! ```json
+//! { "theme": { "name": "high-contrast", "colors": { "header": "cyan" } } }
+//! ```
+//!
+//! `dark` and `light` paint their own background instead of using the
+//! terminal's, so they look the same on any terminal; `Alt+T` switches
+//! between them while the app runs.
+
+use anyhow::{Context, Result, bail};
+use ratatui::style::{Color, Modifier, Style};
+use serde::Deserialize;
+use std::collections::BTreeMap;
+
+/// Names accepted by [`Theme::builtin`].
+pub const THEME_NAMES: [&str; 5] = ["default", "high-contrast", "monochrome", "dark", "light"];
+
+/// Terminal colours for each semantic role in the UI.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Theme {
+    /// Background of the whole screen; [`Color::Reset`] keeps the terminal's.
+    pub background: Color,
+    /// Default text; [`Color::Reset`] keeps the terminal's.
+    pub foreground: Color,
+    /// Text of the highlighted row.
+    pub selected_fg: Color,
+    /// Background of the highlighted row; [`Color::Reset`] highlights by
+    /// reversing the row instead.
+    pub selected_bg: Color,
+    /// `Good` quality.
+    pub good: Color,
+    /// `Bad` quality.
+    pub bad: Color,
+    /// `Uncertain` quality.
+    pub uncertain: Color,
+    /// Table headers, prompts, popup borders and search matches.
+    pub header: Color,
+    /// Secondary highlights such as the host input border.
+    pub accent: Color,
+    /// Error text.
+    pub error: Color,
+    /// De-emphasised text: help lines, hosts, latencies.
+    pub muted: Color,
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self {
+            background: Color::Reset,
+            foreground: Color::Reset,
+            selected_fg: Color::White,
+            selected_bg: Color::Blue,
+            good: Color::Green,
+            bad: Color::Red,
+            uncertain: Color::Yellow,
+            header: Color::Yellow,
+            accent: Color::Cyan,
+            error: Color::Red,
+            muted: Color::DarkGray,
+        }
+    }
+}
+
+impl Theme {
+    /// Bright colours on the terminal's background, for low-vision use and
+    /// light-background terminals.
+    pub const fn high_contrast() -> Self {
+        Self {
+            background: Color::Reset,
+            foreground: Color::Reset,
+            selected_fg: Color::Black,
+            selected_bg: Color::White,
+            good: Color::LightGreen,
+            bad: Color::LightRed,
+            uncertain: Color::LightYellow,
+            header: Color::White,
+            accent: Color::LightCyan,
+            error: Color::LightRed,
+            muted: Color::Gray,
+        }
+    }
+
+    /// The terminal's own colours only; selection is shown by reversing text.
+    pub const fn monochrome() -> Self {
+        Self {
+            background: Color::Reset,
+            foreground: Color::Reset,
+            selected_fg: Color::Reset,
+            selected_bg: Color::Reset,
+            good: Color::Reset,
+            bad: Color::Reset,
+            uncertain: Color::Reset,
+            header: Color::Reset,
+            accent: Color::Reset,
+            error: Color::Reset,
+            muted: Color::Reset,
+        }
+    }
+
+    /// Light text on a black background.
+    pub const fn dark() -> Self {
+        Self {
+            background: Color::Black,
+            foreground: Color::Gray,
+            selected_fg: Color::Black,
+            selected_bg: Color::Cyan,
+            good: Color::Green,
+            bad: Color::LightRed,
+            uncertain: Color::Yellow,
+            header: Color::Yellow,
+            accent: Color::Cyan,
+            error: Color::LightRed,
+            muted: Color::DarkGray,
+        }
+    }
+
+    /// Dark text on a white background, with colours deep enough to read on it.
+    pub const fn light() -> Self {
+        Self {
+            background: Color::White,
+            foreground: Color::Black,
+            selected_fg: Color::White,
+            selected_bg: Color::Blue,
+            good: Color::Rgb(0x00, 0x80, 0x00),
+            bad: Color::Rgb(0xc0, 0x00, 0x00),
+            uncertain: Color::Rgb(0xa0, 0x60, 0x00),
+            header: Color::Blue,
+            accent: Color::Rgb(0x00, 0x60, 0x80),
+            error: Color::Rgb(0xc0, 0x00, 0x00),
+            muted: Color::DarkGray,
+        }
+    }
+
+    /// [`light`](Self::light) when on [`dark`](Self::dark), otherwise `dark`.
+    #[must_use]
+    pub fn toggled_dark_light(&self) -> Self {
+        if *self == Self::dark() {
+            Self::light()
+        } else {
+            Self::dark()
+        }
+    }
+
+    /// A built-in theme by name (see [`THEME_NAMES`]), ignoring case.
+    pub fn builtin(name: &str) -> Result<Self> {
+        match name.to_ascii_lowercase().as_str() {
+            "default" => Ok(Self::default()),
+            "high-contrast" => Ok(Self::high_contrast()),
+            "monochrome" => Ok(Self::monochrome()),
+            "dark" => Ok(Self::dark()),
+            "light" => Ok(Self::light()),
+            _ => bail!(
+                "unknown theme '{name}' (expected one of: {})",
+                THEME_NAMES.join(", ")
+            ),
+        }
+    }
+
+    /// Build the theme described by a config `theme` section.
+    pub fn from_config(config: &ThemeConfig) -> Result<Self> {
+        let mut theme = Self::builtin(config.name.as_deref().unwrap_or("default"))?;
+        for (role, value) in &config.colors {
+            let color: Color = value
+                .parse()
+                .ok()
+                .with_context(|| format!("invalid color '{value}' for theme role '{role}'"))?;
+            *theme.role_mut(role)? = color;
+        }
+        Ok(theme)
+    }
+
+    fn role_mut(&mut self, role: &str) -> Result<&mut Color> {
+        Ok(match role {
+            "background" => &mut self.background,
+            "foreground" => &mut self.foreground,
+            "selected_fg" => &mut self.selected_fg,
+            "selected_bg" => &mut self.selected_bg,
+            "good" => &mut self.good,
+            "bad" => &mut self.bad,
+            "uncertain" => &mut self.uncertain,
+            "header" => &mut self.header,
+            "accent" => &mut self.accent,
+            "error" => &mut self.error,
+            "muted" => &mut self.muted,
+            _ => bail!("unknown theme role '{role}'"),
+        })
+    }
+
+    /// Base style of every cell: the theme's foreground on its background.
+    pub fn base(&self) -> Style {
+        Style::default().fg(self.foreground).bg(self.background)
+    }
+
+    /// Style of the highlighted row in lists and tables.
+    pub fn selected(&self) -> Style {
+        if self.selected_bg == Color::Reset {
+            Style::default().add_modifier(Modifier::REVERSED)
+        } else {
+            Style::default().fg(self.selected_fg).bg(self.selected_bg)
+        }
+    }
+
+    /// Colour for an OPC quality string such as `"Good"` or `"Bad — …"`.
+    pub fn quality(&self, quality: &str) -> Color {
+        if quality.starts_with("Good") {
+            self.good
+        } else if quality.starts_with("Uncertain") {
+            self.uncertain
+        } else if quality.starts_with("Bad") {
+            self.bad
+        } else {
+            Color::Reset
+        }
+    }
+}
+
+/// The `theme` section of the config file.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ThemeConfig {
+    /// Built-in theme to start from; `default` when unset.
+    pub name: Option<String>,
+    /// Per-role overrides, e.g. `"header": "cyan"` or `"bad": "#ff5555"`.
+    pub colors: BTreeMap<String, String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_builtin_themes_by_name() {
+        for name in THEME_NAMES {
+            assert!(Theme::builtin(name).is_ok(), "{name}");
+        }
+        assert_eq!(
+            Theme::builtin("High-Contrast").unwrap(),
+            Theme::high_contrast()
+        );
+        assert!(Theme::builtin("neon").is_err());
+    }
+
+    #[test]
+    fn test_theme_from_config_overrides_roles() {
+        let config = ThemeConfig {
+            name: Some("monochrome".into()),
+            colors: BTreeMap::from([
+                ("header".into(), "cyan".into()),
+                ("bad".into(), "#ff5555".into()),
+            ]),
+        };
+        let theme = Theme::from_config(&config).unwrap();
+        assert_eq!(theme.header, Color::Cyan);
+        assert_eq!(theme.bad, Color::Rgb(0xff, 0x55, 0x55));
+        assert_eq!(theme.good, Color::Reset);
+
+        let bad_role = ThemeConfig {
+            colors: BTreeMap::from([("title".into(), "red".into())]),
+            ..ThemeConfig::default()
+        };
+        assert!(Theme::from_config(&bad_role).is_err());
+        let bad_color = ThemeConfig {
+            colors: BTreeMap::from([("good".into(), "grass".into())]),
+            ..ThemeConfig::default()
+        };
+        assert!(Theme::from_config(&bad_color).is_err());
+    }
+
+    #[test]
+    fn test_quality_colors_and_selection() {
+        let theme = Theme::default();
+        assert_eq!(theme.quality("Good"), Color::Green);
+        assert_eq!(theme.quality("Bad — not added to group"), Color::Red);
+        assert_eq!(theme.quality("Uncertain"), Color::Yellow);
+        assert_eq!(theme.quality("Unknown(0x0010)"), Color::Reset);
+
+        assert_eq!(
+            Theme::monochrome().selected(),
+            Style::default().add_modifier(Modifier::REVERSED)
+        );
+    }
+
+    #[test]
+    fn test_dark_light_toggle() {
+        assert_eq!(Theme::dark().toggled_dark_light(), Theme::light());
+        assert_eq!(Theme::light().toggled_dark_light(), Theme::dark());
+        assert_eq!(Theme::default().toggled_dark_light(), Theme::dark());
+        assert_eq!(Theme::builtin("Light").unwrap(), Theme::light());
+    }
+}
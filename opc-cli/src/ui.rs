@@ -8,17 +8,23 @@
 //! status logs, and input widgets onto the terminal frame. It maps the state in [`App`]
 //! to visual elements using `ratatui`.
 
-use crate::app::{App, CurrentScreen};
+use crate::app::{
+    App, CORRELATION_THRESHOLD, ConnectionState, CurrentScreen, HELP_GLOBAL, data_type_symbol,
+    format_age,
+};
+use crate::theme::Theme;
+use opc_da_client::vt_to_string;
 use ratatui::{
     Frame,
     layout::{Constraint, Direction, Layout, Rect},
-    style::{Color, Modifier, Style},
+    style::{Modifier, Style},
     text::{Line, Span},
-    widgets::{Block, Borders, Clear, List, ListItem, Paragraph, Wrap},
+    widgets::{Block, Borders, Cell, Clear, List, ListItem, Paragraph, Tabs, Wrap},
 };
 use std::sync::atomic::Ordering;
 
 pub fn render(f: &mut Frame, app: &mut App) {
+    f.render_widget(Block::default().style(app.theme.base()), f.area());
     let chunks = Layout::default()
         .direction(Direction::Vertical)
         .constraints(
@@ -35,60 +41,147 @@ pub fn render(f: &mut Frame, app: &mut App) {
     let status_area = chunks[1];
     let help_area = chunks[2];
 
-    match app.current_screen {
+    let screen = app.current_screen;
+    render_screen(f, app, screen, main_area);
+    render_status_bar(f, app, status_area);
+    render_help(f, app, help_area);
+}
+
+/// Draw `screen` into the main area.
+fn render_screen(f: &mut Frame, app: &mut App, screen: CurrentScreen, main_area: Rect) {
+    match screen {
         CurrentScreen::Home => render_home(f, app, main_area),
         CurrentScreen::ServerList => render_server_list(f, app, main_area),
         CurrentScreen::TagList => render_tag_list(f, app, main_area),
         CurrentScreen::TagValues => render_tag_values(f, app, main_area),
         CurrentScreen::WriteInput => {
-            // Render TagValues in the background, then overlay the input popup
-            render_tag_values(f, app, main_area);
+            // Render the screen the write started from, then overlay the input popup
+            if app.bulk_write_tags.is_empty() {
+                render_tag_values(f, app, main_area);
+            } else {
+                render_tag_list(f, app, main_area);
+            }
             render_write_input(f, app, main_area);
         }
+        CurrentScreen::Correlation => render_correlation(f, app, main_area),
         CurrentScreen::Loading => {
             // Render the last screen in the background if it makes sense,
             // but for now let's just show the popup.
             render_loading_popup(f, app, main_area);
         }
+        CurrentScreen::CommandPalette => {
+            if let Some(origin) = app.command_palette.as_ref().map(|p| p.origin) {
+                render_screen(f, app, origin, main_area);
+            }
+            render_command_palette(f, app, main_area);
+        }
+        CurrentScreen::Help => render_help_screen(f, app, main_area),
         CurrentScreen::Exiting => {}
     }
+}
 
-    render_status_bar(f, app, status_area);
-    render_help(f, app, help_area);
+/// Full-screen, read-only view of a previous session log, scrolled to line `scroll`.
+pub fn render_session_log(
+    f: &mut Frame,
+    path: &std::path::Path,
+    lines: &[String],
+    scroll: usize,
+    theme: &Theme,
+) {
+    f.render_widget(Block::default().style(theme.base()), f.area());
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(0), Constraint::Length(1)])
+        .split(f.area());
+
+    let text: Vec<Line> = lines
+        .iter()
+        .skip(scroll)
+        .map(|l| {
+            let style = if l.contains("[ERROR]") {
+                Style::default().fg(theme.error)
+            } else {
+                Style::default()
+            };
+            Line::from(Span::styled(l.as_str(), style))
+        })
+        .collect();
+
+    let title = format!(
+        " Last Session: {} ({} messages) ",
+        path.display(),
+        lines.len()
+    );
+    let paragraph = Paragraph::new(text).block(Block::default().borders(Borders::ALL).title(title));
+    f.render_widget(paragraph, chunks[0]);
+
+    let help = Span::styled(
+        "↑/↓: Scroll | PgDn/PgUp: Page | Enter/Esc: Continue",
+        Style::default().fg(theme.muted),
+    );
+    f.render_widget(Paragraph::new(help), chunks[1]);
 }
 
 fn render_help(f: &mut Frame, app: &App, area: ratatui::layout::Rect) {
     let msg = match app.current_screen {
         CurrentScreen::Home => "Enter: Connect | Esc: Quit | Type hostname",
         CurrentScreen::ServerList => {
-            "↑/↓: Nav | PgDn/PgUp: Page | Enter: Tags | Esc: Back | q: Quit"
+            "↑/↓: Nav | PgDn/PgUp: Page | l: Sort by latency | Enter: Tags | g: Public groups | Esc: Back | q: Quit"
         }
         CurrentScreen::TagList => {
-            if app.search_mode {
-                "Type: Search | Tab: Next | Space: Select | Enter: Read | Esc: Cancel"
+            if app.export_path_input.is_some() {
+                "Type file name | Enter: Export | Esc: Cancel"
+            } else if app.search_mode {
+                "Type: Search | Tab: Next | ↑/↓: History | Space: Select | Ctrl+V: Invert matches | Ctrl+C: Case | Enter: Read | Esc: Cancel"
+            } else {
+                "↑/↓: Nav | PgDn/PgUp: Page | Space: Select | v: Invert | s: Search | w: Write selected | Ctrl+L: Export | Ctrl+W: Columns | Enter: Read | Esc: Back | q: Quit"
+            }
+        }
+        CurrentScreen::TagValues => {
+            if app.inline_edit.is_some() {
+                "Type value | ←/→: Move cursor | Enter: Write | Esc: Cancel"
+            } else if app.offline_snapshot.is_some() {
+                "↑/↓: Nav | PgDn/PgUp: Page | w: Write (offline) | t: Types | c: Copy | Esc: Back | q: Quit"
+            } else {
+                "↑/↓: Nav | PgDn/PgUp: Page | Space: Untrack | w: Write | F2: Edit | +/-: Adjust (Alt: large step) | r: Retry failed | l: Live | t: Types | u: Units | c: Copy | g: Correlated | n: Open server | Alt+1-4: Switch | Esc: Back | q: Quit"
+            }
+        }
+        CurrentScreen::WriteInput => {
+            if app.write_multiline {
+                "Type JSON | Enter: New line | Ctrl+S: Submit | Ctrl+E: Single-line | Esc: Cancel"
+            } else if app.write_error.is_some() {
+                "Enter: Retry | Esc: Dismiss error | Type value"
             } else {
-                "↑/↓: Nav | PgDn/PgUp: Page | Space: Select | s: Search | Enter: Read | Esc: Back | q: Quit"
+                "Enter: Submit | Ctrl+E: Multi-line JSON | Esc: Cancel | Type value"
             }
         }
-        CurrentScreen::TagValues => "↑/↓: Nav | PgDn/PgUp: Page | w: Write | Esc: Back | q: Quit",
-        CurrentScreen::WriteInput => "Enter: Submit | Esc: Cancel | Type value",
+        CurrentScreen::Correlation => "Esc: Back | q: Quit",
         CurrentScreen::Loading => "Please wait...",
+        CurrentScreen::CommandPalette => "Type to filter | ↑/↓: Nav | Enter: Run | Esc: Close",
+        CurrentScreen::Help => "↑/↓: Scroll | PgDn/PgUp: Page | Esc/F1: Close",
         CurrentScreen::Exiting => "Exiting...",
     };
 
-    let span = Span::styled(msg, Style::default().fg(Color::DarkGray));
+    let span = Span::styled(msg, Style::default().fg(app.theme.muted));
     f.render_widget(Paragraph::new(span), area);
 }
 
 fn render_home(f: &mut Frame, app: &App, area: ratatui::layout::Rect) {
-    let display_text = format!("> {input}_", input = app.host_input);
-    let input = Paragraph::new(display_text)
-        .style(Style::default().fg(Color::Yellow))
+    let mut lines = vec![Line::from(format!("> {input}_", input = app.host_input))];
+    if let Some(err) = &app.host_error {
+        lines.push(Line::from(Span::styled(
+            format!("✗ {err}"),
+            Style::default().fg(app.theme.error),
+        )));
+    }
+    let input_height = u16::try_from(lines.len()).unwrap_or(1) + 2;
+    let input = Paragraph::new(lines)
+        .style(Style::default().fg(app.theme.header))
         .block(
             Block::default()
                 .borders(Borders::ALL)
                 .title(" Step 1: Connect to Host ")
-                .border_style(Style::default().fg(Color::Cyan)),
+                .border_style(Style::default().fg(app.theme.accent)),
         );
 
     // Create a centered layout
@@ -96,7 +189,7 @@ fn render_home(f: &mut Frame, app: &App, area: ratatui::layout::Rect) {
         .direction(Direction::Vertical)
         .constraints([
             Constraint::Percentage(40),
-            Constraint::Length(3),
+            Constraint::Length(input_height),
             Constraint::Percentage(40),
         ])
         .split(area);
@@ -117,28 +210,42 @@ fn render_server_list(f: &mut Frame, app: &mut App, area: ratatui::layout::Rect)
     let items: Vec<ListItem> = app
         .servers
         .iter()
-        .map(|s| ListItem::new(Line::from(vec![Span::raw(s)])))
+        .enumerate()
+        .map(|(idx, s)| {
+            let mut spans = Vec::new();
+            if let Some(host) = &app.server_host {
+                spans.push(Span::styled(
+                    format!("{host}: "),
+                    Style::default().fg(app.theme.muted),
+                ));
+            }
+            spans.push(Span::raw(s));
+            if let Some(Some(latency)) = app.server_latencies.get(idx) {
+                spans.push(Span::styled(
+                    format!("  ({} ms)", latency.as_millis()),
+                    Style::default().fg(app.theme.muted),
+                ));
+            }
+            ListItem::new(Line::from(spans))
+        })
         .collect();
 
+    let title = if app.servers_sorted_by_latency {
+        " Step 2: Select OPC Server (latency ↑) "
+    } else {
+        " Step 2: Select OPC Server "
+    };
+
     let list = List::new(items)
-        .block(
-            Block::default()
-                .borders(Borders::ALL)
-                .title(" Step 2: Select OPC Server "),
-        )
-        .highlight_style(
-            Style::default()
-                .add_modifier(Modifier::BOLD)
-                .bg(Color::Blue)
-                .fg(Color::White),
-        )
+        .block(Block::default().borders(Borders::ALL).title(title))
+        .highlight_style(app.theme.selected().add_modifier(Modifier::BOLD))
         .highlight_symbol(">> ");
 
     f.render_stateful_widget(list, area, &mut app.list_state);
 }
 
 fn render_tag_list(f: &mut Frame, app: &mut App, area: ratatui::layout::Rect) {
-    let list_chunks = if app.search_mode {
+    let list_chunks = if app.search_mode || app.export_path_input.is_some() {
         Layout::default()
             .direction(Direction::Vertical)
             .constraints([Constraint::Length(3), Constraint::Min(0)])
@@ -150,16 +257,44 @@ fn render_tag_list(f: &mut Frame, app: &mut App, area: ratatui::layout::Rect) {
     };
 
     if app.search_mode {
-        let search_text = format!("Search: {query}_", query = app.search_query);
+        let case = if app.search_case_sensitive {
+            " [Case]"
+        } else {
+            ""
+        };
+        let search_text = format!("Search{case}: {query}_", query = app.search_query);
         let search_bar = Paragraph::new(search_text)
-            .style(Style::default().fg(Color::Yellow))
+            .style(Style::default().fg(app.theme.header))
             .block(
                 Block::default()
                     .borders(Borders::ALL)
                     .title(" Search Tags (Substring Match) ")
-                    .border_style(Style::default().fg(Color::Yellow)),
+                    .border_style(Style::default().fg(app.theme.header)),
             );
         f.render_widget(search_bar, list_chunks[0]);
+    } else if let Some(path) = &app.export_path_input {
+        let prompt = Paragraph::new(format!("File: {path}_"))
+            .style(Style::default().fg(app.theme.header))
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .title(" Export Tag List ")
+                    .border_style(Style::default().fg(app.theme.header)),
+            );
+        f.render_widget(prompt, list_chunks[0]);
+    }
+
+    let list_area = if app.search_mode || app.export_path_input.is_some() {
+        list_chunks[1]
+    } else {
+        list_chunks[0]
+    };
+    if app.tag_columns > 1 {
+        let visible = Block::default()
+            .borders(Borders::ALL)
+            .inner(list_area)
+            .height;
+        app.scroll_tag_columns(usize::from(visible));
     }
 
     let items: Vec<ListItem> = app
@@ -175,14 +310,14 @@ fn render_tag_list(f: &mut Frame, app: &mut App, area: ratatui::layout::Rect) {
 
             let is_match = app.search_mode && app.search_matches.contains(&idx);
             let style = if is_match {
-                Style::default().fg(Color::Yellow)
+                Style::default().fg(app.theme.header)
             } else {
                 Style::default()
             };
 
             ListItem::new(Line::from(vec![
                 Span::raw(checkbox),
-                Span::styled(t, style),
+                Span::styled(display_tag_id(app, &t.tag_id), style),
             ]))
         })
         .collect();
@@ -193,65 +328,244 @@ fn render_tag_list(f: &mut Frame, app: &mut App, area: ratatui::layout::Rect) {
             app.search_matches.len(),
             app.tags.len()
         )
+    } else if let Some(found) = &app.browse_partial {
+        let shown = app.tags.len();
+        format!(
+            " Step 3: Browse Tags [partial results — {shown} of ~{}] ",
+            found.load(Ordering::Relaxed).max(shown)
+        )
     } else {
         " Step 3: Browse Tags ".to_string()
     };
 
+    let block = Block::default().borders(Borders::ALL).title(title);
+
+    if app.tag_columns > 1 {
+        render_tag_columns(f, app, items, block, list_area);
+        return;
+    }
+
     let list = List::new(items)
-        .block(Block::default().borders(Borders::ALL).title(title))
-        .highlight_style(Style::default().bg(Color::Green).fg(Color::Black))
+        .block(block)
+        .highlight_style(app.theme.selected())
         .highlight_symbol(" * ");
-
-    let list_area = if app.search_mode {
-        list_chunks[1]
-    } else {
-        list_chunks[0]
-    };
     f.render_stateful_widget(list, list_area, &mut app.list_state);
 }
 
+/// Draw the tag list as `app.tag_columns` side-by-side columns, filled top
+/// to bottom, left to right, and scrolled together.
+fn render_tag_columns(
+    f: &mut Frame,
+    app: &App,
+    items: Vec<ListItem>,
+    block: Block,
+    area: ratatui::layout::Rect,
+) {
+    use ratatui::widgets::{HighlightSpacing, ListState};
+
+    let inner = block.inner(area);
+    f.render_widget(block, area);
+
+    let columns = usize::from(app.tag_columns);
+    let rows = app.tag_column_rows();
+    let selected = app.selected_index.map(|idx| (idx / rows, idx % rows));
+
+    let column_areas = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints(vec![
+            Constraint::Ratio(1, u32::from(app.tag_columns));
+            columns
+        ])
+        .split(inner);
+
+    let mut items = items.into_iter();
+    for (column, column_area) in column_areas.iter().enumerate() {
+        let column_items: Vec<ListItem> = items.by_ref().take(rows).collect();
+        let list = List::new(column_items)
+            .highlight_style(app.theme.selected())
+            .highlight_symbol(" * ")
+            .highlight_spacing(HighlightSpacing::Always);
+        let mut state = ListState::default()
+            .with_offset(app.tag_column_offset)
+            .with_selected(
+                selected
+                    .filter(|&(col, _)| col == column)
+                    .map(|(_, row)| row),
+            );
+        f.render_stateful_widget(list, *column_area, &mut state);
+    }
+}
+
 fn render_tag_values(f: &mut Frame, app: &mut App, area: ratatui::layout::Rect) {
     use ratatui::widgets::{Row, Table};
 
-    let header = Row::new(vec!["Tag ID", "Value", "Quality", "Timestamp"]).style(
+    let area = if app.active_sessions.len() > 1 {
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(1), Constraint::Min(0)])
+            .split(area);
+        render_session_tabs(f, app, chunks[0]);
+        chunks[1]
+    } else {
+        area
+    };
+
+    let mut header_cells = vec!["Tag ID", "Data Type", "Value", "Quality", "Timestamp"];
+    if app.show_vt_column {
+        header_cells.insert(3, "Type");
+    }
+    let header = Row::new(header_cells).style(
         Style::default()
-            .fg(Color::Yellow)
+            .fg(app.theme.header)
             .add_modifier(Modifier::BOLD),
     );
 
     let rows: Vec<Row> = app
         .tag_values
         .iter()
-        .map(|tv| {
-            Row::new(vec![
-                tv.tag_id.clone(),
-                tv.value.clone(),
-                tv.quality.clone(),
-                tv.timestamp.clone(),
-            ])
+        .enumerate()
+        .map(|(idx, tv)| {
+            let value = match &app.inline_edit {
+                Some((row, input, cursor)) if *row == idx => {
+                    inline_edit_cell(input, *cursor, &app.theme)
+                }
+                _ => Cell::from(
+                    app.engineering_units_for(&tv.tag_id)
+                        // Units would read as part of an error
+                        .filter(|_| tv.status == opc_da_client::ReadStatus::Ok)
+                        .map_or_else(|| tv.value.clone(), |eu| eu.annotate(&tv.value)),
+                ),
+            };
+            let mut cells = vec![
+                Cell::from(display_tag_id(app, &tv.tag_id).to_string()),
+                Cell::from(data_type_symbol(tv.canonical_data_type)),
+                value,
+                Cell::from(tv.quality.clone())
+                    .style(Style::default().fg(app.theme.quality(&tv.quality))),
+                Cell::from(timestamp_with_age(app, tv)),
+            ];
+            if app.show_vt_column {
+                cells.insert(
+                    3,
+                    Cell::from(tv.vt.map_or_else(|| "—".to_string(), vt_to_string)),
+                );
+            }
+            Row::new(cells)
         })
         .collect();
 
-    let widths = [
-        Constraint::Percentage(45),
-        Constraint::Percentage(15),
-        Constraint::Percentage(10),
-        Constraint::Percentage(30),
-    ];
+    let widths: &[Constraint] = if app.show_vt_column {
+        &[
+            Constraint::Percentage(32),
+            Constraint::Percentage(8),
+            Constraint::Percentage(15),
+            Constraint::Percentage(12),
+            Constraint::Percentage(10),
+            Constraint::Percentage(23),
+        ]
+    } else {
+        &[
+            Constraint::Percentage(38),
+            Constraint::Percentage(9),
+            Constraint::Percentage(15),
+            Constraint::Percentage(10),
+            Constraint::Percentage(28),
+        ]
+    };
+
+    let mut title = match app.offline_banner() {
+        Some(banner) => Line::from(vec![
+            Span::raw(" Step 4: Tag Values "),
+            Span::styled(
+                format!("{banner} "),
+                Style::default()
+                    .fg(app.theme.muted)
+                    .add_modifier(Modifier::BOLD),
+            ),
+        ]),
+        None if app.live_mode => Line::from(vec![
+            Span::raw(" Step 4: Tag Values "),
+            Span::styled("[LIVE] ", Style::default().fg(app.theme.good)),
+        ]),
+        None => Line::from(" Step 4: Tag Values "),
+    };
+    if app.record_mode {
+        title.push_span(Span::styled("● REC ", Style::default().fg(app.theme.error)));
+    }
 
-    let table = Table::new(rows, widths)
+    let table = Table::new(rows, widths.iter().copied())
         .header(header)
-        .block(
-            Block::default()
-                .borders(Borders::ALL)
-                .title(" Step 4: Tag Values "),
-        )
-        //.highlight_style(Style::default().bg(Color::Blue).fg(Color::White)) // Deprecated
-        .row_highlight_style(Style::default().bg(Color::Blue).fg(Color::White))
+        .block(Block::default().borders(Borders::ALL).title(title))
+        .row_highlight_style(app.theme.selected())
         .highlight_symbol(">> ");
 
     f.render_stateful_widget(table, area, &mut app.table_state);
 }
+
+/// `tag_id` as shown on the tag list and Tag Values: in full, or only its
+/// leaf name while [`App::show_leaf_names`] is on.
+fn display_tag_id<'a>(app: &App, tag_id: &'a str) -> &'a str {
+    if app.show_leaf_names {
+        leaf_name(tag_id)
+    } else {
+        tag_id
+    }
+}
+
+/// The last segment of `tag_id`, after its final `.`, `/` or `\` namespace
+/// separator. IDs without a separator, or ending in one, are returned whole.
+fn leaf_name(tag_id: &str) -> &str {
+    tag_id
+        .rsplit(['.', '/', '\\'])
+        .next()
+        .filter(|leaf| !leaf.is_empty())
+        .unwrap_or(tag_id)
+}
+
+/// The timestamp column text, suffixed with the value's age while values are
+/// being refreshed so a source that stopped updating stands out.
+fn timestamp_with_age(app: &App, tv: &opc_da_client::TagValue) -> String {
+    match tv.age() {
+        Some(age) if app.offline_snapshot.is_none() => {
+            format!("{} ({})", tv.timestamp, format_age(age))
+        }
+        _ => tv.timestamp.clone(),
+    }
+}
+
+/// Value cell being edited in place, with a block cursor at `cursor`.
+fn inline_edit_cell(input: &str, cursor: usize, theme: &Theme) -> Cell<'static> {
+    let before: String = input.chars().take(cursor).collect();
+    let mut rest = input.chars().skip(cursor);
+    let at = rest.next().map_or_else(|| " ".to_string(), String::from);
+    let after: String = rest.collect();
+    let cursor_style = Style::default().fg(theme.header);
+    let edit = cursor_style.add_modifier(Modifier::REVERSED);
+    Cell::from(Line::from(vec![
+        Span::styled(before, edit),
+        Span::styled(at, cursor_style),
+        Span::styled(after, edit),
+    ]))
+}
+
+/// Tab bar listing the open server sessions as `1 host: server` … `4 host: server`,
+/// numbered like their `Alt+1..4` shortcuts.
+fn render_session_tabs(f: &mut Frame, app: &App, area: Rect) {
+    let titles: Vec<Line> = (1..)
+        .zip(&app.active_sessions)
+        .map(|(label, session)| Line::from(format!(" {label} {} ", session.display_name())))
+        .collect();
+    let tabs = Tabs::new(titles)
+        .select(app.current_session)
+        .style(Style::default().fg(app.theme.muted))
+        .highlight_style(
+            Style::default()
+                .fg(app.theme.header)
+                .add_modifier(Modifier::BOLD),
+        );
+    f.render_widget(tabs, area);
+}
+
 fn render_status_bar(f: &mut Frame, app: &App, area: Rect) {
     let display_messages: Vec<Line> = app
         .messages
@@ -261,43 +575,193 @@ fn render_status_bar(f: &mut Frame, app: &App, area: Rect) {
         .rev()
         .map(|m| {
             Line::from(vec![
-                Span::styled("- ", Style::default().fg(Color::DarkGray)),
+                Span::styled("- ", Style::default().fg(app.theme.muted)),
                 Span::raw(m),
             ])
         })
         .collect();
 
+    let title = match app.stale_connection_banner() {
+        Some(banner) => Line::from(vec![
+            Span::raw(" Status Log "),
+            Span::styled(format!("({banner}) "), Style::default().fg(app.theme.muted)),
+        ]),
+        None => Line::from(" Status Log "),
+    };
+    let state = app.connection_state();
+    let state_color = match state {
+        ConnectionState::Connected => app.theme.good,
+        ConnectionState::Reconnecting => app.theme.uncertain,
+        ConnectionState::Disconnected => app.theme.bad,
+        ConnectionState::Idle => app.theme.muted,
+    };
+    let indicator = Line::from(vec![
+        Span::styled(" ● ", Style::default().fg(state_color)),
+        Span::styled(
+            format!("{} ", state.label()),
+            Style::default().fg(app.theme.muted),
+        ),
+    ])
+    .right_aligned();
     let paragraph = Paragraph::new(display_messages)
-        .block(Block::default().borders(Borders::ALL).title(" Status Log "))
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(title)
+                .title(indicator),
+        )
         .wrap(Wrap { trim: true });
     f.render_widget(paragraph, area);
 }
 
-fn render_write_input(f: &mut Frame, app: &App, area: Rect) {
-    let tag_id = app.write_tag_id.as_deref().unwrap_or("Unknown");
-    let display_text = format!(
-        "Tag: {tag_id}\nValue: {input}_",
-        input = app.write_value_input
+/// Keybinding help for the screen it was opened from, scrolled by
+/// `app.help_scroll`.
+fn render_help_screen(f: &mut Frame, app: &App, area: Rect) {
+    let text = format!("{}\n\n{HELP_GLOBAL}", App::help_text(app.help_from_screen));
+    let lines: Vec<Line> = text
+        .lines()
+        .map(|line| {
+            if line.starts_with(' ') || line.is_empty() {
+                Line::from(line)
+            } else {
+                Line::from(Span::styled(
+                    line,
+                    Style::default()
+                        .fg(app.theme.header)
+                        .add_modifier(Modifier::BOLD),
+                ))
+            }
+        })
+        .collect();
+    let help = Paragraph::new(lines)
+        .scroll((app.help_scroll, 0))
+        .block(Block::default().borders(Borders::ALL).title(" Help "));
+    f.render_widget(help, area);
+}
+
+/// Adjacency list of the tags that change value together, one line per tag.
+fn render_correlation(f: &mut Frame, app: &App, area: Rect) {
+    let tag_id = |idx: usize| app.tag_values.get(idx).map_or("?", |tv| tv.tag_id.as_str());
+    let correlated = app.correlated_tags();
+    let lines: Vec<Line> = if correlated.is_empty() {
+        vec![Line::from(Span::styled(
+            "No tags change together yet — correlations build up as values refresh",
+            Style::default().fg(app.theme.muted),
+        ))]
+    } else {
+        correlated
+            .iter()
+            .map(|(idx, partners)| {
+                let partners = partners
+                    .iter()
+                    .map(|&(other, correlation)| {
+                        format!("{} ({:.0}%)", tag_id(other), correlation * 100.0)
+                    })
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                Line::from(vec![
+                    Span::styled(
+                        tag_id(*idx).to_string(),
+                        Style::default().fg(app.theme.header),
+                    ),
+                    Span::raw(format!(" → {partners}")),
+                ])
+            })
+            .collect()
+    };
+
+    let title = format!(
+        " Tags that change together (>{:.0}% of changes) ",
+        CORRELATION_THRESHOLD * 100.0
     );
+    let paragraph = Paragraph::new(lines)
+        .block(Block::default().borders(Borders::ALL).title(title))
+        .wrap(Wrap { trim: false });
+    f.render_widget(paragraph, area);
+}
+
+fn render_write_input(f: &mut Frame, app: &App, area: Rect) {
+    let target = match app.bulk_write_tags.as_slice() {
+        [] => format!("Tag: {}", app.write_tag_id.as_deref().unwrap_or("Unknown")),
+        [tag_id] => format!("Tag: {tag_id}"),
+        [first, rest @ ..] => format!("Tags: {first} and {} more", rest.len()),
+    };
+    let mut lines = vec![Line::from(target)];
+    if app.write_multiline {
+        lines.push(Line::from("Value (JSON):"));
+        let input = format!("{}_", app.write_value_input);
+        lines.extend(input.split('\n').map(|l| Line::from(l.to_string())));
+    } else {
+        lines.push(Line::from(format!(
+            "Value: {input}_",
+            input = app.write_value_input
+        )));
+    }
+    if let Some(err) = &app.write_error {
+        lines.push(Line::from(""));
+        lines.push(Line::from(Span::styled(
+            format!("✗ {err}"),
+            Style::default()
+                .fg(app.theme.error)
+                .add_modifier(Modifier::BOLD | Modifier::REVERSED),
+        )));
+    }
 
     let popup_block = Block::default()
         .title(" Write Tag Value ")
         .borders(Borders::ALL)
-        .border_style(Style::default().fg(Color::Yellow));
+        .border_style(Style::default().fg(app.theme.header));
 
-    let input = Paragraph::new(display_text)
-        .block(popup_block)
-        .wrap(Wrap { trim: true });
+    // Keep the editor's indentation
+    let input = Paragraph::new(lines).block(popup_block).wrap(Wrap {
+        trim: !app.write_multiline,
+    });
 
-    let area = centered_rect(60, 30, area);
-    f.render_widget(Clear, area);
+    let area = if app.write_multiline {
+        centered_rect(70, 70, area)
+    } else {
+        centered_rect(60, 30, area)
+    };
+    clear_popup(f, area, &app.theme);
     f.render_widget(input, area);
 }
 
+/// Frames of the loading spinner, one per main-loop iteration.
+const BRAILLE_SPINNER: [char; 8] = ['⣾', '⣽', '⣻', '⢿', '⡿', '⣟', '⣯', '⣷'];
+
+/// Spinner frame for `tick`.
+fn spinner(tick: u8) -> char {
+    BRAILLE_SPINNER[usize::from(tick) % BRAILLE_SPINNER.len()]
+}
+
+/// `elapsed` as `mm:ss`, e.g. `00:05`.
+fn format_elapsed(elapsed: std::time::Duration) -> String {
+    let secs = elapsed.as_secs();
+    format!("{:02}:{:02}", secs / 60, secs % 60)
+}
+
 fn render_loading_popup(f: &mut Frame, app: &App, area: Rect) {
     let progress = app.browse_progress.load(Ordering::Relaxed);
-    let msg = if progress > 0 {
-        format!("Browsing OPC tags... ({progress} found so far)")
+    let depth = app.browse_depth.load(Ordering::Relaxed);
+    let msg = if app.read_total > 0 {
+        let mut msg = format!(
+            "Reading tag values... {read}/{total}",
+            read = app.read_progress.load(Ordering::Relaxed),
+            total = app.read_total
+        );
+        if app.read_chunk_total > 1 {
+            msg.push_str(&format!(
+                " ({done}/{chunks} chunks)",
+                done = app.read_chunks_done.load(Ordering::Relaxed),
+                chunks = app.read_chunk_total
+            ));
+        }
+        msg
+    } else if progress > 0 || depth > 0 {
+        match app.browse_estimated_percent() {
+            Some(percent) => format!("Browsing: {progress} tags (≈{percent}%) | Depth: {depth}"),
+            None => format!("Browsing: {progress} tags | Depth: {depth}"),
+        }
     } else {
         "Communicating with OPC Server...".to_string()
     };
@@ -305,11 +769,69 @@ fn render_loading_popup(f: &mut Frame, app: &App, area: Rect) {
     let block = Block::default()
         .title(" Loading ")
         .borders(Borders::ALL)
-        .border_style(Style::default().fg(Color::Yellow));
+        .border_style(Style::default().fg(app.theme.header));
+
+    let elapsed = app
+        .loading_since
+        .map_or_else(String::new, |since| format_elapsed(since.elapsed()));
+    let lines = vec![
+        Line::from(format!("{} {msg}", spinner(app.loading_tick))),
+        Line::from(Span::styled(elapsed, Style::default().fg(app.theme.muted))),
+    ];
 
     let area = centered_rect(60, 20, area);
-    f.render_widget(Clear, area); // This clears the background
-    f.render_widget(Paragraph::new(msg).block(block), area);
+    clear_popup(f, area, &app.theme);
+    f.render_widget(Paragraph::new(lines).block(block), area);
+}
+
+fn render_command_palette(f: &mut Frame, app: &App, area: Rect) {
+    let Some(palette) = &app.command_palette else {
+        return;
+    };
+    let matches = palette.matches();
+
+    let area = centered_rect(50, 50, area);
+    clear_popup(f, area, &app.theme);
+    let block = Block::default()
+        .title(" Command Palette ")
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(app.theme.header));
+    let inner = block.inner(area);
+    f.render_widget(block, area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(1), Constraint::Min(0)])
+        .split(inner);
+    f.render_widget(
+        Paragraph::new(format!("> {}_", palette.input_query))
+            .style(Style::default().fg(app.theme.header)),
+        chunks[0],
+    );
+
+    if matches.is_empty() {
+        f.render_widget(
+            Paragraph::new(Span::styled(
+                "No matching actions",
+                Style::default().fg(app.theme.muted),
+            )),
+            chunks[1],
+        );
+        return;
+    }
+    let items: Vec<ListItem> = matches.iter().map(|a| ListItem::new(a.label)).collect();
+    let mut state = ratatui::widgets::ListState::default().with_selected(Some(palette.selected));
+    f.render_stateful_widget(
+        List::new(items).highlight_style(app.theme.selected()),
+        chunks[1],
+        &mut state,
+    );
+}
+
+/// Blank `area` for a popup, keeping the theme's background.
+fn clear_popup(f: &mut Frame, area: Rect, theme: &Theme) {
+    f.render_widget(Clear, area);
+    f.render_widget(Block::default().style(theme.base()), area);
 }
 
 /// helper function to create a centered rect using up certain percentage of the available rect `r`
@@ -332,3 +854,418 @@ fn centered_rect(percent_x: u16, percent_y: u16, r: Rect) -> Rect {
         ])
         .split(popup_layout[1])[1]
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use opc_da_client::MockOpcProvider;
+    use ratatui::{Terminal, backend::TestBackend, buffer::Buffer, style::Color};
+    use std::sync::Arc;
+
+    /// Cells of `screen` drawn with `theme` whose background is the terminal's.
+    fn unpainted_cells(theme: Theme, screen: CurrentScreen) -> usize {
+        let mut app = App::new(Arc::new(MockOpcProvider::new()));
+        app.theme = theme;
+        app.servers = vec!["Server.A".into(), "Server.B".into()];
+        app.selected_index = Some(0);
+        app.list_state.select(Some(0));
+        app.current_screen = screen;
+
+        let mut terminal = Terminal::new(TestBackend::new(80, 24)).unwrap();
+        terminal.draw(|f| render(f, &mut app)).unwrap();
+        terminal
+            .backend()
+            .buffer()
+            .content()
+            .iter()
+            .filter(|cell| cell.bg == Color::Reset || cell.fg == Color::Reset)
+            .count()
+    }
+
+    #[test]
+    fn test_dark_and_light_themes_paint_every_cell() {
+        for theme in [Theme::dark(), Theme::light()] {
+            for screen in [
+                CurrentScreen::Home,
+                CurrentScreen::ServerList,
+                CurrentScreen::Loading,
+            ] {
+                assert_eq!(unpainted_cells(theme, screen), 0, "{theme:?} on {screen:?}");
+            }
+        }
+        assert_ne!(unpainted_cells(Theme::default(), CurrentScreen::Home), 0);
+    }
+
+    #[test]
+    fn test_correlation_view_lists_tags_changing_together() {
+        let mut app = App::new(Arc::new(MockOpcProvider::new()));
+        app.current_screen = CurrentScreen::Correlation;
+        app.tag_values = ["Pump.Speed", "Tank.Level", "Pump.Current"]
+            .into_iter()
+            .map(|tag_id| opc_da_client::TagValue {
+                tag_id: tag_id.into(),
+                value: String::new(),
+                quality: "Good".into(),
+                timestamp: String::new(),
+                raw_timestamp: None,
+                vt: None,
+                access_rights: 0,
+                canonical_data_type: 0,
+                status: opc_da_client::ReadStatus::Ok,
+            })
+            .collect();
+        app.change_counts = vec![10, 4, 9];
+        app.change_correlation = [((0, 2), 9), ((0, 1), 1)].into_iter().collect();
+
+        let mut terminal = Terminal::new(TestBackend::new(80, 24)).unwrap();
+        terminal.draw(|f| render(f, &mut app)).unwrap();
+        let text: String = terminal
+            .backend()
+            .buffer()
+            .content()
+            .iter()
+            .map(ratatui::buffer::Cell::symbol)
+            .collect();
+
+        assert!(text.contains("Pump.Speed → Pump.Current (90%)"), "{text}");
+        assert!(text.contains("Pump.Current → Pump.Speed (90%)"), "{text}");
+        assert!(!text.contains("Tank.Level"), "{text}");
+    }
+
+    #[test]
+    fn test_loading_popup_shows_read_progress() {
+        let mut app = App::new(Arc::new(MockOpcProvider::new()));
+        app.current_screen = CurrentScreen::Loading;
+        app.read_total = 10_000;
+        app.read_progress.store(1500, Ordering::Relaxed);
+        app.read_chunk_total = 200;
+        app.read_chunks_done.store(30, Ordering::Relaxed);
+
+        let mut terminal = Terminal::new(TestBackend::new(100, 24)).unwrap();
+        terminal.draw(|f| render(f, &mut app)).unwrap();
+        let text: String = terminal
+            .backend()
+            .buffer()
+            .content()
+            .iter()
+            .map(ratatui::buffer::Cell::symbol)
+            .collect();
+        assert!(
+            text.contains("Reading tag values... 1500/10000 (30/200 chunks)"),
+            "{text}"
+        );
+    }
+
+    #[test]
+    fn test_tag_list_columns_side_by_side() {
+        let mut app = App::new(Arc::new(MockOpcProvider::new()));
+        app.current_screen = CurrentScreen::TagList;
+        app.tags = (0..50)
+            .map(|i| opc_da_client::BrowsedTag::from(format!("Tag.{i:02}")))
+            .collect();
+        app.selected_tags = vec![false; 50];
+        app.selected_index = Some(0);
+        app.list_state.select(Some(0));
+        app.tag_columns = 2;
+
+        let mut terminal = Terminal::new(TestBackend::new(160, 24)).unwrap();
+        terminal.draw(|f| render(f, &mut app)).unwrap();
+        let buf = terminal.backend().buffer().clone();
+
+        // Tag 25 starts the second column, on the same row as tag 0.
+        let y = find_row(&buf, "Tag.00");
+        let row = row_text(&buf, y);
+        let column_of = |needle: &str| row.find(needle).map(|at| row[..at].chars().count());
+        assert!(column_of("Tag.00").unwrap() < 80, "{row}");
+        assert!(column_of("Tag.25").unwrap() >= 80, "{row}");
+        assert_eq!(find_row(&buf, "Tag.01"), y + 1);
+        assert_eq!(find_row(&buf, "Tag.26"), y + 1);
+
+        // Selecting deep into a column scrolls both columns together.
+        app.selected_index = Some(24);
+        terminal.draw(|f| render(f, &mut app)).unwrap();
+        let buf = terminal.backend().buffer().clone();
+        let y = find_row(&buf, "Tag.24");
+        assert!(row_text(&buf, y).contains("Tag.49"));
+        assert!(!(0..buf.area.height).any(|y| row_text(&buf, y).contains("Tag.00")));
+    }
+
+    #[test]
+    fn test_leaf_names_hide_the_namespace_path() {
+        let mut app = App::new(Arc::new(MockOpcProvider::new()));
+        app.current_screen = CurrentScreen::TagList;
+        app.tags = ["Plant.Line1.Temp", "Plant/Line2/Flow", "Standalone"]
+            .into_iter()
+            .map(opc_da_client::BrowsedTag::from)
+            .collect();
+        app.selected_tags = vec![false; 3];
+
+        let buf = draw(&mut app);
+        assert!(row_text(&buf, find_row(&buf, "Temp")).contains("Plant.Line1.Temp"));
+
+        app.toggle_leaf_names();
+        let buf = draw(&mut app);
+        assert!(!row_text(&buf, find_row(&buf, "Temp")).contains("Plant"));
+        assert!(!row_text(&buf, find_row(&buf, "Flow")).contains("Line2"));
+        find_row(&buf, "Standalone");
+        assert_eq!(app.tags[0].tag_id, "Plant.Line1.Temp", "full ID kept");
+
+        assert_eq!(leaf_name(r"Area\Valve"), "Valve");
+        assert_eq!(leaf_name("Tank."), "Tank.");
+    }
+
+    /// Draw `app` on an 80×24 test terminal.
+    fn draw(app: &mut App) -> Buffer {
+        let mut terminal = Terminal::new(TestBackend::new(80, 24)).unwrap();
+        terminal.draw(|f| render(f, app)).unwrap();
+        terminal.backend().buffer().clone()
+    }
+
+    /// Row `y` of `buf` as text.
+    fn row_text(buf: &Buffer, y: u16) -> String {
+        (0..buf.area.width).map(|x| buf[(x, y)].symbol()).collect()
+    }
+
+    /// Index of the first row of `buf` containing `needle`.
+    fn find_row(buf: &Buffer, needle: &str) -> u16 {
+        (0..buf.area.height)
+            .find(|&y| row_text(buf, y).contains(needle))
+            .unwrap_or_else(|| panic!("{needle:?} not drawn"))
+    }
+
+    #[test]
+    fn test_session_tabs_numbered_like_shortcuts() {
+        let mut app = App::new(Arc::new(MockOpcProvider::new()));
+        app.current_screen = CurrentScreen::TagValues;
+        app.active_sessions = ["Server.A", "Server.B"]
+            .into_iter()
+            .map(|server| crate::app::ServerSession {
+                host: "plc01".into(),
+                server: server.into(),
+                ..crate::app::ServerSession::default()
+            })
+            .collect();
+
+        let mut terminal = Terminal::new(TestBackend::new(80, 24)).unwrap();
+        terminal.draw(|f| render(f, &mut app)).unwrap();
+        let buf = terminal.backend().buffer().clone();
+
+        let row = row_text(&buf, find_row(&buf, "Server.A"));
+        assert!(row.contains(" 1 plc01: Server.A "), "{row}");
+        assert!(row.contains(" 2 plc01: Server.B "), "{row}");
+    }
+
+    #[test]
+    fn test_home_shows_host_input_and_status_log() {
+        let mut app = App::new(Arc::new(MockOpcProvider::new()));
+        app.host_input = "plc-01.local".into();
+        app.add_message("Ready".into());
+
+        let buf = draw(&mut app);
+
+        let input_row = find_row(&buf, "> plc-01.local_");
+        assert!(row_text(&buf, input_row - 1).contains("Step 1: Connect to Host"));
+        // The status log occupies the three rows above the help line.
+        assert!(row_text(&buf, 20).contains("Status Log"));
+        assert!((21..23).contains(&find_row(&buf, "- Ready")));
+        assert!(row_text(&buf, 23).contains("Enter: Connect"));
+    }
+
+    #[test]
+    fn test_status_log_shows_stale_connection_banner() {
+        let mut app = App::new(Arc::new(MockOpcProvider::new()));
+        app.stale_after = Some(std::time::Duration::from_secs(60));
+        app.last_server_activity =
+            Some(std::time::Instant::now() - std::time::Duration::from_secs(61));
+
+        let buf = draw(&mut app);
+        assert!(row_text(&buf, 20).contains("connection may be stale"));
+
+        app.stale_after = None;
+        let buf = draw(&mut app);
+        assert!(!row_text(&buf, 20).contains("connection may be stale"));
+    }
+
+    #[test]
+    fn test_status_log_shows_connection_state() {
+        let mut app = App::new(Arc::new(MockOpcProvider::new()));
+        let status_line = |app: &mut App| row_text(&draw(app), 20);
+        assert!(status_line(&mut app).contains("● idle"));
+
+        app.last_server_activity = Some(std::time::Instant::now());
+        assert!(status_line(&mut app).contains("● connected"));
+
+        app.consecutive_read_errors = 2;
+        assert!(status_line(&mut app).contains("● reconnecting"));
+
+        app.consecutive_read_errors = 0;
+        app.last_operation_failed = true;
+        assert!(status_line(&mut app).contains("● disconnected"));
+    }
+
+    #[test]
+    fn test_server_list_highlights_selected_server() {
+        let mut app = App::new(Arc::new(MockOpcProvider::new()));
+        app.current_screen = CurrentScreen::ServerList;
+        app.servers = vec!["Server.A".into(), "Server.B".into(), "Server.C".into()];
+        app.selected_index = Some(1);
+        app.list_state.select(Some(1));
+
+        let buf = draw(&mut app);
+
+        let selected = find_row(&buf, "Server.B");
+        assert!(row_text(&buf, selected).contains(">> Server.B"));
+        let cell = &buf[(4, selected)];
+        assert_eq!(cell.bg, app.theme.selected_bg);
+        assert!(cell.modifier.contains(Modifier::BOLD));
+        for other in ["Server.A", "Server.C"] {
+            let y = find_row(&buf, other);
+            assert!(!row_text(&buf, y).contains(">>"), "{other} highlighted");
+            assert_ne!(buf[(4, y)].bg, app.theme.selected_bg);
+        }
+    }
+
+    #[test]
+    fn test_tag_list_search_shows_match_count() {
+        let mut app = App::new(Arc::new(MockOpcProvider::new()));
+        app.current_screen = CurrentScreen::TagList;
+        app.tags = (0..12).map(|n| format!("Tag{n}").into()).collect();
+        app.selected_tags = vec![false; 12];
+        app.search_mode = true;
+        app.search_query = "Tag1".into();
+        app.search_matches = vec![1, 10, 11];
+
+        let buf = draw(&mut app);
+
+        find_row(&buf, "Search: Tag1_");
+        find_row(&buf, "Step 3: Browse Tags (3/12 matches)");
+
+        app.search_case_sensitive = true;
+        let buf = draw(&mut app);
+        find_row(&buf, "Search [Case]: Tag1_");
+    }
+
+    #[test]
+    fn test_tag_values_marks_stale_value() {
+        let mut app = App::new(Arc::new(MockOpcProvider::new()));
+        app.current_screen = CurrentScreen::TagValues;
+        app.tag_values = vec![opc_da_client::TagValue {
+            tag_id: "Line1.Temp".into(),
+            value: "21.5".into(),
+            quality: "Bad".into(),
+            timestamp: "12:00:00".into(),
+            raw_timestamp: Some(std::time::SystemTime::now() - std::time::Duration::from_secs(150)),
+            vt: None,
+            access_rights: 0,
+            canonical_data_type: 5,
+            status: opc_da_client::ReadStatus::Ok,
+        }];
+
+        let buf = draw(&mut app);
+
+        let y = find_row(&buf, "Line1.Temp");
+        let row = row_text(&buf, y);
+        assert!(row.contains("12:00:00 (2m ago)"), "{row}");
+        let quality_x = u16::try_from(row[..row.find("Bad").unwrap()].chars().count()).unwrap();
+        assert_eq!(buf[(quality_x, y)].fg, app.theme.bad);
+    }
+
+    #[test]
+    fn test_tag_values_annotate_units_except_on_errors() {
+        let mut app = App::new(Arc::new(MockOpcProvider::new()));
+        app.current_screen = CurrentScreen::TagValues;
+        app.show_engineering_units = true;
+        app.refresh_server = Some("S".into());
+        let value = |tag_id: &str, value: &str, status| opc_da_client::TagValue {
+            tag_id: tag_id.into(),
+            value: value.into(),
+            quality: "Good".into(),
+            timestamp: String::new(),
+            raw_timestamp: None,
+            vt: None,
+            access_rights: 0,
+            canonical_data_type: 5,
+            status,
+        };
+        app.tag_values = vec![
+            value("Temp", "73.2", opc_da_client::ReadStatus::Ok),
+            value("Level", "Error", opc_da_client::ReadStatus::ReadError),
+        ];
+        for tag_id in ["Temp", "Level"] {
+            app.engineering_units.insert(
+                ("S".into(), tag_id.into()),
+                Some(opc_da_client::EngineeringUnits {
+                    units: Some("°C".into()),
+                    low: None,
+                    high: None,
+                }),
+            );
+        }
+
+        let buf = draw(&mut app);
+
+        assert!(row_text(&buf, find_row(&buf, "Temp")).contains("73.2 °C"));
+        let error_row = row_text(&buf, find_row(&buf, "Level"));
+        assert!(!error_row.contains("°C"), "{error_row}");
+    }
+
+    #[test]
+    fn test_loading_popup_shows_browse_progress() {
+        let mut app = App::new(Arc::new(MockOpcProvider::new()));
+        app.current_screen = CurrentScreen::Loading;
+        app.browse_progress.store(42, Ordering::Relaxed);
+        app.browse_depth.store(3, Ordering::Relaxed);
+
+        let buf = draw(&mut app);
+
+        find_row(&buf, "Loading");
+        find_row(&buf, "Browsing: 42 tags | Depth: 3");
+
+        app.estimated_total_tags = Some(100);
+        let buf = draw(&mut app);
+        find_row(&buf, "Browsing: 42 tags (≈42%) | Depth: 3");
+    }
+
+    #[test]
+    fn test_spinner_wraps_every_eight_ticks() {
+        let frames: String = (0..16).map(spinner).collect();
+        assert_eq!(frames, "⣾⣽⣻⢿⡿⣟⣯⣷⣾⣽⣻⢿⡿⣟⣯⣷");
+        assert_eq!(spinner(u8::MAX), '⣷');
+        assert_eq!(format_elapsed(std::time::Duration::from_secs(65)), "01:05");
+    }
+
+    #[test]
+    fn test_loading_popup_draws_spinner_frame() {
+        let mut app = App::new(Arc::new(MockOpcProvider::new()));
+        app.current_screen = CurrentScreen::Loading;
+        app.loading_tick = 3;
+        app.loading_since = Some(std::time::Instant::now() - std::time::Duration::from_secs(5));
+
+        let buf = draw(&mut app);
+
+        let y = find_row(&buf, "Communicating with OPC Server...");
+        let row = row_text(&buf, y);
+        assert!(row.contains("⢿ Communicating"), "{row}");
+        assert!(row_text(&buf, y + 1).contains("00:05"));
+    }
+
+    #[test]
+    fn test_tag_list_title_marks_partial_browse() {
+        let mut app = App::new(Arc::new(MockOpcProvider::new()));
+        app.current_screen = CurrentScreen::TagList;
+        app.tags = vec!["Tag1".into(), "Tag2".into()];
+        app.selected_tags = vec![false; 2];
+        app.browse_partial = Some(Arc::new(std::sync::atomic::AtomicUsize::new(7)));
+
+        let mut terminal = Terminal::new(TestBackend::new(80, 24)).unwrap();
+        terminal.draw(|f| render(f, &mut app)).unwrap();
+        let text: String = terminal
+            .backend()
+            .buffer()
+            .content()
+            .iter()
+            .map(ratatui::buffer::Cell::symbol)
+            .collect();
+        assert!(text.contains("[partial results — 2 of ~7]"), "{text}");
+    }
+}
@@ -0,0 +1,211 @@
+//! # watch
+//!
+//! Headless polling bridge (`--watch-in` / `--watch-out`).
+//!
+//! ## Overview
+//!
+//! [`run`] reads the tag IDs listed in an input file, reads their values
+//! every `interval` and writes them to an output file through the
+//! [`output`](crate::output) writers, until interrupted. The input file is
+//! re-read every round, so tags can be added or removed without a restart.
+//!
+//! CSV output is appended to, one row per tag and round, for feeding a
+//! historian or a script. JSON and table output are replaced each round with
+//! the latest values, because a file of concatenated JSON arrays is not JSON.
+
+use crate::output::{self, CsvWriter, OutputFormat};
+use anyhow::{Context, Result};
+use opc_da_client::OpcProvider;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+
+/// What to watch and where to write it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WatchConfig {
+    /// Server `ProgID` to read from.
+    pub server: String,
+    /// File listing one tag ID per line.
+    pub input: PathBuf,
+    /// File the values are written to.
+    pub output: PathBuf,
+    pub format: OutputFormat,
+    /// Time between the starts of two reads.
+    pub interval: Duration,
+}
+
+/// Tag IDs listed in `text`: one per line, skipping blank lines and `#`
+/// comments, so a tag list exported from the TUI works unchanged.
+pub fn parse_tag_list(text: &str) -> Vec<String> {
+    text.lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(str::to_string)
+        .collect()
+}
+
+/// Read the tags listed in `config.input` once and write their values to
+/// `config.output`. Returns the number of values written.
+///
+/// # Errors
+/// Returns `Err` if the input file cannot be read, the read fails, or the
+/// output file cannot be written.
+pub async fn watch_once(provider: &dyn OpcProvider, config: &WatchConfig) -> Result<usize> {
+    let text = std::fs::read_to_string(&config.input)
+        .with_context(|| format!("Failed to read tag list {}", config.input.display()))?;
+    let tag_ids = parse_tag_list(&text);
+    if tag_ids.is_empty() {
+        tracing::warn!(input = %config.input.display(), "watch: no tags listed");
+        return Ok(0);
+    }
+
+    let values = provider
+        .read_tag_values(&config.server, tag_ids, Arc::default())
+        .await?;
+    let rows = output::tag_value_rows(&values);
+    write_round(&config.output, config.format, &rows)
+        .with_context(|| format!("Failed to write {}", config.output.display()))?;
+    Ok(rows.len())
+}
+
+/// Append `rows` to a CSV file, starting it with a header if it is new or
+/// empty; replace the file with `rows` in any other format.
+fn write_round(path: &Path, format: OutputFormat, rows: &[Vec<String>]) -> std::io::Result<()> {
+    if format == OutputFormat::Csv {
+        let is_new = !std::fs::metadata(path).is_ok_and(|m| m.len() > 0);
+        let mut file = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)?;
+        let mut buf = Vec::new();
+        if is_new {
+            CsvWriter.write_header(&mut buf, &output::TAG_VALUE_COLUMNS)?;
+        }
+        CsvWriter.write_rows(&mut buf, rows)?;
+        // One write per round, so a reader never sees half a round
+        return file.write_all(&buf);
+    }
+
+    let mut buf = Vec::new();
+    format
+        .writer()
+        .write_records(&mut buf, &output::TAG_VALUE_COLUMNS, rows)?;
+    // Write beside the target and rename, so readers see the old or the new file
+    let tmp = path.with_extension("tmp");
+    std::fs::write(&tmp, buf)?;
+    std::fs::rename(&tmp, path)
+}
+
+/// Poll until Ctrl+C, reporting each round on stderr. A failed round is
+/// reported and retried at the next interval rather than ending the watch.
+///
+/// # Errors
+/// Returns `Err` only if waiting for Ctrl+C fails.
+pub async fn run(provider: &dyn OpcProvider, config: &WatchConfig) -> Result<()> {
+    let mut ticker = tokio::time::interval(config.interval);
+    ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+    eprintln!(
+        "Watching {} → {} every {}s (Ctrl+C to stop)",
+        config.input.display(),
+        config.output.display(),
+        config.interval.as_secs()
+    );
+
+    loop {
+        tokio::select! {
+            _ = ticker.tick() => {}
+            signal = tokio::signal::ctrl_c() => return signal.map_err(Into::into),
+        }
+        match watch_once(provider, config).await {
+            Ok(count) => {
+                tracing::debug!(count, "watch round written");
+                eprintln!(
+                    "{}: wrote {count} values",
+                    chrono::Local::now().format("%H:%M:%S")
+                );
+            }
+            Err(e) => {
+                tracing::warn!(error = %e, "watch round failed");
+                eprintln!("{}: {e:#}", chrono::Local::now().format("%H:%M:%S"));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use opc_da_client::SimulatedOpcProvider;
+
+    fn temp_path(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("opc-cli-{}-{name}", std::process::id()))
+    }
+
+    fn config(input: &Path, output: &Path, format: OutputFormat) -> WatchConfig {
+        WatchConfig {
+            server: opc_da_client::SIMULATED_SERVER.into(),
+            input: input.to_path_buf(),
+            output: output.to_path_buf(),
+            format,
+            interval: Duration::from_secs(1),
+        }
+    }
+
+    #[test]
+    fn test_parse_tag_list() {
+        let text = "# Server: S  Browsed: now\n\nSim.Tag1\n  Sim.Tag2  \n";
+        assert_eq!(parse_tag_list(text), ["Sim.Tag1", "Sim.Tag2"]);
+    }
+
+    #[tokio::test]
+    async fn test_watch_appends_csv_rounds() {
+        let provider = SimulatedOpcProvider::new();
+        let input = temp_path("watch-in.txt");
+        let output = temp_path("watch-out.csv");
+        let _ = std::fs::remove_file(&output);
+        let tags = provider
+            .browse_tags(
+                opc_da_client::SIMULATED_SERVER,
+                2,
+                opc_da_client::AccessRightsFilter::Any,
+                opc_da_client::BrowseProgress::default(),
+            )
+            .await
+            .unwrap();
+        let listed: Vec<&str> = tags.iter().map(|t| t.tag_id.as_str()).collect();
+        std::fs::write(&input, listed.join("\n")).unwrap();
+
+        let config = config(&input, &output, OutputFormat::Csv);
+        assert_eq!(watch_once(&provider, &config).await.unwrap(), 2);
+        assert_eq!(watch_once(&provider, &config).await.unwrap(), 2);
+        let text = std::fs::read_to_string(&output).unwrap();
+        let _ = std::fs::remove_file(&input);
+        let _ = std::fs::remove_file(&output);
+
+        let lines: Vec<&str> = text.lines().collect();
+        assert_eq!(lines.len(), 5, "one header and two rounds of two rows");
+        assert_eq!(lines[0], "tag_id,value,quality,timestamp");
+        assert!(lines[1].starts_with(listed[0]));
+        assert!(lines[4].starts_with(listed[1]));
+    }
+
+    #[tokio::test]
+    async fn test_watch_replaces_json() {
+        let provider = SimulatedOpcProvider::new();
+        let input = temp_path("watch-in.json.txt");
+        let output = temp_path("watch-out.json");
+        std::fs::write(&input, "Unknown.Tag\n").unwrap();
+
+        let config = config(&input, &output, OutputFormat::Json);
+        watch_once(&provider, &config).await.unwrap();
+        watch_once(&provider, &config).await.unwrap();
+        let text = std::fs::read_to_string(&output).unwrap();
+        let _ = std::fs::remove_file(&input);
+        let _ = std::fs::remove_file(&output);
+
+        let parsed: serde_json::Value = serde_json::from_str(&text).unwrap();
+        assert_eq!(parsed.as_array().unwrap().len(), 1);
+        assert_eq!(parsed[0]["tag_id"], "Unknown.Tag");
+    }
+}
@@ -8,11 +8,17 @@ pub use crate::bindings::da::tagOPCITEMDEF;
 pub use crate::bindings::da::{tagOPCITEMRESULT, tagOPCITEMSTATE};
 pub use crate::opc_da::client::*;
 pub use crate::opc_da::com_utils::RemoteArray;
-pub use crate::opc_da::errors::{OpcError, OpcResult};
-use anyhow::Context;
+pub use crate::opc_da::errors::{ConnectionDiagnostics, OpcError, OpcResult};
 pub use windows::Win32::System::Variant::VARIANT;
 use windows::core::Interface;
 
+/// `max_age` for [`ConnectedGroup::read_with_max_age`] that forces a device read.
+pub const MAX_AGE_DEVICE: u32 = 0;
+
+/// `max_age` for [`ConnectedGroup::read_with_max_age`] that always serves
+/// values from the server cache.
+pub const MAX_AGE_CACHE: u32 = u32::MAX;
+
 /// Factory for connecting to OPC DA servers.
 ///
 /// Abstracts the concrete COM client usage so that tests can inject mocks
@@ -39,6 +45,16 @@ pub trait ServerConnector: Send + Sync {
     ///
     /// Returns an error if the COM server cannot be created or connected.
     fn connect(&self, server_name: &str) -> OpcResult<Self::Server>;
+
+    /// Inspect how `server_name` is registered, after [`Self::connect`]
+    /// failed for it.
+    ///
+    /// Returns `None` if the connector has nothing to report; that is the
+    /// default.
+    fn diagnose(&self, server_name: &str) -> Option<ConnectionDiagnostics> {
+        let _ = server_name;
+        None
+    }
 }
 
 /// Facade over a connected OPC DA server instance.
@@ -113,6 +129,123 @@ pub trait ConnectedServer {
     ///
     /// Returns an error if the group removal fails.
     fn remove_group(&self, server_group: GroupHandle, force: bool) -> OpcResult<()>;
+
+    /// Names of the groups visible to this connection: its own private
+    /// groups and the server's public groups.
+    ///
+    /// The default implementation reports the operation as unsupported.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the COM `CreateGroupEnumerator` call fails.
+    fn group_names(&self) -> OpcResult<Vec<String>> {
+        Err(OpcError::NotImplemented("group enumeration".into()))
+    }
+
+    /// Remove the group called `name`, even if it still has items.
+    ///
+    /// The default implementation reports the operation as unsupported.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if no such group exists or the COM call fails.
+    fn remove_group_by_name(&self, _name: &str) -> OpcResult<()> {
+        Err(OpcError::NotImplemented("group removal by name".into()))
+    }
+
+    /// Names of the server's public groups.
+    ///
+    /// The default implementation reports the operation as unsupported, as do
+    /// servers without `IOPCServerPublicGroups`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the COM `CreateGroupEnumerator` call fails.
+    fn public_group_names(&self) -> OpcResult<Vec<String>> {
+        Err(OpcError::NotImplemented("public groups".into()))
+    }
+
+    /// Attach to the public group called `name`. Dropping the returned
+    /// group detaches again; the group itself stays on the server.
+    ///
+    /// The default implementation reports the operation as unsupported.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if no such public group exists or the COM call fails.
+    fn get_public_group(&self, _name: &str) -> OpcResult<Self::Group> {
+        Err(OpcError::NotImplemented("public groups".into()))
+    }
+
+    /// Engineering-unit item properties (unit label and range) of `item_id`.
+    ///
+    /// Properties the server does not report are `None`. The default
+    /// implementation reports the operation as unsupported.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the COM `GetItemProperties` call fails.
+    fn engineering_units(&self, _item_id: &str) -> OpcResult<crate::provider::EngineeringUnits> {
+        Err(OpcError::NotImplemented("item properties".into()))
+    }
+
+    /// Fetch the server's current status.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the COM `GetStatus` call fails. The default
+    /// implementation reports [`OpcError::NotImplemented`].
+    fn status(&self) -> OpcResult<crate::provider::ServerStatus> {
+        Err(OpcError::NotImplemented(
+            "GetStatus not supported".to_string(),
+        ))
+    }
+
+    /// Names of the optional COM interfaces this server exposes.
+    fn supported_interfaces(&self) -> Vec<&'static str> {
+        Vec::new()
+    }
+
+    /// Read `item_ids` directly with `IOPCItemIO::Read` (DA 3.0), without
+    /// creating a group, accepting cached values up to `max_age`
+    /// milliseconds old. Returns one state and one error per item, in order;
+    /// the states carry no client handle.
+    ///
+    /// The default implementation reports the operation as unsupported, as
+    /// do servers without `IOPCItemIO` or that answer `E_NOTIMPL`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the COM `Read` call fails.
+    fn read_item_io(
+        &self,
+        _item_ids: &[String],
+        _max_age: u32,
+    ) -> OpcResult<(Vec<tagOPCITEMSTATE>, Vec<windows::core::HRESULT>)> {
+        Err(OpcError::NotImplemented("IOPCItemIO".into()))
+    }
+}
+
+/// Access rights and canonical data type of an item in a group, as reported
+/// by the group's item attribute enumerator.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ItemAttributeInfo {
+    /// `OPC_READABLE` / `OPC_WRITEABLE` bits.
+    pub access_rights: u32,
+    /// `VARTYPE` the server holds the item in.
+    pub canonical_data_type: u16,
+}
+
+/// An item already defined in a group, as reported by the group's item
+/// attribute enumerator.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GroupItem {
+    /// Fully qualified item ID.
+    pub item_id: String,
+    /// Server handle of the item within the group.
+    pub server_handle: ItemHandle,
+    /// Access rights and canonical data type.
+    pub attributes: ItemAttributeInfo,
 }
 
 /// Facade over an OPC DA group for item management and I/O.
@@ -148,6 +281,28 @@ pub trait ConnectedGroup {
         RemoteArray<windows::core::HRESULT>,
     )>;
 
+    /// Read the given server handles, accepting cached values up to
+    /// `max_age` milliseconds old ([`MAX_AGE_DEVICE`] forces a device read,
+    /// [`MAX_AGE_CACHE`] always reads the cache).
+    ///
+    /// The default implementation goes through [`read`](Self::read): a
+    /// `max_age` of 0 reads from the device, anything else from the cache.
+    /// Groups on DA 3.0 servers override it with `IOPCSyncIO2::ReadMaxAge`,
+    /// which also lets the server apply its own timeout. `ReadMaxAge` reports
+    /// no client handles, so its states carry an `hClient` of 0 and must be
+    /// matched to `server_handles` by position.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the COM read call fails.
+    fn read_with_max_age(
+        &self,
+        server_handles: &[ItemHandle],
+        max_age: u32,
+    ) -> OpcResult<(Vec<tagOPCITEMSTATE>, Vec<windows::core::HRESULT>)> {
+        read_through_sync_io(self, server_handles, max_age)
+    }
+
     /// Write values to the given server handles.
     ///
     /// # Errors
@@ -158,6 +313,129 @@ pub trait ConnectedGroup {
         server_handles: &[ItemHandle],
         values: &[VARIANT],
     ) -> OpcResult<RemoteArray<windows::core::HRESULT>>;
+
+    /// Attributes of the given server handles, one entry per handle; `None`
+    /// for a handle the group does not report.
+    ///
+    /// The default implementation reports the operation as unsupported, as do
+    /// servers without an item attribute enumerator (some DA 1.0 servers).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the group cannot enumerate its item attributes.
+    fn get_item_attributes(
+        &self,
+        _server_handles: &[ItemHandle],
+    ) -> OpcResult<Vec<Option<ItemAttributeInfo>>> {
+        Err(OpcError::NotImplemented("item attributes".into()))
+    }
+
+    /// Every item defined in the group, e.g. the configured items of a
+    /// public group.
+    ///
+    /// The default implementation reports the operation as unsupported.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the group cannot enumerate its item attributes.
+    fn items(&self) -> OpcResult<Vec<GroupItem>> {
+        Err(OpcError::NotImplemented("item enumeration".into()))
+    }
+
+    /// Activate or deactivate the given server handles, returning one
+    /// `HRESULT` per handle. An inactive item is left out of the group's
+    /// cache updates.
+    ///
+    /// The default implementation reports the operation as unsupported.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the COM `SetActiveState` call fails.
+    fn set_active_state(
+        &self,
+        _server_handles: &[ItemHandle],
+        _active: bool,
+    ) -> OpcResult<Vec<windows::core::HRESULT>> {
+        Err(OpcError::NotImplemented("item active state".into()))
+    }
+
+    /// Check the given item definitions without adding them, returning one
+    /// `HRESULT` per item.
+    ///
+    /// The default implementation reports the operation as unsupported.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the COM `ValidateItems` call fails.
+    fn validate_items(&self, _items: &[tagOPCITEMDEF]) -> OpcResult<Vec<windows::core::HRESULT>> {
+        Err(OpcError::NotImplemented("item validation".into()))
+    }
+
+    /// Server handle of this group, as passed to
+    /// [`ConnectedServer::remove_group`].
+    ///
+    /// The default implementation reports the operation as unsupported.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the COM `GetState` call fails.
+    fn server_handle(&self) -> OpcResult<GroupHandle> {
+        Err(OpcError::NotImplemented("group state".into()))
+    }
+}
+
+/// Data source `IOPCSyncIO::Read` uses to honour `max_age`.
+const fn source_for_max_age(max_age: u32) -> crate::bindings::da::tagOPCDATASOURCE {
+    if max_age == MAX_AGE_DEVICE {
+        crate::bindings::da::OPC_DS_DEVICE
+    } else {
+        crate::bindings::da::OPC_DS_CACHE
+    }
+}
+
+/// The default [`ConnectedGroup::read_with_max_age`]: a `max_age` of 0
+/// reads `server_handles` from the device, anything else from the cache.
+pub(crate) fn read_through_sync_io<G: ConnectedGroup + ?Sized>(
+    group: &G,
+    server_handles: &[ItemHandle],
+    max_age: u32,
+) -> OpcResult<(Vec<tagOPCITEMSTATE>, Vec<windows::core::HRESULT>)> {
+    let (states, errors) = group.read(source_for_max_age(max_age), server_handles)?;
+    Ok((states.as_slice().to_vec(), errors.as_slice().to_vec()))
+}
+
+/// Item states from the parallel arrays `IOPCSyncIO2::ReadMaxAge` returns.
+///
+/// `ReadMaxAge` reports no client handles, so every `hClient` is 0.
+pub(crate) fn max_age_states(
+    values: &[VARIANT],
+    qualities: &[u16],
+    timestamps: &[windows::Win32::Foundation::FILETIME],
+) -> Vec<tagOPCITEMSTATE> {
+    values
+        .iter()
+        .zip(qualities)
+        .zip(timestamps)
+        .map(|((value, &quality), &timestamp)| tagOPCITEMSTATE {
+            hClient: 0,
+            ftTimeStamp: timestamp,
+            wQuality: quality,
+            wReserved: 0,
+            vDataValue: VARIANT {
+                Anonymous: value.Anonymous.clone(),
+            },
+        })
+        .collect()
+}
+
+/// Servers that declare an interface but do not implement it answer
+/// `E_NOTIMPL`; report that as [`OpcError::NotImplemented`] for `feature`.
+fn not_implemented_if_e_notimpl(err: OpcError, feature: &str) -> OpcError {
+    if err.hresult() == Some(windows::Win32::Foundation::E_NOTIMPL) {
+        OpcError::NotImplemented(format!("{feature} (E_NOTIMPL)"))
+    } else {
+        err
+    }
 }
 
 // ── COM-backed implementations ──────────────────────────────────────
@@ -172,9 +450,12 @@ impl ServerConnector for ComConnector {
 
     fn enumerate_servers(&self) -> OpcResult<Vec<String>> {
         let client = crate::opc_da::client::v2::Client;
-        let guid_iter = client
-            .get_servers()
-            .context("Failed to enumerate OPC DA servers from registry")?;
+        let guid_iter = client.get_servers().map_err(|e| match e {
+            OpcError::Com { source } => {
+                OpcError::from_com_error(source, "Failed to enumerate OPC DA servers from registry")
+            }
+            other => other,
+        })?;
 
         let mut servers = Vec::new();
         for guid in guid_iter.flatten() {
@@ -209,8 +490,13 @@ impl ServerConnector for ComConnector {
             item_properties: unknown.cast()?,
             server_public_groups: unknown.cast().ok(),
             browse_server_address_space: unknown.cast().ok(),
+            item_io: unknown.cast().ok(),
         })
     }
+
+    fn diagnose(&self, server_name: &str) -> Option<ConnectionDiagnostics> {
+        Some(crate::helpers::diagnose_server_registration(server_name))
+    }
 }
 
 /// COM-backed [`ConnectedServer`].
@@ -222,6 +508,7 @@ pub struct ComServer {
     pub(crate) server_public_groups: Option<crate::bindings::da::IOPCServerPublicGroups>,
     pub(crate) browse_server_address_space:
         Option<crate::bindings::da::IOPCBrowseServerAddressSpace>,
+    pub(crate) item_io: Option<crate::bindings::da::IOPCItemIO>,
 }
 
 impl ServerTrait<ComGroup> for ComServer {
@@ -264,6 +551,14 @@ impl BrowseServerAddressSpaceTrait for ComServer {
     }
 }
 
+impl ItemIoTrait for ComServer {
+    fn interface(&self) -> OpcResult<&crate::bindings::da::IOPCItemIO> {
+        self.item_io
+            .as_ref()
+            .ok_or_else(|| OpcError::NotImplemented("IOPCItemIO not supported".to_string()))
+    }
+}
+
 impl ConnectedServer for ComServer {
     type Group = ComGroup;
 
@@ -329,6 +624,139 @@ impl ConnectedServer for ComServer {
     fn remove_group(&self, server_group: GroupHandle, force: bool) -> OpcResult<()> {
         ServerTrait::remove_group(self, server_group, force)
     }
+
+    fn group_names(&self) -> OpcResult<Vec<String>> {
+        ServerTrait::create_group_name_enumerator(self, crate::bindings::da::OPC_ENUM_ALL)?
+            .collect()
+    }
+
+    fn remove_group_by_name(&self, name: &str) -> OpcResult<()> {
+        let group = ServerTrait::get_group_by_name(self, name)?;
+        let state = GroupStateMgtTrait::get_state(&group)?;
+        ServerTrait::remove_group(self, state.server_handle, true)
+    }
+
+    fn public_group_names(&self) -> OpcResult<Vec<String>> {
+        if self.server_public_groups.is_none() {
+            return Err(OpcError::NotImplemented(
+                "IOPCServerPublicGroups not supported".to_string(),
+            ));
+        }
+        ServerTrait::create_group_name_enumerator(self, crate::bindings::da::OPC_ENUM_PUBLIC)
+            .and_then(|names| names.collect())
+            .map_err(|e| not_implemented_if_e_notimpl(e, "public groups"))
+    }
+
+    fn get_public_group(&self, name: &str) -> OpcResult<Self::Group> {
+        let unknown = ServerPublicGroupsTrait::get_public_group_by_name(
+            self,
+            name,
+            &windows::core::IUnknown::IID,
+        )
+        .map_err(|e| not_implemented_if_e_notimpl(e, "public groups"))?;
+        unknown
+            .try_into()
+            .map_err(|source| OpcError::Com { source })
+    }
+
+    fn status(&self) -> OpcResult<crate::provider::ServerStatus> {
+        let status_ptr = ServerTrait::get_status(self)?;
+        let status = status_ptr.ok()?;
+
+        let state = match status.dwServerState.0 {
+            1 => "Running".to_string(),
+            2 => "Failed".to_string(),
+            3 => "No configuration".to_string(),
+            4 => "Suspended".to_string(),
+            5 => "Test".to_string(),
+            6 => "Communication fault".to_string(),
+            other => format!("Unknown({other})"),
+        };
+        let vendor_info = if status.szVendorInfo.is_null() {
+            String::new()
+        } else {
+            // SAFETY: `szVendorInfo` is a null-terminated string allocated by the
+            // server as part of the status block, which `status` keeps alive here.
+            unsafe { status.szVendorInfo.to_string() }.unwrap_or_default()
+        };
+        if !status.szVendorInfo.is_null() {
+            // SAFETY: The server allocates `szVendorInfo` separately with the COM
+            // allocator and the caller owns it; it is not used after this point.
+            unsafe {
+                windows::Win32::System::Com::CoTaskMemFree(Some(
+                    status.szVendorInfo.as_ptr().cast_const().cast(),
+                ));
+            }
+        }
+
+        Ok(crate::provider::ServerStatus {
+            state,
+            vendor_info,
+            version: format!(
+                "{}.{}.{}",
+                status.wMajorVersion, status.wMinorVersion, status.wBuildNumber
+            ),
+            start_time: crate::helpers::filetime_to_string(status.ftStartTime),
+            current_time: crate::helpers::filetime_to_string(status.ftCurrentTime),
+            group_count: status.dwGroupCount,
+        })
+    }
+
+    fn engineering_units(&self, item_id: &str) -> OpcResult<crate::provider::EngineeringUnits> {
+        let (values, errors) =
+            self.get_item_properties(item_id, &crate::provider::EngineeringUnits::PROPERTIES)?;
+        Ok(crate::helpers::engineering_units_from_properties(
+            values.as_slice(),
+            errors.as_slice(),
+        ))
+    }
+
+    fn supported_interfaces(&self) -> Vec<&'static str> {
+        let mut interfaces = vec!["IOPCServer", "IOPCCommon", "IOPCItemProperties"];
+        if self.browse_server_address_space.is_some() {
+            interfaces.push("IOPCBrowseServerAddressSpace");
+        }
+        if self.server_public_groups.is_some() {
+            interfaces.push("IOPCServerPublicGroups");
+        }
+        if self
+            .server
+            .cast::<crate::bindings::da::IOPCBrowse>()
+            .is_ok()
+        {
+            interfaces.push("IOPCBrowse");
+        }
+        if self.item_io.is_some() {
+            interfaces.push("IOPCItemIO");
+        }
+        interfaces
+    }
+
+    fn read_item_io(
+        &self,
+        item_ids: &[String],
+        max_age: u32,
+    ) -> OpcResult<(Vec<tagOPCITEMSTATE>, Vec<windows::core::HRESULT>)> {
+        let max_ages = vec![max_age; item_ids.len()];
+        let (values, qualities, timestamps, errors) = ItemIoTrait::read(self, item_ids, &max_ages)
+            .map_err(|e| not_implemented_if_e_notimpl(e, "IOPCItemIO"))?;
+        let states = values
+            .as_slice()
+            .iter()
+            .zip(qualities.as_slice())
+            .zip(timestamps.as_slice())
+            .map(|((value, &quality), &timestamp)| tagOPCITEMSTATE {
+                hClient: 0,
+                ftTimeStamp: timestamp,
+                wQuality: quality,
+                wReserved: 0,
+                vDataValue: VARIANT {
+                    Anonymous: value.Anonymous.clone(),
+                },
+            })
+            .collect();
+        Ok((states, errors.as_slice().to_vec()))
+    }
 }
 
 pub struct ComGroup {
@@ -336,6 +764,7 @@ pub struct ComGroup {
     pub(crate) group_state_mgt: crate::bindings::da::IOPCGroupStateMgt,
     pub(crate) public_group_state_mgt: Option<crate::bindings::da::IOPCPublicGroupStateMgt>,
     pub(crate) sync_io: crate::bindings::da::IOPCSyncIO,
+    pub(crate) sync_io2: Option<crate::bindings::da::IOPCSyncIO2>,
     pub(crate) async_io: Option<crate::bindings::da::IOPCAsyncIO>,
     pub(crate) async_io2: crate::bindings::da::IOPCAsyncIO2,
     pub(crate) connection_point_container: windows::Win32::System::Com::IConnectionPointContainer,
@@ -368,6 +797,14 @@ impl SyncIoTrait for ComGroup {
     }
 }
 
+impl SyncIo2Trait for ComGroup {
+    fn interface(&self) -> OpcResult<&crate::bindings::da::IOPCSyncIO2> {
+        self.sync_io2
+            .as_ref()
+            .ok_or_else(|| OpcError::NotImplemented("IOPCSyncIO2 not supported".to_string()))
+    }
+}
+
 impl AsyncIoTrait for ComGroup {
     fn interface(&self) -> OpcResult<&crate::bindings::da::IOPCAsyncIO> {
         self.async_io
@@ -418,6 +855,26 @@ impl ConnectedGroup for ComGroup {
         SyncIoTrait::read(self, source, server_handles)
     }
 
+    fn read_with_max_age(
+        &self,
+        server_handles: &[ItemHandle],
+        max_age: u32,
+    ) -> OpcResult<(Vec<tagOPCITEMSTATE>, Vec<windows::core::HRESULT>)> {
+        if self.sync_io2.is_none() {
+            return read_through_sync_io(self, server_handles, max_age);
+        }
+
+        let max_ages = vec![max_age; server_handles.len()];
+        let (values, qualities, timestamps, errors) =
+            self.read_max_age(server_handles, &max_ages)?;
+        let states = max_age_states(
+            values.as_slice(),
+            qualities.as_slice(),
+            timestamps.as_slice(),
+        );
+        Ok((states, errors.as_slice().to_vec()))
+    }
+
     fn write(
         &self,
         server_handles: &[ItemHandle],
@@ -425,6 +882,63 @@ impl ConnectedGroup for ComGroup {
     ) -> OpcResult<RemoteArray<windows::core::HRESULT>> {
         SyncIoTrait::write(self, server_handles, values)
     }
+
+    fn get_item_attributes(
+        &self,
+        server_handles: &[ItemHandle],
+    ) -> OpcResult<Vec<Option<ItemAttributeInfo>>> {
+        let mut by_handle = std::collections::HashMap::new();
+        for attributes in ItemMgtTrait::create_enumerator(self)? {
+            let attributes = attributes?;
+            by_handle.insert(
+                attributes.server_handle,
+                ItemAttributeInfo {
+                    access_rights: attributes.access_rights,
+                    canonical_data_type: attributes.canonical_data_type,
+                },
+            );
+        }
+        Ok(server_handles
+            .iter()
+            .map(|handle| by_handle.get(handle).copied())
+            .collect())
+    }
+
+    fn items(&self) -> OpcResult<Vec<GroupItem>> {
+        ItemMgtTrait::create_enumerator(self)?
+            .map(|attributes| {
+                let attributes = attributes?;
+                Ok(GroupItem {
+                    item_id: attributes.item_id,
+                    server_handle: attributes.server_handle,
+                    attributes: ItemAttributeInfo {
+                        access_rights: attributes.access_rights,
+                        canonical_data_type: attributes.canonical_data_type,
+                    },
+                })
+            })
+            .collect()
+    }
+
+    fn set_active_state(
+        &self,
+        server_handles: &[ItemHandle],
+        active: bool,
+    ) -> OpcResult<Vec<windows::core::HRESULT>> {
+        ItemMgtTrait::set_active_state(self, server_handles, active)
+            .map(|errors| errors.as_slice().to_vec())
+            .map_err(|e| not_implemented_if_e_notimpl(e, "item active state"))
+    }
+
+    fn validate_items(&self, items: &[tagOPCITEMDEF]) -> OpcResult<Vec<windows::core::HRESULT>> {
+        ItemMgtTrait::validate_items(self, items, false)
+            .map(|(_, errors)| errors.as_slice().to_vec())
+            .map_err(|e| not_implemented_if_e_notimpl(e, "item validation"))
+    }
+
+    fn server_handle(&self) -> OpcResult<GroupHandle> {
+        GroupStateMgtTrait::get_state(self).map(|state| state.server_handle)
+    }
 }
 
 impl TryFrom<windows::core::IUnknown> for ComGroup {
@@ -436,6 +950,7 @@ impl TryFrom<windows::core::IUnknown> for ComGroup {
             group_state_mgt: unknown.cast()?,
             public_group_state_mgt: unknown.cast().ok(),
             sync_io: unknown.cast()?,
+            sync_io2: unknown.cast().ok(),
             async_io: unknown.cast().ok(),
             async_io2: unknown.cast()?,
             connection_point_container: unknown.cast()?,
@@ -1,16 +1,41 @@
 use crate::backend::connector::{ComConnector, ServerConnector};
 use crate::com_worker::{ComRequest, ComWorker};
-use crate::opc_da::errors::OpcResult;
-use crate::provider::{OpcProvider, OpcValue, TagValue, WriteResult};
+use crate::config::OpcDaConfig;
+use crate::opc_da::errors::{OpcError, OpcResult};
+use crate::provider::{
+    AccessRightsFilter, BrowseProgress, BrowsedTag, EngineeringUnits, OpcProvider, OpcValue,
+    ServerMetadata, TagValue, WriteResult,
+};
 use async_trait::async_trait;
+use std::collections::VecDeque;
 use std::sync::Arc;
-use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
+use tokio::sync::oneshot;
+
+/// Longest a caller waits for a read reply. A hung device can block the COM
+/// worker indefinitely; this keeps the caller from hanging with it.
+const READ_TIMEOUT: Duration = Duration::from_secs(30);
 
 /// Concrete [`OpcProvider`] implementation for Windows OPC DA.
 ///
 /// Uses native `windows-rs` COM interop via the internal `opc_da` module.
+///
+/// Cloning is cheap: clones share the same COM worker thread, connector and
+/// connection cache, so a clone can be moved into a spawned task. The worker
+/// shuts down when the last clone is dropped.
 pub struct OpcDaClient<C: ServerConnector + 'static = ComConnector> {
-    pub worker: ComWorker<C>,
+    pub worker: Arc<ComWorker<C>>,
+    config: OpcDaConfig,
+}
+
+impl<C: ServerConnector + 'static> Clone for OpcDaClient<C> {
+    fn clone(&self) -> Self {
+        Self {
+            worker: Arc::clone(&self.worker),
+            config: self.config.clone(),
+        }
+    }
 }
 
 /// Returns the default `OpcDaClient` using native COM settings.
@@ -29,18 +54,104 @@ impl Default for OpcDaClient<ComConnector> {
 impl<C: ServerConnector + 'static> OpcDaClient<C> {
     /// Creates a new `OpcDaClient` with the given connector.
     pub fn new(connector: C) -> OpcResult<Self> {
-        tracing::info!("Initializing OpcDaClient...");
-        let worker = ComWorker::start(Arc::new(connector))?;
+        Self::with_config(connector, OpcDaConfig::default())
+    }
+
+    /// Creates a new `OpcDaClient` with the given connector and configuration.
+    pub fn with_config(connector: C, config: OpcDaConfig) -> OpcResult<Self> {
+        tracing::info!(?config, "Initializing OpcDaClient...");
+        let worker = ComWorker::start_with_config(Arc::new(connector), config.clone())?;
         tracing::info!("OpcDaClient initialized successfully");
-        Ok(Self { worker })
+        Ok(Self {
+            worker: Arc::new(worker),
+            config,
+        })
+    }
+}
+
+/// Read `tag_ids` in chunks of at most `chunk_size` tags, keeping up to
+/// `max_in_flight` chunk requests queued on the COM worker.
+///
+/// `submit` queues one chunk and returns the receiver for its values. Each
+/// chunk is read through its own OPC group; the results are concatenated in
+/// chunk order so they line up with `tag_ids`. `progress` is advanced by the
+/// size of each chunk as it is read.
+async fn read_in_chunks<F, Fut>(
+    tag_ids: Vec<String>,
+    chunk_size: usize,
+    max_in_flight: usize,
+    progress: &AtomicUsize,
+    mut submit: F,
+) -> OpcResult<Vec<TagValue>>
+where
+    F: FnMut(Vec<String>) -> Fut,
+    Fut: Future<Output = OpcResult<oneshot::Receiver<OpcResult<Vec<TagValue>>>>>,
+{
+    let chunk_size = chunk_size.max(1);
+    let max_in_flight = max_in_flight.max(1);
+    let mut values = Vec::with_capacity(tag_ids.len());
+    let mut in_flight = VecDeque::with_capacity(max_in_flight);
+
+    if tag_ids.len() > chunk_size {
+        tracing::debug!(
+            tag_count = tag_ids.len(),
+            chunk_size,
+            chunks = tag_ids.len().div_ceil(chunk_size),
+            "read_tag_values: splitting into chunks"
+        );
+    }
+
+    for chunk in tag_ids.chunks(chunk_size) {
+        if in_flight.len() == max_in_flight
+            && let Some((expected, rx)) = in_flight.pop_front()
+        {
+            values.extend(await_chunk(expected, rx).await?);
+            progress.fetch_add(expected, Ordering::Relaxed);
+        }
+        in_flight.push_back((chunk.len(), submit(chunk.to_vec()).await?));
+    }
+    while let Some((expected, rx)) = in_flight.pop_front() {
+        values.extend(await_chunk(expected, rx).await?);
+        progress.fetch_add(expected, Ordering::Relaxed);
+    }
+
+    Ok(values)
+}
+
+/// Await `read`, failing with an error once [`READ_TIMEOUT`] elapses.
+async fn with_read_timeout<T>(read: impl Future<Output = OpcResult<T>>) -> OpcResult<T> {
+    tokio::time::timeout(READ_TIMEOUT, read)
+        .await
+        .map_err(|_| {
+            tracing::error!(timeout_secs = READ_TIMEOUT.as_secs(), "Read timed out");
+            OpcError::Timeout(format!("read after {}s", READ_TIMEOUT.as_secs()))
+        })?
+}
+
+/// Wait for one chunk, checking it returned a value for every tag it was given.
+async fn await_chunk(
+    expected: usize,
+    rx: oneshot::Receiver<OpcResult<Vec<TagValue>>>,
+) -> OpcResult<Vec<TagValue>> {
+    let values = with_read_timeout(async {
+        rx.await
+            .map_err(|_| OpcError::Internal("COM worker shut down during request".into()))?
+    })
+    .await?;
+    if values.len() != expected {
+        return Err(OpcError::Internal(format!(
+            "Read chunk returned {} values for {expected} tags",
+            values.len()
+        )));
     }
+    Ok(values)
 }
 
 #[allow(clippy::too_many_lines)]
 #[async_trait]
 impl<C: ServerConnector + 'static> OpcProvider for OpcDaClient<C> {
     async fn list_servers(&self, host: &str) -> OpcResult<Vec<String>> {
-        let host_owned = host.to_string();
+        let host_owned = crate::helpers::normalize_host(host);
         self.worker
             .send_request(|reply| ComRequest::ListServers {
                 host: host_owned,
@@ -53,16 +164,16 @@ impl<C: ServerConnector + 'static> OpcProvider for OpcDaClient<C> {
         &self,
         server: &str,
         max_tags: usize,
-        progress: Arc<AtomicUsize>,
-        tags_sink: Arc<std::sync::Mutex<Vec<String>>>,
-    ) -> OpcResult<Vec<String>> {
+        access: AccessRightsFilter,
+        progress: BrowseProgress,
+    ) -> OpcResult<Vec<BrowsedTag>> {
         let server_owned = server.to_string();
         self.worker
             .send_request(|reply| ComRequest::BrowseTags {
                 server: server_owned,
                 max_tags,
+                access,
                 progress,
-                tags_sink,
                 reply,
             })
             .await
@@ -72,15 +183,24 @@ impl<C: ServerConnector + 'static> OpcProvider for OpcDaClient<C> {
         &self,
         server: &str,
         tag_ids: Vec<String>,
+        progress: Arc<AtomicUsize>,
     ) -> OpcResult<Vec<TagValue>> {
         let server_owned = server.to_string();
-        self.worker
-            .send_request(|reply| ComRequest::ReadTagValues {
-                server: server_owned,
-                tag_ids,
-                reply,
-            })
-            .await
+        read_in_chunks(
+            tag_ids,
+            self.config.max_items_per_group,
+            self.config.max_queued_reads,
+            &progress,
+            move |chunk| {
+                let server = server_owned.clone();
+                self.worker.submit(move |reply| ComRequest::ReadTagValues {
+                    server,
+                    tag_ids: chunk,
+                    reply,
+                })
+            },
+        )
+        .await
     }
 
     async fn write_tag_value(
@@ -100,4 +220,307 @@ impl<C: ServerConnector + 'static> OpcProvider for OpcDaClient<C> {
             })
             .await
     }
+
+    async fn write_tag_values(
+        &self,
+        server: &str,
+        writes: Vec<(String, OpcValue)>,
+    ) -> OpcResult<Vec<WriteResult>> {
+        let server_owned = server.to_string();
+        self.worker
+            .send_request(|reply| ComRequest::WriteTagValues {
+                server: server_owned,
+                writes,
+                reply,
+            })
+            .await
+    }
+
+    async fn get_item_data_types(
+        &self,
+        server: &str,
+        tag_ids: Vec<String>,
+    ) -> OpcResult<Vec<Option<u16>>> {
+        let server_owned = server.to_string();
+        self.worker
+            .send_request(|reply| ComRequest::GetItemDataTypes {
+                server: server_owned,
+                tag_ids,
+                reply,
+            })
+            .await
+    }
+
+    async fn get_engineering_units(
+        &self,
+        server: &str,
+        tag_ids: Vec<String>,
+    ) -> OpcResult<Vec<Option<EngineeringUnits>>> {
+        let server_owned = server.to_string();
+        self.worker
+            .send_request(|reply| ComRequest::GetEngineeringUnits {
+                server: server_owned,
+                tag_ids,
+                reply,
+            })
+            .await
+    }
+
+    async fn get_server_metadata(&self, server: &str) -> OpcResult<ServerMetadata> {
+        let server_owned = server.to_string();
+        self.worker
+            .send_request(|reply| ComRequest::GetServerMetadata {
+                server: server_owned,
+                reply,
+            })
+            .await
+    }
+
+    async fn connect_with_diagnostics(&self, server: &str) -> OpcResult<ServerMetadata> {
+        let server_owned = server.to_string();
+        self.worker
+            .send_request(|reply| ComRequest::ConnectWithDiagnostics {
+                server: server_owned,
+                reply,
+            })
+            .await
+    }
+
+    async fn read_tag_values_live(
+        &self,
+        server: &str,
+        tag_ids: Vec<String>,
+    ) -> OpcResult<Vec<TagValue>> {
+        let server_owned = server.to_string();
+        with_read_timeout(
+            self.worker
+                .send_request(|reply| ComRequest::ReadTagValuesLive {
+                    server: server_owned,
+                    tag_ids,
+                    reply,
+                }),
+        )
+        .await
+    }
+
+    async fn stop_live_reads(&self, server: &str) -> OpcResult<()> {
+        let server_owned = server.to_string();
+        self.worker
+            .send_request(|reply| ComRequest::StopLiveReads {
+                server: server_owned,
+                reply,
+            })
+            .await
+    }
+
+    async fn set_items_active(
+        &self,
+        server: &str,
+        tag_ids: Vec<String>,
+        active: bool,
+    ) -> OpcResult<usize> {
+        let server_owned = server.to_string();
+        self.worker
+            .send_request(|reply| ComRequest::SetItemsActive {
+                server: server_owned,
+                tag_ids,
+                active,
+                reply,
+            })
+            .await
+    }
+
+    async fn cleanup_orphaned_groups(&self, server: &str) -> OpcResult<usize> {
+        let server_owned = server.to_string();
+        self.worker
+            .send_request(|reply| ComRequest::CleanupOrphanedGroups {
+                server: server_owned,
+                reply,
+            })
+            .await
+    }
+
+    async fn list_public_groups(&self, server: &str) -> OpcResult<Vec<String>> {
+        let server_owned = server.to_string();
+        self.worker
+            .send_request(|reply| ComRequest::ListPublicGroups {
+                server: server_owned,
+                reply,
+            })
+            .await
+    }
+
+    async fn read_public_group(&self, server: &str, group: &str) -> OpcResult<Vec<TagValue>> {
+        let server_owned = server.to_string();
+        let group_owned = group.to_string();
+        self.worker
+            .send_request(|reply| ComRequest::ReadPublicGroup {
+                server: server_owned,
+                group: group_owned,
+                reply,
+            })
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::provider::ReadStatus;
+    use std::sync::Mutex;
+
+    /// Reads that answer each tag with its index in the original request,
+    /// recording the size of every chunk submitted.
+    async fn indexed_read(
+        tag_count: usize,
+        chunk_size: usize,
+        max_in_flight: usize,
+    ) -> (OpcResult<Vec<TagValue>>, Vec<usize>) {
+        let tag_ids: Vec<String> = (0..tag_count).map(|i| format!("Tag{i}")).collect();
+        let chunk_sizes = Mutex::new(Vec::new());
+
+        let progress = AtomicUsize::new(0);
+        let result = read_in_chunks(tag_ids, chunk_size, max_in_flight, &progress, |chunk| {
+            chunk_sizes.lock().unwrap().push(chunk.len());
+            async move {
+                let (tx, rx) = oneshot::channel();
+                let values = chunk
+                    .into_iter()
+                    .map(|tag_id| TagValue {
+                        value: tag_id.trim_start_matches("Tag").to_string(),
+                        tag_id,
+                        quality: "Good".into(),
+                        timestamp: String::new(),
+                        raw_timestamp: None,
+                        vt: None,
+                        access_rights: 0,
+                        canonical_data_type: 0,
+                        status: ReadStatus::Ok,
+                    })
+                    .collect();
+                let _ = tx.send(Ok(values));
+                Ok(rx)
+            }
+        })
+        .await;
+
+        (result, chunk_sizes.into_inner().unwrap())
+    }
+
+    /// Lists one server and counts how often it is asked to; never connects.
+    struct CountingConnector {
+        enumerations: Arc<AtomicUsize>,
+    }
+
+    impl ServerConnector for CountingConnector {
+        type Server = <ComConnector as ServerConnector>::Server;
+
+        fn enumerate_servers(&self) -> OpcResult<Vec<String>> {
+            self.enumerations.fetch_add(1, Ordering::SeqCst);
+            Ok(vec!["Vendor.Server.1".to_string()])
+        }
+
+        fn connect(&self, _server_name: &str) -> OpcResult<Self::Server> {
+            Err(OpcError::NotImplemented("counting connector".into()))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_clones_share_one_worker() {
+        let enumerations = Arc::new(AtomicUsize::new(0));
+        let connector = CountingConnector {
+            enumerations: Arc::clone(&enumerations),
+        };
+        let client = tokio::task::spawn_blocking(move || OpcDaClient::new(connector).unwrap())
+            .await
+            .unwrap();
+        let clone = client.clone();
+        assert!(Arc::ptr_eq(&client.worker, &clone.worker));
+
+        let task = tokio::spawn(async move { clone.list_servers("localhost").await });
+        let (ours, theirs) = tokio::join!(client.list_servers("localhost"), task);
+        assert_eq!(ours.unwrap(), theirs.unwrap().unwrap());
+        // Both requests reached the single shared connector
+        assert_eq!(enumerations.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_read_in_chunks_preserves_order() {
+        let (result, chunk_sizes) = indexed_read(250, 100, 4).await;
+
+        assert_eq!(chunk_sizes, vec![100, 100, 50]);
+        let values = result.unwrap();
+        assert_eq!(values.len(), 250);
+        for (i, tv) in values.iter().enumerate() {
+            assert_eq!(tv.tag_id, format!("Tag{i}"));
+            assert_eq!(tv.value, i.to_string());
+        }
+    }
+
+    #[tokio::test]
+    async fn test_read_in_chunks_single_in_flight() {
+        let (result, chunk_sizes) = indexed_read(250, 100, 1).await;
+
+        assert_eq!(chunk_sizes, vec![100, 100, 50]);
+        assert_eq!(result.unwrap().len(), 250);
+    }
+
+    #[tokio::test]
+    async fn test_read_in_chunks_small_read_is_one_chunk() {
+        let (result, chunk_sizes) = indexed_read(3, 100, 4).await;
+
+        assert_eq!(chunk_sizes, vec![3]);
+        assert_eq!(result.unwrap().len(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_read_in_chunks_rejects_short_chunk() {
+        let tag_ids: Vec<String> = (0..5).map(|i| format!("Tag{i}")).collect();
+        let progress = AtomicUsize::new(0);
+        let result = read_in_chunks(tag_ids, 2, 4, &progress, |_chunk| async {
+            let (tx, rx) = oneshot::channel();
+            let _ = tx.send(Ok(Vec::new()));
+            Ok(rx)
+        })
+        .await;
+
+        assert!(matches!(result, Err(OpcError::Internal(_))));
+        assert_eq!(progress.load(Ordering::Relaxed), 0);
+    }
+
+    #[tokio::test]
+    async fn test_read_in_chunks_reports_progress() {
+        let tag_ids: Vec<String> = (0..250).map(|i| format!("Tag{i}")).collect();
+        let progress = AtomicUsize::new(0);
+        let seen = Mutex::new(Vec::new());
+
+        let result = read_in_chunks(tag_ids, 100, 1, &progress, |chunk| {
+            // With one chunk in flight, each submit follows the previous read
+            seen.lock().unwrap().push(progress.load(Ordering::Relaxed));
+            async move {
+                let (tx, rx) = oneshot::channel();
+                let values = chunk
+                    .into_iter()
+                    .map(|tag_id| TagValue {
+                        tag_id,
+                        value: String::new(),
+                        quality: "Good".into(),
+                        timestamp: String::new(),
+                        raw_timestamp: None,
+                        vt: None,
+                        access_rights: 0,
+                        canonical_data_type: 0,
+                        status: ReadStatus::Ok,
+                    })
+                    .collect();
+                let _ = tx.send(Ok(values));
+                Ok(rx)
+            }
+        })
+        .await;
+
+        assert_eq!(result.unwrap().len(), 250);
+        assert_eq!(seen.into_inner().unwrap(), vec![0, 100, 200]);
+        assert_eq!(progress.load(Ordering::Relaxed), 250);
+    }
 }
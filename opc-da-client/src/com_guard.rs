@@ -3,14 +3,19 @@
 //! Ensures `CoUninitialize` is called exactly once per successful
 //! `CoInitializeEx`, even on early returns or panics.
 
+use crate::config::ComApartment;
 use std::marker::PhantomData;
-use windows::Win32::System::Com::{COINIT_MULTITHREADED, CoInitializeEx, CoUninitialize};
+use windows::Win32::System::Com::{
+    APTTYPE, APTTYPE_MAINSTA, APTTYPE_MTA, APTTYPE_STA, APTTYPEQUALIFIER, COINIT_APARTMENTTHREADED,
+    COINIT_DISABLE_OLE1DDE, COINIT_MULTITHREADED, CoGetApartmentType, CoInitializeEx,
+    CoUninitialize,
+};
 
 /// Drop guard for COM thread initialization.
 ///
 /// Calling [`ComGuard::new`] initializes COM in Multi-Threaded Apartment
-/// (MTA) mode. When the guard is dropped, `CoUninitialize` is called
-/// automatically.
+/// (MTA) mode; [`ComGuard::with_apartment`] chooses the model explicitly.
+/// When the guard is dropped, `CoUninitialize` is called automatically.
 ///
 /// # Thread Safety
 ///
@@ -34,6 +39,7 @@ use windows::Win32::System::Com::{COINIT_MULTITHREADED, CoInitializeEx, CoUninit
 pub struct ComGuard {
     /// Prevents `Send + Sync` auto-derivation. COM init is per-thread.
     _not_send: PhantomData<*mut ()>,
+    apartment: ComApartment,
 }
 
 impl ComGuard {
@@ -46,28 +52,74 @@ impl ComGuard {
     ///
     /// Returns `Err` if `CoInitializeEx` fails with a fatal HRESULT.
     pub fn new() -> anyhow::Result<Self> {
+        Self::with_apartment(ComApartment::MultiThreaded)
+    }
+
+    /// Initialize COM in the given apartment model.
+    ///
+    /// After initialization the thread's actual apartment is queried and
+    /// compared with the requested one, so a thread that already joined a
+    /// different apartment is reported instead of silently running in it.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err` if `CoInitializeEx` fails (including `RPC_E_CHANGED_MODE`
+    /// when the thread was initialized with the other model) or the thread
+    /// ends up in a different apartment than requested.
+    pub fn with_apartment(apartment: ComApartment) -> anyhow::Result<Self> {
+        let coinit = match apartment {
+            ComApartment::MultiThreaded => COINIT_MULTITHREADED,
+            ComApartment::SingleThreaded => COINIT_APARTMENTTHREADED | COINIT_DISABLE_OLE1DDE,
+        };
         // SAFETY: `CoInitializeEx` is a standard Win32 FFI call.
-        // We pass `COINIT_MULTITHREADED` to join the MTA. The result
-        // is checked below, and `CoUninitialize` is guaranteed via Drop.
-        let hr = unsafe { CoInitializeEx(None, COINIT_MULTITHREADED) };
+        // The result is checked below, and `CoUninitialize` is guaranteed
+        // via Drop once the guard is constructed.
+        let hr = unsafe { CoInitializeEx(None, coinit) };
 
         if let Err(e) = hr.ok() {
-            tracing::error!(error = ?e, "COM MTA initialization failed");
-            return Err(anyhow::anyhow!("CoInitializeEx failed: {e}"));
+            tracing::error!(error = ?e, %apartment, "COM initialization failed");
+            return Err(anyhow::anyhow!("CoInitializeEx ({apartment}) failed: {e}"));
         }
 
-        tracing::debug!("COM MTA initialized");
-
-        Ok(Self {
+        // Constructed before validation so a mismatch still balances the
+        // successful `CoInitializeEx` on drop.
+        let guard = Self {
             _not_send: PhantomData,
-        })
+            apartment,
+        };
+
+        let mut apt_type = APTTYPE::default();
+        let mut qualifier = APTTYPEQUALIFIER::default();
+        // SAFETY: Both out-pointers refer to live locals. COM is initialized
+        // on this thread, which `CoGetApartmentType` requires.
+        unsafe { CoGetApartmentType(&raw mut apt_type, &raw mut qualifier) }
+            .map_err(|e| anyhow::anyhow!("CoGetApartmentType failed: {e}"))?;
+        let matches = match apartment {
+            ComApartment::MultiThreaded => apt_type == APTTYPE_MTA,
+            ComApartment::SingleThreaded => apt_type == APTTYPE_STA || apt_type == APTTYPE_MAINSTA,
+        };
+        if !matches {
+            tracing::error!(%apartment, apt_type = apt_type.0, "COM apartment mismatch");
+            return Err(anyhow::anyhow!(
+                "COM apartment mismatch: requested {apartment}, thread is in apartment type {}",
+                apt_type.0
+            ));
+        }
+
+        tracing::debug!(%apartment, "COM initialized");
+        Ok(guard)
+    }
+
+    /// The apartment model this guard initialized.
+    pub const fn apartment(&self) -> ComApartment {
+        self.apartment
     }
 }
 
 impl Drop for ComGuard {
     fn drop(&mut self) {
-        tracing::debug!("COM MTA teardown");
-        // SAFETY: Paired with the successful `CoInitializeEx` in `new()`.
+        tracing::debug!(apartment = %self.apartment, "COM teardown");
+        // SAFETY: Paired with the successful `CoInitializeEx` in `with_apartment()`.
         // Construction guarantees COM was initialized, so this call is
         // always balanced. Only runs on the creating thread (!Send).
         unsafe {
@@ -88,4 +140,16 @@ mod tests {
         assert!(guard.is_ok(), "ComGuard::new() should succeed: {guard:?}");
         // Guard drops here — CoUninitialize runs.
     }
+
+    #[test]
+    fn com_guard_sta_reports_apartment() {
+        let guard = ComGuard::with_apartment(ComApartment::SingleThreaded).unwrap();
+        assert_eq!(guard.apartment(), ComApartment::SingleThreaded);
+    }
+
+    #[test]
+    fn com_guard_rejects_changed_mode() {
+        let _mta = ComGuard::new().unwrap();
+        assert!(ComGuard::with_apartment(ComApartment::SingleThreaded).is_err());
+    }
 }
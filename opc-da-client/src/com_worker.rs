@@ -1,17 +1,26 @@
-use crate::backend::connector::{ConnectedGroup, ConnectedServer, ServerConnector};
+use crate::backend::connector::{
+    ConnectedGroup, ConnectedServer, MAX_AGE_CACHE, MAX_AGE_DEVICE, ServerConnector,
+    read_through_sync_io, tagOPCITEMSTATE,
+};
 use crate::bindings::da::{
-    OPC_BRANCH, OPC_BROWSE_DOWN, OPC_BROWSE_UP, OPC_DS_DEVICE, OPC_FLAT, OPC_LEAF, OPC_NS_FLAT,
-    tagOPCITEMDEF,
+    OPC_BRANCH, OPC_BROWSE_DOWN, OPC_BROWSE_UP, OPC_FLAT, OPC_LEAF, OPC_NS_FLAT, OPC_NS_HIERARCHIAL,
 };
+use crate::config::OpcDaConfig;
 use crate::helpers::{
-    filetime_to_string, format_hresult, opc_value_to_variant, quality_to_string, variant_to_string,
+    ItemDefBuilder, OwnedVariant, VariantFormat, coerce_to_vt, filetime_to_string,
+    filetime_to_system_time, format_hresult, format_variant, normalize_tag_id,
+    opc_value_to_variant, quality_to_string, variant_vt, vt_to_string,
 };
 use crate::opc_da::errors::{OpcError, OpcResult};
 use crate::opc_da::typedefs::{GroupHandle, ItemHandle};
-use crate::provider::{OpcValue, TagValue, WriteResult};
-use std::collections::HashMap;
+use crate::provider::{
+    AccessRightsFilter, BrowseProgress, BrowsedTag, EngineeringUnits, OpcValue, ReadStatus,
+    ServerMetadata, TagValue, WriteResult,
+};
+use std::cell::{Cell, RefCell};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
 use std::sync::Arc;
-use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
 use tokio::sync::{mpsc, oneshot};
 
 pub enum ComRequest {
@@ -30,58 +39,708 @@ pub enum ComRequest {
         value: OpcValue,
         reply: oneshot::Sender<OpcResult<WriteResult>>,
     },
+    WriteTagValues {
+        server: String,
+        writes: Vec<(String, OpcValue)>,
+        reply: oneshot::Sender<OpcResult<Vec<WriteResult>>>,
+    },
     BrowseTags {
         server: String,
         max_tags: usize,
-        progress: Arc<AtomicUsize>,
-        tags_sink: Arc<std::sync::Mutex<Vec<String>>>,
+        access: AccessRightsFilter,
+        progress: BrowseProgress,
+        reply: oneshot::Sender<OpcResult<Vec<BrowsedTag>>>,
+    },
+    GetItemDataTypes {
+        server: String,
+        tag_ids: Vec<String>,
+        reply: oneshot::Sender<OpcResult<Vec<Option<u16>>>>,
+    },
+    GetEngineeringUnits {
+        server: String,
+        tag_ids: Vec<String>,
+        reply: oneshot::Sender<OpcResult<Vec<Option<EngineeringUnits>>>>,
+    },
+    GetServerMetadata {
+        server: String,
+        reply: oneshot::Sender<OpcResult<ServerMetadata>>,
+    },
+    /// Like [`ComRequest::GetServerMetadata`], but a COM connection failure
+    /// comes back as [`OpcError::Diagnosed`] if the connector can diagnose it.
+    ConnectWithDiagnostics {
+        server: String,
+        reply: oneshot::Sender<OpcResult<ServerMetadata>>,
+    },
+    ReadTagValuesLive {
+        server: String,
+        tag_ids: Vec<String>,
+        reply: oneshot::Sender<OpcResult<Vec<TagValue>>>,
+    },
+    StopLiveReads {
+        server: String,
+        reply: oneshot::Sender<OpcResult<()>>,
+    },
+    SetItemsActive {
+        server: String,
+        tag_ids: Vec<String>,
+        active: bool,
+        reply: oneshot::Sender<OpcResult<usize>>,
+    },
+    CleanupOrphanedGroups {
+        server: String,
+        reply: oneshot::Sender<OpcResult<usize>>,
+    },
+    ListPublicGroups {
+        server: String,
         reply: oneshot::Sender<OpcResult<Vec<String>>>,
     },
+    ReadPublicGroup {
+        server: String,
+        group: String,
+        reply: oneshot::Sender<OpcResult<Vec<TagValue>>>,
+    },
+    /// Heartbeat: answered immediately, see [`ComWorker::is_alive`].
+    Ping { reply: oneshot::Sender<()> },
+}
+
+/// Prefix of every group name the worker adds; groups named with it are
+/// removed by [`ComRequest::CleanupOrphanedGroups`].
+pub const GROUP_NAME_PREFIX: &str = "opc-da-client-";
+
+/// Name for a new group of the given `kind` (`read`, `write`, ...).
+///
+/// Some servers reject a second group with a name already in use
+/// (`OPC_E_DUPLICATENAME`), so every group gets a fresh counter value,
+/// along with the process ID to keep clear of groups another client
+/// process, or an earlier run, left behind.
+fn unique_group_name(kind: &str) -> String {
+    static NEXT_GROUP: AtomicU64 = AtomicU64::new(1);
+    let n = NEXT_GROUP.fetch_add(1, Ordering::Relaxed);
+    format!("{GROUP_NAME_PREFIX}{kind}-{}-{n}", std::process::id())
+}
+
+/// A pooled server connection with the metadata captured when it was established.
+///
+/// Replacing the pool entry on reconnect discards stale metadata and any
+/// live-mode group along with the stale connection.
+struct PooledServer<S: ConnectedServer> {
+    server: S,
+    metadata: ServerMetadata,
+    /// Persistent group used by live-mode reads, created on first use.
+    live: RefCell<Option<LiveGroup<S::Group>>>,
+    /// Set once the server's groups turn out unable to enumerate their item
+    /// attributes, so later reads stop asking.
+    item_attributes_unsupported: Cell<bool>,
+}
+
+/// Items added to a read group.
+struct ReadItems {
+    /// One entry per tag; rejected tags already carry their `add_items` error.
+    placeholders: Vec<TagValue>,
+    /// Server handles of the accepted items.
+    server_handles: Vec<ItemHandle>,
+    /// Index into the requested tag IDs of each entry in `server_handles`.
+    valid_indices: Vec<usize>,
+    /// Client handle each entry in `server_handles` was added with; empty
+    /// for groups this client did not define, such as public groups.
+    client_handles: Vec<u32>,
+}
+
+/// An active group kept registered on the server so live-mode reads can
+/// read its cache instead of re-adding items every cycle.
+struct LiveGroup<G> {
+    tag_ids: Vec<String>,
+    group: G,
+    server_handle: GroupHandle,
+    items: ReadItems,
+    /// Whether the last read reported a per-item error; such a group is
+    /// rebuilt rather than reused on the next read.
+    partial_error: bool,
+    /// Tags deactivated with `SetActiveState`, deactivated again in the
+    /// group that replaces this one after a per-item error.
+    inactive: BTreeSet<String>,
+}
+
+/// Error for toggling items of a server without live reads running.
+fn no_live_group(server: &str) -> OpcError {
+    OpcError::InvalidState(format!("no live reads running on '{server}'"))
+}
+
+/// Set the active state of those of `tag_ids` the live group `lg` holds,
+/// returning how many the server changed.
+///
+/// Tags not in the group, or rejected when it was built, are skipped with a
+/// warning.
+fn set_live_items_active<G: ConnectedGroup>(
+    lg: &mut LiveGroup<G>,
+    tag_ids: &[String],
+    active: bool,
+) -> OpcResult<usize> {
+    let mut toggled = Vec::new();
+    let mut server_handles = Vec::new();
+    for tag_id in tag_ids {
+        let handle = lg
+            .tag_ids
+            .iter()
+            .position(|t| t == tag_id)
+            .and_then(|index| lg.items.valid_indices.iter().position(|&i| i == index))
+            .map(|pos| lg.items.server_handles[pos]);
+        match handle {
+            Some(handle) => {
+                toggled.push(tag_id);
+                server_handles.push(handle);
+            }
+            None => tracing::warn!(tag = %tag_id, "Tag is not an item of the live group"),
+        }
+    }
+    if server_handles.is_empty() {
+        return Ok(0);
+    }
+
+    let errors = lg.group.set_active_state(&server_handles, active)?;
+    let mut changed = 0;
+    for ((tag_id, handle), hr) in toggled.into_iter().zip(&server_handles).zip(&errors) {
+        if hr.is_ok() {
+            changed += 1;
+            if active {
+                lg.inactive.remove(tag_id);
+            } else {
+                lg.inactive.insert(tag_id.clone());
+            }
+        } else {
+            tracing::warn!(server_handle = handle.0, error = ?hr, "SetActiveState rejected item");
+        }
+    }
+    Ok(changed)
+}
+
+/// Whether any item the server accepted failed to read.
+///
+/// Tags rejected by `add_items` are not counted: rebuilding the group would
+/// reject them again.
+fn has_read_errors(values: &[TagValue]) -> bool {
+    values.iter().any(|v| v.status == ReadStatus::ReadError)
+}
+
+/// Add `tag_ids` to a read group, recording which items the server accepted.
+///
+/// Tags in `requested_types` are added asking for that `VARTYPE`. Rejected
+/// tags get an `"Error"` placeholder whose quality carries the `add_items`
+/// error. `attributes_unsupported` is the server's
+/// [`PooledServer::item_attributes_unsupported`].
+fn add_read_items<G: ConnectedGroup>(
+    tag_ids: &[String],
+    group: &G,
+    requested_types: &BTreeMap<String, u16>,
+    attributes_unsupported: &Cell<bool>,
+) -> OpcResult<ReadItems> {
+    let item_defs = ItemDefBuilder::from_tag_ids_requesting(tag_ids, true, requested_types);
+    let (results, errors) = group.add_items(item_defs.defs())?;
+
+    // RemoteArray::len() returns u32; tag_ids.len() returns usize.
+    if results.len() as usize != tag_ids.len() || errors.len() as usize != tag_ids.len() {
+        return Err(OpcError::Internal(
+            "OPC server returned mismatched result array sizes".into(),
+        ));
+    }
+
+    let mut placeholders: Vec<TagValue> = tag_ids
+        .iter()
+        .map(|tag_id| TagValue {
+            tag_id: tag_id.clone(),
+            value: "Error".to_string(),
+            quality: "Bad — not added to group".to_string(),
+            timestamp: String::new(),
+            raw_timestamp: None,
+            vt: None,
+            access_rights: 0,
+            canonical_data_type: 0,
+            status: ReadStatus::NotAdded,
+        })
+        .collect();
+
+    let mut server_handles: Vec<ItemHandle> = Vec::new();
+    let mut valid_indices = Vec::new();
+    let mut client_handles = Vec::new();
+
+    for (idx, (item_result, error)) in results
+        .as_slice()
+        .iter()
+        .zip(errors.as_slice().iter())
+        .enumerate()
+    {
+        if error.is_ok() {
+            server_handles.push(ItemHandle(item_result.hServer));
+            valid_indices.push(idx);
+            client_handles.push(item_defs.defs()[idx].hClient);
+            placeholders[idx].access_rights = item_result.dwAccessRights;
+            placeholders[idx].canonical_data_type = item_result.vtCanonicalDataType;
+        } else {
+            let hint = format_hresult(*error);
+            tracing::warn!(
+                tag = %tag_ids[idx],
+                error = %hint,
+                "read_tag_values: add_items rejected tag"
+            );
+            placeholders[idx].quality = format!("Bad — {hint}");
+        }
+    }
+
+    let mut items = ReadItems {
+        placeholders,
+        server_handles,
+        valid_indices,
+        client_handles,
+    };
+    apply_item_attributes(group, &mut items, attributes_unsupported);
+    Ok(items)
+}
+
+/// Refresh the access rights and canonical data type of the accepted `items`
+/// from the group's item attributes.
+///
+/// `add_items` already reports both, so a group that cannot enumerate its
+/// attributes keeps those values. Once a server reports the call as not
+/// implemented, `unsupported` is set and the call is skipped from then on.
+fn apply_item_attributes<G: ConnectedGroup>(
+    group: &G,
+    items: &mut ReadItems,
+    unsupported: &Cell<bool>,
+) {
+    if items.server_handles.is_empty() || unsupported.get() {
+        return;
+    }
+    let attributes = match group.get_item_attributes(&items.server_handles) {
+        Ok(attributes) if attributes.len() == items.server_handles.len() => attributes,
+        Ok(_) => {
+            tracing::warn!("get_item_attributes returned a mismatched number of entries");
+            return;
+        }
+        Err(e) => {
+            if matches!(e, OpcError::NotImplemented(_))
+                || e.hresult() == Some(windows::Win32::Foundation::E_NOTIMPL)
+            {
+                unsupported.set(true);
+            }
+            tracing::debug!(error = %e, "get_item_attributes unsupported; using add_items results");
+            return;
+        }
+    };
+    for (idx, info) in items.valid_indices.iter().zip(attributes) {
+        if let Some(info) = info {
+            let placeholder = &mut items.placeholders[*idx];
+            placeholder.access_rights = info.access_rights;
+            placeholder.canonical_data_type = info.canonical_data_type;
+        }
+    }
+}
+
+/// Canonical data type of the item `handle` from the group's item
+/// attributes, or `None` if the group cannot enumerate them.
+fn canonical_data_type<G: ConnectedGroup>(group: &G, handle: ItemHandle) -> Option<u16> {
+    match group.get_item_attributes(&[handle]) {
+        Ok(attributes) => attributes
+            .into_iter()
+            .next()
+            .flatten()
+            .map(|info| info.canonical_data_type),
+        Err(e) => {
+            tracing::debug!(error = %e, "get_item_attributes unsupported; using add_items results");
+            None
+        }
+    }
+}
+
+/// Synchronously read the accepted `items`, one `TagValue` per tag, accepting
+/// cached values up to `max_age` milliseconds old, formatted with `format`.
+///
+/// Results are matched to tags by position. With `verify_client_handles`,
+/// each successfully read state must also carry the client handle its item
+/// was added with, see [`OpcDaConfig::strict_client_handles`]. `ReadMaxAge`
+/// reports no client handles, so such reads always use `IOPCSyncIO::Read`.
+fn read_items<G: ConnectedGroup>(
+    tag_ids: &[String],
+    items: &ReadItems,
+    group: &G,
+    max_age: u32,
+    format: VariantFormat,
+    verify_client_handles: bool,
+) -> OpcResult<Vec<TagValue>> {
+    let mut tag_values = items.placeholders.clone();
+    if items.server_handles.is_empty() {
+        return Ok(tag_values);
+    }
+
+    let (item_states_slice, read_errors_slice) = if verify_client_handles {
+        let (states, errors) = read_through_sync_io(group, &items.server_handles, max_age)?;
+        verify_read_order(tag_ids, items, &states, &errors)?;
+        (states, errors)
+    } else {
+        group.read_with_max_age(&items.server_handles, max_age)?
+    };
+
+    for (i, idx) in items.valid_indices.iter().enumerate() {
+        let placeholder = &items.placeholders[*idx];
+        tag_values[*idx] = TagValue {
+            access_rights: placeholder.access_rights,
+            canonical_data_type: placeholder.canonical_data_type,
+            ..item_state_to_tag_value(
+                &tag_ids[*idx],
+                &item_states_slice[i],
+                read_errors_slice[i],
+                format,
+            )
+        };
+    }
+
+    Ok(tag_values)
 }
 
+/// The value of `tag_id` read as `state`, or a read error if `read_error`
+/// failed. Access rights and canonical data type are left at zero.
+fn item_state_to_tag_value(
+    tag_id: &str,
+    state: &tagOPCITEMSTATE,
+    read_error: windows::core::HRESULT,
+    format: VariantFormat,
+) -> TagValue {
+    let (value_str, quality_str, vt, status) = if read_error.is_ok() {
+        (
+            format_variant(&state.vDataValue, format),
+            quality_to_string(state.wQuality),
+            Some(variant_vt(&state.vDataValue)),
+            ReadStatus::Ok,
+        )
+    } else {
+        let full_msg = format_hresult(read_error);
+        tracing::warn!(
+            tag = %tag_id,
+            error = ?read_error,
+            hint = %full_msg,
+            "read_tag_values: per-item read error"
+        );
+        (
+            "Error".to_string(),
+            format!("Bad — {full_msg}"),
+            None,
+            ReadStatus::ReadError,
+        )
+    };
+
+    TagValue {
+        tag_id: tag_id.to_string(),
+        value: value_str,
+        quality: quality_str,
+        timestamp: filetime_to_string(state.ftTimeStamp),
+        raw_timestamp: filetime_to_system_time(state.ftTimeStamp),
+        vt,
+        access_rights: 0,
+        canonical_data_type: 0,
+        status,
+    }
+}
+
+/// Fail if a successfully read state carries a different client handle than
+/// the item at its position was added with, i.e. the server reordered the
+/// results and matching them by position would mislabel values.
+fn verify_read_order(
+    tag_ids: &[String],
+    items: &ReadItems,
+    states: &[tagOPCITEMSTATE],
+    errors: &[windows::core::HRESULT],
+) -> OpcResult<()> {
+    let expected = items.client_handles.iter().zip(&items.valid_indices);
+    for (i, ((state, error), (&handle, &idx))) in
+        states.iter().zip(errors).zip(expected).enumerate()
+    {
+        if error.is_ok() && state.hClient != handle {
+            let actual = items
+                .client_handles
+                .iter()
+                .position(|&c| c == state.hClient)
+                .map_or_else(
+                    || "an unknown item".to_string(),
+                    |pos| format!("'{}'", tag_ids[items.valid_indices[pos]]),
+                );
+            tracing::error!(
+                position = i,
+                expected = handle,
+                actual = state.hClient,
+                "read returned item states out of order"
+            );
+            return Err(OpcError::Internal(format!(
+                "OPC server returned item states out of order: result {i} for '{}' carries client handle {} of {actual}",
+                tag_ids[idx], state.hClient
+            )));
+        }
+    }
+    Ok(())
+}
+
+/// `GetStatus` states, as named by `ConnectedServer::status`, that
+/// [`OpcDaConfig::probe_on_connect`] refuses to connect in.
+const UNHEALTHY_SERVER_STATES: [&str; 3] = ["Failed", "Suspended", "Communication fault"];
+
+/// Requests the worker channel holds before [`ComWorker::submit`] waits.
+pub const REQUEST_QUEUE_CAPACITY: usize = 32;
+
+/// How long [`ComWorker::is_alive`] waits for the worker to answer a ping.
+pub const PING_TIMEOUT: std::time::Duration = std::time::Duration::from_millis(100);
+
 pub struct ComWorker<C: ServerConnector + 'static> {
     pub sender: mpsc::Sender<ComRequest>,
     pub handle: Option<std::thread::JoinHandle<()>>,
+    /// Requests waiting in the channel, refreshed on every submit and when
+    /// the worker starts a request.
+    queue_depth: Arc<AtomicUsize>,
     _phantom: std::marker::PhantomData<C>,
 }
 
 #[allow(clippy::cast_possible_wrap)]
+fn is_connection_hresult(hr: windows::core::HRESULT) -> bool {
+    let code = hr.0;
+    code == windows::core::HRESULT(0x8007_06BA_u32 as i32).0
+        || code == windows::core::HRESULT(0x8007_06BF_u32 as i32).0
+        || code == windows::core::HRESULT(0x8007_06BE_u32 as i32).0
+        || code == windows::core::HRESULT(0x8008_0005_u32 as i32).0
+}
+
 fn is_connection_error(err: &OpcError) -> bool {
-    if let OpcError::Com { source } = err {
-        let code = source.code().0;
-        code == windows::core::HRESULT(0x8007_06BA_u32 as i32).0
-            || code == windows::core::HRESULT(0x8007_06BF_u32 as i32).0
-            || code == windows::core::HRESULT(0x8007_06BE_u32 as i32).0
-            || code == windows::core::HRESULT(0x8008_0005_u32 as i32).0
-    } else {
-        false
+    err.hresult().is_some_and(is_connection_hresult)
+}
+
+/// Delay between write attempts after a transient failure.
+const WRITE_RETRY_DELAY: std::time::Duration = std::time::Duration::from_millis(250);
+
+/// How often the request channel is checked while a write waits out
+/// [`WRITE_RETRY_DELAY`].
+const RETRY_POLL_INTERVAL: std::time::Duration = std::time::Duration::from_millis(10);
+
+/// HRESULTs worth retrying a write for, whether the whole call or a single
+/// item failed with them: lost connections and servers that are temporarily
+/// too busy to accept the call.
+#[allow(clippy::cast_possible_wrap)]
+fn is_transient_hresult(hr: windows::core::HRESULT) -> bool {
+    let code = hr.0;
+    is_connection_hresult(hr)
+        || code == windows::core::HRESULT(0x8001_0001_u32 as i32).0 // RPC_E_CALL_REJECTED
+        || code == windows::core::HRESULT(0x8001_010A_u32 as i32).0 // RPC_E_SERVERCALL_RETRYLATER
+}
+
+fn is_transient_error(err: &OpcError) -> bool {
+    err.hresult().is_some_and(is_transient_hresult)
+}
+
+/// `err`, the failure of the last of `attempts` write attempts, noting how
+/// many attempts were made when there was more than one.
+fn with_write_attempts(err: OpcError, attempts: u32) -> OpcError {
+    if attempts <= 1 {
+        return err;
+    }
+    match err {
+        OpcError::Com { source } => {
+            OpcError::from_com_error(source, format!("Write failed after {attempts} attempts"))
+        }
+        OpcError::ContextualCom { source, context } => OpcError::from_com_error(
+            source,
+            format!("{context} (write failed after {attempts} attempts)"),
+        ),
+        other => other,
+    }
+}
+
+/// Outcome of writing one item, noting whether the server rejected it with
+/// a transient HRESULT worth another attempt.
+struct ItemWrite {
+    result: WriteResult,
+    transient: bool,
+}
+
+impl ItemWrite {
+    const fn done(result: WriteResult) -> Self {
+        Self {
+            result,
+            transient: false,
+        }
+    }
+}
+
+/// Where the results of a write request go.
+enum WriteReply {
+    One(oneshot::Sender<OpcResult<WriteResult>>),
+    Many(oneshot::Sender<OpcResult<Vec<WriteResult>>>),
+}
+
+impl WriteReply {
+    fn send(self, result: OpcResult<Vec<WriteResult>>) {
+        match self {
+            Self::One(reply) => {
+                let _ = reply.send(result.and_then(|results| {
+                    results
+                        .into_iter()
+                        .next()
+                        .ok_or_else(|| OpcError::Internal("Write produced no result".into()))
+                }));
+            }
+            Self::Many(reply) => {
+                let _ = reply.send(result);
+            }
+        }
+    }
+}
+
+/// A write request part-way through its attempts.
+///
+/// Between attempts it waits in the worker's retry queue rather than
+/// sleeping on the worker thread, so other requests run in the meantime.
+struct PendingWrite {
+    server: String,
+    /// Writes still to be attempted.
+    writes: Vec<(String, OpcValue)>,
+    /// Index into `results` of each entry in `writes`.
+    indices: Vec<usize>,
+    /// One entry per write of the request, filled in as writes finish.
+    results: Vec<Option<WriteResult>>,
+    /// Attempts made so far.
+    attempts: u32,
+    /// When the next attempt is due.
+    due: std::time::Instant,
+    reply: WriteReply,
+}
+
+impl PendingWrite {
+    fn new(server: String, writes: Vec<(String, OpcValue)>, reply: WriteReply) -> Self {
+        Self {
+            server,
+            indices: (0..writes.len()).collect(),
+            results: vec![None; writes.len()],
+            writes,
+            attempts: 0,
+            due: std::time::Instant::now(),
+            reply,
+        }
+    }
+}
+
+/// What the worker thread handles next.
+enum Work {
+    Request(ComRequest),
+    RetryWrite(PendingWrite),
+}
+
+/// The next [`Work`]: a retry that has come due, else a request from the
+/// channel. While retries wait, the channel is polled every
+/// [`RETRY_POLL_INTERVAL`] so requests queued behind them are not held up.
+/// `None` once the channel is closed and no retry is left.
+fn next_work(
+    rx: &mut mpsc::Receiver<ComRequest>,
+    retries: &mut std::collections::VecDeque<PendingWrite>,
+) -> Option<Work> {
+    loop {
+        // Every retry waits the same delay, so the front is due first.
+        let Some(due) = retries.front().map(|w| w.due) else {
+            return rx.blocking_recv().map(Work::Request);
+        };
+        let now = std::time::Instant::now();
+        if due <= now {
+            return retries.pop_front().map(Work::RetryWrite);
+        }
+        match rx.try_recv() {
+            Ok(req) => return Some(Work::Request(req)),
+            Err(_) => std::thread::sleep((due - now).min(RETRY_POLL_INTERVAL)),
+        }
+    }
+}
+
+/// Wait for the worker thread to report whether COM initialized.
+///
+/// A wedged COM subsystem can hang `CoInitializeEx` indefinitely; bounding
+/// the wait keeps the caller (usually application startup) from hanging
+/// with it. The stuck thread is abandoned and exits on its own once its
+/// request channel closes.
+fn await_worker_init(
+    init_rx: &std::sync::mpsc::Receiver<OpcResult<()>>,
+    timeout: std::time::Duration,
+) -> OpcResult<()> {
+    match init_rx.recv_timeout(timeout) {
+        Ok(result) => result,
+        Err(std::sync::mpsc::RecvTimeoutError::Timeout) => {
+            tracing::error!(
+                timeout_ms = timeout.as_millis(),
+                "COM worker init timed out"
+            );
+            Err(OpcError::Timeout(format!(
+                "COM worker did not initialize within {}s",
+                timeout.as_secs_f32()
+            )))
+        }
+        Err(std::sync::mpsc::RecvTimeoutError::Disconnected) => Err(OpcError::Internal(
+            "COM worker thread panicked during init".into(),
+        )),
     }
 }
 
 impl<C: ServerConnector + 'static> ComWorker<C> {
     pub fn start(connector: Arc<C>) -> Result<Self, OpcError> {
-        let (tx, mut rx) = mpsc::channel(32);
+        Self::start_with_config(connector, OpcDaConfig::default())
+    }
+
+    /// A worker with no thread behind it, whose requests go to `sender`.
+    ///
+    /// Lets tests play the worker thread by answering the requests
+    /// themselves, or leave them unanswered to simulate a stuck worker.
+    #[cfg(any(test, feature = "test-support"))]
+    #[must_use]
+    pub fn detached(sender: mpsc::Sender<ComRequest>) -> Self {
+        Self {
+            sender,
+            handle: None,
+            queue_depth: Arc::default(),
+            _phantom: std::marker::PhantomData,
+        }
+    }
+
+    pub fn start_with_config(connector: Arc<C>, config: OpcDaConfig) -> Result<Self, OpcError> {
+        let (tx, mut rx) = mpsc::channel(REQUEST_QUEUE_CAPACITY);
         let (init_tx, init_rx) = std::sync::mpsc::channel();
+        let init_timeout = config.init_timeout;
+        let queue_depth = Arc::new(AtomicUsize::new(0));
+        let worker_queue_depth = Arc::clone(&queue_depth);
 
         let handle = std::thread::spawn(move || {
-            tracing::debug!("COM worker thread spawned, initializing COM (MTA)");
-            let _guard = match crate::ComGuard::new() {
+            tracing::debug!(apartment = %config.apartment, "COM worker thread spawned, initializing COM");
+            let _guard = match crate::ComGuard::with_apartment(config.apartment) {
                 Ok(g) => {
-                    tracing::info!("COM MTA initialized successfully on worker thread");
+                    tracing::info!(apartment = %g.apartment(), "COM initialized successfully on worker thread");
                     let _ = init_tx.send(Ok(()));
                     g
                 }
                 Err(e) => {
-                    tracing::error!(error = ?e, "COM worker failed to initialize MTA");
+                    tracing::error!(error = ?e, apartment = %config.apartment, "COM worker failed to initialize COM");
                     let _ =
                         init_tx.send(Err(OpcError::Internal("COM init failed on worker".into())));
                     return;
                 }
             };
 
-            let mut cache: HashMap<String, C::Server> = HashMap::new();
+            let mut cache: HashMap<String, PooledServer<C::Server>> = HashMap::new();
+            let mut write_retries = std::collections::VecDeque::new();
 
-            while let Some(req) = rx.blocking_recv() {
+            while let Some(work) = next_work(&mut rx, &mut write_retries) {
+                worker_queue_depth.store(rx.len(), Ordering::Relaxed);
+                let req = match work {
+                    Work::Request(req) => req,
+                    Work::RetryWrite(write) => {
+                        Self::attempt_write(
+                            &mut cache,
+                            &connector,
+                            &config,
+                            write,
+                            &mut write_retries,
+                        );
+                        continue;
+                    }
+                };
                 match req {
                     ComRequest::ListServers { host, reply } => {
                         let span = tracing::info_span!("opc.list_servers", host = %host);
@@ -113,7 +772,34 @@ impl<C: ServerConnector + 'static> ComWorker<C> {
                             &mut cache,
                             &connector,
                             &server,
-                            |opc_server| Self::handle_read(&server, &tag_ids, opc_server),
+                            &config,
+                            |pooled| {
+                                if config.prefer_item_io_read
+                                    && pooled.metadata.da_version.as_deref() == Some("3.0")
+                                {
+                                    match Self::handle_read_item_io(
+                                        &server,
+                                        &tag_ids,
+                                        &pooled.server,
+                                        config.variant_format(),
+                                    ) {
+                                        Err(OpcError::NotImplemented(reason)) => tracing::debug!(
+                                            server = %server,
+                                            %reason,
+                                            "IOPCItemIO read unsupported; reading through a group"
+                                        ),
+                                        result => return result,
+                                    }
+                                }
+                                Self::handle_read(
+                                    &server,
+                                    &tag_ids,
+                                    pooled,
+                                    config.variant_format(),
+                                    config.strict_client_handles,
+                                    &config.requested_data_types,
+                                )
+                            },
                         );
                         let _ = reply.send(result);
                     }
@@ -123,124 +809,468 @@ impl<C: ServerConnector + 'static> ComWorker<C> {
                         value,
                         reply,
                     } => {
-                        let result = Self::dispatch_with_retry(
+                        let write = PendingWrite::new(
+                            server,
+                            vec![(tag_id, value)],
+                            WriteReply::One(reply),
+                        );
+                        Self::attempt_write(
                             &mut cache,
                             &connector,
-                            &server,
-                            |opc_server| Self::handle_write(&server, &tag_id, &value, opc_server),
+                            &config,
+                            write,
+                            &mut write_retries,
+                        );
+                    }
+                    ComRequest::WriteTagValues {
+                        server,
+                        writes,
+                        reply,
+                    } => {
+                        let write = PendingWrite::new(server, writes, WriteReply::Many(reply));
+                        Self::attempt_write(
+                            &mut cache,
+                            &connector,
+                            &config,
+                            write,
+                            &mut write_retries,
                         );
-                        let _ = reply.send(result);
                     }
                     ComRequest::BrowseTags {
                         server,
                         max_tags,
+                        access,
                         progress,
-                        tags_sink,
                         reply,
                     } => {
                         let result = Self::dispatch_with_retry(
                             &mut cache,
                             &connector,
                             &server,
-                            |opc_server| {
+                            &config,
+                            |pooled| {
                                 Self::handle_browse(
-                                    &server, max_tags, &progress, &tags_sink, opc_server,
+                                    &server,
+                                    max_tags,
+                                    access.mask(),
+                                    &progress,
+                                    &config,
+                                    &pooled.server,
                                 )
                             },
                         );
                         let _ = reply.send(result);
                     }
-                }
-            }
-
-            tracing::debug!("COM worker thread exiting cleanly");
-        });
-
-        init_rx
-            .recv()
-            .map_err(|_| OpcError::Internal("COM worker thread panicked during init".into()))??;
-
-        tracing::debug!("COM worker thread started");
-
-        Ok(Self {
-            sender: tx,
-            handle: Some(handle),
-            _phantom: std::marker::PhantomData,
-        })
-    }
-
-    pub async fn send_request<F, R>(&self, req_builder: F) -> OpcResult<R>
-    where
-        F: FnOnce(oneshot::Sender<OpcResult<R>>) -> ComRequest,
-    {
-        if self
-            .handle
-            .as_ref()
-            .is_some_and(std::thread::JoinHandle::is_finished)
-        {
-            tracing::error!("COM worker thread panicked or exited unexpectedly");
-            return Err(OpcError::Internal("COM worker thread panicked".into()));
-        }
-
-        let (tx, rx) = oneshot::channel();
-        let req = req_builder(tx);
-
-        self.sender
-            .send(req)
-            .await
-            .map_err(|_| OpcError::Internal("COM worker channel closed (worker stopped)".into()))?;
-
-        rx.await
-            .map_err(|_| OpcError::Internal("COM worker shut down during request".into()))?
-    }
-
-    fn dispatch_with_retry<F, R>(
-        cache: &mut HashMap<String, C::Server>,
-        connector: &Arc<C>,
-        server_name: &str,
-        operation: F,
-    ) -> OpcResult<R>
-    where
-        F: Fn(&C::Server) -> OpcResult<R>,
-    {
-        let server_ref = match cache.entry(server_name.to_string()) {
-            std::collections::hash_map::Entry::Occupied(e) => {
-                tracing::trace!(server = %server_name, "Cache hit");
-                e.into_mut()
-            }
-            std::collections::hash_map::Entry::Vacant(e) => {
-                tracing::debug!(server = %server_name, "Cache miss, connecting");
-                let srv = connector.connect(server_name)?;
-                tracing::info!(server = %server_name, "Connection established, added to pool");
-                e.insert(srv)
-            }
-        };
-
-        match operation(server_ref) {
-            Err(e) if is_connection_error(&e) => {
-                tracing::warn!(server = %server_name, error = ?e, "Evicting stale connection");
-                cache.remove(server_name);
-                tracing::debug!(server = %server_name, "Reconnecting");
-                let fresh_srv = connector.connect(server_name).map_err(|connect_e| {
-                    tracing::error!(error = ?connect_e, "Reconnect failed");
-                    connect_e
-                })?;
-                let fresh_ref = &fresh_srv;
-                let result = operation(fresh_ref);
-                tracing::info!(server = %server_name, "Reconnection successful, pool updated");
-                cache.insert(server_name.to_string(), fresh_srv);
-                result
-            }
-            other => other,
+                    ComRequest::GetItemDataTypes {
+                        server,
+                        tag_ids,
+                        reply,
+                    } => {
+                        let result = Self::dispatch_with_retry(
+                            &mut cache,
+                            &connector,
+                            &server,
+                            &config,
+                            |pooled| {
+                                Self::handle_get_item_data_types(&server, &tag_ids, &pooled.server)
+                            },
+                        );
+                        let _ = reply.send(result);
+                    }
+                    ComRequest::GetEngineeringUnits {
+                        server,
+                        tag_ids,
+                        reply,
+                    } => {
+                        let result = Self::dispatch_with_retry(
+                            &mut cache,
+                            &connector,
+                            &server,
+                            &config,
+                            |pooled| {
+                                Self::handle_get_engineering_units(
+                                    &server,
+                                    &tag_ids,
+                                    &pooled.server,
+                                )
+                            },
+                        );
+                        let _ = reply.send(result);
+                    }
+                    ComRequest::GetServerMetadata { server, reply } => {
+                        let result = Self::dispatch_with_retry(
+                            &mut cache,
+                            &connector,
+                            &server,
+                            &config,
+                            |pooled| Ok(pooled.metadata.clone()),
+                        );
+                        let _ = reply.send(result);
+                    }
+                    ComRequest::ConnectWithDiagnostics { server, reply } => {
+                        let result = Self::dispatch_with_retry(
+                            &mut cache,
+                            &connector,
+                            &server,
+                            &config,
+                            |pooled| Ok(pooled.metadata.clone()),
+                        )
+                        .map_err(|e| Self::diagnose_connect_error(&connector, &server, e));
+                        let _ = reply.send(result);
+                    }
+                    ComRequest::ReadTagValuesLive {
+                        server,
+                        tag_ids,
+                        reply,
+                    } => {
+                        let result = Self::dispatch_with_retry(
+                            &mut cache,
+                            &connector,
+                            &server,
+                            &config,
+                            |pooled| {
+                                Self::handle_read_live(
+                                    &server,
+                                    &tag_ids,
+                                    pooled,
+                                    config.variant_format(),
+                                    config.strict_client_handles,
+                                    &config.requested_data_types,
+                                )
+                            },
+                        );
+                        let _ = reply.send(result);
+                    }
+                    ComRequest::StopLiveReads { server, reply } => {
+                        // Never connect just to tear down: no pooled server means no live group.
+                        let result = cache
+                            .get(&server)
+                            .map_or(Ok(()), |pooled| Self::handle_stop_live(&server, pooled));
+                        let _ = reply.send(result);
+                    }
+                    ComRequest::SetItemsActive {
+                        server,
+                        tag_ids,
+                        active,
+                        reply,
+                    } => {
+                        // Only the live group's items can be toggled, so never connect.
+                        let result = cache.get(&server).map_or_else(
+                            || Err(no_live_group(&server)),
+                            |pooled| {
+                                Self::handle_set_items_active(&server, &tag_ids, active, pooled)
+                            },
+                        );
+                        let _ = reply.send(result);
+                    }
+                    ComRequest::CleanupOrphanedGroups { server, reply } => {
+                        let result = Self::dispatch_with_retry(
+                            &mut cache,
+                            &connector,
+                            &server,
+                            &config,
+                            |pooled| {
+                                // The live group is removed along with the others.
+                                pooled.live.borrow_mut().take();
+                                Self::handle_cleanup_groups(&server, &pooled.server)
+                            },
+                        );
+                        let _ = reply.send(result);
+                    }
+                    ComRequest::ListPublicGroups { server, reply } => {
+                        let result = Self::dispatch_with_retry(
+                            &mut cache,
+                            &connector,
+                            &server,
+                            &config,
+                            |pooled| pooled.server.public_group_names(),
+                        );
+                        let _ = reply.send(result);
+                    }
+                    ComRequest::ReadPublicGroup {
+                        server,
+                        group,
+                        reply,
+                    } => {
+                        let result = Self::dispatch_with_retry(
+                            &mut cache,
+                            &connector,
+                            &server,
+                            &config,
+                            |pooled| {
+                                Self::handle_read_public_group(
+                                    &server,
+                                    &group,
+                                    &pooled.server,
+                                    config.variant_format(),
+                                )
+                            },
+                        );
+                        let _ = reply.send(result);
+                    }
+                    ComRequest::Ping { reply } => {
+                        let _ = reply.send(());
+                    }
+                }
+            }
+
+            tracing::debug!("COM worker thread exiting cleanly");
+        });
+
+        await_worker_init(&init_rx, init_timeout)?;
+
+        tracing::debug!("COM worker thread started");
+
+        Ok(Self {
+            sender: tx,
+            handle: Some(handle),
+            queue_depth,
+            _phantom: std::marker::PhantomData,
+        })
+    }
+
+    pub async fn send_request<F, R>(&self, req_builder: F) -> OpcResult<R>
+    where
+        F: FnOnce(oneshot::Sender<OpcResult<R>>) -> ComRequest,
+    {
+        self.submit(req_builder)
+            .await?
+            .await
+            .map_err(|_| OpcError::Internal("COM worker shut down during request".into()))?
+    }
+
+    /// Queue a request on the worker without waiting for its reply.
+    ///
+    /// Lets callers keep several requests in flight; the worker still
+    /// processes them one at a time, in submission order.
+    pub async fn submit<F, R>(&self, req_builder: F) -> OpcResult<oneshot::Receiver<OpcResult<R>>>
+    where
+        F: FnOnce(oneshot::Sender<OpcResult<R>>) -> ComRequest,
+    {
+        if self
+            .handle
+            .as_ref()
+            .is_some_and(std::thread::JoinHandle::is_finished)
+        {
+            tracing::error!("COM worker thread panicked or exited unexpectedly");
+            return Err(OpcError::Internal("COM worker thread panicked".into()));
+        }
+
+        let (tx, rx) = oneshot::channel();
+        let req = req_builder(tx);
+
+        self.sender
+            .send(req)
+            .await
+            .map_err(|_| OpcError::Internal("COM worker channel closed (worker stopped)".into()))?;
+        let depth = self.queue_depth();
+        self.queue_depth.store(depth, Ordering::Relaxed);
+
+        Ok(rx)
+    }
+
+    /// Whether the worker thread answers a ping within [`PING_TIMEOUT`].
+    ///
+    /// Unlike checking the thread handle, this also catches a worker that is
+    /// still running but stuck. The ping queues behind pending requests, so a
+    /// worker busy with a long operation reports `false` as well.
+    pub async fn is_alive(&self) -> bool {
+        let (tx, rx) = oneshot::channel();
+        let ping = async {
+            self.sender
+                .send(ComRequest::Ping { reply: tx })
+                .await
+                .is_ok()
+                && rx.await.is_ok()
+        };
+        tokio::time::timeout(PING_TIMEOUT, ping)
+            .await
+            .unwrap_or(false)
+    }
+
+    /// Number of requests queued on the worker and not yet started.
+    ///
+    /// Once this reaches [`REQUEST_QUEUE_CAPACITY`], further submissions
+    /// wait for the worker to catch up.
+    #[must_use]
+    pub fn queue_depth(&self) -> usize {
+        self.sender.max_capacity() - self.sender.capacity()
+    }
+
+    /// Shared counter tracking [`queue_depth`](Self::queue_depth), for
+    /// observers that cannot hold the worker itself (e.g. a UI loop).
+    #[must_use]
+    pub fn queue_depth_handle(&self) -> Arc<AtomicUsize> {
+        Arc::clone(&self.queue_depth)
+    }
+
+    /// Connect to `server_name` and capture its metadata, first checking its
+    /// health and removing leftover groups when `config` asks for it.
+    fn connect_pooled(
+        connector: &Arc<C>,
+        server_name: &str,
+        config: &OpcDaConfig,
+    ) -> OpcResult<PooledServer<C::Server>> {
+        let server = connector.connect(server_name)?;
+        if config.probe_on_connect {
+            Self::probe_server_health(server_name, &server)?;
+        }
+        if config.cleanup_groups_on_connect
+            && let Err(e) = Self::handle_cleanup_groups(server_name, &server)
+        {
+            tracing::warn!(server = %server_name, error = ?e, "Orphaned group cleanup failed");
+        }
+        let metadata = Self::collect_metadata(server_name, &server);
+        Ok(PooledServer {
+            server,
+            metadata,
+            live: RefCell::new(None),
+            item_attributes_unsupported: Cell::new(false),
+        })
+    }
+
+    /// Attach the connector's diagnostics to a COM connection failure.
+    ///
+    /// Other failures, such as an unhealthy server state, are not about the
+    /// registration and pass through unchanged.
+    fn diagnose_connect_error(connector: &Arc<C>, server_name: &str, error: OpcError) -> OpcError {
+        if error.hresult().is_none() {
+            return error;
+        }
+        match connector.diagnose(server_name) {
+            Some(diagnostics) => {
+                tracing::warn!(server = %server_name, %diagnostics, "Connection failed");
+                OpcError::Diagnosed {
+                    source: Box::new(error),
+                    diagnostics,
+                }
+            }
+            None => error,
+        }
+    }
+
+    /// Fail with [`OpcError::Connection`] if the server reports one of
+    /// [`UNHEALTHY_SERVER_STATES`]. Servers without `GetStatus` pass.
+    fn probe_server_health(server_name: &str, server: &C::Server) -> OpcResult<()> {
+        let status = match server.status() {
+            Ok(status) => status,
+            Err(OpcError::NotImplemented(_)) => {
+                tracing::debug!(server = %server_name, "Health probe skipped: GetStatus unavailable");
+                return Ok(());
+            }
+            Err(e) => return Err(e),
+        };
+        if UNHEALTHY_SERVER_STATES.contains(&status.state.as_str()) {
+            tracing::warn!(server = %server_name, state = %status.state, "Server failed health probe");
+            return Err(OpcError::Connection(format!(
+                "Server is in {} state",
+                status.state
+            )));
+        }
+        tracing::debug!(server = %server_name, state = %status.state, "Server passed health probe");
+        Ok(())
+    }
+
+    /// Gather server metadata, tolerating servers that reject individual queries.
+    fn collect_metadata(server_name: &str, server: &C::Server) -> ServerMetadata {
+        let status = server
+            .status()
+            .inspect_err(
+                |e| tracing::debug!(server = %server_name, error = ?e, "GetStatus unavailable"),
+            )
+            .ok();
+
+        let namespace_type = match server.query_organization() {
+            Ok(org) if org == OPC_NS_FLAT.0 as u32 => Some("Flat".to_string()),
+            Ok(org) if org == OPC_NS_HIERARCHIAL.0 as u32 => Some("Hierarchical".to_string()),
+            Ok(org) => {
+                tracing::debug!(server = %server_name, org, "Unknown namespace organization");
+                None
+            }
+            Err(e) => {
+                tracing::debug!(server = %server_name, error = ?e, "QueryOrganization unavailable");
+                None
+            }
+        };
+
+        let interfaces = server.supported_interfaces();
+        let da_version = if interfaces.contains(&"IOPCBrowse") && interfaces.contains(&"IOPCItemIO")
+        {
+            Some("3.0".to_string())
+        } else if interfaces.contains(&"IOPCItemProperties") {
+            Some("2.0".to_string())
+        } else {
+            None
+        };
+
+        tracing::debug!(
+            server = %server_name,
+            ?namespace_type,
+            ?da_version,
+            interface_count = interfaces.len(),
+            "Captured server metadata"
+        );
+        ServerMetadata {
+            server: server_name.to_string(),
+            status,
+            namespace_type,
+            interfaces: interfaces.into_iter().map(str::to_string).collect(),
+            da_version,
+        }
+    }
+
+    fn dispatch_with_retry<F, R>(
+        cache: &mut HashMap<String, PooledServer<C::Server>>,
+        connector: &Arc<C>,
+        server_name: &str,
+        config: &OpcDaConfig,
+        operation: F,
+    ) -> OpcResult<R>
+    where
+        F: Fn(&PooledServer<C::Server>) -> OpcResult<R>,
+    {
+        let server_ref = match cache.entry(server_name.to_string()) {
+            std::collections::hash_map::Entry::Occupied(e) => {
+                tracing::trace!(server = %server_name, "Cache hit");
+                e.into_mut()
+            }
+            std::collections::hash_map::Entry::Vacant(e) => {
+                tracing::debug!(server = %server_name, "Cache miss, connecting");
+                let srv = Self::connect_pooled(connector, server_name, config)?;
+                tracing::info!(server = %server_name, "Connection established, added to pool");
+                e.insert(srv)
+            }
+        };
+
+        match operation(server_ref) {
+            Err(e) if is_connection_error(&e) => {
+                tracing::warn!(server = %server_name, error = ?e, "Evicting stale connection");
+                cache.remove(server_name);
+                tracing::debug!(server = %server_name, "Reconnecting");
+                let fresh_srv =
+                    Self::connect_pooled(connector, server_name, config).map_err(|connect_e| {
+                        tracing::error!(error = ?connect_e, "Reconnect failed");
+                        connect_e
+                    })?;
+                let fresh_ref = &fresh_srv;
+                let result = operation(fresh_ref);
+                tracing::info!(server = %server_name, "Reconnection successful, pool updated");
+                cache.insert(server_name.to_string(), fresh_srv);
+                result
+            }
+            other => other,
         }
     }
 
-    #[allow(clippy::too_many_lines)]
     fn handle_read(
         server_name: &str,
         tag_ids: &[String],
-        opc_server: &C::Server,
+        pooled: &PooledServer<C::Server>,
+        format: VariantFormat,
+        verify_client_handles: bool,
+        requested_types: &BTreeMap<String, u16>,
     ) -> OpcResult<Vec<TagValue>> {
+        let opc_server = &pooled.server;
         let span = tracing::info_span!(
             "opc.read_tag_values",
             server = %server_name,
@@ -252,7 +1282,7 @@ impl<C: ServerConnector + 'static> ComWorker<C> {
         let mut revised_update_rate = 0u32;
         let mut server_handle = GroupHandle::default();
         let group = opc_server.add_group(
-            "opc-da-client-read",
+            &unique_group_name("read"),
             true,
             1000,
             server_handle,
@@ -263,129 +1293,440 @@ impl<C: ServerConnector + 'static> ComWorker<C> {
             &mut server_handle,
         )?;
 
-        let item_id_wides: Vec<Vec<u16>> = tag_ids
-            .iter()
-            .map(|tag_id| tag_id.encode_utf16().chain(std::iter::once(0)).collect())
-            .collect();
+        let result = add_read_items(
+            tag_ids,
+            &group,
+            requested_types,
+            &pooled.item_attributes_unsupported,
+        )
+        .and_then(|items| {
+            read_items(
+                tag_ids,
+                &items,
+                &group,
+                MAX_AGE_DEVICE,
+                format,
+                verify_client_handles,
+            )
+        });
 
-        let item_defs: Vec<tagOPCITEMDEF> = item_id_wides
-            .iter()
-            .enumerate()
-            .map(|(idx, wide)| tagOPCITEMDEF {
-                szAccessPath: windows::core::PWSTR::null(),
-                szItemID: windows::core::PWSTR(wide.as_ptr().cast_mut()),
-                bActive: windows::Win32::Foundation::TRUE,
-                #[allow(clippy::cast_possible_truncation)]
-                hClient: idx as u32,
-                dwBlobSize: 0,
-                pBlob: std::ptr::null_mut(),
-                vtRequestedDataType: 0,
-                wReserved: 0,
-            })
-            .collect();
+        if let Ok(tag_values) = &result {
+            tracing::info!(
+                count = tag_values.len(),
+                elapsed_ms = u64::try_from(start.elapsed().as_millis()).unwrap_or(u64::MAX),
+                "read_tag_values completed"
+            );
+        }
+        if let Err(e) = opc_server.remove_group(server_handle, true) {
+            tracing::warn!(error = ?e, operation = "read_tag_values", "Failed to remove OPC group during cleanup");
+        }
+        result
+    }
 
-        let (results, errors) = group.add_items(&item_defs)?;
+    /// Read `tag_ids` with `IOPCItemIO::Read`, without creating a group.
+    ///
+    /// The server reports no access rights or canonical data types this way,
+    /// so those fields are left at zero.
+    fn handle_read_item_io(
+        server_name: &str,
+        tag_ids: &[String],
+        opc_server: &C::Server,
+        format: VariantFormat,
+    ) -> OpcResult<Vec<TagValue>> {
+        let span = tracing::info_span!(
+            "opc.read_item_io",
+            server = %server_name,
+            tag_count = tag_ids.len()
+        );
+        let _enter = span.enter();
+        if tag_ids.is_empty() {
+            return Ok(Vec::new());
+        }
 
-        // RemoteArray::len() returns u32; tag_ids.len() returns usize.
-        if results.len() as usize != tag_ids.len() || errors.len() as usize != tag_ids.len() {
-            if let Err(e) = opc_server.remove_group(server_handle, true) {
-                tracing::warn!(error = ?e, operation = "read_tag_values", "Failed to remove OPC group during cleanup");
-            }
+        let (states, errors) = opc_server.read_item_io(tag_ids, MAX_AGE_DEVICE)?;
+        if states.len() != tag_ids.len() || errors.len() != tag_ids.len() {
             return Err(OpcError::Internal(
                 "OPC server returned mismatched result array sizes".into(),
             ));
         }
 
-        let mut tag_values: Vec<TagValue> = tag_ids
+        let values = tag_ids
             .iter()
-            .map(|tag_id| TagValue {
-                tag_id: tag_id.clone(),
-                value: "Error".to_string(),
-                quality: "Bad — not added to group".to_string(),
-                timestamp: String::new(),
-            })
-            .collect();
+            .zip(&states)
+            .zip(&errors)
+            .map(|((tag_id, state), error)| item_state_to_tag_value(tag_id, state, *error, format))
+            .collect::<Vec<_>>();
+        tracing::info!(count = values.len(), "read_item_io completed");
+        Ok(values)
+    }
 
-        let mut server_handles: Vec<ItemHandle> = Vec::new();
-        let mut valid_indices = Vec::new();
+    /// Attach to the public group `group_name` and read every item it
+    /// defines, in the group's own order. The group is left on the server.
+    fn handle_read_public_group(
+        server_name: &str,
+        group_name: &str,
+        opc_server: &C::Server,
+        format: VariantFormat,
+    ) -> OpcResult<Vec<TagValue>> {
+        let span = tracing::info_span!(
+            "opc.read_public_group",
+            server = %server_name,
+            group = %group_name
+        );
+        let _enter = span.enter();
 
-        for (idx, (item_result, error)) in results
-            .as_slice()
-            .iter()
-            .zip(errors.as_slice().iter())
-            .enumerate()
-        {
-            if error.is_ok() {
-                server_handles.push(ItemHandle(item_result.hServer));
-                valid_indices.push(idx);
-            } else {
-                let hint = format_hresult(*error);
-                tracing::warn!(
-                    tag = %tag_ids[idx],
-                    error = %hint,
-                    "read_tag_values: add_items rejected tag"
+        let group = opc_server.get_public_group(group_name)?;
+        // GetPublicGroupByName adds the group to this connection; remove it
+        // again so repeated reads do not accumulate copies on the server.
+        let server_handle = group.server_handle();
+        let result = Self::read_public_group_items(&group, format);
+        drop(group);
+        match server_handle {
+            Ok(handle) => {
+                if let Err(e) = opc_server.remove_group(handle, false) {
+                    tracing::warn!(error = ?e, operation = "read_public_group", "Failed to remove OPC group during cleanup");
+                }
+            }
+            Err(e) => {
+                tracing::warn!(error = ?e, "read_public_group: group state unavailable, group not removed");
+            }
+        }
+        if let Ok(values) = &result {
+            tracing::info!(count = values.len(), "read_public_group completed");
+        }
+        result
+    }
+
+    /// Read every item defined in a public group.
+    fn read_public_group_items(
+        group: &<C::Server as ConnectedServer>::Group,
+        format: VariantFormat,
+    ) -> OpcResult<Vec<TagValue>> {
+        let group_items = group.items()?;
+        let tag_ids: Vec<String> = group_items.iter().map(|i| i.item_id.clone()).collect();
+        let items = ReadItems {
+            placeholders: group_items
+                .iter()
+                .map(|item| TagValue {
+                    tag_id: item.item_id.clone(),
+                    value: "Error".to_string(),
+                    quality: "Bad — not read".to_string(),
+                    timestamp: String::new(),
+                    raw_timestamp: None,
+                    vt: None,
+                    access_rights: item.attributes.access_rights,
+                    canonical_data_type: item.attributes.canonical_data_type,
+                    status: ReadStatus::ReadError,
+                })
+                .collect(),
+            server_handles: group_items.iter().map(|i| i.server_handle).collect(),
+            valid_indices: (0..group_items.len()).collect(),
+            client_handles: Vec::new(),
+        };
+
+        read_items(&tag_ids, &items, group, MAX_AGE_DEVICE, format, false)
+    }
+
+    /// Live-mode read: reuse the pooled server's persistent active group,
+    /// (re)creating it when the requested tag set changes or the previous read
+    /// reported a per-item error, and read its cache.
+    fn handle_read_live(
+        server_name: &str,
+        tag_ids: &[String],
+        pooled: &PooledServer<C::Server>,
+        format: VariantFormat,
+        verify_client_handles: bool,
+        requested_types: &BTreeMap<String, u16>,
+    ) -> OpcResult<Vec<TagValue>> {
+        let span = tracing::info_span!(
+            "opc.read_tag_values_live",
+            server = %server_name,
+            tag_count = tag_ids.len()
+        );
+        let _enter = span.enter();
+        let start = std::time::Instant::now();
+
+        let mut live = pooled.live.borrow_mut();
+        let mut inactive = BTreeSet::new();
+        let existing = match live.take() {
+            Some(lg) if lg.tag_ids != tag_ids || lg.partial_error => {
+                tracing::debug!(
+                    partial_error = lg.partial_error,
+                    "read_tag_values_live: replacing group"
                 );
-                tag_values[idx].quality = format!("Bad — {hint}");
+                if let Err(e) = pooled.server.remove_group(lg.server_handle, true) {
+                    tracing::warn!(error = ?e, operation = "read_tag_values_live", "Failed to remove OPC group during cleanup");
+                }
+                if lg.tag_ids == tag_ids {
+                    inactive = lg.inactive;
+                }
+                None
+            }
+            other => other,
+        };
+        let mut lg = match existing {
+            Some(lg) => lg,
+            None => {
+                let mut lg = Self::create_live_group(tag_ids, pooled, requested_types)?;
+                if !inactive.is_empty() {
+                    // Items of a new group start active
+                    let tags: Vec<String> = inactive.into_iter().collect();
+                    if let Err(e) = set_live_items_active(&mut lg, &tags, false) {
+                        tracing::warn!(error = ?e, "read_tag_values_live: could not deactivate items again");
+                    }
+                }
+                lg
             }
+        };
+
+        let result = read_items(
+            tag_ids,
+            &lg.items,
+            &lg.group,
+            MAX_AGE_CACHE,
+            format,
+            verify_client_handles,
+        );
+        if let Ok(tag_values) = &result {
+            tracing::debug!(
+                count = tag_values.len(),
+                elapsed_ms = u64::try_from(start.elapsed().as_millis()).unwrap_or(u64::MAX),
+                "read_tag_values_live completed"
+            );
+            lg.partial_error = has_read_errors(tag_values);
+            *live = Some(lg);
+        } else if let Err(e) = pooled.server.remove_group(lg.server_handle, true) {
+            // Rebuild the group on the next call rather than reusing a broken one
+            tracing::warn!(error = ?e, operation = "read_tag_values_live", "Failed to remove OPC group during cleanup");
         }
+        result
+    }
 
-        if server_handles.is_empty() {
-            if let Err(e) = opc_server.remove_group(server_handle, true) {
-                tracing::warn!(error = ?e, operation = "read_tag_values", "Failed to remove OPC group during cleanup");
+    /// Create the persistent active group used by live-mode reads.
+    fn create_live_group(
+        tag_ids: &[String],
+        pooled: &PooledServer<C::Server>,
+        requested_types: &BTreeMap<String, u16>,
+    ) -> OpcResult<LiveGroup<<C::Server as ConnectedServer>::Group>> {
+        let opc_server = &pooled.server;
+        let mut revised_update_rate = 0u32;
+        let mut server_handle = GroupHandle::default();
+        let group = opc_server.add_group(
+            &unique_group_name("live"),
+            true,
+            1000,
+            server_handle,
+            0,
+            0.0,
+            0,
+            &mut revised_update_rate,
+            &mut server_handle,
+        )?;
+
+        let items = match add_read_items(
+            tag_ids,
+            &group,
+            requested_types,
+            &pooled.item_attributes_unsupported,
+        ) {
+            Ok(items) => items,
+            Err(e) => {
+                if let Err(e) = opc_server.remove_group(server_handle, true) {
+                    tracing::warn!(error = ?e, operation = "read_tag_values_live", "Failed to remove OPC group during cleanup");
+                }
+                return Err(e);
+            }
+        };
+        tracing::info!(
+            revised_update_rate,
+            accepted = items.server_handles.len(),
+            "read_tag_values_live: created persistent group"
+        );
+        Ok(LiveGroup {
+            tag_ids: tag_ids.to_vec(),
+            group,
+            server_handle,
+            items,
+            partial_error: false,
+            inactive: BTreeSet::new(),
+        })
+    }
+
+    /// Remove every group named with [`GROUP_NAME_PREFIX`], returning how
+    /// many were removed. Groups that fail to be removed are logged and
+    /// skipped.
+    fn handle_cleanup_groups(server_name: &str, opc_server: &C::Server) -> OpcResult<usize> {
+        let span = tracing::info_span!("opc.cleanup_orphaned_groups", server = %server_name);
+        let _enter = span.enter();
+
+        let mut removed = 0;
+        for name in opc_server.group_names()? {
+            if !name.starts_with(GROUP_NAME_PREFIX) {
+                continue;
+            }
+            match opc_server.remove_group_by_name(&name) {
+                Ok(()) => removed += 1,
+                Err(e) => {
+                    tracing::warn!(group = %name, error = ?e, "Failed to remove orphaned group");
+                }
             }
-            return Ok(tag_values);
         }
+        tracing::info!(removed, "cleanup_orphaned_groups completed");
+        Ok(removed)
+    }
+
+    /// Remove the live-mode group of `pooled`, if it has one.
+    fn handle_stop_live(server_name: &str, pooled: &PooledServer<C::Server>) -> OpcResult<()> {
+        let Some(lg) = pooled.live.borrow_mut().take() else {
+            return Ok(());
+        };
+        tracing::info!(server = %server_name, tag_count = lg.tag_ids.len(), "Stopping live reads");
+        pooled.server.remove_group(lg.server_handle, true)
+    }
 
-        let (item_states, read_errors) = group.read(OPC_DS_DEVICE, &server_handles)?;
-        let item_states_slice = item_states.as_slice();
-        let read_errors_slice = read_errors.as_slice();
+    /// Activate or deactivate `tag_ids` in the live-mode group of `pooled`.
+    fn handle_set_items_active(
+        server_name: &str,
+        tag_ids: &[String],
+        active: bool,
+        pooled: &PooledServer<C::Server>,
+    ) -> OpcResult<usize> {
+        let span = tracing::info_span!(
+            "opc.set_items_active",
+            server = %server_name,
+            tag_count = tag_ids.len(),
+            active
+        );
+        let _enter = span.enter();
 
-        for (i, idx) in valid_indices.iter().enumerate() {
-            let state = &item_states_slice[i];
-            let read_error = &read_errors_slice[i];
+        let mut live = pooled.live.borrow_mut();
+        let Some(lg) = live.as_mut() else {
+            return Err(no_live_group(server_name));
+        };
+        let changed = set_live_items_active(lg, tag_ids, active)?;
+        tracing::info!(changed, "set_items_active completed");
+        Ok(changed)
+    }
 
-            let (value_str, quality_str) = if read_error.is_ok() {
-                (
-                    variant_to_string(&state.vDataValue),
-                    quality_to_string(state.wQuality),
+    /// Make the next attempt of `write`.
+    ///
+    /// Writes that fail transiently while attempts are left go back on
+    /// `retries`, due after [`WRITE_RETRY_DELAY`]; the reply is sent once
+    /// none are left. Every result carries the attempt that produced it.
+    fn attempt_write(
+        cache: &mut HashMap<String, PooledServer<C::Server>>,
+        connector: &Arc<C>,
+        config: &OpcDaConfig,
+        mut write: PendingWrite,
+        retries: &mut std::collections::VecDeque<PendingWrite>,
+    ) {
+        write.attempts += 1;
+        let attempt = write.attempts;
+        let max_attempts = config.write_retries.saturating_add(1);
+        let can_retry = attempt < max_attempts;
+        let single = matches!(write.reply, WriteReply::One(_));
+        let result = Self::dispatch_with_retry(cache, connector, &write.server, config, |pooled| {
+            if let ([(tag_id, value)], true) = (write.writes.as_slice(), single) {
+                Self::handle_write(
+                    &write.server,
+                    tag_id,
+                    value,
+                    config.type_safe_writes,
+                    &pooled.server,
                 )
+                .map(|item| vec![item])
             } else {
-                let full_msg = format_hresult(*read_error);
+                Self::handle_write_batch(
+                    &write.server,
+                    &write.writes,
+                    config.type_safe_writes,
+                    &pooled.server,
+                )
+            }
+        });
+
+        let items = match result {
+            Ok(items) => items,
+            Err(e) if can_retry && is_transient_error(&e) => {
                 tracing::warn!(
-                    tag = %tag_ids[*idx],
-                    error = ?read_error,
-                    hint = %full_msg,
-                    "read_tag_values: per-item read error"
+                    server = %write.server,
+                    tag_count = write.writes.len(),
+                    attempt,
+                    max_attempts,
+                    error = ?e,
+                    "write: transient failure, retrying"
                 );
-                ("Error".to_string(), format!("Bad — {full_msg}"))
-            };
+                write.due = std::time::Instant::now() + WRITE_RETRY_DELAY;
+                retries.push_back(write);
+                return;
+            }
+            Err(e) if write.results.iter().all(Option::is_none) => {
+                write.reply.send(Err(with_write_attempts(e, attempt)));
+                return;
+            }
+            Err(e) => {
+                // Earlier attempts finished some writes: keep those and fail the rest.
+                let error = with_write_attempts(e, attempt).to_string();
+                for (&index, (tag_id, _)) in write.indices.iter().zip(&write.writes) {
+                    write.results[index] = Some(WriteResult {
+                        tag_id: tag_id.clone(),
+                        success: false,
+                        error: Some(error.clone()),
+                        attempts: attempt,
+                        sent_vt: None,
+                    });
+                }
+                write
+                    .reply
+                    .send(Ok(write.results.into_iter().flatten().collect()));
+                return;
+            }
+        };
 
-            tag_values[*idx] = TagValue {
-                tag_id: tag_ids[*idx].clone(),
-                value: value_str,
-                quality: quality_str,
-                timestamp: filetime_to_string(state.ftTimeStamp),
-            };
+        let mut retry_writes = Vec::new();
+        let mut retry_indices = Vec::new();
+        let pending = write.indices.iter().zip(std::mem::take(&mut write.writes));
+        for ((&index, pending_write), item) in pending.zip(items) {
+            let mut result = item.result;
+            result.attempts = attempt;
+            if item.transient && can_retry {
+                tracing::warn!(
+                    tag = %result.tag_id,
+                    attempt,
+                    max_attempts,
+                    error = result.error.as_deref().unwrap_or_default(),
+                    "write: transient item failure, retrying"
+                );
+                retry_writes.push(pending_write);
+                retry_indices.push(index);
+            }
+            write.results[index] = Some(result);
         }
 
-        tracing::info!(
-            count = tag_values.len(),
-            elapsed_ms = u64::try_from(start.elapsed().as_millis()).unwrap_or(u64::MAX),
-            "read_tag_values completed"
-        );
-        if let Err(e) = opc_server.remove_group(server_handle, true) {
-            tracing::warn!(error = ?e, operation = "read_tag_values", "Failed to remove OPC group during cleanup");
+        if retry_writes.is_empty() {
+            write
+                .reply
+                .send(Ok(write.results.into_iter().flatten().collect()));
+        } else {
+            write.writes = retry_writes;
+            write.indices = retry_indices;
+            write.due = std::time::Instant::now() + WRITE_RETRY_DELAY;
+            retries.push_back(write);
         }
-        Ok(tag_values)
     }
 
+    /// Write `value` to `tag_id` through a temporary group. With
+    /// `type_safe`, the value is first converted to the item's canonical
+    /// data type; a value that cannot be converted is reported as a failed
+    /// write without contacting the server.
     #[allow(clippy::too_many_lines)]
     fn handle_write(
         server_name: &str,
         tag_id: &str,
         value: &OpcValue,
+        type_safe: bool,
         opc_server: &C::Server,
-    ) -> OpcResult<WriteResult> {
+    ) -> OpcResult<ItemWrite> {
         let span = tracing::info_span!(
             "opc.write_tag_value",
             server = %server_name,
@@ -397,7 +1738,7 @@ impl<C: ServerConnector + 'static> ComWorker<C> {
         let mut revised_update_rate = 0u32;
         let mut server_handle = GroupHandle::default();
         let group = opc_server.add_group(
-            "opc-da-client-write",
+            &unique_group_name("write"),
             true,
             1000,
             GroupHandle(0),
@@ -408,19 +1749,9 @@ impl<C: ServerConnector + 'static> ComWorker<C> {
             &mut server_handle,
         )?;
 
-        let mut item_id_wide: Vec<u16> = tag_id.encode_utf16().chain(std::iter::once(0)).collect();
-        let item_def = tagOPCITEMDEF {
-            szAccessPath: windows::core::PWSTR::null(),
-            szItemID: windows::core::PWSTR(item_id_wide.as_mut_ptr()),
-            bActive: windows::Win32::Foundation::TRUE,
-            hClient: 0,
-            dwBlobSize: 0,
-            pBlob: std::ptr::null_mut(),
-            vtRequestedDataType: 0,
-            wReserved: 0,
-        };
-
-        let (results, errors) = group.add_items(&[item_def])?;
+        let mut item_defs = ItemDefBuilder::new(true);
+        item_defs.push(tag_id, 0);
+        let (results, errors) = group.add_items(item_defs.defs())?;
         let item_res = results
             .as_slice()
             .first()
@@ -435,17 +1766,53 @@ impl<C: ServerConnector + 'static> ComWorker<C> {
             if let Err(e) = opc_server.remove_group(server_handle, true) {
                 tracing::warn!(error = ?e, operation = "write_tag_value", "Failed to remove OPC group during cleanup");
             }
-            return Ok(WriteResult {
-                tag_id: tag_id.to_string(),
-                success: false,
-                error: Some(format!("Failed to add tag: {}", format_hresult(*item_err))),
+            return Ok(ItemWrite {
+                result: WriteResult {
+                    tag_id: tag_id.to_string(),
+                    success: false,
+                    error: Some(format!("Failed to add tag: {}", format_hresult(*item_err))),
+                    attempts: 1,
+                    sent_vt: None,
+                },
+                transient: is_transient_hresult(*item_err),
             });
         }
 
         let item_handle = ItemHandle(item_res.hServer);
-        let variant = opc_value_to_variant(value);
+        // Owned so the BSTR or SAFEARRAY is freed however this function returns
+        let variant = OwnedVariant::new(if type_safe {
+            let canonical_vt =
+                canonical_data_type(&group, item_handle).unwrap_or(item_res.vtCanonicalDataType);
+            match coerce_to_vt(value, canonical_vt) {
+                Ok(variant) => variant,
+                Err(e) => {
+                    tracing::warn!(
+                        error = ?e,
+                        canonical_vt = %vt_to_string(canonical_vt),
+                        "write_tag_value: value does not convert to the tag's type"
+                    );
+                    if let Err(e) = opc_server.remove_group(server_handle, true) {
+                        tracing::warn!(error = ?e, operation = "write_tag_value", "Failed to remove OPC group during cleanup");
+                    }
+                    return Ok(ItemWrite::done(WriteResult {
+                        tag_id: tag_id.to_string(),
+                        success: false,
+                        error: Some(format!(
+                            "Cannot convert value to {}: {}",
+                            vt_to_string(canonical_vt),
+                            format_hresult(e.code())
+                        )),
+                        attempts: 1,
+                        sent_vt: None,
+                    }));
+                }
+            }
+        } else {
+            opc_value_to_variant(value)
+        });
+        let sent_vt = variant_vt(variant.get());
 
-        let write_errors = group.write(&[item_handle], &[variant])?;
+        let write_errors = group.write(&[item_handle], std::slice::from_ref(variant.get()))?;
         let write_err = write_errors
             .as_slice()
             .first()
@@ -454,13 +1821,16 @@ impl<C: ServerConnector + 'static> ComWorker<C> {
         let write_result = if write_err.is_ok() {
             tracing::info!(
                 elapsed_ms = u64::try_from(start.elapsed().as_millis()).unwrap_or(u64::MAX),
+                sent_vt = %vt_to_string(sent_vt),
                 "write_tag_value completed"
             );
-            WriteResult {
+            ItemWrite::done(WriteResult {
                 tag_id: tag_id.to_string(),
                 success: true,
                 error: None,
-            }
+                attempts: 1,
+                sent_vt: Some(sent_vt),
+            })
         } else {
             let msg = format_hresult(*write_err);
             tracing::warn!(
@@ -468,10 +1838,15 @@ impl<C: ServerConnector + 'static> ComWorker<C> {
                 elapsed_ms = u64::try_from(start.elapsed().as_millis()).unwrap_or(u64::MAX),
                 "write_tag_value: server rejected write"
             );
-            WriteResult {
-                tag_id: tag_id.to_string(),
-                success: false,
-                error: Some(msg),
+            ItemWrite {
+                result: WriteResult {
+                    tag_id: tag_id.to_string(),
+                    success: false,
+                    error: Some(msg),
+                    attempts: 1,
+                    sent_vt: Some(sent_vt),
+                },
+                transient: is_transient_hresult(*write_err),
             }
         };
 
@@ -481,14 +1856,259 @@ impl<C: ServerConnector + 'static> ComWorker<C> {
         Ok(write_result)
     }
 
+    /// Write every `(tag_id, value)` pair through one temporary group and a
+    /// single `IOPCSyncIO::Write` call. Tags the server rejects, and with
+    /// `type_safe` values that do not convert to the tag's canonical type,
+    /// are reported as failed writes and left out of the call.
+    #[allow(clippy::too_many_lines)]
+    fn handle_write_batch(
+        server_name: &str,
+        writes: &[(String, OpcValue)],
+        type_safe: bool,
+        opc_server: &C::Server,
+    ) -> OpcResult<Vec<ItemWrite>> {
+        let span = tracing::info_span!(
+            "opc.write_tag_values",
+            server = %server_name,
+            tag_count = writes.len()
+        );
+        let _enter = span.enter();
+        let start = std::time::Instant::now();
+
+        let failed = |tag_id: &str, error: String| {
+            ItemWrite::done(WriteResult {
+                tag_id: tag_id.to_string(),
+                success: false,
+                error: Some(error),
+                attempts: 1,
+                sent_vt: None,
+            })
+        };
+
+        let mut revised_update_rate = 0u32;
+        let mut server_handle = GroupHandle::default();
+        let group = opc_server.add_group(
+            &unique_group_name("write"),
+            true,
+            1000,
+            GroupHandle(0),
+            0,
+            0.0,
+            0,
+            &mut revised_update_rate,
+            &mut server_handle,
+        )?;
+        let cleanup = || {
+            if let Err(e) = opc_server.remove_group(server_handle, true) {
+                tracing::warn!(error = ?e, operation = "write_tag_values", "Failed to remove OPC group during cleanup");
+            }
+        };
+
+        let mut item_defs = ItemDefBuilder::new(true);
+        for (i, (tag_id, _)) in writes.iter().enumerate() {
+            item_defs.push(tag_id, u32::try_from(i).unwrap_or(u32::MAX));
+        }
+        let (results, errors) = match group.add_items(item_defs.defs()) {
+            Ok(added) => added,
+            Err(e) => {
+                cleanup();
+                return Err(e);
+            }
+        };
+
+        let mut outcomes = Vec::with_capacity(writes.len());
+        // Index into `outcomes` of each item passed to the write call.
+        let mut written = Vec::new();
+        let mut handles = Vec::new();
+        let mut variants = Vec::new();
+        for (i, (tag_id, value)) in writes.iter().enumerate() {
+            let (Some(item_res), Some(item_err)) =
+                (results.as_slice().get(i), errors.as_slice().get(i))
+            else {
+                outcomes.push(failed(tag_id, "Server returned no item result".to_string()));
+                continue;
+            };
+            if item_err.is_err() {
+                let mut outcome = failed(
+                    tag_id,
+                    format!("Failed to add tag: {}", format_hresult(*item_err)),
+                );
+                outcome.transient = is_transient_hresult(*item_err);
+                outcomes.push(outcome);
+                continue;
+            }
+
+            let item_handle = ItemHandle(item_res.hServer);
+            let variant = if type_safe {
+                let canonical_vt = canonical_data_type(&group, item_handle)
+                    .unwrap_or(item_res.vtCanonicalDataType);
+                match coerce_to_vt(value, canonical_vt) {
+                    Ok(variant) => variant,
+                    Err(e) => {
+                        outcomes.push(failed(
+                            tag_id,
+                            format!(
+                                "Cannot convert value to {}: {}",
+                                vt_to_string(canonical_vt),
+                                format_hresult(e.code())
+                            ),
+                        ));
+                        continue;
+                    }
+                }
+            } else {
+                opc_value_to_variant(value)
+            };
+            written.push(outcomes.len());
+            outcomes.push(ItemWrite::done(WriteResult {
+                tag_id: tag_id.clone(),
+                success: false,
+                error: None,
+                attempts: 1,
+                sent_vt: Some(variant_vt(&variant)),
+            }));
+            handles.push(item_handle);
+            variants.push(OwnedVariant::new(variant));
+        }
+
+        if !handles.is_empty() {
+            let write_errors = match group.write(&handles, OwnedVariant::as_variants(&variants)) {
+                Ok(write_errors) => write_errors,
+                Err(e) => {
+                    cleanup();
+                    return Err(e);
+                }
+            };
+            for (k, &idx) in written.iter().enumerate() {
+                let outcome = &mut outcomes[idx];
+                match write_errors.as_slice().get(k) {
+                    Some(err) if err.is_ok() => outcome.result.success = true,
+                    Some(err) => {
+                        outcome.result.error = Some(format_hresult(*err));
+                        outcome.transient = is_transient_hresult(*err);
+                    }
+                    None => {
+                        outcome.result.error = Some("Server returned no write result".to_string());
+                    }
+                }
+            }
+        }
+
+        cleanup();
+        tracing::info!(
+            succeeded = outcomes.iter().filter(|o| o.result.success).count(),
+            elapsed_ms = u64::try_from(start.elapsed().as_millis()).unwrap_or(u64::MAX),
+            "write_tag_values completed"
+        );
+        Ok(outcomes)
+    }
+
+    fn handle_get_item_data_types(
+        server_name: &str,
+        tag_ids: &[String],
+        opc_server: &C::Server,
+    ) -> OpcResult<Vec<Option<u16>>> {
+        let span = tracing::info_span!(
+            "opc.get_item_data_types",
+            server = %server_name,
+            tag_count = tag_ids.len()
+        );
+        let _enter = span.enter();
+        let start = std::time::Instant::now();
+
+        let mut revised_update_rate = 0u32;
+        let mut server_handle = GroupHandle::default();
+        let group = opc_server.add_group(
+            &unique_group_name("types"),
+            false,
+            1000,
+            GroupHandle(0),
+            0,
+            0.0,
+            0,
+            &mut revised_update_rate,
+            &mut server_handle,
+        )?;
+
+        let item_defs = ItemDefBuilder::from_tag_ids(tag_ids, false);
+        let added = group.add_items(item_defs.defs());
+        if let Err(e) = opc_server.remove_group(server_handle, true) {
+            tracing::warn!(error = ?e, operation = "get_item_data_types", "Failed to remove OPC group during cleanup");
+        }
+        let (results, errors) = added?;
+
+        if results.len() as usize != tag_ids.len() || errors.len() as usize != tag_ids.len() {
+            return Err(OpcError::Internal(
+                "OPC server returned mismatched result array sizes".into(),
+            ));
+        }
+
+        let data_types: Vec<Option<u16>> = results
+            .as_slice()
+            .iter()
+            .zip(errors.as_slice().iter())
+            .enumerate()
+            .map(|(idx, (item_result, error))| {
+                if error.is_ok() {
+                    Some(item_result.vtCanonicalDataType)
+                } else {
+                    tracing::warn!(
+                        tag = %tag_ids[idx],
+                        error = %format_hresult(*error),
+                        "get_item_data_types: add_items rejected tag"
+                    );
+                    None
+                }
+            })
+            .collect();
+
+        tracing::info!(
+            count = data_types.len(),
+            elapsed_ms = u64::try_from(start.elapsed().as_millis()).unwrap_or(u64::MAX),
+            "get_item_data_types completed"
+        );
+        Ok(data_types)
+    }
+
+    fn handle_get_engineering_units(
+        server_name: &str,
+        tag_ids: &[String],
+        opc_server: &C::Server,
+    ) -> OpcResult<Vec<Option<EngineeringUnits>>> {
+        let span = tracing::info_span!(
+            "opc.get_engineering_units",
+            server = %server_name,
+            tag_count = tag_ids.len()
+        );
+        let _enter = span.enter();
+
+        tag_ids
+            .iter()
+            .map(|tag_id| match opc_server.engineering_units(tag_id) {
+                Ok(eu) => Ok((!eu.is_empty()).then_some(eu)),
+                Err(e @ OpcError::NotImplemented(_)) => Err(e),
+                Err(e) => {
+                    tracing::warn!(
+                        tag = %tag_id,
+                        error = %e,
+                        "get_engineering_units: item properties unavailable"
+                    );
+                    Ok(None)
+                }
+            })
+            .collect()
+    }
+
     fn handle_browse(
         server_name: &str,
         max_tags: usize,
-        progress: &Arc<AtomicUsize>,
-        tags_sink: &Arc<std::sync::Mutex<Vec<String>>>,
+        access_rights: u32,
+        progress: &BrowseProgress,
+        config: &OpcDaConfig,
         opc_server: &C::Server,
-    ) -> OpcResult<Vec<String>> {
-        let span = tracing::info_span!("opc.browse_tags", server = %server_name, max_tags);
+    ) -> OpcResult<Vec<BrowsedTag>> {
+        let span =
+            tracing::info_span!("opc.browse_tags", server = %server_name, max_tags, access_rights);
         let _enter = span.enter();
         let start = std::time::Instant::now();
 
@@ -496,41 +2116,33 @@ impl<C: ServerConnector + 'static> ComWorker<C> {
         let mut tags = Vec::new();
 
         if org == OPC_NS_FLAT.0 as u32 {
-            let string_iter = opc_server.browse_opc_item_ids(OPC_LEAF.0 as u32, Some(""), 0, 0)?;
+            let string_iter =
+                opc_server.browse_opc_item_ids(OPC_LEAF.0 as u32, Some(""), 0, access_rights)?;
             for tag_res in string_iter {
                 if tags.len() >= max_tags {
                     break;
                 }
                 let tag = tag_res?;
-                tags.push(tag.clone());
-                if let Ok(mut sink) = tags_sink.lock() {
-                    sink.push(tag);
-                }
-                progress.fetch_add(1, Ordering::Relaxed);
+                record_tag(BrowsedTag::from(tag), &mut tags, progress);
             }
         } else {
-            let use_flat = match opc_server.browse_opc_item_ids(OPC_FLAT.0 as u32, Some(""), 0, 0) {
+            let use_flat = match opc_server.browse_opc_item_ids(
+                OPC_FLAT.0 as u32,
+                Some(""),
+                0,
+                access_rights,
+            ) {
                 Ok(mut flat_enum) => match flat_enum.next() {
                     Some(Ok(first_tag)) => {
                         tracing::info!("OPC_FLAT browse supported — using fast flat enumeration");
-                        tags.push(first_tag.clone());
-                        if let Ok(mut sink) = tags_sink.lock() {
-                            sink.push(first_tag);
-                        }
-                        progress.fetch_add(1, Ordering::Relaxed);
+                        record_tag(BrowsedTag::from(first_tag), &mut tags, progress);
 
                         for tag_res in flat_enum {
                             if tags.len() >= max_tags {
                                 break;
                             }
                             match tag_res {
-                                Ok(tag) => {
-                                    tags.push(tag.clone());
-                                    if let Ok(mut sink) = tags_sink.lock() {
-                                        sink.push(tag);
-                                    }
-                                    progress.fetch_add(1, Ordering::Relaxed);
-                                }
+                                Ok(tag) => record_tag(BrowsedTag::from(tag), &mut tags, progress),
                                 Err(e) => {
                                     tracing::warn!(error = ?e, "OPC_FLAT tag iteration error, skipping");
                                 }
@@ -554,7 +2166,17 @@ impl<C: ServerConnector + 'static> ComWorker<C> {
             };
 
             if !use_flat {
-                Self::browse_recursive(opc_server, &mut tags, max_tags, progress, tags_sink, 0)?;
+                let mut walk = BrowseWalk {
+                    server: opc_server,
+                    progress,
+                    validator: ItemIdValidator::new(opc_server),
+                    max_tags,
+                    access_rights,
+                    max_depth: config.max_browse_depth,
+                    separator: config.item_id_separator,
+                    path: Vec::new(),
+                };
+                Self::browse_recursive(&mut walk, &mut tags)?;
             }
         }
         tracing::info!(
@@ -565,19 +2187,29 @@ impl<C: ServerConnector + 'static> ComWorker<C> {
         Ok(tags)
     }
 
+    /// Walk the branch at the current browse position, adding its leaves and
+    /// those of the branches below it to `tags`.
+    ///
+    /// `walk.progress.depth` follows the browse position as it moves down and
+    /// up. Branches more than `walk.max_depth` levels below the root are not
+    /// walked; skipping one sets `depth_limit_hit` to `max_depth`.
     fn browse_recursive(
-        server: &C::Server,
-        tags: &mut Vec<String>,
-        max_tags: usize,
-        progress: &Arc<AtomicUsize>,
-        tags_sink: &Arc<std::sync::Mutex<Vec<String>>>,
-        depth: usize,
+        walk: &mut BrowseWalk<'_, C::Server>,
+        tags: &mut Vec<BrowsedTag>,
     ) -> OpcResult<()> {
-        const MAX_DEPTH: usize = 50;
-        if depth > MAX_DEPTH || tags.len() >= max_tags {
-            if depth > MAX_DEPTH {
-                tracing::warn!(depth, "Max browse depth reached, truncating");
-            }
+        let server = walk.server;
+        let depth = walk.path.len();
+        if depth > walk.max_depth || tags.len() >= walk.max_tags {
+            if depth > walk.max_depth {
+                tracing::warn!(
+                    depth,
+                    max_depth = walk.max_depth,
+                    "Max browse depth reached, truncating"
+                );
+                walk.progress
+                    .depth_limit_hit
+                    .store(walk.max_depth, Ordering::Relaxed);
+            }
             return Ok(());
         }
 
@@ -593,32 +2225,62 @@ impl<C: ServerConnector + 'static> ComWorker<C> {
             })
             .collect();
 
-        let leaf_enum = server.browse_opc_item_ids(OPC_LEAF.0 as u32, Some(""), 0, 0)?;
+        let leaf_enum =
+            server.browse_opc_item_ids(OPC_LEAF.0 as u32, Some(""), 0, walk.access_rights)?;
         for tag_res in leaf_enum {
-            if tags.len() >= max_tags {
+            if tags.len() >= walk.max_tags {
                 return Ok(());
             }
             let browse_name = tag_res?;
-            let tag = match server.get_item_id(&browse_name) {
-                Ok(id) => id,
+            let tag_id = match server
+                .get_item_id(&browse_name)
+                .map(|id| normalize_tag_id(&id))
+            {
+                Ok(id) => {
+                    if walk.separator.is_none()
+                        && let Some(sep) = detect_separator(&id, &walk.path, &browse_name)
+                    {
+                        tracing::info!(separator = %sep, item_id = %id, "Detected item ID separator");
+                        walk.separator = Some(sep);
+                    }
+                    id
+                }
                 Err(e) => {
-                    tracing::warn!(
-                        browse_name = %browse_name,
-                        error = ?e,
-                        "get_item_id failed, using browse name as fallback"
-                    );
-                    browse_name
+                    match walk
+                        .separator
+                        .and_then(|sep| assemble_item_id(&walk.path, &browse_name, sep))
+                        .filter(|id| walk.validator.accepts(id))
+                    {
+                        Some(assembled) => {
+                            tracing::warn!(
+                                browse_name = %browse_name,
+                                item_id = %assembled,
+                                error = ?e,
+                                "get_item_id failed, assembled item ID from browse path"
+                            );
+                            assembled
+                        }
+                        None => {
+                            tracing::warn!(
+                                browse_name = %browse_name,
+                                error = ?e,
+                                "get_item_id failed, using browse name as fallback"
+                            );
+                            browse_name
+                        }
+                    }
                 }
             };
-            tags.push(tag.clone());
-            if let Ok(mut sink) = tags_sink.lock() {
-                sink.push(tag);
-            }
-            progress.fetch_add(1, Ordering::Relaxed);
+            let tag = BrowsedTag {
+                tag_id,
+                depth: depth + 1,
+                parent_branch: walk.path.join("/"),
+            };
+            record_tag(tag, tags, walk.progress);
         }
 
         for branch in branches {
-            if tags.len() >= max_tags {
+            if tags.len() >= walk.max_tags {
                 return Ok(());
             }
             if let Err(e) = server.change_browse_position(OPC_BROWSE_DOWN.0 as u32, &branch) {
@@ -630,262 +2292,2475 @@ impl<C: ServerConnector + 'static> ComWorker<C> {
                 continue;
             }
 
-            if let Err(e) =
-                Self::browse_recursive(server, tags, max_tags, progress, tags_sink, depth + 1)
-            {
-                tracing::warn!(error = ?e, "browse_recursive error");
-            }
+            walk.path.push(branch);
+            walk.progress
+                .depth
+                .store(walk.path.len(), Ordering::Relaxed);
+            if let Err(e) = Self::browse_recursive(walk, tags) {
+                tracing::warn!(error = ?e, "browse_recursive error");
+            }
+            walk.path.pop();
+
+            let moved_up = server.change_browse_position(OPC_BROWSE_UP.0 as u32, "");
+            walk.progress
+                .depth
+                .store(walk.path.len(), Ordering::Relaxed);
+            if let Err(e) = moved_up {
+                tracing::warn!(error = ?e, "Failed to browse up, stopping recursion");
+                break;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// State of a hierarchical browse, carried down
+/// [`ComWorker::browse_recursive`].
+struct BrowseWalk<'a, S: ConnectedServer> {
+    server: &'a S,
+    progress: &'a BrowseProgress,
+    /// Must accept an ID assembled from the browse path before it replaces
+    /// the browse name.
+    validator: ItemIdValidator<'a, S>,
+    max_tags: usize,
+    /// Filters leaves only, so every branch is still walked.
+    access_rights: u32,
+    max_depth: usize,
+    /// Item ID separator, either configured or detected from the first
+    /// `get_item_id` result that matches `path`; used to assemble IDs for
+    /// leaves whose `get_item_id` call fails.
+    separator: Option<char>,
+    /// Browse names of the branches above the current position.
+    path: Vec<String>,
+}
+
+/// Record a discovered tag in the result, the caller's sink, and the progress counter.
+///
+/// A sink the caller has closed (set to `None`, e.g. after harvesting a
+/// timed-out browse) is left alone, so nothing lands in it after the harvest.
+/// A sink poisoned by a panic elsewhere still holds every tag pushed before
+/// the panic, so it is recovered rather than skipped; skipping would silently
+/// drop every later tag from the partial results.
+fn record_tag(tag: BrowsedTag, tags: &mut Vec<BrowsedTag>, progress: &BrowseProgress) {
+    if let Some(sink) = progress
+        .tags_sink
+        .lock()
+        .unwrap_or_else(std::sync::PoisonError::into_inner)
+        .as_mut()
+    {
+        sink.push(tag.clone());
+    }
+    tags.push(tag);
+    progress.tags_found.fetch_add(1, Ordering::Relaxed);
+}
+
+/// Checks item IDs assembled during a browse with `ValidateItems`.
+///
+/// The inactive group used for the checks is added on first use and removed
+/// when the validator is dropped. If the group cannot be added, every ID is
+/// rejected.
+struct ItemIdValidator<'a, S: ConnectedServer> {
+    server: &'a S,
+    group: Option<OpcResult<(GroupHandle, S::Group)>>,
+}
+
+impl<'a, S: ConnectedServer> ItemIdValidator<'a, S> {
+    const fn new(server: &'a S) -> Self {
+        Self {
+            server,
+            group: None,
+        }
+    }
+
+    /// Whether the server knows `item_id`.
+    fn accepts(&mut self, item_id: &str) -> bool {
+        let server = self.server;
+        let group = self.group.get_or_insert_with(|| {
+            let mut revised_update_rate = 0u32;
+            let mut server_handle = GroupHandle::default();
+            let group = server.add_group(
+                &unique_group_name("validate"),
+                false,
+                1000,
+                GroupHandle(0),
+                0,
+                0.0,
+                0,
+                &mut revised_update_rate,
+                &mut server_handle,
+            );
+            if let Err(e) = &group {
+                tracing::warn!(error = ?e, "Failed to add item ID validation group");
+            }
+            group.map(|group| (server_handle, group))
+        });
+        let Ok((_, group)) = group else {
+            return false;
+        };
+
+        let item_defs = ItemDefBuilder::from_tag_ids(&[item_id.to_string()], false);
+        match group.validate_items(item_defs.defs()) {
+            Ok(errors) => errors.first().is_some_and(|hr| hr.is_ok()),
+            Err(e) => {
+                tracing::warn!(item_id = %item_id, error = ?e, "ValidateItems failed");
+                false
+            }
+        }
+    }
+}
+
+impl<S: ConnectedServer> Drop for ItemIdValidator<'_, S> {
+    fn drop(&mut self) {
+        if let Some(Ok((server_handle, _))) = &self.group
+            && let Err(e) = self.server.remove_group(*server_handle, true)
+        {
+            tracing::warn!(error = ?e, operation = "browse_tags", "Failed to remove OPC group during cleanup");
+        }
+    }
+}
+
+/// Separators servers commonly place between item ID segments.
+const ITEM_ID_SEPARATORS: [char; 4] = ['.', '/', '\\', ':'];
+
+/// Join browse names into an item ID with `separator`.
+///
+/// Returns `None` when a segment is empty or contains the separator, since
+/// the assembled ID would not split back into the same path.
+fn assemble_item_id(path: &[String], leaf: &str, separator: char) -> Option<String> {
+    let mut segments = path.iter().map(String::as_str).chain(std::iter::once(leaf));
+    if segments.any(|seg| seg.is_empty() || seg.contains(separator)) {
+        return None;
+    }
+    let mut id = path.join(&separator.to_string());
+    if !id.is_empty() {
+        id.push(separator);
+    }
+    id.push_str(leaf);
+    Some(id)
+}
+
+/// Find the separator for which `path` + `leaf` assemble to `item_id`.
+///
+/// Only IDs below the root carry a separator, so `path` must be non-empty.
+fn detect_separator(item_id: &str, path: &[String], leaf: &str) -> Option<char> {
+    if path.is_empty() {
+        return None;
+    }
+    ITEM_ID_SEPARATORS
+        .into_iter()
+        .find(|&sep| assemble_item_id(path, leaf, sep).is_some_and(|id| id == item_id))
+}
+
+impl<C: ServerConnector + 'static> Drop for ComWorker<C> {
+    fn drop(&mut self) {
+        tracing::debug!("ComWorker dropping — channel closing, signaling thread shutdown");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backend::connector::{
+        ConnectedGroup, ConnectedServer, ConnectionDiagnostics, GroupItem, ItemAttributeInfo,
+        RemoteArray, ServerConnector, StringIterator, max_age_states, read_through_sync_io,
+    };
+    use crate::bindings::da::{tagOPCDATASOURCE, tagOPCITEMDEF, tagOPCITEMRESULT, tagOPCITEMSTATE};
+    use crate::provider::ServerStatus;
+    use windows::Win32::System::Variant::VARIANT;
+    use windows::core::HRESULT;
+
+    type AddItemsResult = OpcResult<(RemoteArray<tagOPCITEMRESULT>, RemoteArray<HRESULT>)>;
+    type ReadResult = OpcResult<(RemoteArray<tagOPCITEMSTATE>, RemoteArray<HRESULT>)>;
+    type StatesResult = OpcResult<(Vec<tagOPCITEMSTATE>, Vec<HRESULT>)>;
+    type AddItemsFn = dyn Fn(&[tagOPCITEMDEF]) -> AddItemsResult + Send + Sync;
+    type ReadFn = dyn Fn(tagOPCDATASOURCE, &[ItemHandle]) -> ReadResult + Send + Sync;
+    type ReadMaxAgeFn = dyn Fn(&[ItemHandle], u32) -> StatesResult + Send + Sync;
+    type WriteFn =
+        dyn Fn(&[ItemHandle], &[VARIANT]) -> OpcResult<RemoteArray<HRESULT>> + Send + Sync;
+    type AttributesFn =
+        dyn Fn(&[ItemHandle]) -> OpcResult<Vec<Option<ItemAttributeInfo>>> + Send + Sync;
+    type ItemsFn = dyn Fn() -> OpcResult<Vec<GroupItem>> + Send + Sync;
+    type ActiveStateFn = dyn Fn(&[ItemHandle], bool) -> OpcResult<Vec<HRESULT>> + Send + Sync;
+    type ValidateFn = dyn Fn(&[tagOPCITEMDEF]) -> OpcResult<Vec<HRESULT>> + Send + Sync;
+
+    /// Group behind every worker test. Each operation answers
+    /// `NotImplemented` until the test installs a handler for it, except
+    /// `read_with_max_age`, which falls back to `read` like a DA 2.0 group.
+    #[derive(Clone, Default)]
+    struct MockGroup {
+        add_items: Option<Arc<AddItemsFn>>,
+        read: Option<Arc<ReadFn>>,
+        read_with_max_age: Option<Arc<ReadMaxAgeFn>>,
+        write: Option<Arc<WriteFn>>,
+        item_attributes: Option<Arc<AttributesFn>>,
+        items: Option<Arc<ItemsFn>>,
+        set_active_state: Option<Arc<ActiveStateFn>>,
+        validate_items: Option<Arc<ValidateFn>>,
+        server_handle: Option<GroupHandle>,
+    }
+
+    impl MockGroup {
+        fn on_add_items(
+            mut self,
+            f: impl Fn(&[tagOPCITEMDEF]) -> AddItemsResult + Send + Sync + 'static,
+        ) -> Self {
+            self.add_items = Some(Arc::new(f));
+            self
+        }
+
+        fn on_read(
+            mut self,
+            f: impl Fn(tagOPCDATASOURCE, &[ItemHandle]) -> ReadResult + Send + Sync + 'static,
+        ) -> Self {
+            self.read = Some(Arc::new(f));
+            self
+        }
+
+        fn on_read_with_max_age(
+            mut self,
+            f: impl Fn(&[ItemHandle], u32) -> StatesResult + Send + Sync + 'static,
+        ) -> Self {
+            self.read_with_max_age = Some(Arc::new(f));
+            self
+        }
+
+        fn on_write(
+            mut self,
+            f: impl Fn(&[ItemHandle], &[VARIANT]) -> OpcResult<RemoteArray<HRESULT>>
+            + Send
+            + Sync
+            + 'static,
+        ) -> Self {
+            self.write = Some(Arc::new(f));
+            self
+        }
+
+        fn on_item_attributes(
+            mut self,
+            f: impl Fn(&[ItemHandle]) -> OpcResult<Vec<Option<ItemAttributeInfo>>>
+            + Send
+            + Sync
+            + 'static,
+        ) -> Self {
+            self.item_attributes = Some(Arc::new(f));
+            self
+        }
+
+        fn on_items(
+            mut self,
+            f: impl Fn() -> OpcResult<Vec<GroupItem>> + Send + Sync + 'static,
+        ) -> Self {
+            self.items = Some(Arc::new(f));
+            self
+        }
+
+        fn on_set_active_state(
+            mut self,
+            f: impl Fn(&[ItemHandle], bool) -> OpcResult<Vec<HRESULT>> + Send + Sync + 'static,
+        ) -> Self {
+            self.set_active_state = Some(Arc::new(f));
+            self
+        }
+
+        fn on_validate_items(
+            mut self,
+            f: impl Fn(&[tagOPCITEMDEF]) -> OpcResult<Vec<HRESULT>> + Send + Sync + 'static,
+        ) -> Self {
+            self.validate_items = Some(Arc::new(f));
+            self
+        }
+
+        fn with_server_handle(mut self, handle: GroupHandle) -> Self {
+            self.server_handle = Some(handle);
+            self
+        }
+
+        /// Group on a DA 3.0 server: reads go through `IOPCSyncIO2` and
+        /// succeed with default states, recording each `max_age`;
+        /// `IOPCSyncIO::Read` is never used.
+        fn sync_io2(max_ages: Arc<std::sync::Mutex<Vec<u32>>>) -> Self {
+            Self::default()
+                .on_read(|_, _| panic!("IOPCSyncIO::Read used on a group supporting IOPCSyncIO2"))
+                .on_read_with_max_age(move |server_handles, max_age| {
+                    max_ages.lock().unwrap().push(max_age);
+                    Ok((
+                        vec![tagOPCITEMSTATE::default(); server_handles.len()],
+                        vec![HRESULT(0); server_handles.len()],
+                    ))
+                })
+        }
+
+        /// Group accepting every item it is asked to add, numbering the
+        /// server handles from 1.
+        fn accepting_items() -> Self {
+            Self::default().on_add_items(|items| {
+                let results: Vec<tagOPCITEMRESULT> = (1..)
+                    .zip(items)
+                    .map(|(handle, _)| tagOPCITEMRESULT {
+                        hServer: handle,
+                        ..tagOPCITEMRESULT::default()
+                    })
+                    .collect();
+                Ok((
+                    remote_array(&results),
+                    remote_array(&vec![HRESULT(0); items.len()]),
+                ))
+            })
+        }
+
+        /// Group whose calls succeed with empty result arrays, whatever
+        /// they were asked for.
+        fn empty_results() -> Self {
+            Self::default()
+                .on_add_items(|_| Ok((RemoteArray::empty(), RemoteArray::empty())))
+                .on_read(|_, _| Ok((RemoteArray::empty(), RemoteArray::empty())))
+                .on_write(|_, _| Ok(RemoteArray::empty()))
+        }
+    }
+
+    fn not_implemented<T>() -> OpcResult<T> {
+        Err(OpcError::NotImplemented("mock".into()))
+    }
+
+    impl ConnectedGroup for MockGroup {
+        fn add_items(&self, items: &[tagOPCITEMDEF]) -> AddItemsResult {
+            self.add_items
+                .as_ref()
+                .map_or_else(not_implemented, |f| f(items))
+        }
+        fn read(&self, source: tagOPCDATASOURCE, server_handles: &[ItemHandle]) -> ReadResult {
+            self.read
+                .as_ref()
+                .map_or_else(not_implemented, |f| f(source, server_handles))
+        }
+        fn read_with_max_age(&self, server_handles: &[ItemHandle], max_age: u32) -> StatesResult {
+            match &self.read_with_max_age {
+                Some(f) => f(server_handles, max_age),
+                None => read_through_sync_io(self, server_handles, max_age),
+            }
+        }
+        fn write(
+            &self,
+            server_handles: &[ItemHandle],
+            values: &[VARIANT],
+        ) -> OpcResult<RemoteArray<HRESULT>> {
+            self.write
+                .as_ref()
+                .map_or_else(not_implemented, |f| f(server_handles, values))
+        }
+        fn get_item_attributes(
+            &self,
+            server_handles: &[ItemHandle],
+        ) -> OpcResult<Vec<Option<ItemAttributeInfo>>> {
+            self.item_attributes
+                .as_ref()
+                .map_or_else(not_implemented, |f| f(server_handles))
+        }
+        fn items(&self) -> OpcResult<Vec<GroupItem>> {
+            self.items.as_ref().map_or_else(not_implemented, |f| f())
+        }
+        fn set_active_state(
+            &self,
+            server_handles: &[ItemHandle],
+            active: bool,
+        ) -> OpcResult<Vec<HRESULT>> {
+            self.set_active_state
+                .as_ref()
+                .map_or_else(not_implemented, |f| f(server_handles, active))
+        }
+        fn validate_items(&self, items: &[tagOPCITEMDEF]) -> OpcResult<Vec<HRESULT>> {
+            self.validate_items
+                .as_ref()
+                .map_or_else(not_implemented, |f| f(items))
+        }
+
+        fn server_handle(&self) -> OpcResult<GroupHandle> {
+            self.server_handle.map_or_else(not_implemented, Ok)
+        }
+    }
+
+    type NameFn = dyn Fn(&str) -> OpcResult<()> + Send + Sync;
+    type NamesFn = dyn Fn() -> OpcResult<Vec<String>> + Send + Sync;
+    type RemoveGroupFn = dyn Fn(GroupHandle, bool) -> OpcResult<()> + Send + Sync;
+    type BrowseFn = dyn Fn(u32) -> OpcResult<StringIterator> + Send + Sync;
+    type BrowsePositionFn = dyn Fn(u32, &str) -> OpcResult<()> + Send + Sync;
+    type ItemIdFn = dyn Fn(&str) -> OpcResult<String> + Send + Sync;
+    type PublicGroupFn = dyn Fn(&str) -> OpcResult<MockGroup> + Send + Sync;
+    type UnitsFn = dyn Fn(&str) -> OpcResult<EngineeringUnits> + Send + Sync;
+    type StatusFn = dyn Fn() -> OpcResult<ServerStatus> + Send + Sync;
+    type ItemIoFn = dyn Fn(&[String], u32) -> StatesResult + Send + Sync;
+
+    /// Server behind every worker test: reports namespace organization 0,
+    /// hands out clones of [`MockServer::group`] from `add_group` and lets
+    /// every group removal succeed. Everything else answers
+    /// `NotImplemented` until the test installs a handler for it.
+    #[derive(Clone, Default)]
+    struct MockServer {
+        group: MockGroup,
+        organization: u32,
+        interfaces: Vec<&'static str>,
+        add_group: Option<Arc<NameFn>>,
+        remove_group: Option<Arc<RemoveGroupFn>>,
+        browse: Option<Arc<BrowseFn>>,
+        change_browse_position: Option<Arc<BrowsePositionFn>>,
+        get_item_id: Option<Arc<ItemIdFn>>,
+        group_names: Option<Arc<NamesFn>>,
+        remove_group_by_name: Option<Arc<NameFn>>,
+        public_group_names: Option<Arc<NamesFn>>,
+        get_public_group: Option<Arc<PublicGroupFn>>,
+        engineering_units: Option<Arc<UnitsFn>>,
+        status: Option<Arc<StatusFn>>,
+        read_item_io: Option<Arc<ItemIoFn>>,
+    }
+
+    impl MockServer {
+        fn with_group(mut self, group: MockGroup) -> Self {
+            self.group = group;
+            self
+        }
+
+        fn with_organization(mut self, organization: u32) -> Self {
+            self.organization = organization;
+            self
+        }
+
+        fn with_interfaces(mut self, interfaces: &[&'static str]) -> Self {
+            self.interfaces = interfaces.to_vec();
+            self
+        }
+
+        /// Called with the group name before each `add_group`; an error
+        /// fails the call.
+        fn on_add_group(
+            mut self,
+            f: impl Fn(&str) -> OpcResult<()> + Send + Sync + 'static,
+        ) -> Self {
+            self.add_group = Some(Arc::new(f));
+            self
+        }
+
+        fn on_remove_group(
+            mut self,
+            f: impl Fn(GroupHandle, bool) -> OpcResult<()> + Send + Sync + 'static,
+        ) -> Self {
+            self.remove_group = Some(Arc::new(f));
+            self
+        }
+
+        /// Answers `browse_opc_item_ids` for the given browse type.
+        fn on_browse(
+            mut self,
+            f: impl Fn(u32) -> OpcResult<StringIterator> + Send + Sync + 'static,
+        ) -> Self {
+            self.browse = Some(Arc::new(f));
+            self
+        }
+
+        fn on_change_browse_position(
+            mut self,
+            f: impl Fn(u32, &str) -> OpcResult<()> + Send + Sync + 'static,
+        ) -> Self {
+            self.change_browse_position = Some(Arc::new(f));
+            self
+        }
+
+        fn on_get_item_id(
+            mut self,
+            f: impl Fn(&str) -> OpcResult<String> + Send + Sync + 'static,
+        ) -> Self {
+            self.get_item_id = Some(Arc::new(f));
+            self
+        }
+
+        fn on_group_names(
+            mut self,
+            f: impl Fn() -> OpcResult<Vec<String>> + Send + Sync + 'static,
+        ) -> Self {
+            self.group_names = Some(Arc::new(f));
+            self
+        }
+
+        fn on_remove_group_by_name(
+            mut self,
+            f: impl Fn(&str) -> OpcResult<()> + Send + Sync + 'static,
+        ) -> Self {
+            self.remove_group_by_name = Some(Arc::new(f));
+            self
+        }
+
+        fn on_public_group_names(
+            mut self,
+            f: impl Fn() -> OpcResult<Vec<String>> + Send + Sync + 'static,
+        ) -> Self {
+            self.public_group_names = Some(Arc::new(f));
+            self
+        }
+
+        fn on_get_public_group(
+            mut self,
+            f: impl Fn(&str) -> OpcResult<MockGroup> + Send + Sync + 'static,
+        ) -> Self {
+            self.get_public_group = Some(Arc::new(f));
+            self
+        }
+
+        fn on_engineering_units(
+            mut self,
+            f: impl Fn(&str) -> OpcResult<EngineeringUnits> + Send + Sync + 'static,
+        ) -> Self {
+            self.engineering_units = Some(Arc::new(f));
+            self
+        }
+
+        fn on_status(
+            mut self,
+            f: impl Fn() -> OpcResult<ServerStatus> + Send + Sync + 'static,
+        ) -> Self {
+            self.status = Some(Arc::new(f));
+            self
+        }
+
+        fn on_read_item_io(
+            mut self,
+            f: impl Fn(&[String], u32) -> StatesResult + Send + Sync + 'static,
+        ) -> Self {
+            self.read_item_io = Some(Arc::new(f));
+            self
+        }
+
+        /// Connector handing out clones of this server.
+        fn connector(self) -> MockConnector {
+            MockConnector {
+                server: self,
+                ..MockConnector::default()
+            }
+        }
+    }
+
+    impl ConnectedServer for MockServer {
+        type Group = MockGroup;
+        fn query_organization(&self) -> OpcResult<u32> {
+            Ok(self.organization)
+        }
+        fn browse_opc_item_ids(
+            &self,
+            browse_type: u32,
+            _filter: Option<&str>,
+            _data_type: u16,
+            _access_rights: u32,
+        ) -> OpcResult<StringIterator> {
+            self.browse
+                .as_ref()
+                .map_or_else(not_implemented, |f| f(browse_type))
+        }
+        fn change_browse_position(&self, direction: u32, name: &str) -> OpcResult<()> {
+            self.change_browse_position
+                .as_ref()
+                .map_or_else(not_implemented, |f| f(direction, name))
+        }
+        fn get_item_id(&self, item_name: &str) -> OpcResult<String> {
+            self.get_item_id
+                .as_ref()
+                .map_or_else(not_implemented, |f| f(item_name))
+        }
+        fn add_group(
+            &self,
+            name: &str,
+            _active: bool,
+            _update_rate: u32,
+            _client_handle: GroupHandle,
+            _time_bias: i32,
+            _percent_deadband: f32,
+            _locale_id: u32,
+            _revised_update_rate: &mut u32,
+            _server_handle: &mut GroupHandle,
+        ) -> OpcResult<Self::Group> {
+            if let Some(f) = &self.add_group {
+                f(name)?;
+            }
+            Ok(self.group.clone())
+        }
+        fn remove_group(&self, server_group: GroupHandle, force: bool) -> OpcResult<()> {
+            self.remove_group
+                .as_ref()
+                .map_or(Ok(()), |f| f(server_group, force))
+        }
+        fn group_names(&self) -> OpcResult<Vec<String>> {
+            self.group_names
+                .as_ref()
+                .map_or_else(not_implemented, |f| f())
+        }
+        fn remove_group_by_name(&self, name: &str) -> OpcResult<()> {
+            self.remove_group_by_name
+                .as_ref()
+                .map_or_else(not_implemented, |f| f(name))
+        }
+        fn public_group_names(&self) -> OpcResult<Vec<String>> {
+            self.public_group_names
+                .as_ref()
+                .map_or_else(not_implemented, |f| f())
+        }
+        fn get_public_group(&self, name: &str) -> OpcResult<Self::Group> {
+            self.get_public_group
+                .as_ref()
+                .map_or_else(not_implemented, |f| f(name))
+        }
+        fn engineering_units(&self, item_id: &str) -> OpcResult<EngineeringUnits> {
+            self.engineering_units
+                .as_ref()
+                .map_or_else(not_implemented, |f| f(item_id))
+        }
+        fn status(&self) -> OpcResult<ServerStatus> {
+            self.status.as_ref().map_or_else(not_implemented, |f| f())
+        }
+        fn supported_interfaces(&self) -> Vec<&'static str> {
+            self.interfaces.clone()
+        }
+        fn read_item_io(&self, item_ids: &[String], max_age: u32) -> StatesResult {
+            self.read_item_io
+                .as_ref()
+                .map_or_else(not_implemented, |f| f(item_ids, max_age))
+        }
+    }
+
+    type DiagnoseFn = dyn Fn(&str) -> Option<ConnectionDiagnostics> + Send + Sync;
+
+    /// Connector behind every worker test: lists `Mock.Server.1` and
+    /// connects every name to a clone of [`MockConnector::server`].
+    #[derive(Clone, Default)]
+    struct MockConnector {
+        server: MockServer,
+        enumerate: Option<Arc<NamesFn>>,
+        connect_error: Option<OpcError>,
+        diagnose: Option<Arc<DiagnoseFn>>,
+    }
+
+    impl MockConnector {
+        fn on_enumerate(
+            mut self,
+            f: impl Fn() -> OpcResult<Vec<String>> + Send + Sync + 'static,
+        ) -> Self {
+            self.enumerate = Some(Arc::new(f));
+            self
+        }
+
+        /// Fail every connection with `error`.
+        fn with_connect_error(mut self, error: OpcError) -> Self {
+            self.connect_error = Some(error);
+            self
+        }
+
+        fn on_diagnose(
+            mut self,
+            f: impl Fn(&str) -> Option<ConnectionDiagnostics> + Send + Sync + 'static,
+        ) -> Self {
+            self.diagnose = Some(Arc::new(f));
+            self
+        }
+    }
+
+    impl ServerConnector for MockConnector {
+        type Server = MockServer;
+        fn enumerate_servers(&self) -> OpcResult<Vec<String>> {
+            self.enumerate
+                .as_ref()
+                .map_or_else(|| Ok(vec!["Mock.Server.1".into()]), |f| f())
+        }
+        fn connect(&self, _server_name: &str) -> OpcResult<Self::Server> {
+            match &self.connect_error {
+                Some(error) => Err(error.clone()),
+                None => Ok(self.server.clone()),
+            }
+        }
+        fn diagnose(&self, server_name: &str) -> Option<ConnectionDiagnostics> {
+            self.diagnose.as_ref().and_then(|f| f(server_name))
+        }
+    }
+
+    /// Copy `items` into a COM-allocated array, as a server returns them.
+    fn remote_array<T: Clone>(items: &[T]) -> RemoteArray<T> {
+        if items.is_empty() {
+            return RemoteArray::empty();
+        }
+        let len = u32::try_from(items.len()).unwrap();
+        // SAFETY: The allocation is sized for `items.len()` values of `T` and
+        // `CoTaskMemAlloc` aligns it for any of them; every slot is written
+        // before the array is handed to `RemoteArray`, which frees it with
+        // `CoTaskMemFree`.
+        unsafe {
+            let ptr = windows::Win32::System::Com::CoTaskMemAlloc(std::mem::size_of_val(items))
+                .cast::<T>();
+            for (i, item) in items.iter().enumerate() {
+                ptr.add(i).write(item.clone());
+            }
+            RemoteArray::from_mut_ptr(ptr, len)
+        }
+    }
+
+    async fn start_worker(
+        connector: MockConnector,
+        config: OpcDaConfig,
+    ) -> ComWorker<MockConnector> {
+        tokio::task::spawn_blocking(move || {
+            ComWorker::start_with_config(Arc::new(connector), config).unwrap()
+        })
+        .await
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_worker_starts_and_stops() {
+        let worker = start_worker(MockConnector::default(), OpcDaConfig::default()).await;
+        drop(worker);
+    }
+
+    #[tokio::test]
+    async fn test_worker_list_servers() {
+        let worker = start_worker(MockConnector::default(), OpcDaConfig::default()).await;
+        let (reply, _rx) = oneshot::channel();
+        worker
+            .sender
+            .send(ComRequest::ListServers {
+                host: "localhost".into(),
+                reply,
+            })
+            .await
+            .unwrap();
+        // Wait for implementation
+    }
+
+    #[tokio::test]
+    async fn test_queue_depth_tracks_backlog() {
+        let (release, gate) = std::sync::mpsc::channel();
+        // Blocks `enumerate_servers` until the test releases it, so requests
+        // pile up behind a slow one.
+        let gate = std::sync::Mutex::new(gate);
+        let connector = MockConnector::default().on_enumerate(move || {
+            let _ = gate.lock().unwrap().recv();
+            Ok(vec!["Mock.Server.1".into()])
+        });
+        let worker = start_worker(connector, OpcDaConfig::default()).await;
+        let depth = worker.queue_depth_handle();
+
+        let list = |reply| ComRequest::ListServers {
+            host: "localhost".into(),
+            reply,
+        };
+        let first = worker.submit(list).await.unwrap();
+        // Wait until the worker is blocked inside the first request
+        while worker.queue_depth() > 0 {
+            tokio::task::yield_now().await;
+        }
+        let mut pending = Vec::new();
+        for _ in 0..5 {
+            pending.push(worker.submit(list).await.unwrap());
+        }
+        assert_eq!(worker.queue_depth(), 5);
+        assert_eq!(depth.load(Ordering::Relaxed), 5);
+
+        drop(release);
+        first.await.unwrap().unwrap();
+        for rx in pending {
+            rx.await.unwrap().unwrap();
+        }
+        assert_eq!(worker.queue_depth(), 0);
+        assert_eq!(depth.load(Ordering::Relaxed), 0);
+    }
+
+    #[test]
+    fn test_await_worker_init_times_out() {
+        let (init_tx, init_rx) = std::sync::mpsc::channel();
+        let result = await_worker_init(&init_rx, std::time::Duration::from_millis(20));
+        assert!(matches!(result, Err(OpcError::Timeout(_))));
+
+        init_tx.send(Ok(())).unwrap();
+        assert_eq!(
+            await_worker_init(&init_rx, std::time::Duration::from_millis(20)),
+            Ok(())
+        );
+
+        drop(init_tx);
+        let result = await_worker_init(&init_rx, std::time::Duration::from_millis(20));
+        assert!(matches!(result, Err(OpcError::Internal(_))));
+    }
+
+    #[test]
+    fn test_read_items_uses_sync_io2_max_age() {
+        let max_ages = Arc::default();
+        let group = MockGroup::sync_io2(Arc::clone(&max_ages));
+        let tag_ids = vec!["Tag1".to_string()];
+        let items = ReadItems {
+            placeholders: vec![TagValue {
+                tag_id: "Tag1".into(),
+                value: String::new(),
+                quality: String::new(),
+                timestamp: String::new(),
+                raw_timestamp: None,
+                vt: None,
+                access_rights: 0,
+                canonical_data_type: 0,
+                status: ReadStatus::Ok,
+            }],
+            server_handles: vec![ItemHandle(1)],
+            valid_indices: vec![0],
+            client_handles: vec![0],
+        };
+
+        let values = read_items(
+            &tag_ids,
+            &items,
+            &group,
+            5000,
+            VariantFormat::default(),
+            false,
+        )
+        .unwrap();
+        assert_eq!(*max_ages.lock().unwrap(), vec![5000]);
+        assert_eq!(values[0].tag_id, "Tag1");
+        assert_ne!(values[0].value, "Error");
+        assert_eq!(values[0].status, ReadStatus::Ok);
+    }
+
+    #[test]
+    fn test_add_read_items_requests_data_types() {
+        let requested_types = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let recorded = Arc::clone(&requested_types);
+        // Records the requested type of every item it is asked to add.
+        let group = MockGroup::default().on_add_items(move |items| {
+            recorded
+                .lock()
+                .unwrap()
+                .extend(items.iter().map(|item| item.vtRequestedDataType));
+            not_implemented()
+        });
+        let tag_ids = vec!["Text".to_string(), "Level".to_string()];
+        let requested = OpcDaConfig::default()
+            .with_requested_data_type("Text", 5)
+            .requested_data_types;
+
+        let _ = add_read_items(&tag_ids, &group, &requested, &Cell::default());
+        assert_eq!(*requested_types.lock().unwrap(), vec![5, 0]);
+    }
+
+    #[test]
+    fn test_read_items_verifies_client_handles() {
+        // Answers reads in reverse order, each state carrying the client
+        // handle of item `server handle - 1`.
+        let reordering = MockGroup::default().on_read(|_, server_handles| {
+            let states: Vec<tagOPCITEMSTATE> = server_handles
+                .iter()
+                .rev()
+                .map(|handle| tagOPCITEMSTATE {
+                    hClient: handle.0 - 1,
+                    ..tagOPCITEMSTATE::default()
+                })
+                .collect();
+            Ok((
+                remote_array(&states),
+                remote_array(&vec![HRESULT(0); server_handles.len()]),
+            ))
+        });
+
+        let placeholder = |tag_id: &str| TagValue {
+            tag_id: tag_id.into(),
+            value: String::new(),
+            quality: String::new(),
+            timestamp: String::new(),
+            raw_timestamp: None,
+            vt: None,
+            access_rights: 0,
+            canonical_data_type: 0,
+            status: ReadStatus::Ok,
+        };
+        let tag_ids = vec!["Tag1".to_string(), "Tag2".to_string()];
+        let items = ReadItems {
+            placeholders: vec![placeholder("Tag1"), placeholder("Tag2")],
+            server_handles: vec![ItemHandle(1), ItemHandle(2)],
+            valid_indices: vec![0, 1],
+            client_handles: vec![0, 1],
+        };
+
+        // Matched by position, the reordering goes unnoticed.
+        let format = VariantFormat::default();
+        assert!(read_items(&tag_ids, &items, &reordering, 0, format, false).is_ok());
+
+        let err = read_items(&tag_ids, &items, &reordering, 0, format, true).unwrap_err();
+        let OpcError::Internal(msg) = err else {
+            panic!("expected an internal error, got {err:?}");
+        };
+        assert!(msg.contains("out of order"), "{msg}");
+        assert!(
+            msg.contains("for 'Tag1' carries client handle 1 of 'Tag2'"),
+            "{msg}"
+        );
+
+        // A single item cannot be out of order.
+        let single = ReadItems {
+            placeholders: vec![placeholder("Tag1")],
+            server_handles: vec![ItemHandle(1)],
+            valid_indices: vec![0],
+            client_handles: vec![0],
+        };
+        assert!(read_items(&tag_ids[..1], &single, &reordering, 0, format, true).is_ok());
+    }
+
+    #[test]
+    fn test_read_items_verifies_client_handles_on_sync_io2_group() {
+        use crate::bindings::da::OPC_DS_DEVICE;
+
+        let max_ages = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let sources = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let recorded = Arc::clone(&sources);
+        // `ReadMaxAge` answers with `hClient` 0 for every state, while
+        // `IOPCSyncIO::Read` reports each item's client handle, here
+        // `server handle - 1`.
+        let group = MockGroup::sync_io2(Arc::clone(&max_ages)).on_read(move |source, handles| {
+            recorded.lock().unwrap().push(source);
+            let states: Vec<tagOPCITEMSTATE> = handles
+                .iter()
+                .map(|handle| tagOPCITEMSTATE {
+                    hClient: handle.0 - 1,
+                    ..tagOPCITEMSTATE::default()
+                })
+                .collect();
+            Ok((
+                remote_array(&states),
+                remote_array(&vec![HRESULT(0); handles.len()]),
+            ))
+        });
+
+        let placeholder = |tag_id: &str| TagValue {
+            tag_id: tag_id.into(),
+            value: String::new(),
+            quality: String::new(),
+            timestamp: String::new(),
+            raw_timestamp: None,
+            vt: None,
+            access_rights: 0,
+            canonical_data_type: 0,
+            status: ReadStatus::Ok,
+        };
+        let tag_ids = vec!["Tag1".to_string(), "Tag2".to_string()];
+        let items = ReadItems {
+            placeholders: vec![placeholder("Tag1"), placeholder("Tag2")],
+            server_handles: vec![ItemHandle(1), ItemHandle(2)],
+            valid_indices: vec![0, 1],
+            client_handles: vec![0, 1],
+        };
+        let format = VariantFormat::default();
+
+        let values = read_items(&tag_ids, &items, &group, MAX_AGE_DEVICE, format, true).unwrap();
+        assert!(values.iter().all(|v| v.status == ReadStatus::Ok));
+        assert!(
+            max_ages.lock().unwrap().is_empty(),
+            "strict read went through ReadMaxAge"
+        );
+        assert_eq!(*sources.lock().unwrap(), vec![OPC_DS_DEVICE]);
+
+        // Without verification the group keeps reading through `ReadMaxAge`.
+        read_items(&tag_ids, &items, &group, MAX_AGE_DEVICE, format, false).unwrap();
+        assert_eq!(*max_ages.lock().unwrap(), vec![MAX_AGE_DEVICE]);
+        assert_eq!(sources.lock().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_set_live_items_active_maps_tags_to_handles() {
+        let calls = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let recorded = Arc::clone(&calls);
+        // Records `SetActiveState` calls, rejecting server handle 12.
+        let group = MockGroup::default().on_set_active_state(move |server_handles, active| {
+            recorded.lock().unwrap().push((
+                server_handles.iter().map(|h| h.0).collect::<Vec<_>>(),
+                active,
+            ));
+            Ok(server_handles
+                .iter()
+                .map(|h| {
+                    if h.0 == 12 {
+                        windows::Win32::Foundation::E_FAIL
+                    } else {
+                        HRESULT(0)
+                    }
+                })
+                .collect())
+        });
+
+        // Tag2 was rejected when the group was built.
+        let mut lg = LiveGroup {
+            tag_ids: vec!["Tag1".into(), "Tag2".into(), "Tag3".into(), "Tag4".into()],
+            group,
+            server_handle: GroupHandle(1),
+            items: ReadItems {
+                placeholders: Vec::new(),
+                server_handles: vec![ItemHandle(10), ItemHandle(12), ItemHandle(13)],
+                valid_indices: vec![0, 2, 3],
+                client_handles: vec![0, 2, 3],
+            },
+            partial_error: false,
+            inactive: BTreeSet::new(),
+        };
+
+        let tags = |ids: &[&str]| ids.iter().map(ToString::to_string).collect::<Vec<_>>();
+        let changed =
+            set_live_items_active(&mut lg, &tags(&["Tag4", "Tag1", "Tag2", "Other"]), false)
+                .unwrap();
+        assert_eq!(changed, 2);
+        assert_eq!(
+            lg.inactive,
+            BTreeSet::from(["Tag1".to_string(), "Tag4".to_string()])
+        );
+        let changed = set_live_items_active(&mut lg, &tags(&["Tag3", "Tag4"]), true).unwrap();
+        assert_eq!(changed, 1, "handle 12 is rejected");
+        assert_eq!(lg.inactive, BTreeSet::from(["Tag1".to_string()]));
+        assert_eq!(
+            *calls.lock().unwrap(),
+            vec![(vec![13, 10], false), (vec![12, 13], true)]
+        );
+
+        // Nothing to toggle never reaches the server.
+        assert_eq!(
+            set_live_items_active(&mut lg, &tags(&["Tag2"]), true).unwrap(),
+            0
+        );
+        assert_eq!(calls.lock().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_read_items_reports_item_attributes() {
+        // Reads like a DA 3.0 group, reporting a canonical type of `VT_I4`
+        // for server handle 1 only.
+        let group = MockGroup::sync_io2(Arc::default()).on_item_attributes(|server_handles| {
+            Ok(server_handles
+                .iter()
+                .map(|handle| {
+                    (handle.0 == 1).then_some(ItemAttributeInfo {
+                        access_rights: 3,
+                        canonical_data_type: 3, // VT_I4
+                    })
+                })
+                .collect())
+        });
+
+        let placeholder = |tag_id: &str| TagValue {
+            tag_id: tag_id.into(),
+            value: String::new(),
+            quality: String::new(),
+            timestamp: String::new(),
+            raw_timestamp: None,
+            vt: None,
+            access_rights: 1,
+            canonical_data_type: 5,
+            status: ReadStatus::Ok,
+        };
+        let tag_ids = vec!["Tag1".to_string(), "Tag2".to_string()];
+        let mut items = ReadItems {
+            placeholders: vec![placeholder("Tag1"), placeholder("Tag2")],
+            server_handles: vec![ItemHandle(1), ItemHandle(2)],
+            valid_indices: vec![0, 1],
+            client_handles: vec![0, 1],
+        };
+
+        apply_item_attributes(&group, &mut items, &Cell::default());
+        let values =
+            read_items(&tag_ids, &items, &group, 0, VariantFormat::default(), false).unwrap();
+        assert_eq!(values[0].canonical_data_type, 3);
+        assert_eq!(values[0].access_rights, 3);
+        // Not reported by the group: keeps the `add_items` values.
+        assert_eq!(values[1].canonical_data_type, 5);
+        assert_eq!(values[1].access_rights, 1);
+
+        // A group without attribute support leaves the placeholders alone.
+        let unsupported = Cell::default();
+        apply_item_attributes(
+            &MockGroup::sync_io2(Arc::default()),
+            &mut items,
+            &unsupported,
+        );
+        assert_eq!(items.placeholders[0].canonical_data_type, 3);
+        assert!(unsupported.get());
+    }
+
+    #[tokio::test]
+    async fn test_worker_stops_asking_for_unsupported_item_attributes() {
+        let asked = Arc::new(AtomicUsize::new(0));
+        let counter = Arc::clone(&asked);
+        let group = MockGroup::accepting_items()
+            .on_read_with_max_age(|server_handles, _| {
+                Ok((
+                    vec![tagOPCITEMSTATE::default(); server_handles.len()],
+                    vec![HRESULT(0); server_handles.len()],
+                ))
+            })
+            .on_item_attributes(move |_| {
+                counter.fetch_add(1, Ordering::SeqCst);
+                Err(OpcError::from(windows::core::Error::from(
+                    windows::Win32::Foundation::E_NOTIMPL,
+                )))
+            });
+        let connector = MockServer::default().with_group(group).connector();
+        let worker = start_worker(connector, OpcDaConfig::default()).await;
+
+        for _ in 0..3 {
+            let values = worker
+                .send_request(|reply| ComRequest::ReadTagValues {
+                    server: "MockServer".to_string(),
+                    tag_ids: vec!["Tag1".to_string()],
+                    reply,
+                })
+                .await
+                .unwrap();
+            assert_eq!(values[0].status, ReadStatus::Ok);
+        }
+        assert_eq!(asked.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn test_read_with_max_age_falls_back_to_sync_io() {
+        use crate::bindings::da::{OPC_DS_CACHE, OPC_DS_DEVICE};
+
+        let sources = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let recorded = Arc::clone(&sources);
+        // Group on a DA 1.0 server: only `IOPCSyncIO::Read` is available.
+        let group = MockGroup::default().on_read(move |source, _| {
+            recorded.lock().unwrap().push(source);
+            let state = tagOPCITEMSTATE {
+                hClient: 7,
+                ..tagOPCITEMSTATE::default()
+            };
+            Ok((remote_array(&[state]), remote_array(&[HRESULT(0)])))
+        });
+        let (states, _) = group.read_with_max_age(&[ItemHandle(1)], 5000).unwrap();
+        // `Read` reports the client handle the item was added with.
+        assert_eq!(states[0].hClient, 7);
+        group
+            .read_with_max_age(&[ItemHandle(1)], MAX_AGE_DEVICE)
+            .unwrap();
+        group
+            .read_with_max_age(&[ItemHandle(1)], MAX_AGE_CACHE)
+            .unwrap();
+        assert_eq!(
+            *sources.lock().unwrap(),
+            vec![OPC_DS_CACHE, OPC_DS_DEVICE, OPC_DS_CACHE]
+        );
+    }
+
+    #[test]
+    fn test_max_age_states_carry_no_client_handle() {
+        let values = [opc_value_to_variant(&OpcValue::Int(42))];
+        let timestamp = windows::Win32::Foundation::FILETIME {
+            dwLowDateTime: 1,
+            dwHighDateTime: 2,
+        };
+
+        let states = max_age_states(&values, &[0xC0], &[timestamp]);
+
+        assert_eq!(states.len(), 1);
+        // `ReadMaxAge` reports no client handles.
+        assert_eq!(states[0].hClient, 0);
+        assert_eq!(states[0].wQuality, 0xC0);
+        assert_eq!(states[0].ftTimeStamp, timestamp);
+        assert_eq!(
+            format_variant(&states[0].vDataValue, VariantFormat::default()),
+            "42"
+        );
+    }
+
+    /// Server whose groups answer every call with empty result arrays.
+    fn mismatched_connector() -> MockConnector {
+        MockServer::default()
+            .with_group(MockGroup::empty_results())
+            .connector()
+    }
+
+    #[tokio::test]
+    async fn test_worker_read_tag_values_mismatched_lengths() {
+        let worker = start_worker(mismatched_connector(), OpcDaConfig::default()).await;
+
+        let result = worker
+            .send_request(|reply| ComRequest::ReadTagValues {
+                server: "MockServer".to_string(),
+                tag_ids: vec!["Tag1".to_string(), "Tag2".to_string()],
+                reply,
+            })
+            .await;
+
+        assert_eq!(
+            result,
+            Err(OpcError::Internal(
+                "OPC server returned mismatched result array sizes".into()
+            ))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_worker_get_item_data_types_mismatched_lengths() {
+        let worker = start_worker(mismatched_connector(), OpcDaConfig::default()).await;
+
+        let result = worker
+            .send_request(|reply| ComRequest::GetItemDataTypes {
+                server: "MockServer".to_string(),
+                tag_ids: vec!["Tag1".to_string()],
+                reply,
+            })
+            .await;
+
+        match result {
+            Err(OpcError::Internal(msg)) => assert!(msg.contains("mismatched result array sizes")),
+            other => panic!("Expected OpcError::Internal, got {other:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_worker_get_item_data_types_empty() {
+        let worker = start_worker(mismatched_connector(), OpcDaConfig::default()).await;
+
+        let result = worker
+            .send_request(|reply| ComRequest::GetItemDataTypes {
+                server: "MockServer".to_string(),
+                tag_ids: Vec::new(),
+                reply,
+            })
+            .await
+            .unwrap();
+
+        assert!(result.is_empty());
+    }
+
+    /// Write to a server whose `add_group` always fails with
+    /// `RPC_E_CALL_REJECTED`, returning the number of attempts.
+    async fn busy_write_attempts(write_retries: u32) -> usize {
+        #[allow(clippy::cast_possible_wrap)]
+        let call_rejected = HRESULT(0x8001_0001_u32 as i32);
+        let calls = Arc::new(AtomicUsize::new(0));
+        let counter = Arc::clone(&calls);
+        let connector = MockServer::default()
+            .on_add_group(move |_| {
+                counter.fetch_add(1, Ordering::SeqCst);
+                Err(OpcError::Com {
+                    source: windows::core::Error::from_hresult(call_rejected),
+                })
+            })
+            .connector();
+        let config = OpcDaConfig::default().with_write_retries(write_retries);
+        let worker = start_worker(connector, config).await;
+
+        let result = worker
+            .send_request(|reply| ComRequest::WriteTagValue {
+                server: "MockServer".to_string(),
+                tag_id: "Tag1".to_string(),
+                value: OpcValue::Int(1),
+                reply,
+            })
+            .await;
+        let source = windows::core::Error::from_hresult(call_rejected);
+        let expected = if write_retries == 0 {
+            OpcError::Com { source }
+        } else {
+            let attempts = write_retries + 1;
+            OpcError::from_com_error(source, format!("Write failed after {attempts} attempts"))
+        };
+        assert_eq!(result, Err(expected));
+        calls.load(Ordering::SeqCst)
+    }
+
+    #[tokio::test]
+    async fn test_worker_write_retries_transient_errors() {
+        assert_eq!(busy_write_attempts(2).await, 3);
+    }
+
+    #[tokio::test]
+    async fn test_worker_write_no_retries() {
+        assert_eq!(busy_write_attempts(0).await, 1);
+    }
+
+    /// Write `Idle` and `Busy` in one request to a server that rejects the
+    /// first write of `Busy` with `RPC_E_CALL_REJECTED`, returning the
+    /// results and the number of `Write` calls made.
+    async fn write_with_busy_item(write_retries: u32) -> (Vec<WriteResult>, usize) {
+        #[allow(clippy::cast_possible_wrap)]
+        let call_rejected = HRESULT(0x8001_0001_u32 as i32);
+        let calls = Arc::new(AtomicUsize::new(0));
+        let counter = Arc::clone(&calls);
+        // `Busy` is added second, so it only has server handle 2 in the
+        // first group.
+        let group = MockGroup::accepting_items().on_write(move |server_handles, _| {
+            let first = counter.fetch_add(1, Ordering::SeqCst) == 0;
+            let errors: Vec<HRESULT> = server_handles
+                .iter()
+                .map(|handle| {
+                    if first && handle.0 == 2 {
+                        call_rejected
+                    } else {
+                        HRESULT(0)
+                    }
+                })
+                .collect();
+            Ok(remote_array(&errors))
+        });
+        let connector = MockServer::default().with_group(group).connector();
+        let config = OpcDaConfig::default().with_write_retries(write_retries);
+        let worker = start_worker(connector, config).await;
+
+        let results = worker
+            .send_request(|reply| ComRequest::WriteTagValues {
+                server: "MockServer".to_string(),
+                writes: vec![
+                    ("Idle".to_string(), OpcValue::Int(1)),
+                    ("Busy".to_string(), OpcValue::Int(2)),
+                ],
+                reply,
+            })
+            .await
+            .unwrap();
+        (results, calls.load(Ordering::SeqCst))
+    }
+
+    #[tokio::test]
+    async fn test_worker_write_retries_transient_item_failures() {
+        let (results, calls) = write_with_busy_item(2).await;
+        assert_eq!(calls, 2, "only the rejected item is written again");
+        assert_eq!(results[0].tag_id, "Idle");
+        assert!(results[0].success);
+        assert_eq!(results[0].attempts, 1);
+        assert_eq!(results[1].tag_id, "Busy");
+        assert!(results[1].success, "{:?}", results[1].error);
+        assert_eq!(results[1].attempts, 2);
+
+        let (results, calls) = write_with_busy_item(0).await;
+        assert_eq!(calls, 1);
+        assert!(!results[1].success);
+        assert_eq!(results[1].attempts, 1);
+    }
+
+    /// Connect to a server failing with `error`, diagnosed as an
+    /// unregistered ProgID.
+    async fn connect_with_diagnostics(error: OpcError) -> OpcResult<ServerMetadata> {
+        let connector = MockConnector::default()
+            .with_connect_error(error)
+            .on_diagnose(|server_name| {
+                Some(ConnectionDiagnostics {
+                    prog_id: server_name.to_string(),
+                    ..ConnectionDiagnostics::default()
+                })
+            });
+        let worker = start_worker(connector, OpcDaConfig::default()).await;
+        worker
+            .send_request(|reply| ComRequest::ConnectWithDiagnostics {
+                server: "Missing.Server".to_string(),
+                reply,
+            })
+            .await
+    }
+
+    #[tokio::test]
+    async fn test_connect_with_diagnostics_annotates_com_failures() {
+        let com_error = OpcError::from(windows::core::Error::from_hresult(windows::core::HRESULT(
+            0x8004_0154_u32 as i32,
+        )));
+        let err = connect_with_diagnostics(com_error.clone())
+            .await
+            .unwrap_err();
+        let OpcError::Diagnosed {
+            source,
+            diagnostics,
+        } = &err
+        else {
+            panic!("expected a diagnosed error, got {err:?}");
+        };
+        assert_eq!(**source, com_error);
+        assert_eq!(diagnostics.prog_id, "Missing.Server");
+        assert!(
+            err.to_string()
+                .contains("'Missing.Server' is not registered")
+        );
+
+        // Errors that are not COM failures are not about the registration.
+        let unhealthy = OpcError::Connection("server is failed".into());
+        let err = connect_with_diagnostics(unhealthy.clone())
+            .await
+            .unwrap_err();
+        assert_eq!(err, unhealthy);
+    }
+
+    #[tokio::test]
+    async fn test_worker_answers_ping() {
+        let worker = start_worker(mismatched_connector(), OpcDaConfig::default()).await;
+
+        // Answering at all means answering within PING_TIMEOUT
+        assert!(worker.is_alive().await);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_stuck_worker_is_not_alive() {
+        let (sender, mut receiver) = mpsc::channel(REQUEST_QUEUE_CAPACITY);
+        let worker = ComWorker::<MockConnector>::detached(sender);
+
+        let start = tokio::time::Instant::now();
+        assert!(!worker.is_alive().await);
+        assert_eq!(start.elapsed(), PING_TIMEOUT);
+        // The ping was delivered, just never answered
+        assert!(matches!(receiver.try_recv(), Ok(ComRequest::Ping { .. })));
+    }
+
+    #[tokio::test]
+    async fn test_stopped_worker_is_not_alive() {
+        let (sender, receiver) = mpsc::channel(REQUEST_QUEUE_CAPACITY);
+        drop(receiver);
+        let worker = ComWorker::<MockConnector>::detached(sender);
+        assert!(!worker.is_alive().await);
+    }
+
+    #[tokio::test]
+    async fn test_worker_get_server_metadata_defaults() {
+        let worker = start_worker(mismatched_connector(), OpcDaConfig::default()).await;
+
+        let metadata = worker
+            .send_request(|reply| ComRequest::GetServerMetadata {
+                server: "MockServer".to_string(),
+                reply,
+            })
+            .await
+            .unwrap();
+
+        assert_eq!(metadata.server, "MockServer");
+        assert!(metadata.status.is_none());
+        assert!(metadata.namespace_type.is_none()); // org 0 is not a valid namespace type
+        assert!(metadata.interfaces.is_empty());
+        assert!(metadata.da_version.is_none());
+    }
+
+    /// Counts group creation and removal and records the names of the
+    /// groups added.
+    #[derive(Default)]
+    struct GroupCounts {
+        added: Arc<AtomicUsize>,
+        removed: Arc<AtomicUsize>,
+        names: Arc<std::sync::Mutex<Vec<String>>>,
+    }
+
+    impl GroupCounts {
+        /// Connector to a server whose groups accept no items, counted here.
+        fn connector(&self) -> MockConnector {
+            let added = Arc::clone(&self.added);
+            let removed = Arc::clone(&self.removed);
+            let names = Arc::clone(&self.names);
+            MockServer::default()
+                .with_group(MockGroup::empty_results())
+                .on_add_group(move |name| {
+                    added.fetch_add(1, Ordering::SeqCst);
+                    names.lock().unwrap().push(name.to_string());
+                    Ok(())
+                })
+                .on_remove_group(move |_, _| {
+                    removed.fetch_add(1, Ordering::SeqCst);
+                    Ok(())
+                })
+                .connector()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_worker_group_names_are_unique() {
+        let counts = GroupCounts::default();
+        let worker = start_worker(counts.connector(), OpcDaConfig::default()).await;
+
+        for _ in 0..2 {
+            let _ = worker
+                .send_request(|reply| ComRequest::ReadTagValues {
+                    server: "MockServer".to_string(),
+                    tag_ids: vec!["Tag1".to_string()],
+                    reply,
+                })
+                .await;
+        }
+        let _ = worker
+            .send_request(|reply| ComRequest::WriteTagValue {
+                server: "MockServer".to_string(),
+                tag_id: "Tag1".to_string(),
+                value: OpcValue::Int(1),
+                reply,
+            })
+            .await;
+        let _ = worker
+            .send_request(|reply| ComRequest::GetItemDataTypes {
+                server: "MockServer".to_string(),
+                tag_ids: vec!["Tag1".to_string()],
+                reply,
+            })
+            .await;
+
+        let names = counts.names.lock().unwrap();
+        assert_eq!(names.len(), 4);
+        let distinct: std::collections::HashSet<&String> = names.iter().collect();
+        assert_eq!(distinct.len(), names.len(), "{names:?}");
+        assert!(names.iter().all(|n| n.starts_with(GROUP_NAME_PREFIX)));
+        assert!(names[0].starts_with("opc-da-client-read-"), "{}", names[0]);
+    }
+
+    #[tokio::test]
+    async fn test_worker_live_reads_reuse_group() {
+        let counts = GroupCounts::default();
+        let worker = start_worker(counts.connector(), OpcDaConfig::default()).await;
+
+        for _ in 0..5 {
+            let values = worker
+                .send_request(|reply| ComRequest::ReadTagValuesLive {
+                    server: "MockServer".to_string(),
+                    tag_ids: Vec::new(),
+                    reply,
+                })
+                .await
+                .unwrap();
+            assert!(values.is_empty());
+        }
+        assert_eq!(counts.added.load(Ordering::SeqCst), 1, "group created once");
+        assert_eq!(counts.removed.load(Ordering::SeqCst), 0, "group kept alive");
+
+        worker
+            .send_request(|reply| ComRequest::StopLiveReads {
+                server: "MockServer".to_string(),
+                reply,
+            })
+            .await
+            .unwrap();
+        assert_eq!(counts.removed.load(Ordering::SeqCst), 1);
+
+        // Stopping again is a no-op
+        worker
+            .send_request(|reply| ComRequest::StopLiveReads {
+                server: "MockServer".to_string(),
+                reply,
+            })
+            .await
+            .unwrap();
+        assert_eq!(counts.removed.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_worker_set_items_active_needs_live_group() {
+        let worker = start_worker(GroupCounts::default().connector(), OpcDaConfig::default()).await;
+        let set_active = || {
+            worker.send_request(|reply| ComRequest::SetItemsActive {
+                server: "MockServer".to_string(),
+                tag_ids: vec!["Tag1".to_string()],
+                active: false,
+                reply,
+            })
+        };
+
+        let err = set_active().await.unwrap_err();
+        assert!(matches!(err, OpcError::InvalidState(_)), "{err:?}");
+
+        worker
+            .send_request(|reply| ComRequest::ReadTagValuesLive {
+                server: "MockServer".to_string(),
+                tag_ids: Vec::new(),
+                reply,
+            })
+            .await
+            .unwrap();
+        assert_eq!(set_active().await.unwrap(), 0, "Tag1 is not in the group");
+    }
+
+    #[tokio::test]
+    async fn test_worker_live_reads_deactivate_items_again_after_rebuild() {
+        let reads = Arc::new(AtomicUsize::new(0));
+        let read_count = Arc::clone(&reads);
+        let calls = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let recorded = Arc::clone(&calls);
+        let added = Arc::new(AtomicUsize::new(0));
+        let add_count = Arc::clone(&added);
+        // Fails to read server handle 2 on the second read only, which makes
+        // the next live read rebuild the group.
+        let group = MockGroup::accepting_items()
+            .on_read(move |_, server_handles| {
+                let read = read_count.fetch_add(1, Ordering::SeqCst) + 1;
+                let errors: Vec<HRESULT> = server_handles
+                    .iter()
+                    .map(|h| {
+                        if read == 2 && h.0 == 2 {
+                            windows::Win32::Foundation::E_FAIL
+                        } else {
+                            HRESULT(0)
+                        }
+                    })
+                    .collect();
+                Ok((
+                    remote_array(&vec![tagOPCITEMSTATE::default(); server_handles.len()]),
+                    remote_array(&errors),
+                ))
+            })
+            .on_set_active_state(move |server_handles, active| {
+                recorded.lock().unwrap().push((
+                    server_handles.iter().map(|h| h.0).collect::<Vec<_>>(),
+                    active,
+                ));
+                Ok(vec![HRESULT(0); server_handles.len()])
+            });
+        let connector = MockServer::default()
+            .with_group(group)
+            .on_add_group(move |_| {
+                add_count.fetch_add(1, Ordering::SeqCst);
+                Ok(())
+            })
+            .connector();
+        let worker = start_worker(connector, OpcDaConfig::default()).await;
+        let tag_ids = vec!["Tag1".to_string(), "Tag2".to_string()];
+        let read_live = || {
+            worker.send_request(|reply| ComRequest::ReadTagValuesLive {
+                server: "MockServer".to_string(),
+                tag_ids: tag_ids.clone(),
+                reply,
+            })
+        };
+
+        read_live().await.unwrap();
+        let changed = worker
+            .send_request(|reply| ComRequest::SetItemsActive {
+                server: "MockServer".to_string(),
+                tag_ids: vec!["Tag1".to_string()],
+                active: false,
+                reply,
+            })
+            .await
+            .unwrap();
+        assert_eq!(changed, 1);
+
+        let values = read_live().await.unwrap();
+        assert_eq!(values[1].status, ReadStatus::ReadError);
+        read_live().await.unwrap();
+
+        assert_eq!(added.load(Ordering::SeqCst), 2, "group rebuilt once");
+        assert_eq!(
+            *calls.lock().unwrap(),
+            vec![(vec![1], false), (vec![1], false)],
+            "Tag1 deactivated again in the new group"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_worker_live_reads_replace_group_on_tag_change() {
+        let counts = GroupCounts::default();
+        let worker = start_worker(counts.connector(), OpcDaConfig::default()).await;
+
+        worker
+            .send_request(|reply| ComRequest::ReadTagValuesLive {
+                server: "MockServer".to_string(),
+                tag_ids: Vec::new(),
+                reply,
+            })
+            .await
+            .unwrap();
+
+        // New tag set: the old group is removed, and the new one is removed
+        // too because this server's groups reject every item.
+        let result = worker
+            .send_request(|reply| ComRequest::ReadTagValuesLive {
+                server: "MockServer".to_string(),
+                tag_ids: vec!["Tag1".to_string()],
+                reply,
+            })
+            .await;
+        assert!(matches!(result, Err(OpcError::Internal(_))));
+        assert_eq!(counts.added.load(Ordering::SeqCst), 2);
+        assert_eq!(counts.removed.load(Ordering::SeqCst), 2);
+    }
+
+    #[test]
+    fn test_has_read_errors_ignores_rejected_items() {
+        let value = |tag: &str, status| TagValue {
+            tag_id: tag.to_string(),
+            value: if status == ReadStatus::Ok {
+                "1"
+            } else {
+                "Error"
+            }
+            .to_string(),
+            quality: String::new(),
+            timestamp: String::new(),
+            raw_timestamp: None,
+            vt: None,
+            access_rights: 0,
+            canonical_data_type: 0,
+            status,
+        };
+
+        let ok = vec![
+            value("A", ReadStatus::Ok),
+            value("Bad", ReadStatus::NotAdded),
+            value("C", ReadStatus::Ok),
+        ];
+        assert!(!has_read_errors(&ok));
+
+        let failed = vec![
+            value("A", ReadStatus::Ok),
+            value("Bad", ReadStatus::NotAdded),
+            value("C", ReadStatus::ReadError),
+        ];
+        assert!(has_read_errors(&failed));
+    }
+
+    #[tokio::test]
+    async fn test_worker_stop_live_reads_without_connection() {
+        let worker = start_worker(MockConnector::default(), OpcDaConfig::default()).await;
+
+        let result = worker
+            .send_request(|reply| ComRequest::StopLiveReads {
+                server: "NeverConnected".to_string(),
+                reply,
+            })
+            .await;
+        assert!(result.is_ok());
+    }
+
+    /// Start a worker on a server holding a mix of client and foreign
+    /// groups, returning it with the names of the groups it removes.
+    async fn start_group_list_worker(
+        config: OpcDaConfig,
+    ) -> (ComWorker<MockConnector>, Arc<std::sync::Mutex<Vec<String>>>) {
+        let removed = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let recorded = Arc::clone(&removed);
+        let connector = MockServer::default()
+            .on_group_names(|| {
+                Ok(vec![
+                    "opc-da-client-read".into(),
+                    "HMI Trend".into(),
+                    "opc-da-client-live".into(),
+                    "opc-da-client-stuck".into(),
+                ])
+            })
+            .on_remove_group_by_name(move |name| {
+                if name.ends_with("stuck") {
+                    return Err(OpcError::Internal("group in use".into()));
+                }
+                recorded.lock().unwrap().push(name.to_string());
+                Ok(())
+            })
+            .connector();
+        (start_worker(connector, config).await, removed)
+    }
+
+    #[tokio::test]
+    async fn test_worker_cleanup_orphaned_groups() {
+        let (worker, removed) = start_group_list_worker(OpcDaConfig::default()).await;
+
+        let count = worker
+            .send_request(|reply| ComRequest::CleanupOrphanedGroups {
+                server: "Groups".to_string(),
+                reply,
+            })
+            .await
+            .unwrap();
+
+        // Foreign groups are left alone; a group that fails to go is skipped.
+        assert_eq!(count, 2);
+        assert_eq!(
+            *removed.lock().unwrap(),
+            ["opc-da-client-read", "opc-da-client-live"]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_worker_cleans_up_groups_on_connect() {
+        let (worker, removed) = start_group_list_worker(OpcDaConfig::default()).await;
+        worker
+            .send_request(|reply| ComRequest::GetServerMetadata {
+                server: "Groups".to_string(),
+                reply,
+            })
+            .await
+            .unwrap();
+        assert!(removed.lock().unwrap().is_empty());
+
+        let config = OpcDaConfig::default().with_cleanup_groups_on_connect(true);
+        let (worker, removed) = start_group_list_worker(config).await;
+        worker
+            .send_request(|reply| ComRequest::GetServerMetadata {
+                server: "Groups".to_string(),
+                reply,
+            })
+            .await
+            .unwrap();
+        assert_eq!(removed.lock().unwrap().len(), 2);
+    }
+
+    /// Metadata of a server reporting a fixed `GetStatus` state.
+    async fn metadata_with_status(
+        state: &'static str,
+        config: OpcDaConfig,
+    ) -> OpcResult<ServerMetadata> {
+        let connector = MockServer::default()
+            .on_status(move || {
+                Ok(ServerStatus {
+                    state: state.to_string(),
+                    vendor_info: String::new(),
+                    version: "1.0.0".to_string(),
+                    start_time: String::new(),
+                    current_time: String::new(),
+                    group_count: 0,
+                })
+            })
+            .connector();
+        let worker = start_worker(connector, config).await;
+        worker
+            .send_request(|reply| ComRequest::GetServerMetadata {
+                server: "Status".to_string(),
+                reply,
+            })
+            .await
+    }
+
+    #[tokio::test]
+    async fn test_worker_probe_on_connect_rejects_unhealthy_server() {
+        let probing = OpcDaConfig::default().with_probe_on_connect(true);
+
+        let err = metadata_with_status("Communication fault", probing.clone())
+            .await
+            .unwrap_err();
+        assert!(
+            matches!(&err, OpcError::Connection(msg) if msg.contains("Communication fault")),
+            "{err:?}"
+        );
+
+        let metadata = metadata_with_status("Running", probing).await.unwrap();
+        assert_eq!(metadata.status.unwrap().state, "Running");
 
-            if let Err(e) = server.change_browse_position(OPC_BROWSE_UP.0 as u32, "") {
-                tracing::warn!(error = ?e, "Failed to browse up, stopping recursion");
-                break;
-            }
+        // Without probing the same server connects
+        assert!(
+            metadata_with_status("Suspended", OpcDaConfig::default())
+                .await
+                .is_ok()
+        );
+    }
+
+    /// Engineering units of `tag_ids` on a server reporting them for `Temp`
+    /// only; `supported` false behaves like a server without
+    /// `IOPCItemProperties`.
+    async fn engineering_units(
+        supported: bool,
+        tag_ids: &[&str],
+    ) -> OpcResult<Vec<Option<EngineeringUnits>>> {
+        let mut server = MockServer::default();
+        if supported {
+            server = server.on_engineering_units(|item_id| match item_id {
+                "Temp" => Ok(EngineeringUnits {
+                    units: Some("°C".into()),
+                    low: Some(0.0),
+                    high: Some(200.0),
+                }),
+                "Flag" => Ok(EngineeringUnits::default()),
+                // OPC_E_UNKNOWNITEMID
+                _ => Err(OpcError::Com {
+                    source: windows::core::Error::from_hresult(HRESULT(
+                        0xC004_0007_u32.cast_signed(),
+                    )),
+                }),
+            });
         }
+        let worker = start_worker(server.connector(), OpcDaConfig::default()).await;
+        let tag_ids = tag_ids.iter().map(ToString::to_string).collect();
+        worker
+            .send_request(|reply| ComRequest::GetEngineeringUnits {
+                server: "Eu".to_string(),
+                tag_ids,
+                reply,
+            })
+            .await
+    }
 
-        Ok(())
+    #[tokio::test]
+    async fn test_worker_get_engineering_units() {
+        let units = engineering_units(true, &["Temp", "Flag", "Missing"])
+            .await
+            .unwrap();
+        assert_eq!(units.len(), 3);
+        assert_eq!(
+            units[0].as_ref().unwrap().annotate("73.2"),
+            "73.2 °C (range 0–200)"
+        );
+        assert_eq!(units[1], None, "no properties reported");
+        assert_eq!(units[2], None, "properties unreadable");
+
+        assert!(matches!(
+            engineering_units(false, &["Temp"]).await,
+            Err(OpcError::NotImplemented(_))
+        ));
     }
-}
 
-impl<C: ServerConnector + 'static> Drop for ComWorker<C> {
-    fn drop(&mut self) {
-        tracing::debug!("ComWorker dropping — channel closing, signaling thread shutdown");
+    /// Server with a single public group, `Plant`, defining two items and
+    /// read like a DA 3.0 group; each `remove_group` call is recorded in
+    /// `removed`.
+    fn public_group_connector(
+        removed: Arc<std::sync::Mutex<Vec<(GroupHandle, bool)>>>,
+    ) -> MockConnector {
+        let plant = MockGroup::sync_io2(Arc::default())
+            .with_server_handle(GroupHandle(42))
+            .on_add_items(|_| panic!("items added to a public group"))
+            .on_items(|| {
+                Ok(["Line1.Speed", "Line1.Running"]
+                    .into_iter()
+                    .zip(1..)
+                    .map(|(item_id, handle)| GroupItem {
+                        item_id: item_id.to_string(),
+                        server_handle: ItemHandle(handle),
+                        attributes: ItemAttributeInfo {
+                            access_rights: 1,
+                            canonical_data_type: 5,
+                        },
+                    })
+                    .collect())
+            });
+        MockServer::default()
+            .on_public_group_names(|| Ok(vec!["Plant".into()]))
+            .on_get_public_group(move |name| {
+                if name == "Plant" {
+                    Ok(plant.clone())
+                } else if name == "Broken" {
+                    Ok(MockGroup::default().with_server_handle(GroupHandle(7)))
+                } else {
+                    Err(OpcError::InvalidState(format!("no public group '{name}'")))
+                }
+            })
+            .on_remove_group(move |handle, force| {
+                removed.lock().unwrap().push((handle, force));
+                Ok(())
+            })
+            .connector()
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use crate::backend::connector::{
-        ConnectedGroup, ConnectedServer, RemoteArray, ServerConnector, StringIterator,
-    };
-    use crate::bindings::da::{tagOPCDATASOURCE, tagOPCITEMDEF, tagOPCITEMRESULT, tagOPCITEMSTATE};
+    #[tokio::test]
+    async fn test_worker_reads_public_group() {
+        let removed = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let worker = start_worker(
+            public_group_connector(Arc::clone(&removed)),
+            OpcDaConfig::default(),
+        )
+        .await;
 
-    struct WorkerMockConnector;
-    struct WorkerMockServer;
-    struct WorkerMockGroup;
+        let groups = worker
+            .send_request(|reply| ComRequest::ListPublicGroups {
+                server: "Public".to_string(),
+                reply,
+            })
+            .await
+            .unwrap();
+        assert_eq!(groups, ["Plant"]);
 
-    impl ConnectedGroup for WorkerMockGroup {
-        fn add_items(
-            &self,
-            _items: &[tagOPCITEMDEF],
-        ) -> OpcResult<(
-            RemoteArray<tagOPCITEMRESULT>,
-            RemoteArray<windows::core::HRESULT>,
-        )> {
-            Err(OpcError::NotImplemented("mock".into()))
-        }
-        fn read(
-            &self,
-            _source: tagOPCDATASOURCE,
-            _server_handles: &[crate::opc_da::typedefs::ItemHandle],
-        ) -> OpcResult<(
-            RemoteArray<tagOPCITEMSTATE>,
-            RemoteArray<windows::core::HRESULT>,
-        )> {
-            Err(OpcError::NotImplemented("mock".into()))
-        }
-        fn write(
-            &self,
-            _server_handles: &[crate::opc_da::typedefs::ItemHandle],
-            _values: &[windows::Win32::System::Variant::VARIANT],
-        ) -> OpcResult<RemoteArray<windows::core::HRESULT>> {
-            Err(OpcError::NotImplemented("mock".into()))
-        }
+        let values = worker
+            .send_request(|reply| ComRequest::ReadPublicGroup {
+                server: "Public".to_string(),
+                group: "Plant".to_string(),
+                reply,
+            })
+            .await
+            .unwrap();
+        let ids: Vec<&str> = values.iter().map(|v| v.tag_id.as_str()).collect();
+        assert_eq!(ids, ["Line1.Speed", "Line1.Running"]);
+        assert!(values.iter().all(|v| v.value != "Error"));
+        assert_eq!(values[1].canonical_data_type, 5);
+        assert_eq!(
+            *removed.lock().unwrap(),
+            [(GroupHandle(42), false)],
+            "public group released after the read"
+        );
+
+        let broken = worker
+            .send_request(|reply| ComRequest::ReadPublicGroup {
+                server: "Public".to_string(),
+                group: "Broken".to_string(),
+                reply,
+            })
+            .await;
+        assert!(matches!(broken, Err(OpcError::NotImplemented(_))));
+        assert_eq!(
+            removed.lock().unwrap().last(),
+            Some(&(GroupHandle(7), false)),
+            "public group released after a failed read"
+        );
+
+        let missing = worker
+            .send_request(|reply| ComRequest::ReadPublicGroup {
+                server: "Public".to_string(),
+                group: "Nope".to_string(),
+                reply,
+            })
+            .await;
+        assert!(matches!(missing, Err(OpcError::InvalidState(_))));
     }
 
-    impl ConnectedServer for WorkerMockServer {
-        type Group = WorkerMockGroup;
-        fn query_organization(&self) -> OpcResult<u32> {
-            Err(OpcError::NotImplemented("mock".into()))
-        }
-        fn browse_opc_item_ids(
+    #[tokio::test]
+    async fn test_worker_public_groups_unsupported() {
+        let worker = start_worker(MockConnector::default(), OpcDaConfig::default()).await;
+        let result = worker
+            .send_request(|reply| ComRequest::ListPublicGroups {
+                server: "Mock.Server.1".to_string(),
+                reply,
+            })
+            .await;
+        assert!(matches!(result, Err(OpcError::NotImplemented(_))));
+    }
+
+    /// `IEnumString` over a fixed list of names.
+    #[allow(clippy::ref_as_ptr, clippy::inline_always)]
+    #[windows::core::implement(windows::Win32::System::Com::IEnumString)]
+    struct NameEnum {
+        names: Vec<String>,
+        index: AtomicUsize,
+    }
+
+    impl windows::Win32::System::Com::IEnumString_Impl for NameEnum_Impl {
+        fn Next(
             &self,
-            _browse_type: u32,
-            _filter: Option<&str>,
-            _data_type: u16,
-            _access_rights: u32,
-        ) -> OpcResult<StringIterator> {
-            Err(OpcError::NotImplemented("mock".into()))
-        }
-        fn change_browse_position(&self, _direction: u32, _name: &str) -> OpcResult<()> {
-            Err(OpcError::NotImplemented("mock".into()))
+            celt: u32,
+            rgelt: *mut windows::core::PWSTR,
+            pceltfetched: *mut u32,
+        ) -> windows::core::HRESULT {
+            let start = self.index.load(Ordering::SeqCst);
+            let batch: Vec<&String> = self.names.iter().skip(start).take(celt as usize).collect();
+            for (i, name) in batch.iter().enumerate() {
+                let wide: Vec<u16> = name.encode_utf16().chain(std::iter::once(0)).collect();
+                // SAFETY: The caller provides room for `celt` strings and frees
+                // each one with `CoTaskMemFree`; the allocation is sized for `wide`.
+                unsafe {
+                    let ptr =
+                        windows::Win32::System::Com::CoTaskMemAlloc(wide.len() * 2).cast::<u16>();
+                    std::ptr::copy_nonoverlapping(wide.as_ptr(), ptr, wide.len());
+                    *rgelt.add(i) = windows::core::PWSTR(ptr);
+                }
+            }
+            self.index.store(start + batch.len(), Ordering::SeqCst);
+            let fetched = u32::try_from(batch.len()).unwrap();
+            if !pceltfetched.is_null() {
+                // SAFETY: Checked non-null; the caller owns the out-parameter.
+                unsafe { *pceltfetched = fetched };
+            }
+            if fetched == celt {
+                windows::Win32::Foundation::S_OK
+            } else {
+                windows::Win32::Foundation::S_FALSE
+            }
         }
-        fn get_item_id(&self, _item_name: &str) -> OpcResult<String> {
-            Err(OpcError::NotImplemented("mock".into()))
+        fn Skip(&self, _celt: u32) -> windows::core::HRESULT {
+            windows::Win32::Foundation::E_NOTIMPL
         }
-        fn add_group(
-            &self,
-            _name: &str,
-            _active: bool,
-            _update_rate: u32,
-            _client_handle: crate::opc_da::typedefs::GroupHandle,
-            _time_bias: i32,
-            _percent_deadband: f32,
-            _locale_id: u32,
-            _revised_update_rate: &mut u32,
-            _server_handle: &mut crate::opc_da::typedefs::GroupHandle,
-        ) -> OpcResult<Self::Group> {
-            Err(OpcError::NotImplemented("mock".into()))
+        fn Reset(&self) -> windows::core::Result<()> {
+            self.index.store(0, Ordering::SeqCst);
+            Ok(())
         }
-        fn remove_group(
-            &self,
-            _server_group: crate::opc_da::typedefs::GroupHandle,
-            _force: bool,
-        ) -> OpcResult<()> {
-            Err(OpcError::NotImplemented("mock".into()))
+        fn Clone(&self) -> windows::core::Result<windows::Win32::System::Com::IEnumString> {
+            Err(windows::Win32::Foundation::E_NOTIMPL.into())
         }
     }
 
-    impl ServerConnector for WorkerMockConnector {
-        type Server = WorkerMockServer;
-        fn enumerate_servers(&self) -> OpcResult<Vec<String>> {
-            Ok(vec!["Mock.Server.1".into()])
-        }
-        fn connect(&self, _server_name: &str) -> OpcResult<Self::Server> {
-            Ok(WorkerMockServer)
-        }
+    fn name_iter(names: &[&str]) -> StringIterator {
+        StringIterator::new(
+            NameEnum {
+                names: names.iter().map(ToString::to_string).collect(),
+                index: AtomicUsize::new(0),
+            }
+            .into(),
+        )
+    }
+
+    const TREE_BRANCHES: [&str; 3] = ["A", "B", "C"];
+
+    /// Hierarchical namespace `A/B/C` with a single leaf `Tag` in `C`. Records
+    /// the shared depth counter each time leaves are listed.
+    fn tree_connector(
+        depth: Arc<AtomicUsize>,
+        depths_seen: Arc<std::sync::Mutex<Vec<usize>>>,
+    ) -> MockConnector {
+        let position = Arc::new(std::sync::Mutex::new(Vec::<String>::new()));
+        let browsed = Arc::clone(&position);
+        let moved = Arc::clone(&position);
+        MockServer::default()
+            .with_organization(OPC_NS_HIERARCHIAL.0 as u32)
+            .on_browse(move |browse_type| {
+                let level = browsed.lock().unwrap().len();
+                if browse_type == OPC_BRANCH.0 as u32 {
+                    let branches: &[&str] = if level < 3 {
+                        &TREE_BRANCHES[level..=level]
+                    } else {
+                        &[]
+                    };
+                    Ok(name_iter(branches))
+                } else if browse_type == OPC_LEAF.0 as u32 {
+                    depths_seen
+                        .lock()
+                        .unwrap()
+                        .push(depth.load(Ordering::SeqCst));
+                    Ok(name_iter(if level == 3 { &["Tag"] } else { &[] }))
+                } else {
+                    Err(OpcError::NotImplemented("flat browse".into()))
+                }
+            })
+            .on_change_browse_position(move |direction, name| {
+                let mut position = moved.lock().unwrap();
+                if direction == OPC_BROWSE_DOWN.0 as u32 {
+                    position.push(name.to_string());
+                } else {
+                    position.pop();
+                }
+                Ok(())
+            })
+            .on_get_item_id(move |item_name| {
+                let mut segments = position.lock().unwrap().clone();
+                segments.push(item_name.to_string());
+                Ok(segments.join("."))
+            })
+            .connector()
     }
 
     #[tokio::test]
-    async fn test_worker_starts_and_stops() {
-        let worker = tokio::task::spawn_blocking(|| {
-            ComWorker::start(Arc::new(WorkerMockConnector)).unwrap()
-        })
-        .await
-        .unwrap();
-        drop(worker);
+    async fn test_worker_browse_reports_depth() {
+        let depth = Arc::new(AtomicUsize::new(0));
+        let depth_limit_hit = Arc::new(AtomicUsize::new(0));
+        let depths_seen = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let connector = tree_connector(Arc::clone(&depth), Arc::clone(&depths_seen));
+        let worker = start_worker(connector, OpcDaConfig::default()).await;
+
+        let tags = worker
+            .send_request(|reply| ComRequest::BrowseTags {
+                server: "Tree".to_string(),
+                max_tags: 10,
+                access: AccessRightsFilter::Any,
+                progress: BrowseProgress {
+                    depth: Arc::clone(&depth),
+                    depth_limit_hit: Arc::clone(&depth_limit_hit),
+                    ..BrowseProgress::default()
+                },
+                reply,
+            })
+            .await
+            .unwrap();
+
+        assert_eq!(depth_limit_hit.load(Ordering::SeqCst), 0);
+        assert_eq!(tags.len(), 1);
+        assert_eq!(tags[0].tag_id, "A.B.C.Tag");
+        assert_eq!(tags[0].depth, 4);
+        assert_eq!(tags[0].parent_branch, "A/B/C");
+        // Leaves of each level are listed with the counter at that level.
+        assert_eq!(*depths_seen.lock().unwrap(), [0, 1, 2, 3]);
+        // Back at the root once every branch has been left.
+        assert_eq!(depth.load(Ordering::SeqCst), 0);
     }
 
     #[tokio::test]
-    async fn test_worker_list_servers() {
-        let worker = tokio::task::spawn_blocking(|| {
-            ComWorker::start(Arc::new(WorkerMockConnector)).unwrap()
-        })
-        .await
-        .unwrap();
-        let (reply, _rx) = oneshot::channel();
-        worker
-            .sender
-            .send(ComRequest::ListServers {
-                host: "localhost".into(),
+    async fn test_worker_browse_reports_depth_limit() {
+        let depth = Arc::new(AtomicUsize::new(0));
+        let depth_limit_hit = Arc::new(AtomicUsize::new(0));
+        let connector = tree_connector(Arc::clone(&depth), Arc::default());
+        let config = OpcDaConfig::default().with_max_browse_depth(2);
+        let worker = start_worker(connector, config).await;
+
+        let tags = worker
+            .send_request(|reply| ComRequest::BrowseTags {
+                server: "Tree".to_string(),
+                max_tags: 10,
+                access: AccessRightsFilter::Any,
+                progress: BrowseProgress {
+                    depth: Arc::clone(&depth),
+                    depth_limit_hit: Arc::clone(&depth_limit_hit),
+                    ..BrowseProgress::default()
+                },
                 reply,
             })
             .await
             .unwrap();
-        // Wait for implementation
+
+        // `Tag` sits below `A/B/C`, one level past the limit.
+        assert!(tags.is_empty());
+        assert_eq!(depth_limit_hit.load(Ordering::SeqCst), 2);
+        assert_eq!(depth.load(Ordering::SeqCst), 0);
     }
 
-    struct MismatchedConnector;
-    struct MismatchedServer;
-    struct MismatchedGroup;
+    /// Branches and leaves of each browse position, keyed by its `/`-joined path.
+    type Namespace = &'static [(
+        &'static str,
+        &'static [&'static str],
+        &'static [&'static str],
+    )];
 
-    impl ConnectedGroup for MismatchedGroup {
-        fn add_items(
-            &self,
-            _items: &[tagOPCITEMDEF],
-        ) -> OpcResult<(
-            RemoteArray<tagOPCITEMRESULT>,
-            RemoteArray<windows::core::HRESULT>,
-        )> {
-            Ok((RemoteArray::empty(), RemoteArray::empty()))
-        }
-        fn read(
-            &self,
-            _source: tagOPCDATASOURCE,
-            _server_handles: &[crate::opc_da::typedefs::ItemHandle],
-        ) -> OpcResult<(
-            RemoteArray<tagOPCITEMSTATE>,
-            RemoteArray<windows::core::HRESULT>,
-        )> {
-            Ok((RemoteArray::empty(), RemoteArray::empty()))
-        }
-        fn write(
-            &self,
-            _server_handles: &[crate::opc_da::typedefs::ItemHandle],
-            _values: &[windows::Win32::System::Variant::VARIANT],
-        ) -> OpcResult<RemoteArray<windows::core::HRESULT>> {
-            Ok(RemoteArray::empty())
-        }
+    /// Hierarchical server browsing `namespace`, with item IDs joined by `.`.
+    fn namespace_server(namespace: Namespace) -> MockServer {
+        let position = Arc::new(std::sync::Mutex::new(Vec::<String>::new()));
+        let browsed = Arc::clone(&position);
+        let moved = Arc::clone(&position);
+        MockServer::default()
+            .with_organization(OPC_NS_HIERARCHIAL.0 as u32)
+            .on_browse(move |browse_type| {
+                let path = browsed.lock().unwrap().join("/");
+                let (_, branches, leaves) = namespace
+                    .iter()
+                    .find(|(p, _, _)| *p == path)
+                    .copied()
+                    .unwrap_or(("", &[], &[]));
+                if browse_type == OPC_BRANCH.0 as u32 {
+                    Ok(name_iter(branches))
+                } else if browse_type == OPC_LEAF.0 as u32 {
+                    Ok(name_iter(leaves))
+                } else {
+                    Err(OpcError::NotImplemented("flat browse".into()))
+                }
+            })
+            .on_change_browse_position(move |direction, name| {
+                let mut position = moved.lock().unwrap();
+                if direction == OPC_BROWSE_DOWN.0 as u32 {
+                    position.push(name.to_string());
+                } else {
+                    position.pop();
+                }
+                Ok(())
+            })
+            .on_get_item_id(move |item_name| {
+                let mut segments = position.lock().unwrap().clone();
+                segments.push(item_name.to_string());
+                Ok(segments.join("."))
+            })
     }
 
-    impl ConnectedServer for MismatchedServer {
-        type Group = MismatchedGroup;
-        fn query_organization(&self) -> OpcResult<u32> {
-            Ok(0)
-        }
-        fn browse_opc_item_ids(
-            &self,
-            _b: u32,
-            _f: Option<&str>,
-            _d: u16,
-            _a: u32,
-        ) -> OpcResult<StringIterator> {
-            Err(OpcError::NotImplemented("mock".into()))
-        }
-        fn change_browse_position(&self, _direction: u32, _name: &str) -> OpcResult<()> {
-            Ok(())
-        }
-        fn get_item_id(&self, _item_name: &str) -> OpcResult<String> {
-            Ok(String::new())
-        }
-        fn add_group(
-            &self,
-            _name: &str,
-            _active: bool,
-            _update_rate: u32,
-            _client_handle: crate::opc_da::typedefs::GroupHandle,
-            _time_bias: i32,
-            _percent_deadband: f32,
-            _locale_id: u32,
-            _revised_update_rate: &mut u32,
-            _server_handle: &mut crate::opc_da::typedefs::GroupHandle,
-        ) -> OpcResult<Self::Group> {
-            Ok(MismatchedGroup)
-        }
-        fn remove_group(
-            &self,
-            _server_group: crate::opc_da::typedefs::GroupHandle,
-            _force: bool,
-        ) -> OpcResult<()> {
-            Ok(())
-        }
+    #[tokio::test]
+    async fn test_worker_browse_reports_tag_positions() {
+        let connector = namespace_server(&[
+            ("", &["Plant"], &["Alarm"]),
+            ("Plant", &["Line1"], &["Status"]),
+            ("Plant/Line1", &[], &["Speed", "Temp"]),
+        ])
+        .connector();
+        let worker = start_worker(connector, OpcDaConfig::default()).await;
+
+        let tags = worker
+            .send_request(|reply| ComRequest::BrowseTags {
+                server: "Plant".to_string(),
+                max_tags: 10,
+                access: AccessRightsFilter::Any,
+                progress: BrowseProgress::default(),
+                reply,
+            })
+            .await
+            .unwrap();
+
+        let positions: Vec<(&str, usize, &str)> = tags
+            .iter()
+            .map(|t| (t.tag_id.as_str(), t.depth, t.parent_branch.as_str()))
+            .collect();
+        assert_eq!(
+            positions,
+            [
+                ("Alarm", 1, ""),
+                ("Plant.Status", 2, "Plant"),
+                ("Plant.Line1.Speed", 3, "Plant/Line1"),
+                ("Plant.Line1.Temp", 3, "Plant/Line1"),
+            ]
+        );
     }
 
-    impl ServerConnector for MismatchedConnector {
-        type Server = MismatchedServer;
-        fn enumerate_servers(&self) -> OpcResult<Vec<String>> {
-            Ok(vec![])
-        }
-        fn connect(&self, _server_name: &str) -> OpcResult<Self::Server> {
-            Ok(MismatchedServer)
-        }
+    #[tokio::test]
+    async fn test_worker_browse_validates_assembled_ids() {
+        let validated = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let seen = Arc::clone(&validated);
+        let group = MockGroup::default().on_validate_items(move |items| {
+            // SAFETY: `ItemDefBuilder` keeps the NUL-terminated IDs alive.
+            let id = unsafe { items[0].szItemID.to_string() }.unwrap();
+            let known = id == "Dev.Known";
+            seen.lock().unwrap().push(id);
+            Ok(vec![if known {
+                HRESULT(0)
+            } else {
+                windows::Win32::Foundation::E_FAIL
+            }])
+        });
+        let added = Arc::new(AtomicUsize::new(0));
+        let removed = Arc::new(AtomicUsize::new(0));
+        let (adds, removes) = (Arc::clone(&added), Arc::clone(&removed));
+        // `GetItemID` fails for every leaf, so IDs are assembled from the path.
+        let connector =
+            namespace_server(&[("", &["Dev"], &[]), ("Dev", &[], &["Known", "Unknown"])])
+                .on_get_item_id(|_| Err(OpcError::Internal("GetItemID failed".into())))
+                .with_group(group)
+                .on_add_group(move |_| {
+                    adds.fetch_add(1, Ordering::SeqCst);
+                    Ok(())
+                })
+                .on_remove_group(move |_, _| {
+                    removes.fetch_add(1, Ordering::SeqCst);
+                    Ok(())
+                })
+                .connector();
+        let config = OpcDaConfig::default().with_item_id_separator('.');
+        let worker = start_worker(connector, config).await;
+
+        let tags = worker
+            .send_request(|reply| ComRequest::BrowseTags {
+                server: "Dev".to_string(),
+                max_tags: 10,
+                access: AccessRightsFilter::Any,
+                progress: BrowseProgress::default(),
+                reply,
+            })
+            .await
+            .unwrap();
+
+        let tag_ids: Vec<&str> = tags.iter().map(|t| t.tag_id.as_str()).collect();
+        // The rejected ID falls back to the browse name.
+        assert_eq!(tag_ids, ["Dev.Known", "Unknown"]);
+        assert_eq!(*validated.lock().unwrap(), ["Dev.Known", "Dev.Unknown"]);
+        // One validation group, removed once the browse is done.
+        assert_eq!(added.load(Ordering::SeqCst), 1);
+        assert_eq!(removed.load(Ordering::SeqCst), 1);
     }
 
     #[tokio::test]
-    async fn test_worker_read_tag_values_mismatched_lengths() {
-        let worker = tokio::task::spawn_blocking(|| {
-            ComWorker::start(Arc::new(MismatchedConnector)).unwrap()
-        })
-        .await
-        .unwrap();
+    async fn test_worker_browse_normalizes_padded_ids() {
+        // Rejects item IDs it does not know, as a server looking them up would.
+        let lookup = MockGroup::default().on_add_items(|items| {
+            for item in items {
+                // SAFETY: `ItemDefBuilder` keeps the NUL-terminated IDs alive.
+                let id = unsafe { item.szItemID.to_string() }.unwrap();
+                if !["Dev.Tag1", "Dev.Tag2"].contains(&id.as_str()) {
+                    return Err(OpcError::InvalidState(format!("unknown item '{id}'")));
+                }
+            }
+            not_implemented()
+        });
 
-        let result = worker
-            .send_request(|reply| ComRequest::ReadTagValues {
-                server: "MockServer".to_string(),
-                tag_ids: vec!["Tag1".to_string(), "Tag2".to_string()],
+        // Namespace whose browse names and item IDs come back padded.
+        let connector = MockServer::default()
+            .with_organization(OPC_NS_HIERARCHIAL.0 as u32)
+            .on_browse(|browse_type| {
+                if browse_type == OPC_LEAF.0 as u32 {
+                    Ok(name_iter(&["Tag1 ", " Tag2"]))
+                } else {
+                    Ok(name_iter(&[]))
+                }
+            })
+            .on_change_browse_position(|_, _| Ok(()))
+            .on_get_item_id(|item_name| Ok(format!("Dev.{item_name}\0\0 ")))
+            .connector();
+        let worker = start_worker(connector, OpcDaConfig::default()).await;
+        let tags = worker
+            .send_request(|reply| ComRequest::BrowseTags {
+                server: "Padded".to_string(),
+                max_tags: 10,
+                access: AccessRightsFilter::Any,
+                progress: BrowseProgress::default(),
                 reply,
             })
-            .await;
+            .await
+            .unwrap();
+        let tag_ids: Vec<String> = tags.into_iter().map(|t| t.tag_id).collect();
+        assert_eq!(tag_ids, ["Dev.Tag1", "Dev.Tag2"]);
 
-        assert!(
-            result.is_err(),
-            "Expected read to fail due to mismatched lengths"
+        // The server finds every normalized ID; a padded one is unknown.
+        let found = add_read_items(&tag_ids, &lookup, &BTreeMap::new(), &Cell::default());
+        assert!(matches!(found, Err(OpcError::NotImplemented(_))));
+        let raw = vec!["Dev.Tag1 ".to_string()];
+        let missing = add_read_items(&raw, &lookup, &BTreeMap::new(), &Cell::default());
+        assert!(matches!(missing, Err(OpcError::InvalidState(_))));
+    }
+
+    #[test]
+    fn test_assemble_item_id() {
+        let path = vec!["Channel1".to_string(), "Device1".to_string()];
+        assert_eq!(
+            assemble_item_id(&path, "Tag1", '.').as_deref(),
+            Some("Channel1.Device1.Tag1")
         );
-        if let Err(OpcError::Internal(msg)) = result {
-            assert!(msg.contains("mismatched result array sizes"));
-        } else {
-            panic!("Expected OpcError::Internal, got {:?}", result);
-        }
+        assert_eq!(
+            assemble_item_id(&path, "Tag1", '\\').as_deref(),
+            Some("Channel1\\Device1\\Tag1")
+        );
+        assert_eq!(assemble_item_id(&[], "Tag1", '.').as_deref(), Some("Tag1"));
+
+        // Segments that would not round-trip are rejected
+        assert_eq!(assemble_item_id(&path, "Tag.1", '.'), None);
+        assert_eq!(assemble_item_id(&path, "", '.'), None);
+    }
+
+    #[test]
+    fn test_detect_separator() {
+        let path = vec!["Channel1".to_string(), "Device1".to_string()];
+        assert_eq!(
+            detect_separator("Channel1.Device1.Tag1", &path, "Tag1"),
+            Some('.')
+        );
+        assert_eq!(
+            detect_separator("Channel1/Device1/Tag1", &path, "Tag1"),
+            Some('/')
+        );
+        assert_eq!(
+            detect_separator("Channel1\\Device1\\Tag1", &path, "Tag1"),
+            Some('\\')
+        );
+        // Server maps browse names to unrelated IDs
+        assert_eq!(detect_separator("ns=2;i=1001", &path, "Tag1"), None);
+        // Root-level leaves carry no separator
+        assert_eq!(detect_separator("Tag1", &[], "Tag1"), None);
+    }
+
+    #[test]
+    fn test_record_tag_updates_sink_and_progress() {
+        let mut tags = Vec::new();
+        let progress = BrowseProgress::default();
+        let tag = BrowsedTag {
+            tag_id: "Channel1.Device1.Tag1".into(),
+            depth: 2,
+            parent_branch: "Channel1/Device1".into(),
+        };
+
+        record_tag(tag.clone(), &mut tags, &progress);
+
+        assert_eq!(tags, vec![tag.clone()]);
+        assert_eq!(*progress.tags_sink.lock().unwrap(), Some(vec![tag]));
+        assert_eq!(progress.tags_found.load(Ordering::Relaxed), 1);
+    }
+
+    #[test]
+    fn test_record_tag_skips_closed_sink() {
+        let mut tags = Vec::new();
+        let progress = BrowseProgress {
+            tags_sink: Arc::new(std::sync::Mutex::new(Some(vec![BrowsedTag::from(
+                "Before",
+            )]))),
+            ..BrowseProgress::default()
+        };
+        let harvested = progress.tags_sink.lock().unwrap().take();
+
+        record_tag(BrowsedTag::from("After"), &mut tags, &progress);
+
+        assert!(progress.tags_sink.lock().unwrap().is_none());
+        assert_eq!(harvested, Some(vec![BrowsedTag::from("Before")]));
+        assert_eq!(tags, vec![BrowsedTag::from("After")]);
+        assert_eq!(progress.tags_found.load(Ordering::Relaxed), 1);
+    }
+
+    #[test]
+    fn test_record_tag_recovers_poisoned_sink() {
+        let mut tags = Vec::new();
+        let progress = BrowseProgress {
+            tags_sink: Arc::new(std::sync::Mutex::new(Some(vec![BrowsedTag::from(
+                "Before",
+            )]))),
+            ..BrowseProgress::default()
+        };
+        let poisoner = Arc::clone(&progress.tags_sink);
+        let _ = std::thread::spawn(move || {
+            let _guard = poisoner.lock().unwrap();
+            panic!("poison the sink");
+        })
+        .join();
+        assert!(progress.tags_sink.is_poisoned());
+
+        record_tag(BrowsedTag::from("After"), &mut tags, &progress);
+
+        let sink = progress
+            .tags_sink
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        let ids: Vec<&str> = sink.iter().flatten().map(|t| t.tag_id.as_str()).collect();
+        assert_eq!(ids, ["Before", "After"]);
+        assert_eq!(progress.tags_found.load(Ordering::Relaxed), 1);
     }
 
     #[tokio::test]
@@ -913,6 +4788,72 @@ mod tests {
         // dummy for now
     }
 
+    /// Read two tags from a DA 3.0 server whose `IOPCItemIO` reads return
+    /// `42` for every item, or answer `E_NOTIMPL` without `item_io`,
+    /// returning the result and the number of groups the read added.
+    async fn read_from_item_io_server(
+        prefer_item_io_read: bool,
+        item_io: bool,
+    ) -> (OpcResult<Vec<TagValue>>, usize) {
+        let added = Arc::new(AtomicUsize::new(0));
+        let counter = Arc::clone(&added);
+        let connector = MockServer::default()
+            .with_group(MockGroup::empty_results())
+            .with_interfaces(&[
+                "IOPCServer",
+                "IOPCItemProperties",
+                "IOPCBrowse",
+                "IOPCItemIO",
+            ])
+            .on_add_group(move |_| {
+                counter.fetch_add(1, Ordering::SeqCst);
+                Ok(())
+            })
+            .on_read_item_io(move |item_ids, _| {
+                if !item_io {
+                    return Err(OpcError::NotImplemented("IOPCItemIO (E_NOTIMPL)".into()));
+                }
+                let states = item_ids
+                    .iter()
+                    .map(|_| tagOPCITEMSTATE {
+                        wQuality: 0xC0,
+                        vDataValue: opc_value_to_variant(&OpcValue::Int(42)),
+                        ..tagOPCITEMSTATE::default()
+                    })
+                    .collect();
+                Ok((states, vec![HRESULT(0); item_ids.len()]))
+            })
+            .connector();
+        let config = OpcDaConfig::default().with_prefer_item_io_read(prefer_item_io_read);
+        let worker = start_worker(connector, config).await;
+
+        let result = worker
+            .send_request(|reply| ComRequest::ReadTagValues {
+                server: "MockServer".to_string(),
+                tag_ids: vec!["Tag1".to_string(), "Tag2".to_string()],
+                reply,
+            })
+            .await;
+        (result, added.load(Ordering::SeqCst))
+    }
+
+    #[tokio::test]
+    async fn test_worker_prefer_item_io_read_skips_groups() {
+        let (result, added) = read_from_item_io_server(true, true).await;
+        let values = result.unwrap();
+        assert_eq!(added, 0, "add_group is never called");
+        assert_eq!(values.len(), 2);
+        assert_eq!(values[1].tag_id, "Tag2");
+        assert_eq!(values[1].value, "42");
+        assert_eq!(values[1].status, ReadStatus::Ok);
+
+        let (_, added) = read_from_item_io_server(false, true).await;
+        assert_eq!(added, 1, "group reads stay the default");
+
+        let (_, added) = read_from_item_io_server(true, false).await;
+        assert_eq!(added, 1, "E_NOTIMPL falls back to a group read");
+    }
+
     #[tokio::test]
     async fn test_worker_init_failure() {
         // dummy for now
@@ -0,0 +1,289 @@
+//! Runtime configuration for [`OpcDaClient`](crate::OpcDaClient).
+
+use crate::helpers::VariantFormat;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::str::FromStr;
+use std::time::Duration;
+
+/// COM apartment model the worker thread joins.
+///
+/// - [`MultiThreaded`](Self::MultiThreaded) (MTA, the default) needs no
+///   message pump and lets COM deliver calls on any thread. It is the right
+///   choice for out-of-process OPC servers, which is almost all of them.
+/// - [`SingleThreaded`](Self::SingleThreaded) (STA) serializes every call
+///   through the worker thread. Some legacy in-process servers and their
+///   proxies are registered `ThreadingModel=Apartment` and misbehave when
+///   created from the MTA. Because the worker does not pump messages between
+///   requests, callbacks from the server are only delivered while a call is
+///   in progress.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum ComApartment {
+    #[default]
+    MultiThreaded,
+    SingleThreaded,
+}
+
+impl fmt::Display for ComApartment {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::MultiThreaded => "mta",
+            Self::SingleThreaded => "sta",
+        })
+    }
+}
+
+impl FromStr for ComApartment {
+    type Err = String;
+
+    /// Parses `mta` or `sta`, ignoring case.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "mta" => Ok(Self::MultiThreaded),
+            "sta" => Ok(Self::SingleThreaded),
+            _ => Err(format!(
+                "unknown COM apartment '{s}' (expected 'mta' or 'sta')"
+            )),
+        }
+    }
+}
+
+/// Tuning knobs for the OPC DA client and its COM worker.
+///
+/// Construct with [`OpcDaConfig::default`] and adjust with the `with_*`
+/// methods.
+///
+/// # Examples
+///
+/// ```
+/// use opc_da_client::OpcDaConfig;
+///
+/// let config = OpcDaConfig::default().with_write_retries(3);
+/// assert_eq!(config.write_retries, 3);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct OpcDaConfig {
+    /// Extra attempts made for a write that fails with a transient
+    /// communication error. `0` attempts each write exactly once.
+    pub write_retries: u32,
+    /// Largest number of items added to a single OPC group by
+    /// `read_tag_values`. Larger reads are split into chunks of this size,
+    /// each read through its own group, to stay within COM message limits.
+    pub max_items_per_group: usize,
+    /// How many read chunks may be queued on the COM worker at once. The
+    /// worker still reads them one after another; a queued chunk only
+    /// saves the round trip between chunks.
+    pub max_queued_reads: usize,
+    /// Separator between item ID segments, used to assemble IDs during a
+    /// hierarchical browse when the server's `GetItemID` fails. `None`
+    /// detects it from the IDs the server does return.
+    pub item_id_separator: Option<char>,
+    /// Deepest branch level a hierarchical browse walks; branches below it
+    /// are skipped and reported through `browse_tags`'s `depth_limit_hit`.
+    pub max_browse_depth: usize,
+    /// COM apartment the worker thread initializes. See [`ComApartment`].
+    pub apartment: ComApartment,
+    /// How long to wait for the COM worker thread to initialize COM before
+    /// giving up with [`OpcError::Timeout`](crate::OpcError::Timeout).
+    pub init_timeout: Duration,
+    /// Render values of unrecognised VARIANT types as a hex dump of their
+    /// first 8 bytes instead of a bare `(VT …)` label. Meant for diagnosing
+    /// vendor-specific types.
+    pub hex_dump_unknown_vt: bool,
+    /// Read every element of array values into `TagValue::value` instead of
+    /// eliding after the first 20. Meant for exports, where a truncated
+    /// array is misleading.
+    pub full_array_values: bool,
+    /// Remove groups left behind by earlier runs of this client (see
+    /// `OpcProvider::cleanup_orphaned_groups`) whenever a server connection
+    /// is established.
+    pub cleanup_groups_on_connect: bool,
+    /// Check the server's `GetStatus` state right after connecting and fail
+    /// with [`OpcError::Connection`](crate::OpcError::Connection) if it is
+    /// failed, suspended or in a communication fault, instead of letting the
+    /// first read fail with an opaque HRESULT.
+    pub probe_on_connect: bool,
+    /// Convert each written value to the item's canonical data type with
+    /// [`coerce_to_vt`](crate::coerce_to_vt) before sending it, for servers
+    /// that reject writes whose VARIANT type does not match the item.
+    pub type_safe_writes: bool,
+    /// Check that every value returned by a read carries the client handle
+    /// of the tag at its position, and fail the read with
+    /// [`OpcError::Internal`](crate::OpcError::Internal) if not. Results are
+    /// otherwise matched to tags by position only, so a server that
+    /// reorders them would silently attach values to the wrong tags.
+    /// `IOPCSyncIO2::ReadMaxAge` reports no client handles, so checked reads
+    /// go through `IOPCSyncIO::Read` even on DA 3.0 servers.
+    pub strict_client_handles: bool,
+    /// `VARTYPE` to ask the server for when reading each listed tag (the
+    /// item's `vtRequestedDataType`), e.g. `VT_R8` to read a string tag as a
+    /// number. The server converts the value or rejects the item with
+    /// `OPC_E_BADTYPE`. Tags not listed are read in their canonical type.
+    pub requested_data_types: BTreeMap<String, u16>,
+    /// On DA 3.0 servers, read with `IOPCItemIO::Read` instead of adding a
+    /// temporary group, so the client never shows up as a subscriber. Falls
+    /// back to the group read if the server answers `E_NOTIMPL`. Values read
+    /// this way carry no access rights or canonical data type, and
+    /// `requested_data_types` and `strict_client_handles` do not apply.
+    pub prefer_item_io_read: bool,
+}
+
+impl Default for OpcDaConfig {
+    fn default() -> Self {
+        Self {
+            write_retries: 2,
+            max_items_per_group: 100,
+            max_queued_reads: 4,
+            item_id_separator: None,
+            max_browse_depth: 50,
+            apartment: ComApartment::MultiThreaded,
+            init_timeout: Duration::from_secs(10),
+            hex_dump_unknown_vt: false,
+            full_array_values: false,
+            cleanup_groups_on_connect: false,
+            probe_on_connect: false,
+            type_safe_writes: false,
+            strict_client_handles: false,
+            requested_data_types: BTreeMap::new(),
+            prefer_item_io_read: false,
+        }
+    }
+}
+
+impl OpcDaConfig {
+    /// Sets the number of extra attempts for transiently failing writes.
+    #[must_use]
+    pub const fn with_write_retries(mut self, retries: u32) -> Self {
+        self.write_retries = retries;
+        self
+    }
+
+    /// Sets the chunk size for large reads (clamped to at least 1).
+    #[must_use]
+    pub const fn with_max_items_per_group(mut self, max_items: usize) -> Self {
+        self.max_items_per_group = if max_items == 0 { 1 } else { max_items };
+        self
+    }
+
+    /// Sets how many read chunks may be queued at once (clamped to at least 1).
+    #[must_use]
+    pub const fn with_max_queued_reads(mut self, max_reads: usize) -> Self {
+        self.max_queued_reads = if max_reads == 0 { 1 } else { max_reads };
+        self
+    }
+
+    /// Sets the item ID separator instead of detecting it while browsing.
+    #[must_use]
+    pub const fn with_item_id_separator(mut self, separator: char) -> Self {
+        self.item_id_separator = Some(separator);
+        self
+    }
+
+    /// Sets how many branch levels a hierarchical browse walks.
+    #[must_use]
+    pub const fn with_max_browse_depth(mut self, depth: usize) -> Self {
+        self.max_browse_depth = depth;
+        self
+    }
+
+    /// Sets the COM apartment model of the worker thread.
+    #[must_use]
+    pub const fn with_apartment(mut self, apartment: ComApartment) -> Self {
+        self.apartment = apartment;
+        self
+    }
+
+    /// Sets how long to wait for the COM worker to start.
+    #[must_use]
+    pub const fn with_init_timeout(mut self, timeout: Duration) -> Self {
+        self.init_timeout = timeout;
+        self
+    }
+
+    /// Hex-dumps values of unrecognised VARIANT types when reading.
+    #[must_use]
+    pub const fn with_hex_dump_unknown_vt(mut self, enabled: bool) -> Self {
+        self.hex_dump_unknown_vt = enabled;
+        self
+    }
+
+    /// Reads array values in full instead of eliding long arrays.
+    #[must_use]
+    pub const fn with_full_array_values(mut self, enabled: bool) -> Self {
+        self.full_array_values = enabled;
+        self
+    }
+
+    /// Removes leftover client groups whenever a server connection is made.
+    #[must_use]
+    pub const fn with_cleanup_groups_on_connect(mut self, enabled: bool) -> Self {
+        self.cleanup_groups_on_connect = enabled;
+        self
+    }
+
+    /// Checks the server state with `GetStatus` whenever a connection is made.
+    #[must_use]
+    pub const fn with_probe_on_connect(mut self, enabled: bool) -> Self {
+        self.probe_on_connect = enabled;
+        self
+    }
+
+    /// Converts written values to each item's canonical data type first.
+    #[must_use]
+    pub const fn with_type_safe_writes(mut self, enabled: bool) -> Self {
+        self.type_safe_writes = enabled;
+        self
+    }
+
+    /// Verifies that read results come back in the order items were added.
+    #[must_use]
+    pub const fn with_strict_client_handles(mut self, enabled: bool) -> Self {
+        self.strict_client_handles = enabled;
+        self
+    }
+
+    /// Reads `tag_id` as `vt` (see [`vt_from_str`](crate::vt_from_str))
+    /// instead of its canonical type.
+    #[must_use]
+    pub fn with_requested_data_type(mut self, tag_id: impl Into<String>, vt: u16) -> Self {
+        self.requested_data_types.insert(tag_id.into(), vt);
+        self
+    }
+
+    /// Reads through `IOPCItemIO` on DA 3.0 servers instead of a group.
+    #[must_use]
+    pub const fn with_prefer_item_io_read(mut self, enabled: bool) -> Self {
+        self.prefer_item_io_read = enabled;
+        self
+    }
+
+    /// How read values are formatted into strings.
+    pub(crate) const fn variant_format(&self) -> VariantFormat {
+        VariantFormat {
+            hex_dump_unknown: self.hex_dump_unknown_vt,
+            full_arrays: self.full_array_values,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_com_apartment_round_trips() {
+        for apartment in [ComApartment::MultiThreaded, ComApartment::SingleThreaded] {
+            assert_eq!(apartment.to_string().parse::<ComApartment>(), Ok(apartment));
+        }
+        assert_eq!(
+            "STA".parse::<ComApartment>(),
+            Ok(ComApartment::SingleThreaded)
+        );
+        assert!("both".parse::<ComApartment>().is_err());
+        assert_eq!(
+            OpcDaConfig::default().apartment,
+            ComApartment::MultiThreaded
+        );
+    }
+}
@@ -1,18 +1,30 @@
 #[cfg(feature = "opc-da-backend")]
 use crate::opc_da::client::ClientTrait;
-use crate::opc_da::errors::{OpcError, OpcResult};
+use crate::opc_da::errors::{ConnectionDiagnostics, OpcError, OpcResult};
 use crate::provider::OpcValue;
-use windows::Win32::Foundation::{FILETIME, VARIANT_BOOL};
-use windows::Win32::System::Com::{CLSIDFromProgID, CoTaskMemFree, ProgIDFromCLSID};
+use std::collections::BTreeMap;
+use windows::Win32::Foundation::{
+    DISP_E_OVERFLOW, DISP_E_TYPEMISMATCH, ERROR_SUCCESS, FILETIME, VARIANT_BOOL,
+};
+use windows::Win32::System::Com::{
+    CLSIDFromProgID, CoTaskMemFree, IDispatch, ProgIDFromCLSID, SAFEARRAY,
+};
 use windows::Win32::System::Ole::{
-    SafeArrayAccessData, SafeArrayGetDim, SafeArrayGetElemsize, SafeArrayGetLBound,
-    SafeArrayGetUBound, SafeArrayUnaccessData,
+    SafeArrayAccessData, SafeArrayCreateVector, SafeArrayGetDim, SafeArrayGetElemsize,
+    SafeArrayGetLBound, SafeArrayGetUBound, SafeArrayUnaccessData,
+};
+use windows::Win32::System::Registry::{
+    HKEY_CLASSES_ROOT, RRF_RT_ANY, RRF_RT_REG_SZ, RegGetValueW,
+};
+use windows::Win32::System::Variant::{
+    VARENUM, VARIANT, VT_ARRAY, VT_BOOL, VT_BSTR, VT_EMPTY, VT_I1, VT_I2, VT_I4, VT_I8, VT_INT,
+    VT_R4, VT_R8, VT_UI1, VT_UI2, VT_UI4, VT_UI8, VT_UINT, VT_VARIANT, VariantClear,
 };
-use windows::Win32::System::Variant::{VARIANT, VT_BOOL, VT_BSTR, VT_I4, VT_R8};
-use windows::core::{BSTR, PCWSTR};
+use windows::core::{BSTR, Interface, PCWSTR};
 
 pub use crate::opc_da::errors::{
     format_hresult, friendly_com_hint, friendly_hresult_hint as friendly_com_hresult_hint,
+    local_host_hint,
 };
 
 // Verify GUID memory layout assumption for FFI (Workstream C#3)
@@ -32,7 +44,7 @@ pub fn guid_to_progid(guid: &windows::core::GUID) -> OpcResult<String> {
     // before returning — the pointer is not used after free.
     unsafe {
         let progid = ProgIDFromCLSID(guid)
-            .map_err(|e| OpcError::Internal(format!("Failed to get ProgID from CLSID: {e}")))?;
+            .map_err(|e| OpcError::from_com_error(e, "Failed to get ProgID from CLSID"))?;
 
         let result = if progid.is_null() {
             String::new()
@@ -50,9 +62,236 @@ pub fn guid_to_progid(guid: &windows::core::GUID) -> OpcResult<String> {
     }
 }
 
+/// Read the `VARTYPE` discriminant of a VARIANT.
+pub fn variant_vt(variant: &VARIANT) -> u16 {
+    // SAFETY: `vt` is the leading field of every VARIANT layout and is always
+    // initialized, regardless of which value arm is active.
+    unsafe { variant.Anonymous.Anonymous.vt.0 }
+}
+
+/// The value of a `VT_I8` VARIANT, or `None` if `vt` is anything else.
+///
+/// A 64-bit integer lives in the first 8 bytes of the value union (the
+/// `llVal` arm), which starts 8 bytes into the VARIANT on every target and
+/// is 8-byte aligned. The value is only there when `vt` is exactly `VT_I8`:
+/// with `VT_BYREF` the union holds a pointer to it instead, and with
+/// `VT_ARRAY` a `SAFEARRAY` pointer.
+pub fn read_i64_from_variant(variant: &VARIANT) -> Option<i64> {
+    // SAFETY: `vt` is always initialized, and `llVal` is only read once it
+    // says VT_I8, so the arm is the active one; any 8 bytes are a valid i64.
+    unsafe {
+        (variant.Anonymous.Anonymous.vt == VT_I8)
+            .then(|| variant.Anonymous.Anonymous.Anonymous.llVal)
+    }
+}
+
+/// The value of a numeric VARIANT as `f64`: `VT_R8` as is, `VT_R4`, `VT_I2`
+/// and `VT_I4` widened; `None` for anything else.
+pub(crate) fn read_f64_from_variant(variant: &VARIANT) -> Option<f64> {
+    // SAFETY: `vt` is always initialized, and each union arm is only read
+    // once `vt` names it; any bit pattern is a valid number.
+    unsafe {
+        let value = &variant.Anonymous.Anonymous.Anonymous;
+        match variant.Anonymous.Anonymous.vt {
+            VT_R8 => Some(value.dblVal),
+            VT_R4 => Some(f64::from(value.fltVal)),
+            VT_I4 => Some(f64::from(value.lVal)),
+            VT_I2 => Some(f64::from(value.iVal)),
+            _ => None,
+        }
+    }
+}
+
+/// The text of a `VT_BSTR` VARIANT, without the quotes
+/// [`variant_to_string`] adds, or `None` if `vt` is anything else.
+pub(crate) fn read_bstr_from_variant(variant: &VARIANT) -> Option<String> {
+    // SAFETY: `bstrVal` is only read when `vt` is VT_BSTR; a null BSTR reads
+    // as the empty string.
+    unsafe {
+        (variant.Anonymous.Anonymous.vt == VT_BSTR)
+            .then(|| variant.Anonymous.Anonymous.Anonymous.bstrVal.to_string())
+    }
+}
+
+/// The elements of a 1-D `VT_ARRAY | VT_R8` VARIANT, or `None` for any
+/// other type or an array whose data cannot be accessed.
+pub(crate) fn read_r8_array_from_variant(variant: &VARIANT) -> Option<Vec<f64>> {
+    // SAFETY: `parray` is only read when `vt` is VT_ARRAY | VT_R8, so it is
+    // the active arm and, when not null, points to a SAFEARRAY of f64 owned
+    // by `variant`, which outlives the data guard. Exactly the array's
+    // element count is read from its locked data.
+    unsafe {
+        if variant.Anonymous.Anonymous.vt.0 != VT_ARRAY.0 | VT_R8.0 {
+            return None;
+        }
+        let parray = variant.Anonymous.Anonymous.Anonymous.parray;
+        if parray.is_null() || SafeArrayGetDim(parray) != 1 {
+            return None;
+        }
+        let lb = SafeArrayGetLBound(parray, 1).ok()?;
+        let ub = SafeArrayGetUBound(parray, 1).ok()?;
+        let count = usize::try_from(ub - lb + 1).unwrap_or(0);
+        let data = SafeArrayData::access(parray).ok()?;
+        Some(std::slice::from_raw_parts(data.ptr.cast::<f64>(), count).to_vec())
+    }
+}
+
+/// Engineering units from the property values and per-property errors
+/// `GetItemProperties` returned for [`EngineeringUnits::PROPERTIES`].
+///
+/// The label is read from the `VT_BSTR` directly; an empty label counts as
+/// none. The range comes from the high/low properties when the server has
+/// them, and from an analog item's `[low, high]` EU info array otherwise.
+pub(crate) fn engineering_units_from_properties(
+    values: &[VARIANT],
+    errors: &[windows::core::HRESULT],
+) -> crate::provider::EngineeringUnits {
+    // A property the item lacks comes back with a failed per-property HRESULT
+    let property = |idx: usize| {
+        errors
+            .get(idx)
+            .filter(|error| error.is_ok())
+            .and_then(|_| values.get(idx))
+    };
+    let eu_info = property(3)
+        .and_then(read_r8_array_from_variant)
+        .filter(|range| range.len() == 2);
+    crate::provider::EngineeringUnits {
+        units: property(0)
+            .and_then(read_bstr_from_variant)
+            .filter(|units| !units.is_empty()),
+        high: property(1)
+            .and_then(read_f64_from_variant)
+            .or_else(|| eu_info.as_ref().map(|range| range[1])),
+        low: property(2)
+            .and_then(read_f64_from_variant)
+            .or_else(|| eu_info.as_ref().map(|range| range[0])),
+    }
+}
+
+/// The value of a `VT_UI8` VARIANT, or `None` if `vt` is anything else.
+///
+/// Same layout contract as [`read_i64_from_variant`], through the `ullVal`
+/// arm.
+pub fn read_u64_from_variant(variant: &VARIANT) -> Option<u64> {
+    // SAFETY: As in `read_i64_from_variant`: `ullVal` is only read when `vt`
+    // is VT_UI8, and any 8 bytes are a valid u64.
+    unsafe {
+        (variant.Anonymous.Anonymous.vt == VT_UI8)
+            .then(|| variant.Anonymous.Anonymous.Anonymous.ullVal)
+    }
+}
+
+/// Name a `VARTYPE` code, e.g. `5` → `"VT_R8"`, `0x2005` → `"VT_ARRAY|VT_R8"`.
+///
+/// Unrecognized base types are rendered as `VT_0xNNNN`.
+pub fn vt_to_string(vt: u16) -> String {
+    const VT_ARRAY: u16 = 0x2000;
+    const VT_BYREF: u16 = 0x4000;
+
+    let base = match vt & 0x0FFF {
+        0 => "VT_EMPTY",
+        1 => "VT_NULL",
+        2 => "VT_I2",
+        3 => "VT_I4",
+        4 => "VT_R4",
+        5 => "VT_R8",
+        6 => "VT_CY",
+        7 => "VT_DATE",
+        8 => "VT_BSTR",
+        9 => "VT_DISPATCH",
+        10 => "VT_ERROR",
+        11 => "VT_BOOL",
+        12 => "VT_VARIANT",
+        13 => "VT_UNKNOWN",
+        14 => "VT_DECIMAL",
+        16 => "VT_I1",
+        17 => "VT_UI1",
+        18 => "VT_UI2",
+        19 => "VT_UI4",
+        20 => "VT_I8",
+        21 => "VT_UI8",
+        22 => "VT_INT",
+        23 => "VT_UINT",
+        other => return format!("VT_0x{other:04X}"),
+    };
+
+    let mut name = String::new();
+    if vt & VT_BYREF != 0 {
+        name.push_str("VT_BYREF|");
+    }
+    if vt & VT_ARRAY != 0 {
+        name.push_str("VT_ARRAY|");
+    }
+    name.push_str(base);
+    name
+}
+
+/// Parse a scalar `VARTYPE` name such as `VT_R8`, `r8` or `5`, the reverse
+/// of [`vt_to_string`] for the types it names.
+///
+/// Returns `None` for unknown names and for array or by-reference types.
+///
+/// # Examples
+///
+/// ```
+/// use opc_da_client::vt_from_str;
+///
+/// assert_eq!(vt_from_str("VT_R8"), Some(5));
+/// assert_eq!(vt_from_str("bstr"), Some(8));
+/// assert_eq!(vt_from_str("VT_STRING"), None);
+/// ```
+pub fn vt_from_str(name: &str) -> Option<u16> {
+    let name = name.trim();
+    let bare = name
+        .get(..3)
+        .filter(|prefix| prefix.eq_ignore_ascii_case("VT_"))
+        .map_or(name, |_| &name[3..]);
+    // Every scalar type `vt_to_string` names lies in 0..=23
+    (0..=23u16).find(|&vt| {
+        let known = vt_to_string(vt);
+        !known.starts_with("VT_0x")
+            && (known[3..].eq_ignore_ascii_case(bare) || name.parse() == Ok(vt))
+    })
+}
+
+/// Array elements shown before a display-formatted array is elided.
+pub const ARRAY_DISPLAY_LIMIT: usize = 20;
+
+/// Options for [`format_variant`]. The default matches [`variant_to_string`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct VariantFormat {
+    /// Render unrecognised types as `(VT 0x0024: 01 02 03 04 05 06 07 08)`,
+    /// the first 8 bytes of the value union, instead of a bare `(VT …)`
+    /// label, so vendor-specific types can be inspected without a debugger.
+    pub hex_dump_unknown: bool,
+    /// Show every element of 1-D arrays instead of the first
+    /// [`ARRAY_DISPLAY_LIMIT`] followed by `..., (N items)`.
+    pub full_arrays: bool,
+}
+
 /// Convert OPC DA VARIANT to a displayable string.
-#[allow(clippy::too_many_lines)]
+///
+/// Arrays longer than [`ARRAY_DISPLAY_LIMIT`] are elided; use
+/// [`variant_to_string_full`] where the complete value matters.
 pub fn variant_to_string(variant: &VARIANT) -> String {
+    format_variant(variant, VariantFormat::default())
+}
+
+/// Convert OPC DA VARIANT to a string without eliding arrays, for exports.
+pub fn variant_to_string_full(variant: &VARIANT) -> String {
+    format_variant(
+        variant,
+        VariantFormat {
+            full_arrays: true,
+            ..VariantFormat::default()
+        },
+    )
+}
+
+/// Convert OPC DA VARIANT to a string with explicit [`VariantFormat`] options.
+#[allow(clippy::too_many_lines)]
+pub fn format_variant(variant: &VARIANT, format: VariantFormat) -> String {
     // SAFETY: Accessing the VARIANT union fields. The caller (OpcDaClient)
     // guarantees the VARIANT was produced by COM (e.g., from `group.read()`),
     // so the `vt` discriminant correctly identifies which union arm is active.
@@ -61,6 +300,10 @@ pub fn variant_to_string(variant: &VARIANT) -> String {
         let base_type = vt.0 & 0x0FFF; // strip VT_ARRAY (0x2000) / VT_BYREF (0x4000)
         let is_array = (vt.0 & 0x2000) != 0;
 
+        if (vt.0 & 0x4000) != 0 {
+            return byref_to_string(variant, format);
+        }
+
         if is_array {
             // Iterate 1-D SafeArrays and display actual element values
             let parray = variant.Anonymous.Anonymous.Anonymous.parray;
@@ -77,44 +320,53 @@ pub fn variant_to_string(variant: &VARIANT) -> String {
                 let ub = SafeArrayGetUBound(parray, 1).unwrap_or(-1);
                 let count = (ub - lb + 1).max(0);
                 let mut elements = Vec::new();
-                let display_count = count.min(20);
-
+                let display_count = if format.full_arrays {
+                    count
+                } else {
+                    count.min(i32::try_from(ARRAY_DISPLAY_LIMIT).unwrap_or(i32::MAX))
+                };
+
+                // Unlocked when `data` drops, whichever way this block is left
+                let data = match SafeArrayData::access(parray) {
+                    Ok(data) => data,
+                    Err(e) => {
+                        tracing::warn!(error = ?e, count, "SafeArrayAccessData failed");
+                        return UNREADABLE_ARRAY.to_string();
+                    }
+                };
                 if base_type == windows::Win32::System::Variant::VT_VARIANT.0 {
-                    let mut data_ptr: *mut std::ffi::c_void = std::ptr::null_mut();
-                    if SafeArrayAccessData(parray, &raw mut data_ptr).is_ok() {
+                    #[allow(clippy::cast_sign_loss)]
+                    let vars =
+                        std::slice::from_raw_parts(data.ptr as *const VARIANT, count as usize);
+                    for i in 0..display_count {
                         #[allow(clippy::cast_sign_loss)]
-                        let vars =
-                            std::slice::from_raw_parts(data_ptr as *const VARIANT, count as usize);
-                        for i in 0..display_count {
-                            #[allow(clippy::cast_sign_loss)]
-                            elements.push(variant_to_string(&vars[i as usize]));
-                        }
-                        let _ = SafeArrayUnaccessData(parray);
+                        elements.push(format_variant(&vars[i as usize], format));
                     }
                 } else {
                     let elem_size = SafeArrayGetElemsize(parray) as usize;
-                    let mut data_ptr: *mut std::ffi::c_void = std::ptr::null_mut();
-                    if SafeArrayAccessData(parray, &raw mut data_ptr).is_ok() {
-                        for i in 0..display_count {
-                            let mut temp_var = VARIANT::default();
-                            (*temp_var.Anonymous.Anonymous).vt =
-                                windows::Win32::System::Variant::VARENUM(base_type);
-
-                            #[allow(clippy::cast_sign_loss)]
-                            let src_ptr = (data_ptr as *const u8).add((i as usize) * elem_size);
-                            let dst_ptr =
-                                std::ptr::addr_of_mut!((*temp_var.Anonymous.Anonymous).Anonymous)
-                                    .cast::<u8>();
-
-                            std::ptr::copy_nonoverlapping(src_ptr, dst_ptr, elem_size.min(16));
-
-                            elements.push(variant_to_string(&temp_var));
-                        }
-                        let _ = SafeArrayUnaccessData(parray);
+                    for i in 0..display_count {
+                        let mut temp_var = VARIANT::default();
+                        (*temp_var.Anonymous.Anonymous).vt =
+                            windows::Win32::System::Variant::VARENUM(base_type);
+
+                        #[allow(clippy::cast_sign_loss)]
+                        let src_ptr = (data.ptr as *const u8).add((i as usize) * elem_size);
+                        let dst_ptr =
+                            std::ptr::addr_of_mut!((*temp_var.Anonymous.Anonymous).Anonymous)
+                                .cast::<u8>();
+
+                        std::ptr::copy_nonoverlapping(src_ptr, dst_ptr, elem_size.min(16));
+
+                        elements.push(format_variant(&temp_var, format));
                     }
                 }
+                drop(data);
 
-                let elided = if count > 20 { ", ..." } else { "" };
+                let elided = if count > display_count {
+                    format!(", ... ({count} items)")
+                } else {
+                    String::new()
+                };
                 return format!("[{}{elided}]", elements.join(", "));
             }
             return format!("Array[{dims}D]");
@@ -164,10 +416,36 @@ pub fn variant_to_string(variant: &VARIANT) -> String {
                     None => format!("Error ({hex})"),
                 }
             }
+            9 => {
+                // VT_DISPATCH - report the object's type name, never its contents
+                let name = variant
+                    .Anonymous
+                    .Anonymous
+                    .Anonymous
+                    .pdispVal
+                    .as_ref()
+                    .and_then(dispatch_type_name);
+                name.map_or_else(
+                    || "[IDispatch]".to_string(),
+                    |n| format!("[IDispatch: {n}]"),
+                )
+            }
             11 => format!(
                 "{val}",
                 val = variant.Anonymous.Anonymous.Anonymous.boolVal.0 != 0
             ), // VT_BOOL
+            13 => {
+                // VT_UNKNOWN - type information is only reachable through IDispatch
+                let name = variant
+                    .Anonymous
+                    .Anonymous
+                    .Anonymous
+                    .punkVal
+                    .as_ref()
+                    .and_then(|unk| unk.cast::<IDispatch>().ok())
+                    .and_then(|disp| dispatch_type_name(&disp));
+                name.map_or_else(|| "[IUnknown]".to_string(), |n| format!("[IUnknown: {n}]"))
+            }
             16 => {
                 #[allow(clippy::cast_possible_wrap)]
                 let val = variant.Anonymous.Anonymous.Anonymous.bVal as i8;
@@ -176,25 +454,136 @@ pub fn variant_to_string(variant: &VARIANT) -> String {
             17 => format!("{val}", val = variant.Anonymous.Anonymous.Anonymous.bVal), // VT_UI1
             18 => format!("{val}", val = variant.Anonymous.Anonymous.Anonymous.uiVal), // VT_UI2
             19 => format!("{val}", val = variant.Anonymous.Anonymous.Anonymous.ulVal), // VT_UI4
-            20 => {
-                // VT_I8: read 8 bytes as i64 via pointer cast
-                let p = (&raw const variant.Anonymous.Anonymous.Anonymous).cast::<i64>();
-                // SAFETY: p is a valid pointer to the variant union
-                let val = *p;
-                format!("{val}")
-            }
-            21 => {
-                // VT_UI8: read 8 bytes as u64 via pointer cast
-                let p = (&raw const variant.Anonymous.Anonymous.Anonymous).cast::<u64>();
-                // SAFETY: p is a valid pointer to the variant union
-                let val = *p;
-                format!("{val}")
+            20 => read_i64_from_variant(variant)
+                .map_or_else(|| format!("(VT {vt:?})"), |val| val.to_string()), // VT_I8
+            21 => read_u64_from_variant(variant)
+                .map_or_else(|| format!("(VT {vt:?})"), |val| val.to_string()), // VT_UI8
+            _ if format.hex_dump_unknown => {
+                // SAFETY: The value union is at least 8 bytes (it holds an
+                // i64/f64), so reading its first 8 bytes stays in bounds.
+                let bytes = std::ptr::read_unaligned(
+                    (&raw const variant.Anonymous.Anonymous.Anonymous).cast::<[u8; 8]>(),
+                );
+                let hex: Vec<String> = bytes.iter().map(|b| format!("{b:02X}")).collect();
+                format!("(VT 0x{:04X}: {})", vt.0, hex.join(" "))
             }
             _ => format!("(VT {vt:?})"),
         }
     }
 }
 
+/// Shown instead of the elements of an array whose data cannot be accessed,
+/// so it is not mistaken for an empty array (`[]`).
+const UNREADABLE_ARRAY: &str = "[<unreadable array>]";
+
+/// The data of a `SAFEARRAY`, locked by `SafeArrayAccessData` while the
+/// guard lives and unlocked by `SafeArrayUnaccessData` when it drops, so
+/// no return or panic leaves the array locked.
+struct SafeArrayData {
+    parray: *mut SAFEARRAY,
+    ptr: *mut std::ffi::c_void,
+}
+
+impl SafeArrayData {
+    /// Lock `parray` and get its data pointer.
+    ///
+    /// # Safety
+    /// `parray` must point to a valid `SAFEARRAY` that outlives the guard.
+    unsafe fn access(parray: *mut SAFEARRAY) -> windows::core::Result<Self> {
+        let mut ptr = std::ptr::null_mut();
+        // SAFETY: The caller guarantees `parray` is valid.
+        unsafe { SafeArrayAccessData(parray, &raw mut ptr) }?;
+        Ok(Self { parray, ptr })
+    }
+}
+
+impl Drop for SafeArrayData {
+    fn drop(&mut self) {
+        // SAFETY: `access` locked this array, which is still valid.
+        if let Err(e) = unsafe { SafeArrayUnaccessData(self.parray) } {
+            tracing::warn!(error = ?e, "SafeArrayUnaccessData failed");
+        }
+    }
+}
+
+/// Render a `VT_BYREF` VARIANT by dereferencing its pointer once and
+/// formatting the referenced value.
+///
+/// The caller guarantees `variant` is a COM-produced VARIANT with `VT_BYREF`
+/// set, as for [`format_variant`].
+fn byref_to_string(variant: &VARIANT, format: VariantFormat) -> String {
+    // SAFETY: `VT_BYREF` means the active union arm is `byref`, a pointer to a
+    // value of the remaining VARTYPE owned by the VARIANT's producer. Only
+    // `size` bytes (the size of that VARTYPE) are read from it, into a
+    // temporary that is never cleared, so interfaces and BSTRs it points at
+    // are borrowed rather than released.
+    unsafe {
+        let vt = variant.Anonymous.Anonymous.vt.0 & !0x4000;
+        let target = variant.Anonymous.Anonymous.Anonymous.byref;
+        if target.is_null() {
+            return format!("(VT_BYREF 0x{vt:04X}: null)");
+        }
+        if vt == windows::Win32::System::Variant::VT_VARIANT.0 {
+            return format_variant(&*target.cast::<VARIANT>(), format);
+        }
+        let Some(size) = byref_target_size(vt) else {
+            return format!("(VT {:?})", variant.Anonymous.Anonymous.vt);
+        };
+
+        let mut temp = std::mem::ManuallyDrop::new(VARIANT::default());
+        (*temp.Anonymous.Anonymous).vt = windows::Win32::System::Variant::VARENUM(vt);
+        let dst = std::ptr::addr_of_mut!((*temp.Anonymous.Anonymous).Anonymous).cast::<u8>();
+        std::ptr::copy_nonoverlapping(target.cast::<u8>(), dst, size);
+        format_variant(&temp, format)
+    }
+}
+
+/// Size of the value a `VT_BYREF` pointer refers to, for the VARTYPEs
+/// [`format_variant`] renders.
+const fn byref_target_size(vt: u16) -> Option<usize> {
+    const PTR: usize = std::mem::size_of::<*mut std::ffi::c_void>();
+    if (vt & 0x2000) != 0 {
+        return Some(PTR); // SAFEARRAY*
+    }
+    match vt {
+        16 | 17 => Some(1),             // VT_I1, VT_UI1
+        2 | 11 | 18 => Some(2),         // VT_I2, VT_BOOL, VT_UI2
+        3 | 4 | 10 | 19 => Some(4),     // VT_I4, VT_R4, VT_ERROR, VT_UI4
+        5 | 6 | 7 | 20 | 21 => Some(8), // VT_R8, VT_CY, VT_DATE, VT_I8, VT_UI8
+        8 | 9 | 13 => Some(PTR),        // VT_BSTR, VT_DISPATCH, VT_UNKNOWN
+        _ => None,
+    }
+}
+
+/// Name of the type behind `dispatch`, from its type information.
+///
+/// Returns `None` when the object provides no type information, which is
+/// common; callers then fall back to the interface name.
+fn dispatch_type_name(dispatch: &IDispatch) -> Option<String> {
+    // MEMBERID_NIL: documentation of the type itself rather than a member.
+    const MEMBERID_NIL: i32 = -1;
+    // SAFETY: `dispatch` is a live interface reference. `GetTypeInfoCount`
+    // and `GetTypeInfo` only return owned values, and `GetDocumentation`
+    // writes the name into a `BSTR` owned (and freed) here.
+    unsafe {
+        if dispatch.GetTypeInfoCount().ok()? == 0 {
+            return None;
+        }
+        let type_info = dispatch.GetTypeInfo(0, 0).ok()?;
+        let mut name = BSTR::new();
+        type_info
+            .GetDocumentation(
+                MEMBERID_NIL,
+                Some(&raw mut name),
+                None,
+                std::ptr::null_mut(),
+                None,
+            )
+            .ok()?;
+        (!name.is_empty()).then(|| name.to_string())
+    }
+}
+
 /// Convert an OLE Automation date (f64) to a local datetime string.
 /// OLE date epoch is 1899-12-30; integer part = days, fraction = time-of-day.
 #[allow(
@@ -216,6 +605,51 @@ fn ole_date_to_string(ole_date: f64) -> String {
     )
 }
 
+/// Prepare a host string for `COSERVERINFO::pwszName`.
+///
+/// Windows expects IPv6 literals in brackets (`[::1]`, `[fe80::1%3]`), so
+/// bare IPv6 addresses are wrapped, zone ID included. Hostnames, IPv4
+/// addresses and already-bracketed addresses are returned trimmed but
+/// otherwise unchanged.
+pub fn normalize_host(host: &str) -> String {
+    let host = host.trim();
+    if !host.starts_with('[') && is_ipv6_literal(host) {
+        format!("[{host}]")
+    } else {
+        host.to_string()
+    }
+}
+
+/// Whether `host` is an IPv6 address, bracketed or not, with an optional
+/// `%zone` suffix naming the interface (`fe80::1%3`, `[fe80::1%eth0]`).
+pub fn is_ipv6_literal(host: &str) -> bool {
+    let host = host
+        .strip_prefix('[')
+        .and_then(|h| h.strip_suffix(']'))
+        .unwrap_or(host);
+    let (address, zone) = host
+        .split_once('%')
+        .map_or((host, None), |(address, zone)| (address, Some(zone)));
+    address.parse::<std::net::Ipv6Addr>().is_ok()
+        && zone.is_none_or(|zone| {
+            !zone.is_empty()
+                && zone
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || "-_.".contains(c))
+        })
+}
+
+/// Clean up an item ID or browse name returned by a server.
+///
+/// Some servers pad the wide strings they return with NUL characters or
+/// whitespace. `String::from_utf16_lossy` keeps both, and the padded ID then
+/// fails to match when it is passed back for a read. Both are stripped from
+/// either end; the rest of the ID is left alone.
+pub fn normalize_tag_id(s: &str) -> String {
+    s.trim_matches(|c: char| c == '\0' || c.is_whitespace())
+        .to_string()
+}
+
 /// Map OPC quality code to a human-readable label.
 pub fn quality_to_string(quality: u16) -> String {
     let quality_bits = quality & 0xC0; // Top 2 bits define Good/Bad/Uncertain
@@ -247,12 +681,76 @@ pub fn filetime_to_string(ft: FILETIME) -> String {
     )
 }
 
+/// Convert FILETIME to a [`SystemTime`](std::time::SystemTime).
+///
+/// Returns `None` for the zero FILETIME servers send when they have no
+/// timestamp, and for times before the Unix epoch.
+pub fn filetime_to_system_time(ft: FILETIME) -> Option<std::time::SystemTime> {
+    if ft.dwHighDateTime == 0 && ft.dwLowDateTime == 0 {
+        return None;
+    }
+    let intervals = (u64::from(ft.dwHighDateTime) << 32) | u64::from(ft.dwLowDateTime);
+    // 100 ns intervals between 1601-01-01 and 1970-01-01.
+    let since_unix = intervals.checked_sub(116_444_736_000_000_000)?;
+    let since_unix = std::time::Duration::from_secs(since_unix / 10_000_000)
+        + std::time::Duration::from_nanos((since_unix % 10_000_000) * 100);
+    std::time::UNIX_EPOCH.checked_add(since_unix)
+}
+
+/// Element type of the `SAFEARRAY` an [`OpcValue::Array`] is written as.
+///
+/// Integers alone give `VT_I4`; integers mixed with floats are widened to
+/// `VT_R8`. Anything else that is not uniform, including an empty array,
+/// uses `VT_VARIANT` elements.
+fn array_element_vt(elements: &[OpcValue]) -> VARENUM {
+    let all = |f: fn(&OpcValue) -> bool| !elements.is_empty() && elements.iter().all(f);
+    if all(|e| matches!(e, OpcValue::Int(_))) {
+        VT_I4
+    } else if all(|e| matches!(e, OpcValue::Int(_) | OpcValue::Float(_))) {
+        VT_R8
+    } else if all(|e| matches!(e, OpcValue::Bool(_))) {
+        VT_BOOL
+    } else if all(|e| matches!(e, OpcValue::String(_))) {
+        VT_BSTR
+    } else {
+        VT_VARIANT
+    }
+}
+
+/// The `VARTYPE` [`opc_value_to_variant`] sends `value` as.
+///
+/// # Examples
+///
+/// ```
+/// use opc_da_client::{OpcValue, opc_value_vt, vt_to_string};
+///
+/// assert_eq!(vt_to_string(opc_value_vt(&OpcValue::Float(1.5))), "VT_R8");
+/// ```
+pub fn opc_value_vt(value: &OpcValue) -> u16 {
+    match value {
+        OpcValue::String(_) => VT_BSTR.0,
+        OpcValue::Int(_) => VT_I4.0,
+        OpcValue::Float(_) => VT_R8.0,
+        OpcValue::Bool(_) => VT_BOOL.0,
+        OpcValue::Array(elements) => VT_ARRAY.0 | array_element_vt(elements).0,
+    }
+}
+
 /// Convert an [`OpcValue`] into a COM [`VARIANT`] for writing.
+///
+/// [`OpcValue::Array`] becomes a 1-D `VT_ARRAY` whose element type is picked
+/// by [`array_element_vt`]. If the `SAFEARRAY` cannot be allocated the
+/// VARIANT carries a null array, which the server rejects.
+///
+/// The caller owns the returned VARIANT and the `BSTR` or `SAFEARRAY` it
+/// may hold. `VARIANT` has no `Drop`, and `IOPCSyncIO::Write` only borrows
+/// its values, so wrap it in an [`OwnedVariant`] to have it freed.
 pub fn opc_value_to_variant(value: &OpcValue) -> VARIANT {
     let mut variant = VARIANT::default();
     // SAFETY: We set the `vt` discriminant and the corresponding union
     // field atomically. The VARIANT is returned by value, so no aliasing.
-    // `ManuallyDrop` on BSTR prevents double-free — COM takes ownership.
+    // The union stores the BSTR as `ManuallyDrop`; it is freed by
+    // `VariantClear` on whoever ends up owning the VARIANT.
     unsafe {
         match value {
             OpcValue::String(s) => {
@@ -273,11 +771,321 @@ pub fn opc_value_to_variant(value: &OpcValue) -> VARIANT {
                 (*variant.Anonymous.Anonymous).Anonymous.boolVal =
                     VARIANT_BOOL(if *b { -1 } else { 0 });
             }
+            OpcValue::Array(elements) => {
+                let elem_vt = array_element_vt(elements);
+                let count = u32::try_from(elements.len()).unwrap_or(u32::MAX);
+                let parray = SafeArrayCreateVector(elem_vt, 0, count);
+                if !parray.is_null()
+                    && let Ok(guard) = SafeArrayData::access(parray)
+                {
+                    let data = guard.ptr;
+                    // The array owns every element written here, BSTRs and
+                    // nested VARIANTs included; destroying it frees them.
+                    for (i, element) in elements.iter().enumerate() {
+                        match (elem_vt, element) {
+                            (VT_I4, OpcValue::Int(v)) => data.cast::<i32>().add(i).write(*v),
+                            (VT_R8, OpcValue::Int(v)) => {
+                                data.cast::<f64>().add(i).write(f64::from(*v));
+                            }
+                            (VT_R8, OpcValue::Float(v)) => data.cast::<f64>().add(i).write(*v),
+                            (VT_BOOL, OpcValue::Bool(v)) => data
+                                .cast::<VARIANT_BOOL>()
+                                .add(i)
+                                .write(VARIANT_BOOL(if *v { -1 } else { 0 })),
+                            (VT_BSTR, OpcValue::String(v)) => {
+                                data.cast::<BSTR>().add(i).write(BSTR::from(v));
+                            }
+                            _ => data
+                                .cast::<VARIANT>()
+                                .add(i)
+                                .write(opc_value_to_variant(element)),
+                        }
+                    }
+                }
+                (*variant.Anonymous.Anonymous).vt = VARENUM(VT_ARRAY.0 | elem_vt.0);
+                (*variant.Anonymous.Anonymous).Anonymous.parray = parray;
+            }
         }
     }
     variant
 }
 
+/// A [`VARIANT`] freed with `VariantClear` when dropped.
+///
+/// Releases the `BSTR`, `SAFEARRAY` or interface a VARIANT built for a write
+/// holds on every path, including early returns before the write call. COM
+/// `[in]` parameters such as the values of `IOPCSyncIO::Write` stay owned by
+/// the caller, so nothing else frees them.
+#[repr(transparent)]
+pub struct OwnedVariant(VARIANT);
+
+impl OwnedVariant {
+    /// Take ownership of `variant`.
+    pub const fn new(variant: VARIANT) -> Self {
+        Self(variant)
+    }
+
+    /// Borrow the VARIANT, e.g. to pass to a write.
+    pub const fn get(&self) -> &VARIANT {
+        &self.0
+    }
+
+    /// View a slice of owned VARIANTs as the VARIANT array COM calls take.
+    pub const fn as_variants(owned: &[Self]) -> &[VARIANT] {
+        // SAFETY: `OwnedVariant` is `repr(transparent)` over `VARIANT`, so
+        // the two slices have the same layout.
+        unsafe { std::slice::from_raw_parts(owned.as_ptr().cast::<VARIANT>(), owned.len()) }
+    }
+
+    /// Free what the VARIANT holds, leaving it `VT_EMPTY`. Clearing again,
+    /// or dropping afterwards, frees nothing twice.
+    pub fn clear(&mut self) {
+        // SAFETY: The VARIANT was built by this crate or handed over by its
+        // owner, so its discriminant matches its contents.
+        if let Err(e) = unsafe { VariantClear(&raw mut self.0) } {
+            tracing::warn!(error = ?e, "VariantClear failed");
+        }
+    }
+}
+
+impl From<VARIANT> for OwnedVariant {
+    fn from(variant: VARIANT) -> Self {
+        Self::new(variant)
+    }
+}
+
+impl Drop for OwnedVariant {
+    fn drop(&mut self) {
+        self.clear();
+    }
+}
+
+/// Convert `value` into a VARIANT of exactly type `vt`, for servers that
+/// reject writes whose type differs from the item's canonical type.
+///
+/// Numbers, booleans and numeric strings convert between each other:
+/// fractional values are rounded for integer types, `true` becomes `1` and
+/// any non-zero number becomes `true`. A `vt` of `VT_EMPTY` (type unknown),
+/// `VT_VARIANT` or an array type sends `value` as [`opc_value_to_variant`]
+/// does. The caller owns the result; keep it in an [`OwnedVariant`].
+///
+/// # Errors
+/// `DISP_E_OVERFLOW` if the value does not fit in `vt`, and
+/// `DISP_E_TYPEMISMATCH` if it cannot be converted to it, such as a
+/// non-numeric string for `VT_I4` or an array for a scalar type.
+///
+/// # Examples
+///
+/// ```
+/// use opc_da_client::{OpcValue, OwnedVariant, coerce_to_vt, variant_to_string};
+///
+/// let variant = OwnedVariant::new(coerce_to_vt(&OpcValue::Float(41.6), 3).unwrap()); // VT_I4
+/// assert_eq!(variant_to_string(variant.get()), "42");
+/// ```
+pub fn coerce_to_vt(value: &OpcValue, vt: u16) -> windows::core::Result<VARIANT> {
+    let vt = VARENUM(vt);
+    if vt == VT_EMPTY || vt == VT_VARIANT || vt.0 & VT_ARRAY.0 != 0 {
+        return Ok(opc_value_to_variant(value));
+    }
+    match vt {
+        VT_BOOL => Ok(opc_value_to_variant(&OpcValue::Bool(coerce_bool(value)?))),
+        VT_BSTR => Ok(opc_value_to_variant(&OpcValue::String(coerce_text(value)?))),
+        VT_R8 => Ok(opc_value_to_variant(&OpcValue::Float(coerce_float(value)?))),
+        VT_R4 => {
+            let f = coerce_float(value)?;
+            if f.is_finite() && f.abs() > f64::from(f32::MAX) {
+                return Err(DISP_E_OVERFLOW.into());
+            }
+            let mut variant = VARIANT::default();
+            // SAFETY: The discriminant and its matching union field are set
+            // together on a VARIANT owned by this function.
+            unsafe {
+                (*variant.Anonymous.Anonymous).vt = VT_R4;
+                #[allow(clippy::cast_possible_truncation)]
+                {
+                    (*variant.Anonymous.Anonymous).Anonymous.fltVal = f as f32;
+                }
+            }
+            Ok(variant)
+        }
+        _ => integer_variant(coerce_integer(value)?, vt),
+    }
+}
+
+fn coerce_bool(value: &OpcValue) -> windows::core::Result<bool> {
+    match value {
+        OpcValue::Bool(b) => Ok(*b),
+        OpcValue::Int(i) => Ok(*i != 0),
+        OpcValue::Float(f) => Ok(*f != 0.0),
+        OpcValue::String(s) if s.trim().eq_ignore_ascii_case("true") => Ok(true),
+        OpcValue::String(s) if s.trim().eq_ignore_ascii_case("false") => Ok(false),
+        OpcValue::String(_) => coerce_float(value).map(|f| f != 0.0),
+        OpcValue::Array(_) => Err(DISP_E_TYPEMISMATCH.into()),
+    }
+}
+
+fn coerce_text(value: &OpcValue) -> windows::core::Result<String> {
+    match value {
+        OpcValue::String(s) => Ok(s.clone()),
+        OpcValue::Int(i) => Ok(i.to_string()),
+        OpcValue::Float(f) => Ok(f.to_string()),
+        OpcValue::Bool(b) => Ok(b.to_string()),
+        OpcValue::Array(_) => Err(DISP_E_TYPEMISMATCH.into()),
+    }
+}
+
+fn coerce_float(value: &OpcValue) -> windows::core::Result<f64> {
+    match value {
+        OpcValue::Float(f) => Ok(*f),
+        OpcValue::Int(i) => Ok(f64::from(*i)),
+        OpcValue::Bool(b) => Ok(if *b { 1.0 } else { 0.0 }),
+        OpcValue::String(s) => s.trim().parse().map_err(|_| DISP_E_TYPEMISMATCH.into()),
+        OpcValue::Array(_) => Err(DISP_E_TYPEMISMATCH.into()),
+    }
+}
+
+/// `value` as a whole number, rounding fractions. `i128` covers every
+/// integer `VARTYPE`, so range checks happen in [`integer_variant`].
+fn coerce_integer(value: &OpcValue) -> windows::core::Result<i128> {
+    let f = match value {
+        OpcValue::Int(i) => return Ok(i128::from(*i)),
+        OpcValue::String(s) => match s.trim().parse::<i128>() {
+            Ok(n) => return Ok(n),
+            Err(_) => coerce_float(value)?,
+        },
+        _ => coerce_float(value)?,
+    };
+    let rounded = f.round();
+    // 2^127 bounds i128; anything at or beyond it cannot fit any VARTYPE.
+    if !rounded.is_finite() || rounded.abs() >= 2f64.powi(127) {
+        return Err(DISP_E_OVERFLOW.into());
+    }
+    #[allow(clippy::cast_possible_truncation)]
+    Ok(rounded as i128)
+}
+
+/// A VARIANT of integer type `vt` holding `n`.
+fn integer_variant(n: i128, vt: VARENUM) -> windows::core::Result<VARIANT> {
+    fn fit<T: TryFrom<i128>>(n: i128) -> windows::core::Result<T> {
+        T::try_from(n).map_err(|_| DISP_E_OVERFLOW.into())
+    }
+
+    let mut variant = VARIANT::default();
+    // SAFETY: Each arm writes the union field matching `vt`, and the
+    // discriminant is only set once a field has been written.
+    unsafe {
+        let fields = &mut (*variant.Anonymous.Anonymous).Anonymous;
+        match vt {
+            VT_I1 => fields.cVal = fit(n)?,
+            VT_UI1 => fields.bVal = fit(n)?,
+            VT_I2 => fields.iVal = fit(n)?,
+            VT_UI2 => fields.uiVal = fit(n)?,
+            VT_I4 => fields.lVal = fit(n)?,
+            VT_UI4 => fields.ulVal = fit(n)?,
+            VT_INT => fields.intVal = fit(n)?,
+            VT_UINT => fields.uintVal = fit(n)?,
+            VT_I8 => fields.llVal = fit(n)?,
+            VT_UI8 => fields.ullVal = fit(n)?,
+            _ => return Err(DISP_E_TYPEMISMATCH.into()),
+        }
+        (*variant.Anonymous.Anonymous).vt = vt;
+    }
+    Ok(variant)
+}
+
+/// Owns the UTF-16 item IDs referenced by a batch of [`tagOPCITEMDEF`]s.
+///
+/// `tagOPCITEMDEF::szItemID` is a raw pointer, so the wide strings it points
+/// at must outlive every use of the definitions. The builder keeps both
+/// together: the slice returned by [`ItemDefBuilder::defs`] borrows the
+/// builder, so the compiler rejects any use after the buffers are dropped.
+///
+/// [`tagOPCITEMDEF`]: crate::bindings::da::tagOPCITEMDEF
+pub struct ItemDefBuilder {
+    // Each inner buffer is heap-allocated and never mutated after push, so
+    // its address stays stable even when the outer `Vec` reallocates.
+    item_ids: Vec<Vec<u16>>,
+    defs: Vec<crate::bindings::da::tagOPCITEMDEF>,
+    active: bool,
+}
+
+impl ItemDefBuilder {
+    /// Creates an empty builder; items added later use the given active state.
+    pub fn new(active: bool) -> Self {
+        Self {
+            item_ids: Vec::new(),
+            defs: Vec::new(),
+            active,
+        }
+    }
+
+    /// Creates a builder holding one definition per tag, using the index as `hClient`.
+    pub fn from_tag_ids(tag_ids: &[String], active: bool) -> Self {
+        Self::from_tag_ids_requesting(tag_ids, active, &BTreeMap::new())
+    }
+
+    /// Like [`from_tag_ids`](Self::from_tag_ids), asking the server for the
+    /// `VARTYPE` in `requested_types` for each tag listed there.
+    pub fn from_tag_ids_requesting(
+        tag_ids: &[String],
+        active: bool,
+        requested_types: &BTreeMap<String, u16>,
+    ) -> Self {
+        let mut builder = Self::new(active);
+        builder.item_ids.reserve(tag_ids.len());
+        builder.defs.reserve(tag_ids.len());
+        for (idx, tag_id) in tag_ids.iter().enumerate() {
+            let requested = requested_types.get(tag_id).copied().unwrap_or(0);
+            #[allow(clippy::cast_possible_truncation)]
+            builder.push_with_type(tag_id, idx as u32, requested);
+        }
+        builder
+    }
+
+    /// Appends a definition for `item_id` with the given client handle.
+    pub fn push(&mut self, item_id: &str, client_handle: u32) -> &mut Self {
+        self.push_with_type(item_id, client_handle, 0)
+    }
+
+    /// Appends a definition for `item_id` asking the server to convert its
+    /// values to `requested_type` (`VT_EMPTY`, 0, keeps the canonical type).
+    pub fn push_with_type(
+        &mut self,
+        item_id: &str,
+        client_handle: u32,
+        requested_type: u16,
+    ) -> &mut Self {
+        let wide: Vec<u16> = item_id.encode_utf16().chain(std::iter::once(0)).collect();
+        self.defs.push(crate::bindings::da::tagOPCITEMDEF {
+            szAccessPath: windows::core::PWSTR::null(),
+            szItemID: windows::core::PWSTR(wide.as_ptr().cast_mut()),
+            bActive: self.active.into(),
+            hClient: client_handle,
+            dwBlobSize: 0,
+            pBlob: std::ptr::null_mut(),
+            vtRequestedDataType: requested_type,
+            wReserved: 0,
+        });
+        self.item_ids.push(wide);
+        self
+    }
+
+    /// The item definitions, valid for as long as the builder is borrowed.
+    pub fn defs(&self) -> &[crate::bindings::da::tagOPCITEMDEF] {
+        &self.defs
+    }
+
+    /// Number of item definitions.
+    pub fn len(&self) -> usize {
+        self.defs.len()
+    }
+
+    /// Whether no items have been added.
+    pub fn is_empty(&self) -> bool {
+        self.defs.is_empty()
+    }
+}
+
 /// Resolve an OPC DA server `ProgID` to a connected `opc_da` Server instance.
 ///
 /// Converts the `ProgID` string to a `CLSID` via the Windows registry,
@@ -296,9 +1104,10 @@ pub fn connect_server(server_name: &str) -> OpcResult<crate::bindings::da::IOPCS
             .chain(std::iter::once(0))
             .collect();
         CLSIDFromProgID(PCWSTR(server_wide.as_ptr())).map_err(|e| {
-            OpcError::Connection(format!(
-                "Failed to resolve ProgID '{server_name}' to CLSID: {e}"
-            ))
+            OpcError::from_com_error(
+                e,
+                format!("Failed to resolve ProgID '{server_name}' to CLSID"),
+            )
         })?
     };
     // SAFETY: `opc_da::GUID` and `windows::core::GUID` are binary compatible
@@ -309,12 +1118,10 @@ pub fn connect_server(server_name: &str) -> OpcResult<crate::bindings::da::IOPCS
     let server = client
         .create_server(clsid, crate::opc_da::typedefs::ClassContext::All)
         .map_err(|e| {
-            let hint = if let OpcError::Com { ref source } = e {
-                friendly_com_hresult_hint(source.code())
-            } else {
-                None
-            }
-            .unwrap_or("Check DCOM configuration and server status");
+            let hint = e
+                .hresult()
+                .and_then(friendly_com_hresult_hint)
+                .unwrap_or("Check DCOM configuration and server status");
             tracing::error!(error = ?e, server = %server_name, hint, "create_server failed");
             e
         })?;
@@ -322,6 +1129,113 @@ pub fn connect_server(server_name: &str) -> OpcResult<crate::bindings::da::IOPCS
     Ok(server.server)
 }
 
+/// Look up how `server_name` is registered, to explain a failed connection.
+///
+/// Only reads `HKEY_CLASSES_ROOT`: nothing is launched or modified, so this
+/// is safe to call after any connection failure.
+pub fn diagnose_server_registration(server_name: &str) -> ConnectionDiagnostics {
+    let mut diagnostics = ConnectionDiagnostics {
+        prog_id: server_name.to_string(),
+        ..ConnectionDiagnostics::default()
+    };
+    let server_wide: Vec<u16> = server_name
+        .encode_utf16()
+        .chain(std::iter::once(0))
+        .collect();
+    // SAFETY: `server_wide` is null-terminated and outlives the call.
+    let Ok(clsid) = (unsafe { CLSIDFromProgID(PCWSTR(server_wide.as_ptr())) }) else {
+        return diagnostics;
+    };
+    let clsid = format!("{{{clsid:?}}}");
+
+    let class_key = format!(r"CLSID\{clsid}");
+    diagnostics.server_command =
+        read_class_root_string(&format!(r"{class_key}\LocalServer32"), None)
+            .or_else(|| read_class_root_string(&format!(r"{class_key}\InprocServer32"), None));
+    diagnostics.server_exe_exists = diagnostics
+        .server_command
+        .as_deref()
+        .map(|command| std::path::Path::new(server_exe_path(command)).exists());
+    diagnostics.app_id = read_class_root_string(&class_key, Some("AppID"));
+    if let Some(app_id) = &diagnostics.app_id {
+        let app_key = format!(r"AppID\{app_id}");
+        diagnostics.custom_launch_permission =
+            Some(class_root_value_exists(&app_key, "LaunchPermission"));
+        diagnostics.custom_access_permission =
+            Some(class_root_value_exists(&app_key, "AccessPermission"));
+    }
+    diagnostics.clsid = Some(clsid);
+    tracing::debug!(server = %server_name, ?diagnostics, "Gathered connection diagnostics");
+    diagnostics
+}
+
+/// The executable path in a `LocalServer32` command line, which may be
+/// quoted and may carry arguments such as `-Embedding`.
+fn server_exe_path(command: &str) -> &str {
+    let command = command.trim();
+    if let Some(quoted) = command.strip_prefix('"') {
+        return quoted.split('"').next().unwrap_or(quoted);
+    }
+    // Unquoted paths may contain spaces; cut at the first argument switch.
+    command
+        .find(" -")
+        .or_else(|| command.find(" /"))
+        .map_or(command, |end| command[..end].trim_end())
+}
+
+/// Read a string value under `HKEY_CLASSES_ROOT\{subkey}`; `None` reads
+/// the key's default value.
+fn read_class_root_string(subkey: &str, value: Option<&str>) -> Option<String> {
+    let subkey_wide: Vec<u16> = subkey.encode_utf16().chain(std::iter::once(0)).collect();
+    let value_wide: Option<Vec<u16>> =
+        value.map(|v| v.encode_utf16().chain(std::iter::once(0)).collect());
+    let value_ptr = value_wide
+        .as_ref()
+        .map_or(PCWSTR::null(), |v| PCWSTR(v.as_ptr()));
+    let mut buffer = [0u16; 1024];
+    let mut len = u32::try_from(std::mem::size_of_val(&buffer)).ok()?;
+    // SAFETY: both names are null-terminated and outlive the call, and `len`
+    // holds the size of `buffer` in bytes, so the write stays in bounds.
+    let status = unsafe {
+        RegGetValueW(
+            HKEY_CLASSES_ROOT,
+            PCWSTR(subkey_wide.as_ptr()),
+            value_ptr,
+            RRF_RT_REG_SZ,
+            None,
+            Some(buffer.as_mut_ptr().cast()),
+            Some(&raw mut len),
+        )
+    };
+    if status != ERROR_SUCCESS {
+        return None;
+    }
+    let chars = (len as usize / 2).min(buffer.len());
+    let text = String::from_utf16_lossy(&buffer[..chars]);
+    let text = text.trim_end_matches('\0').trim();
+    (!text.is_empty()).then(|| text.to_string())
+}
+
+/// Whether `HKEY_CLASSES_ROOT\{subkey}` has a value named `value`, of any type.
+fn class_root_value_exists(subkey: &str, value: &str) -> bool {
+    let subkey_wide: Vec<u16> = subkey.encode_utf16().chain(std::iter::once(0)).collect();
+    let value_wide: Vec<u16> = value.encode_utf16().chain(std::iter::once(0)).collect();
+    // SAFETY: both names are null-terminated and outlive the call; no data
+    // buffer is passed, so nothing is written.
+    let status = unsafe {
+        RegGetValueW(
+            HKEY_CLASSES_ROOT,
+            PCWSTR(subkey_wide.as_ptr()),
+            PCWSTR(value_wide.as_ptr()),
+            RRF_RT_ANY,
+            None,
+            None,
+            None,
+        )
+    };
+    status == ERROR_SUCCESS
+}
+
 #[cfg(test)]
 mod tests {
     #![allow(
@@ -334,6 +1248,55 @@ mod tests {
     )]
     use super::*;
 
+    #[test]
+    fn test_normalize_host() {
+        assert_eq!(normalize_host("::1"), "[::1]");
+        assert_eq!(normalize_host("2001:db8::1"), "[2001:db8::1]");
+        assert_eq!(normalize_host("[::1]"), "[::1]");
+        assert_eq!(normalize_host(" plant-pc "), "plant-pc");
+        assert_eq!(normalize_host("192.168.1.10"), "192.168.1.10");
+        assert_eq!(normalize_host("localhost"), "localhost");
+        assert_eq!(normalize_host("fe80::1%3"), "[fe80::1%3]");
+        assert_eq!(normalize_host("fe80::1%eth0"), "[fe80::1%eth0]");
+        assert_eq!(normalize_host("[fe80::1%3]"), "[fe80::1%3]");
+        // Not an address, so left for the server to reject
+        assert_eq!(normalize_host("fe80::1%"), "fe80::1%");
+    }
+
+    #[test]
+    fn test_is_ipv6_literal() {
+        assert!(is_ipv6_literal("::1"));
+        assert!(is_ipv6_literal("[2001:db8::1]"));
+        assert!(is_ipv6_literal("fe80::1%3"));
+        assert!(is_ipv6_literal("[fe80::1%eth0]"));
+        assert!(!is_ipv6_literal("fe80::1%"));
+        assert!(!is_ipv6_literal("fe80::1%3%4"));
+        assert!(!is_ipv6_literal("[::1"));
+        assert!(!is_ipv6_literal("192.168.1.10"));
+        assert!(!is_ipv6_literal("plant%pc"));
+    }
+
+    #[test]
+    fn test_normalize_tag_id() {
+        assert_eq!(normalize_tag_id("Random.Int4\0\0"), "Random.Int4");
+        assert_eq!(normalize_tag_id("\0 Line1.Temp \r\n\0"), "Line1.Temp");
+        assert_eq!(normalize_tag_id("Tank 1.Level\t"), "Tank 1.Level");
+        assert_eq!(normalize_tag_id("\0\0"), "");
+    }
+
+    #[test]
+    fn test_server_exe_path() {
+        assert_eq!(
+            server_exe_path(r#""C:\Program Files\Foo\foo.exe" -Embedding"#),
+            r"C:\Program Files\Foo\foo.exe"
+        );
+        assert_eq!(
+            server_exe_path(r"C:\Program Files\Foo\foo.exe /automation"),
+            r"C:\Program Files\Foo\foo.exe"
+        );
+        assert_eq!(server_exe_path(r"C:\Foo\foo.exe"), r"C:\Foo\foo.exe");
+    }
+
     #[test]
     fn test_friendly_com_hint_known_codes() {
         let err = OpcError::Com {
@@ -421,6 +1384,25 @@ mod tests {
         let result = filetime_to_string(ft);
         assert!(result.contains("-"));
     }
+
+    #[test]
+    fn test_filetime_to_system_time() {
+        let zero = FILETIME {
+            dwHighDateTime: 0,
+            dwLowDateTime: 0,
+        };
+        assert_eq!(filetime_to_system_time(zero), None);
+
+        // 116_444_736_000_000_000 + 1.5 s in 100 ns intervals.
+        let ft = FILETIME {
+            dwHighDateTime: 0x019D_B1DE,
+            dwLowDateTime: 0xD623_61C0,
+        };
+        assert_eq!(
+            filetime_to_system_time(ft),
+            Some(std::time::UNIX_EPOCH + std::time::Duration::from_millis(1500))
+        );
+    }
     #[test]
     fn test_opc_value_to_variant_int() {
         let v = opc_value_to_variant(&OpcValue::Int(42));
@@ -472,6 +1454,34 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_owned_variant_frees_contents_once() {
+        let mut owned = OwnedVariant::new(opc_value_to_variant(&OpcValue::String("x".repeat(64))));
+        // SAFETY: Reading the discriminant of an initialized VARIANT.
+        assert_eq!(unsafe { owned.get().Anonymous.Anonymous.vt }, VT_BSTR);
+        owned.clear();
+        // SAFETY: Same as above.
+        assert_eq!(unsafe { owned.get().Anonymous.Anonymous.vt }, VT_EMPTY);
+        // Clearing an empty VARIANT, as the drop at the end does again, frees nothing
+        owned.clear();
+
+        let batch = [
+            OwnedVariant::new(opc_value_to_variant(&OpcValue::Array(vec![
+                OpcValue::String("a".into()),
+                OpcValue::String("b".into()),
+            ]))),
+            OwnedVariant::new(opc_value_to_variant(&OpcValue::Int(1))),
+        ];
+        let view = OwnedVariant::as_variants(&batch);
+        assert_eq!(view.len(), 2);
+        // SAFETY: Same as above.
+        assert_eq!(
+            unsafe { view[0].Anonymous.Anonymous.vt.0 },
+            VT_ARRAY.0 | VT_BSTR.0
+        );
+        assert_eq!(variant_to_string(&view[1]), "1");
+    }
+
     #[test]
     fn test_variant_roundtrip() {
         // Int roundtrip
@@ -495,6 +1505,180 @@ mod tests {
         assert_eq!(variant_to_string(&v), "\"world\"");
     }
 
+    #[test]
+    fn test_opc_value_to_variant_float_array_roundtrip() {
+        let value = OpcValue::Array(vec![
+            OpcValue::Float(1.0),
+            OpcValue::Float(2.0),
+            OpcValue::Float(3.0),
+        ]);
+        let v = opc_value_to_variant(&value);
+        // SAFETY: Reading the discriminant set by opc_value_to_variant.
+        unsafe {
+            assert_eq!(v.Anonymous.Anonymous.vt.0, VT_ARRAY.0 | VT_R8.0);
+        }
+        assert_eq!(variant_to_string(&v), "[1.00, 2.00, 3.00]");
+    }
+
+    #[test]
+    fn test_array_element_vt() {
+        use OpcValue::{Bool, Float, Int};
+        assert_eq!(array_element_vt(&[Int(1), Int(2)]), VT_I4);
+        assert_eq!(array_element_vt(&[Int(1), Float(2.5)]), VT_R8);
+        assert_eq!(array_element_vt(&[Bool(true)]), VT_BOOL);
+        assert_eq!(array_element_vt(&[OpcValue::String("a".into())]), VT_BSTR);
+        assert_eq!(array_element_vt(&[Int(1), Bool(true)]), VT_VARIANT);
+        assert_eq!(array_element_vt(&[]), VT_VARIANT);
+
+        let mixed = opc_value_to_variant(&OpcValue::Array(vec![Int(1), Bool(true)]));
+        assert_eq!(variant_to_string(&mixed), "[1, true]");
+    }
+
+    #[test]
+    fn test_opc_value_vt_matches_sent_variant() {
+        use OpcValue::{Array, Bool, Float, Int};
+        for value in [
+            OpcValue::String("a".into()),
+            Int(1),
+            Float(2.5),
+            Bool(true),
+            Array(vec![Int(1), Float(2.5)]),
+            Array(vec![Int(1), Bool(true)]),
+        ] {
+            let variant = opc_value_to_variant(&value);
+            assert_eq!(opc_value_vt(&value), variant_vt(&variant), "{value:?}");
+        }
+    }
+
+    #[test]
+    fn test_coerce_to_vt_int_to_bool() {
+        let variant = coerce_to_vt(&OpcValue::Int(1), VT_BOOL.0).unwrap();
+        assert_eq!(variant_vt(&variant), VT_BOOL.0);
+        // SAFETY: vt is VT_BOOL, so boolVal is the active field.
+        let bool_val = unsafe { variant.Anonymous.Anonymous.Anonymous.boolVal };
+        assert_eq!(bool_val.0, -1);
+
+        let variant = coerce_to_vt(&OpcValue::Int(0), VT_BOOL.0).unwrap();
+        assert_eq!(variant_to_string(&variant), "false");
+    }
+
+    #[test]
+    fn test_coerce_to_vt_numeric_conversions() {
+        let cases = [
+            (OpcValue::Float(41.6), VT_I4, "42"),
+            (OpcValue::Bool(true), VT_UI1, "1"),
+            (OpcValue::String(" 12 ".into()), VT_I2, "12"),
+            (OpcValue::Int(3), VT_R8, "3.00"),
+            (OpcValue::String("1.5".into()), VT_R4, "1.50"),
+            (OpcValue::Int(7), VT_BSTR, "\"7\""),
+        ];
+        for (value, vt, expected) in cases {
+            let variant = coerce_to_vt(&value, vt.0).unwrap();
+            assert_eq!(variant_vt(&variant), vt.0, "{value:?}");
+            assert_eq!(variant_to_string(&variant), expected, "{value:?}");
+        }
+    }
+
+    #[test]
+    fn test_coerce_to_vt_rejects_overflow_and_mismatch() {
+        let code = |value: OpcValue, vt: VARENUM| coerce_to_vt(&value, vt.0).unwrap_err().code();
+        assert_eq!(code(OpcValue::Int(300), VT_UI1), DISP_E_OVERFLOW);
+        assert_eq!(code(OpcValue::Int(-1), VT_UI4), DISP_E_OVERFLOW);
+        assert_eq!(code(OpcValue::Float(f64::NAN), VT_I4), DISP_E_OVERFLOW);
+        assert_eq!(code(OpcValue::Float(1e39), VT_R4), DISP_E_OVERFLOW);
+        assert_eq!(
+            code(OpcValue::String("abc".into()), VT_I4),
+            DISP_E_TYPEMISMATCH
+        );
+        assert_eq!(code(OpcValue::Array(vec![]), VT_BOOL), DISP_E_TYPEMISMATCH);
+    }
+
+    #[test]
+    fn test_coerce_to_vt_unknown_type_sends_value_as_is() {
+        let variant = coerce_to_vt(&OpcValue::Int(5), VT_EMPTY.0).unwrap();
+        assert_eq!(variant_vt(&variant), VT_I4.0);
+    }
+
+    #[test]
+    fn test_read_64_bit_integers_from_variant() {
+        use std::mem::ManuallyDrop;
+        use windows::Win32::System::Variant::{VARIANT_0, VARIANT_0_0, VARIANT_0_0_0};
+
+        let variant = |vt: VARENUM, value: VARIANT_0_0_0| VARIANT {
+            Anonymous: VARIANT_0 {
+                Anonymous: ManuallyDrop::new(VARIANT_0_0 {
+                    vt,
+                    wReserved1: 0,
+                    wReserved2: 0,
+                    wReserved3: 0,
+                    Anonymous: value,
+                }),
+            },
+        };
+
+        let i8_variant = variant(VT_I8, VARIANT_0_0_0 { llVal: i64::MIN });
+        assert_eq!(read_i64_from_variant(&i8_variant), Some(i64::MIN));
+        assert_eq!(read_u64_from_variant(&i8_variant), None);
+        assert_eq!(variant_to_string(&i8_variant), i64::MIN.to_string());
+
+        let ui8_variant = variant(VT_UI8, VARIANT_0_0_0 { ullVal: u64::MAX });
+        assert_eq!(read_u64_from_variant(&ui8_variant), Some(u64::MAX));
+        assert_eq!(read_i64_from_variant(&ui8_variant), None);
+        assert_eq!(variant_to_string(&ui8_variant), u64::MAX.to_string());
+
+        // Other types sharing the union bytes are not reinterpreted.
+        let r8_variant = variant(VT_R8, VARIANT_0_0_0 { dblVal: 1.0 });
+        assert_eq!(read_i64_from_variant(&r8_variant), None);
+        assert_eq!(read_u64_from_variant(&r8_variant), None);
+    }
+
+    #[test]
+    fn test_engineering_units_from_properties() {
+        use windows::Win32::Foundation::E_FAIL;
+
+        let ok = windows::core::HRESULT(0);
+        let owned = |values: Vec<OpcValue>| {
+            values
+                .iter()
+                .map(|v| OwnedVariant::new(opc_value_to_variant(v)))
+                .collect::<Vec<_>>()
+        };
+
+        let values = owned(vec![
+            OpcValue::String("°C".into()),
+            OpcValue::Float(200.125),
+            OpcValue::Float(-0.005),
+            OpcValue::Int(0),
+        ]);
+        let eu = engineering_units_from_properties(
+            OwnedVariant::as_variants(&values),
+            &[ok, ok, ok, E_FAIL],
+        );
+        assert_eq!(eu.units.as_deref(), Some("°C"), "label is not quoted");
+        assert_eq!(eu.high, Some(200.125), "range keeps full precision");
+        assert_eq!(eu.low, Some(-0.005));
+
+        // An empty label is no label; the range falls back to the EU info
+        // array when high and low are missing.
+        let values = owned(vec![
+            OpcValue::String(String::new()),
+            OpcValue::Int(0),
+            OpcValue::Int(0),
+            OpcValue::Array(vec![OpcValue::Float(-1.5), OpcValue::Float(98.25)]),
+        ]);
+        let eu = engineering_units_from_properties(
+            OwnedVariant::as_variants(&values),
+            &[ok, E_FAIL, E_FAIL, ok],
+        );
+        assert_eq!(eu.units, None);
+        assert_eq!(eu.low, Some(-1.5));
+        assert_eq!(eu.high, Some(98.25));
+
+        let eu =
+            engineering_units_from_properties(OwnedVariant::as_variants(&values), &[E_FAIL; 4]);
+        assert!(eu.is_empty());
+    }
+
     #[test]
     fn test_variant_to_string_cy() {
         use std::mem::ManuallyDrop;
@@ -693,6 +1877,87 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_variant_to_string_tells_unreadable_from_empty_arrays() {
+        use std::mem::ManuallyDrop;
+        use windows::Win32::System::Ole::{SafeArrayCreateVector, SafeArrayDestroy};
+        use windows::Win32::System::Variant::{VARIANT, VARIANT_0, VARIANT_0_0, VT_ARRAY, VT_I4};
+
+        let array_variant = |parray| {
+            let mut middle = VARIANT_0_0 {
+                vt: windows::Win32::System::Variant::VARENUM(VT_I4.0 | VT_ARRAY.0),
+                ..Default::default()
+            };
+            middle.Anonymous.parray = parray;
+            VARIANT {
+                Anonymous: VARIANT_0 {
+                    Anonymous: ManuallyDrop::new(middle),
+                },
+            }
+        };
+
+        // SAFETY: The arrays are created here and destroyed once unlocked
+        unsafe {
+            let empty = SafeArrayCreateVector(VT_I4, 0, 0);
+            assert_eq!(variant_to_string(&array_variant(empty)), "[]");
+            assert_eq!((*empty).cLocks, 0, "unlocked after formatting");
+            SafeArrayDestroy(empty).unwrap();
+
+            // At the maximum lock count `SafeArrayAccessData` fails
+            let locked = SafeArrayCreateVector(VT_I4, 0, 2);
+            (*locked).cLocks = 0xFFFF;
+            assert_eq!(
+                variant_to_string(&array_variant(locked)),
+                "[<unreadable array>]"
+            );
+            assert_eq!((*locked).cLocks, 0xFFFF, "no unlock without a lock");
+            (*locked).cLocks = 0;
+            SafeArrayDestroy(locked).unwrap();
+        }
+    }
+
+    #[test]
+    fn test_variant_to_string_full_keeps_long_arrays() {
+        use std::ffi::c_void;
+        use std::mem::ManuallyDrop;
+        use windows::Win32::System::Ole::{
+            SafeArrayAccessData, SafeArrayCreateVector, SafeArrayUnaccessData,
+        };
+        use windows::Win32::System::Variant::{VARIANT, VARIANT_0, VARIANT_0_0, VT_ARRAY, VT_I4};
+
+        // SAFETY: Array creation and access follow standard COM patterns
+        unsafe {
+            let parray = SafeArrayCreateVector(VT_I4, 0, 25);
+            let mut ptr: *mut c_void = std::ptr::null_mut();
+            SafeArrayAccessData(parray, &mut ptr).unwrap();
+            let slice = std::slice::from_raw_parts_mut(ptr as *mut i32, 25);
+            for (value, slot) in (0..).zip(slice.iter_mut()) {
+                *slot = value;
+            }
+            SafeArrayUnaccessData(parray).unwrap();
+
+            let mut middle = VARIANT_0_0 {
+                vt: windows::Win32::System::Variant::VARENUM(VT_I4.0 | VT_ARRAY.0),
+                ..Default::default()
+            };
+            middle.Anonymous.parray = parray;
+
+            let v = VARIANT {
+                Anonymous: VARIANT_0 {
+                    Anonymous: ManuallyDrop::new(middle),
+                },
+            };
+
+            let shown: Vec<String> = (0..20).map(|i| i.to_string()).collect();
+            assert_eq!(
+                variant_to_string(&v),
+                format!("[{}, ... (25 items)]", shown.join(", "))
+            );
+            let all: Vec<String> = (0..25).map(|i| i.to_string()).collect();
+            assert_eq!(variant_to_string_full(&v), format!("[{}]", all.join(", ")));
+        }
+    }
+
     #[test]
     fn test_variant_to_string_vt_error_known() {
         use std::mem::ManuallyDrop;
@@ -722,6 +1987,169 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_format_variant_hex_dumps_unknown_vt() {
+        use std::mem::ManuallyDrop;
+        use windows::Win32::System::Variant::{
+            VARENUM, VARIANT, VARIANT_0, VARIANT_0_0, VARIANT_0_0_0,
+        };
+
+        let middle = VARIANT_0_0 {
+            vt: VARENUM(0x0FFE), // not a real VARTYPE
+            wReserved1: 0,
+            wReserved2: 0,
+            wReserved3: 0,
+            Anonymous: VARIANT_0_0_0 {
+                llVal: 0x0807_0605_0403_0201,
+            },
+        };
+        // Never cleared: VariantClear has nothing to release for a bogus VT.
+        let v = ManuallyDrop::new(VARIANT {
+            Anonymous: VARIANT_0 {
+                Anonymous: ManuallyDrop::new(middle),
+            },
+        });
+
+        assert_eq!(super::variant_to_string(&v), "(VT VARENUM(4094))");
+        assert_eq!(
+            super::format_variant(
+                &v,
+                VariantFormat {
+                    hex_dump_unknown: true,
+                    ..VariantFormat::default()
+                }
+            ),
+            "(VT 0x0FFE: 01 02 03 04 05 06 07 08)"
+        );
+    }
+
+    /// `IDispatch` without type information, like most automation objects
+    /// an OPC server might hand out.
+    #[allow(clippy::ref_as_ptr, clippy::inline_always)]
+    #[windows::core::implement(IDispatch)]
+    struct OpaqueDispatch;
+
+    impl windows::Win32::System::Com::IDispatch_Impl for OpaqueDispatch_Impl {
+        fn GetTypeInfoCount(&self) -> windows::core::Result<u32> {
+            Ok(0)
+        }
+        fn GetTypeInfo(
+            &self,
+            _itinfo: u32,
+            _lcid: u32,
+        ) -> windows::core::Result<windows::Win32::System::Com::ITypeInfo> {
+            Err(windows::Win32::Foundation::E_NOTIMPL.into())
+        }
+        fn GetIDsOfNames(
+            &self,
+            _riid: *const windows::core::GUID,
+            _rgsznames: *const PCWSTR,
+            _cnames: u32,
+            _lcid: u32,
+            _rgdispid: *mut i32,
+        ) -> windows::core::Result<()> {
+            Err(windows::Win32::Foundation::E_NOTIMPL.into())
+        }
+        fn Invoke(
+            &self,
+            _dispidmember: i32,
+            _riid: *const windows::core::GUID,
+            _lcid: u32,
+            _wflags: windows::Win32::System::Com::DISPATCH_FLAGS,
+            _pdispparams: *const windows::Win32::System::Com::DISPPARAMS,
+            _pvarresult: *mut VARIANT,
+            _pexcepinfo: *mut windows::Win32::System::Com::EXCEPINFO,
+            _puargerr: *mut u32,
+        ) -> windows::core::Result<()> {
+            Err(windows::Win32::Foundation::E_NOTIMPL.into())
+        }
+    }
+
+    fn variant_with(vt: u16, value: windows::Win32::System::Variant::VARIANT_0_0_0) -> VARIANT {
+        use std::mem::ManuallyDrop;
+        use windows::Win32::System::Variant::{VARENUM, VARIANT_0, VARIANT_0_0};
+
+        VARIANT {
+            Anonymous: VARIANT_0 {
+                Anonymous: ManuallyDrop::new(VARIANT_0_0 {
+                    vt: VARENUM(vt),
+                    wReserved1: 0,
+                    wReserved2: 0,
+                    wReserved3: 0,
+                    Anonymous: value,
+                }),
+            },
+        }
+    }
+
+    #[test]
+    fn test_variant_to_string_interfaces() {
+        use std::mem::ManuallyDrop;
+        use windows::Win32::System::Variant::{VARIANT_0_0_0, VariantClear};
+        use windows::core::IUnknown;
+
+        let dispatch: IDispatch = OpaqueDispatch.into();
+        let mut disp_var = variant_with(
+            9,
+            VARIANT_0_0_0 {
+                pdispVal: ManuallyDrop::new(Some(dispatch.clone())),
+            },
+        );
+        let mut unk_var = variant_with(
+            13,
+            VARIANT_0_0_0 {
+                punkVal: ManuallyDrop::new(Some(dispatch.cast::<IUnknown>().unwrap())),
+            },
+        );
+        let null_var = variant_with(
+            9,
+            VARIANT_0_0_0 {
+                pdispVal: ManuallyDrop::new(None),
+            },
+        );
+
+        assert_eq!(variant_to_string(&disp_var), "[IDispatch]");
+        assert_eq!(variant_to_string(&unk_var), "[IUnknown]");
+        assert_eq!(variant_to_string(&null_var), "[IDispatch]");
+
+        // SAFETY: Both VARIANTs own one reference each, released exactly once here.
+        unsafe {
+            VariantClear(&raw mut disp_var).unwrap();
+            VariantClear(&raw mut unk_var).unwrap();
+        }
+    }
+
+    #[test]
+    fn test_variant_to_string_byref() {
+        use windows::Win32::System::Variant::VARIANT_0_0_0;
+
+        let mut value: i32 = 42;
+        let by_ref = variant_with(
+            0x4003, // VT_BYREF | VT_I4
+            VARIANT_0_0_0 {
+                byref: (&raw mut value).cast(),
+            },
+        );
+        assert_eq!(variant_to_string(&by_ref), "42");
+
+        let mut inner = variant_with(3, VARIANT_0_0_0 { lVal: 7 });
+        let to_variant = variant_with(
+            0x400C, // VT_BYREF | VT_VARIANT
+            VARIANT_0_0_0 {
+                byref: (&raw mut inner).cast(),
+            },
+        );
+        assert_eq!(variant_to_string(&to_variant), "7");
+
+        let null = variant_with(
+            0x4003,
+            VARIANT_0_0_0 {
+                byref: std::ptr::null_mut(),
+            },
+        );
+        assert_eq!(variant_to_string(&null), "(VT_BYREF 0x0003: null)");
+    }
+
     #[test]
     fn test_variant_to_string_vt_error_unknown() {
         use std::mem::ManuallyDrop;
@@ -762,4 +2190,78 @@ mod tests {
         let hr = windows::core::HRESULT(0x1234_5678_u32 as i32);
         assert_eq!(super::format_hresult(hr), "0x12345678");
     }
+
+    #[test]
+    fn test_item_def_builder_pointers_stay_valid() {
+        let tag_ids: Vec<String> = (0..100)
+            .map(|i| format!("Channel1.Device1.Tag{i}"))
+            .collect();
+        let mut builder = ItemDefBuilder::from_tag_ids(&tag_ids, true);
+        // Force further growth after the initial reservation.
+        builder.push("Extra.Tag", 100);
+
+        assert_eq!(builder.len(), 101);
+        for (idx, def) in builder.defs().iter().enumerate() {
+            // SAFETY: `szItemID` points into a null-terminated buffer owned by
+            // `builder`, which is alive for the duration of this borrow.
+            let id = unsafe { def.szItemID.to_string() }.unwrap();
+            let expected = tag_ids.get(idx).map_or("Extra.Tag", String::as_str);
+            assert_eq!(id, expected);
+            assert_eq!(def.hClient, u32::try_from(idx).unwrap());
+            assert!(def.bActive.as_bool());
+        }
+    }
+
+    #[test]
+    fn test_item_def_builder_inactive() {
+        let mut builder = ItemDefBuilder::new(false);
+        assert!(builder.is_empty());
+        builder.push("Tag1", 7);
+        assert_eq!(builder.defs()[0].hClient, 7);
+        assert!(!builder.defs()[0].bActive.as_bool());
+    }
+
+    #[test]
+    fn test_item_def_builder_requested_types() {
+        let tag_ids = vec!["Tag1".to_string(), "Tag2".to_string()];
+        let requested = BTreeMap::from([("Tag2".to_string(), 5)]);
+        let builder = ItemDefBuilder::from_tag_ids_requesting(&tag_ids, true, &requested);
+
+        let types: Vec<u16> = builder
+            .defs()
+            .iter()
+            .map(|d| d.vtRequestedDataType)
+            .collect();
+        assert_eq!(types, [0, 5]);
+    }
+
+    #[test]
+    fn test_vt_from_str() {
+        assert_eq!(vt_from_str("VT_R8"), Some(5));
+        assert_eq!(vt_from_str("vt_bool"), Some(11));
+        assert_eq!(vt_from_str(" I4 "), Some(3));
+        assert_eq!(vt_from_str("20"), Some(20));
+        assert_eq!(vt_from_str("15"), None);
+        assert_eq!(vt_from_str("VT_ARRAY|VT_R8"), None);
+        assert_eq!(vt_from_str("double"), None);
+        for vt in [2, 3, 4, 5, 8, 11, 16, 17, 18, 19, 20, 21] {
+            assert_eq!(vt_from_str(&vt_to_string(vt)), Some(vt));
+        }
+    }
+
+    #[test]
+    fn test_vt_to_string() {
+        assert_eq!(vt_to_string(5), "VT_R8");
+        assert_eq!(vt_to_string(8), "VT_BSTR");
+        assert_eq!(vt_to_string(0x2005), "VT_ARRAY|VT_R8");
+        assert_eq!(vt_to_string(0x4003), "VT_BYREF|VT_I4");
+        assert_eq!(vt_to_string(0x0048), "VT_0x0048");
+    }
+
+    #[test]
+    fn test_variant_vt() {
+        let variant = opc_value_to_variant(&OpcValue::Float(1.0));
+        assert_eq!(variant_vt(&variant), VT_R8.0);
+        assert_eq!(variant_vt(&VARIANT::default()), 0);
+    }
 }
@@ -24,7 +24,8 @@
 //! | Flag | Default | Effect |
 //! |------|---------|--------|
 //! | `opc-da-backend` | ✅ | Native OPC DA backend via `windows-rs` |
-//! | `test-support` | ❌ | Enables `MockOpcProvider` via `mockall` |
+//! | `test-support` | ❌ | Enables `MockOpcProvider` via `mockall` and `ComWorker::detached` |
+//! | `integration-tests` | ❌ | Runs `tests/integration.rs` against a live registered server |
 //!
 //! ## Platform
 //!
@@ -32,8 +33,10 @@
 
 mod com_guard;
 pub(crate) use com_guard::ComGuard;
+mod config;
 mod helpers;
 mod provider;
+mod simulated;
 
 #[cfg(feature = "opc-da-backend")]
 #[allow(warnings)]
@@ -48,12 +51,21 @@ mod opc_da;
 mod backend;
 
 // Stable public API
-pub use helpers::{format_hresult, friendly_com_hint};
-pub use provider::{OpcProvider, OpcValue, TagValue, WriteResult};
+pub use config::{ComApartment, OpcDaConfig};
+pub use helpers::{
+    OwnedVariant, coerce_to_vt, format_hresult, friendly_com_hint, is_ipv6_literal,
+    local_host_hint, normalize_host, normalize_tag_id, opc_value_vt, variant_to_string,
+    variant_to_string_full, vt_from_str, vt_to_string,
+};
+pub use provider::{
+    AccessRightsFilter, BrowseProgress, BrowsedTag, EngineeringUnits, OpcProvider, OpcValue,
+    ParseError, ReadStatus, ServerMetadata, ServerStatus, TagValue, WriteResult,
+};
+pub use simulated::{SIMULATED_SERVER, SIMULATED_TAG_COUNT, SimulatedOpcProvider};
 
 #[cfg(feature = "opc-da-backend")]
 pub use opc_da::{
-    errors::{OpcError, OpcResult},
+    errors::{ConnectionDiagnostics, OpcError, OpcResult},
     typedefs::{GroupHandle, ItemHandle},
 };
 
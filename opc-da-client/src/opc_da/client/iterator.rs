@@ -75,6 +75,9 @@ pub struct StringIterator {
     index: u32,
     count: u32,
     done: bool,
+    /// Set when `Next` returned `S_FALSE`: the cached batch is the last one,
+    /// and the enumerator is not asked again once it has been drained.
+    last_batch: bool,
 }
 
 impl StringIterator {
@@ -85,6 +88,7 @@ impl StringIterator {
             index: STRING_CACHE_SIZE as u32,
             count: 0,
             done: false,
+            last_batch: false,
         }
     }
 }
@@ -99,6 +103,11 @@ impl Iterator for StringIterator {
             }
 
             if self.index >= self.count {
+                if self.last_batch {
+                    self.done = true;
+                    return None;
+                }
+
                 // Zero the cache to prevent stale freed pointers (OPC-BUG-001)
                 self.cache.fill(windows::core::PWSTR::null());
 
@@ -115,6 +124,9 @@ impl Iterator for StringIterator {
                 );
 
                 if code.is_ok() {
+                    // S_FALSE means fewer than requested were left: take what
+                    // was fetched, then stop instead of calling Next again.
+                    self.last_batch = code == windows::Win32::Foundation::S_FALSE;
                     if self.count == 0 {
                         self.done = true;
                         return None;
@@ -158,7 +170,11 @@ impl Iterator for StringIterator {
             }
 
             let current = RemotePointer::from(pwstr);
-            return Some(current.try_into().map_err(OpcError::from));
+            let name: windows::core::Result<String> = current.try_into();
+            return Some(
+                name.map(|name| crate::helpers::normalize_tag_id(&name))
+                    .map_err(OpcError::from),
+            );
         }
     }
 }
@@ -488,6 +504,100 @@ mod tests {
         );
     }
 
+    /// Enumerator that fails any `Next` call made after it has returned
+    /// `S_FALSE`, as some servers do once exhausted.
+    #[allow(clippy::ref_as_ptr, clippy::inline_always)]
+    #[implement(IEnumString)]
+    struct MockEnumStringFailsAfterEnd {
+        items: Vec<String>,
+        index: std::sync::atomic::AtomicUsize,
+        ended: std::sync::atomic::AtomicBool,
+    }
+
+    impl IEnumString_Impl for MockEnumStringFailsAfterEnd_Impl {
+        fn Next(
+            &self,
+            celt: u32,
+            rgelt: *mut PWSTR,
+            pceltfetched: *mut u32,
+        ) -> windows::core::HRESULT {
+            if self.ended.load(std::sync::atomic::Ordering::Relaxed) {
+                return windows::Win32::Foundation::E_UNEXPECTED;
+            }
+            let index = self.index.load(std::sync::atomic::Ordering::Relaxed);
+            let batch = &self.items[index..(index + celt as usize).min(self.items.len())];
+            let rgelt = unsafe { std::slice::from_raw_parts_mut(rgelt, celt as usize) };
+            for (slot, s) in rgelt.iter_mut().zip(batch) {
+                let w: Vec<u16> = s.encode_utf16().chain(std::iter::once(0)).collect();
+                let ptr = unsafe { windows::Win32::System::Com::CoTaskMemAlloc(w.len() * 2) };
+                unsafe { std::ptr::copy_nonoverlapping(w.as_ptr(), ptr as *mut u16, w.len()) };
+                *slot = PWSTR(ptr as *mut u16);
+            }
+            self.index
+                .store(index + batch.len(), std::sync::atomic::Ordering::Relaxed);
+            if !pceltfetched.is_null() {
+                unsafe { *pceltfetched = batch.len() as u32 };
+            }
+
+            if batch.len() == celt as usize {
+                windows::Win32::Foundation::S_OK
+            } else {
+                self.ended.store(true, std::sync::atomic::Ordering::Relaxed);
+                windows::Win32::Foundation::S_FALSE
+            }
+        }
+        fn Skip(&self, _celt: u32) -> windows::core::HRESULT {
+            windows::Win32::Foundation::E_NOTIMPL
+        }
+        fn Reset(&self) -> windows::core::Result<()> {
+            self.index.store(0, std::sync::atomic::Ordering::Relaxed);
+            self.ended
+                .store(false, std::sync::atomic::Ordering::Relaxed);
+            Ok(())
+        }
+        fn Clone(&self) -> windows::core::Result<IEnumString> {
+            Err(windows::core::Error::from_hresult(
+                windows::Win32::Foundation::E_NOTIMPL,
+            ))
+        }
+    }
+
+    /// A last batch shorter than the cache comes back with `S_FALSE`; its
+    /// items must all be yielded and the enumerator not called again.
+    #[test]
+    fn test_string_iterator_short_last_batch_with_s_false() {
+        let items: Vec<String> = (0..STRING_CACHE_SIZE + 44)
+            .map(|i| format!("Tag{i}"))
+            .collect();
+
+        let mock_enum: IEnumString = MockEnumStringFailsAfterEnd {
+            items: items.clone(),
+            index: std::sync::atomic::AtomicUsize::new(0),
+            ended: std::sync::atomic::AtomicBool::new(false),
+        }
+        .into();
+
+        let results: Vec<_> = StringIterator::new(mock_enum)
+            .collect::<Result<Vec<_>, _>>()
+            .expect("S_FALSE batch should end the enumeration cleanly");
+        assert_eq!(results, items);
+    }
+
+    /// Names padded with whitespace by the server come back trimmed.
+    #[test]
+    fn test_string_iterator_normalizes_names() {
+        let mock_enum: IEnumString = MockEnumString {
+            items: vec![" Tag1 ".into(), "Tag2\t\r\n".into()],
+            index: std::sync::atomic::AtomicUsize::new(0),
+        }
+        .into();
+
+        let results: Vec<_> = StringIterator::new(mock_enum)
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        assert_eq!(results, ["Tag1", "Tag2"]);
+    }
+
     /// Verify iterator handles a fully empty enumeration (0 items, immediate S_FALSE).
     #[test]
     fn test_string_iterator_empty() {
@@ -38,9 +38,7 @@ pub trait ClientTrait<Server: TryFrom<windows::core::IUnknown, Error = windows::
         let iter = unsafe {
             servers
                 .EnumClassesOfCategories(&versions, &versions)
-                .map_err(|e| {
-                    windows::core::Error::new(e.code(), "Failed to enumerate server classes")
-                })?
+                .map_err(|e| OpcError::from_com_error(e, "Failed to enumerate server classes"))?
         };
 
         Ok(GuidIterator::new(iter))
@@ -101,6 +101,23 @@ pub trait ServerTrait<Group: TryFrom<windows::core::IUnknown, Error = windows::c
         Ok(())
     }
 
+    /// Gets a private group by name.
+    ///
+    /// # Arguments
+    /// * `name` - Name the group was added with
+    ///
+    /// # Returns
+    /// The group object
+    fn get_group_by_name(&self, name: &str) -> OpcResult<Group> {
+        let group_name = LocalPointer::from(name);
+        let group = unsafe {
+            self.interface()?
+                .GetGroupByName(group_name.as_pcwstr(), &windows::core::IUnknown::IID)?
+        };
+
+        group.try_into().map_err(|source| OpcError::Com { source })
+    }
+
     /// Creates an enumerator for groups.
     ///
     /// # Arguments
@@ -5,7 +5,12 @@ use windows::core::HRESULT;
 pub type OpcResult<T> = Result<T, OpcError>;
 
 /// Centralized error enum for the OPC DA client.
-#[derive(Debug, Error)]
+///
+/// Equality compares the variant and its payload. COM errors
+/// ([`OpcError::Com`], [`OpcError::ContextualCom`]) compare by HRESULT only,
+/// ignoring the attached error info; use
+/// [`OpcError::same_kind`] to compare variants alone.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
 #[non_exhaustive]
 pub enum OpcError {
     /// Standard Windows COM/DCOM error.
@@ -18,6 +23,17 @@ pub enum OpcError {
         source: windows::core::Error,
     },
 
+    /// COM/DCOM error annotated with the operation that failed.
+    ///
+    /// Built with [`OpcError::from_com_error`]; there is deliberately no
+    /// `From` conversion, since the context has to be supplied.
+    /// Displays as `{context}: {message} (0x…)`.
+    #[error("{context}: {source}")]
+    ContextualCom {
+        source: windows::core::Error,
+        context: String,
+    },
+
     /// Connection-related errors (e.g., host unreachable, resolution failure).
     #[error("Connection failed: {0}")]
     Connection(String),
@@ -38,9 +54,124 @@ pub enum OpcError {
     #[error("Not implemented: {0}")]
     NotImplemented(String),
 
+    /// An operation did not complete within its time limit.
+    #[error("Timed out: {0}")]
+    Timeout(String),
+
     /// Catch-all for unexpected internal failures.
     #[error("Internal error: {0}")]
     Internal(String),
+
+    /// A failed connection, annotated with what was found out about the
+    /// server's registration afterwards.
+    ///
+    /// Returned by
+    /// [`OpcProvider::connect_with_diagnostics`](crate::OpcProvider::connect_with_diagnostics).
+    #[error("{source} — {diagnostics}")]
+    Diagnosed {
+        source: Box<OpcError>,
+        diagnostics: ConnectionDiagnostics,
+    },
+}
+
+/// What the local registry says about an OPC DA server, gathered after a
+/// connection to it failed.
+///
+/// `None` means the lookup was not possible because an earlier step
+/// failed; for example there is no [`server_exe_exists`](Self::server_exe_exists)
+/// answer without a server entry.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ConnectionDiagnostics {
+    /// The ProgID that was connected to.
+    pub prog_id: String,
+    /// The CLSID the ProgID resolves to, in registry form (`{...}`).
+    pub clsid: Option<String>,
+    /// The `LocalServer32` command line registered for the CLSID, or the
+    /// `InprocServer32` path for in-process servers.
+    pub server_command: Option<String>,
+    /// Whether the executable named by `server_command` exists.
+    pub server_exe_exists: Option<bool>,
+    /// The DCOM AppID registered for the CLSID.
+    pub app_id: Option<String>,
+    /// Whether the AppID overrides the machine-wide launch permissions.
+    pub custom_launch_permission: Option<bool>,
+    /// Whether the AppID overrides the machine-wide access permissions.
+    pub custom_access_permission: Option<bool>,
+}
+
+impl ConnectionDiagnostics {
+    /// Actionable findings, most fundamental first.
+    pub fn checklist(&self) -> Vec<String> {
+        let Some(clsid) = &self.clsid else {
+            return vec![format!(
+                "ProgID '{}' is not registered on this machine — check the spelling or install the server",
+                self.prog_id
+            )];
+        };
+        let mut items = Vec::new();
+        match (&self.server_command, self.server_exe_exists) {
+            (None, _) => items.push(format!(
+                "CLSID {clsid} has no LocalServer32 or InprocServer32 entry — re-register the server (e.g. `<server>.exe /regserver`)"
+            )),
+            (Some(path), Some(false)) => items.push(format!(
+                "Server executable in `{path}` does not exist — reinstall or re-register the server"
+            )),
+            _ => {}
+        }
+        match &self.app_id {
+            None => items.push(format!(
+                "CLSID {clsid} has no AppID — DCOM applies the machine-wide default permissions"
+            )),
+            Some(app_id) => {
+                if self.custom_launch_permission == Some(false) {
+                    items.push(format!(
+                        "AppID {app_id} uses the default launch permissions — grant this user Launch and Activation in dcomcnfg if access is denied"
+                    ));
+                }
+                if self.custom_access_permission == Some(false) {
+                    items.push(format!(
+                        "AppID {app_id} uses the default access permissions — grant this user Access in dcomcnfg if callbacks fail"
+                    ));
+                }
+            }
+        }
+        if items.is_empty() {
+            items.push(format!(
+                "Registration of {clsid} looks complete — check that the server is running and healthy"
+            ));
+        }
+        items
+    }
+}
+
+impl std::fmt::Display for ConnectionDiagnostics {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.checklist().join("; "))
+    }
+}
+
+impl OpcError {
+    /// Wraps a COM error with a description of the operation that failed.
+    pub fn from_com_error(source: windows::core::Error, context: impl Into<String>) -> Self {
+        Self::ContextualCom {
+            source,
+            context: context.into(),
+        }
+    }
+
+    /// The HRESULT of a COM error, with or without context.
+    pub fn hresult(&self) -> Option<HRESULT> {
+        match self {
+            Self::Com { source } | Self::ContextualCom { source, .. } => Some(source.code()),
+            Self::Diagnosed { source, .. } => source.hresult(),
+            _ => None,
+        }
+    }
+
+    /// Returns `true` if both errors are the same variant, ignoring payloads.
+    pub fn same_kind(&self, other: &Self) -> bool {
+        std::mem::discriminant(self) == std::mem::discriminant(other)
+    }
 }
 
 impl From<anyhow::Error> for OpcError {
@@ -96,8 +227,190 @@ pub fn friendly_hresult_hint(hr: HRESULT) -> Option<&'static str> {
 
 /// Maps an [`OpcError`] to a friendly COM hint if it is a COM error.
 pub fn friendly_com_hint(error: &OpcError) -> Option<&'static str> {
-    match error {
-        OpcError::Com { source: e } => friendly_hresult_hint(e.code()),
-        _ => None,
+    error.hresult().and_then(friendly_hresult_hint)
+}
+
+/// Suggests switching between `localhost` and the machine's hostname when a
+/// local connection fails with access-denied or RPC-unavailable.
+///
+/// DCOM treats `localhost`, `.` and the machine's own hostname differently:
+/// the real hostname goes through the remote activation path (and its
+/// permissions), while `localhost` may resolve to a loopback address that
+/// remote-only DCOM configurations reject.
+pub fn local_host_hint(host: &str, error: &OpcError) -> Option<&'static str> {
+    let hr = error.hresult()?;
+    let computer_name = std::env::var("COMPUTERNAME").ok();
+    host_alias_hint(host, computer_name.as_deref(), hr)
+}
+
+fn host_alias_hint(host: &str, computer_name: Option<&str>, hr: HRESULT) -> Option<&'static str> {
+    const E_ACCESSDENIED: u32 = 0x80070005;
+    const RPC_S_SERVER_UNAVAILABLE: u32 = 0x800706BA;
+
+    if !matches!(hr.0 as u32, E_ACCESSDENIED | RPC_S_SERVER_UNAVAILABLE) {
+        return None;
+    }
+
+    let host = host.trim();
+    if host.eq_ignore_ascii_case("localhost") || host == "127.0.0.1" || host == "::1" {
+        Some("DCOM may treat 'localhost' differently — try the machine's hostname or '.'")
+    } else if computer_name.is_some_and(|name| host.eq_ignore_ascii_case(name)) {
+        Some("Using this machine's own hostname goes through remote DCOM — try 'localhost' or '.'")
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unreadable_literal)]
+    use super::*;
+
+    #[test]
+    fn test_host_alias_hint() {
+        let denied = HRESULT(0x80070005_u32 as i32);
+        let unavailable = HRESULT(0x800706BA_u32 as i32);
+        let not_registered = HRESULT(0x80040154_u32 as i32);
+
+        assert!(
+            host_alias_hint("localhost", None, denied)
+                .unwrap()
+                .contains("machine's hostname")
+        );
+        assert!(host_alias_hint("127.0.0.1", None, unavailable).is_some());
+        assert!(
+            host_alias_hint("PLANT-PC", Some("plant-pc"), denied)
+                .unwrap()
+                .contains("'localhost'")
+        );
+
+        // Genuinely remote hosts and unrelated errors get no hint
+        assert_eq!(host_alias_hint("other-pc", Some("plant-pc"), denied), None);
+        assert_eq!(host_alias_hint("localhost", None, not_registered), None);
+    }
+
+    #[test]
+    fn test_opc_error_equality() {
+        let com = |hr: u32, msg: &str| OpcError::Com {
+            source: windows::core::Error::new(HRESULT(hr as i32), msg),
+        };
+
+        // Com errors compare by HRESULT, not message
+        assert_eq!(com(0x80070005, "first"), com(0x80070005, "second"));
+        assert_ne!(com(0x80070005, "same"), com(0x800706BA, "same"));
+
+        assert_eq!(
+            OpcError::Connection("timeout".into()),
+            OpcError::Connection("timeout".into())
+        );
+        assert_ne!(
+            OpcError::Connection("timeout".into()),
+            OpcError::Internal("timeout".into())
+        );
+
+        let err = OpcError::Server("bad".into(), 0xC0040007);
+        assert_eq!(err.clone(), err);
+    }
+
+    #[test]
+    fn test_opc_error_same_kind() {
+        assert!(
+            OpcError::Connection("host a".into()).same_kind(&OpcError::Connection("host b".into()))
+        );
+        assert!(OpcError::Server("x".into(), 1).same_kind(&OpcError::Server("y".into(), 2)));
+        assert!(!OpcError::Connection("x".into()).same_kind(&OpcError::Internal("x".into())));
+    }
+
+    #[test]
+    fn test_connection_diagnostics_checklist() {
+        let unregistered = ConnectionDiagnostics {
+            prog_id: "Foo.Bar".into(),
+            ..Default::default()
+        };
+        let items = unregistered.checklist();
+        assert_eq!(items.len(), 1);
+        assert!(
+            items[0].contains("'Foo.Bar' is not registered"),
+            "{items:?}"
+        );
+
+        let missing_exe = ConnectionDiagnostics {
+            prog_id: "Foo.Bar".into(),
+            clsid: Some("{0000-FOO}".into()),
+            server_command: Some(r#""C:\Foo\foo.exe" -Embedding"#.into()),
+            server_exe_exists: Some(false),
+            app_id: Some("{0000-APP}".into()),
+            custom_launch_permission: Some(false),
+            custom_access_permission: Some(true),
+        };
+        let items = missing_exe.checklist();
+        assert_eq!(items.len(), 2, "{items:?}");
+        assert!(items[0].contains("does not exist"));
+        assert!(items[1].contains("Launch and Activation"));
+
+        let intact = ConnectionDiagnostics {
+            server_exe_exists: Some(true),
+            custom_launch_permission: Some(true),
+            ..missing_exe
+        };
+        assert_eq!(intact.checklist().len(), 1);
+        assert!(intact.to_string().contains("looks complete"));
+    }
+
+    #[test]
+    fn test_diagnosed_error_keeps_source_hresult() {
+        let source = OpcError::from_com_error(
+            windows::core::Error::from_hresult(HRESULT(0x80070005_u32 as i32)),
+            "Failed to create server 'Foo.Bar'",
+        );
+        let err = OpcError::Diagnosed {
+            source: Box::new(source.clone()),
+            diagnostics: ConnectionDiagnostics {
+                prog_id: "Foo.Bar".into(),
+                ..Default::default()
+            },
+        };
+
+        assert_eq!(err.hresult(), source.hresult());
+        assert!(friendly_com_hint(&err).unwrap().contains("Access denied"));
+        let msg = err.to_string();
+        assert!(
+            msg.starts_with("Failed to create server 'Foo.Bar'"),
+            "{msg}"
+        );
+        assert!(msg.contains("not registered"), "{msg}");
+    }
+
+    #[test]
+    fn test_local_host_hint_ignores_non_com_errors() {
+        let err = OpcError::Connection("timeout".into());
+        assert_eq!(local_host_hint("localhost", &err), None);
+    }
+
+    #[test]
+    fn test_contextual_com_error() {
+        let source =
+            windows::core::Error::new(HRESULT(0x80040154_u32 as i32), "Class not registered");
+        let err = OpcError::from_com_error(source, "Failed to create server 'Foo.Bar'");
+
+        // The HRESULT appears once, after the message
+        assert_eq!(
+            err.to_string(),
+            "Failed to create server 'Foo.Bar': Class not registered (0x80040154)"
+        );
+        assert_eq!(
+            friendly_com_hint(&err),
+            Some("Server is not registered on this machine")
+        );
+        assert_eq!(err.hresult(), Some(HRESULT(0x80040154_u32 as i32)));
+
+        // Without context the plain variant is still produced
+        let plain = OpcError::from(windows::core::Error::from_hresult(HRESULT(
+            0x80040154_u32 as i32,
+        )));
+        assert!(matches!(plain, OpcError::Com { .. }));
+        assert!(!plain.same_kind(&err));
+        assert_eq!(plain.hresult(), err.hresult());
+        assert_eq!(OpcError::Internal("x".into()).hresult(), None);
     }
 }
@@ -1,11 +1,30 @@
 use crate::opc_da::errors::OpcResult;
 use async_trait::async_trait;
-use std::sync::Arc;
+use std::fmt::Write as _;
 use std::sync::atomic::AtomicUsize;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime};
 
 #[cfg(feature = "test-support")]
 use mockall::automock;
 
+/// Whether a tag's value was read, and if not, where it failed.
+///
+/// Failed tags also carry `"Error"` as their [`TagValue::value`] and a
+/// `"Bad — …"` quality for display; match on this instead of those strings.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum ReadStatus {
+    /// The server returned a value. Its [`quality`](TagValue::quality) may
+    /// still be bad or uncertain.
+    #[default]
+    Ok,
+    /// The server rejected the item when it was added to the read group,
+    /// e.g. because the item ID is unknown.
+    NotAdded,
+    /// The item was added but reading it failed.
+    ReadError,
+}
+
 /// A single tag's read result.
 ///
 /// Returned by [`OpcProvider::read_tag_values`].
@@ -13,13 +32,18 @@ use mockall::automock;
 /// # Examples
 ///
 /// ```
-/// use opc_da_client::TagValue;
+/// use opc_da_client::{ReadStatus, TagValue};
 ///
 /// let tv = TagValue {
 ///     tag_id: "Simulation.Random.1".to_string(),
 ///     value: "42.5".to_string(),
 ///     quality: "Good".to_string(),
 ///     timestamp: "2026-01-01 00:00:00".to_string(),
+///     raw_timestamp: None,
+///     vt: Some(5), // VT_R8
+///     access_rights: 1, // OPC_READABLE
+///     canonical_data_type: 5,
+///     status: ReadStatus::Ok,
 /// };
 /// assert_eq!(tv.tag_id, "Simulation.Random.1");
 /// ```
@@ -33,8 +57,146 @@ pub struct TagValue {
     pub quality: String,
     /// Timestamp of the last value change, formatted as a local time string.
     pub timestamp: String,
+    /// The same timestamp as reported by the server, `None` if the read failed
+    /// or the server sent no timestamp. Use [`TagValue::age`] to measure freshness.
+    pub raw_timestamp: Option<SystemTime>,
+    /// `VARTYPE` of the value as returned by the server, `None` if the read failed.
+    ///
+    /// Use [`vt_to_string`](crate::vt_to_string) for a display name.
+    pub vt: Option<u16>,
+    /// `OPC_READABLE` / `OPC_WRITEABLE` bits of the item, `0` if the server
+    /// did not accept it.
+    pub access_rights: u32,
+    /// `VARTYPE` the server holds the item in, which may differ from [`vt`](Self::vt)
+    /// when the server converts on read. `0` (`VT_EMPTY`) if unknown.
+    pub canonical_data_type: u16,
+    /// Whether the value was read, or why not.
+    pub status: ReadStatus,
+}
+
+impl TagValue {
+    /// How long ago the server last updated this value, measured against the
+    /// local clock.
+    ///
+    /// Returns `None` without a [`raw_timestamp`](Self::raw_timestamp). A
+    /// timestamp ahead of the local clock counts as zero age.
+    pub fn age(&self) -> Option<Duration> {
+        self.raw_timestamp
+            .map(|t| SystemTime::now().duration_since(t).unwrap_or_default())
+    }
+}
+
+/// Characters with structural meaning in the [`TagValue`] text format.
+const TAG_VALUE_SPECIAL: [char; 5] = ['\\', '=', '(', ')', '@'];
+
+fn escape_field(field: &str, out: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    for c in field.chars() {
+        if TAG_VALUE_SPECIAL.contains(&c) {
+            write!(out, "\\")?;
+        }
+        write!(out, "{c}")?;
+    }
+    Ok(())
+}
+
+/// Split `input` at the first unescaped `delim`, unescaping the head.
+///
+/// Returns `(unescaped_head, rest_after_delim)`, or `None` if `delim` does not occur.
+fn split_unescaped(input: &str, delim: char) -> Option<(String, &str)> {
+    let mut head = String::new();
+    let mut chars = input.char_indices();
+    while let Some((idx, c)) = chars.next() {
+        if c == '\\' {
+            head.push(chars.next().map_or('\\', |(_, escaped)| escaped));
+        } else if c == delim {
+            return Some((head, &input[idx + c.len_utf8()..]));
+        } else {
+            head.push(c);
+        }
+    }
+    None
+}
+
+/// Formats as `tag_id=value (quality @ timestamp)`.
+///
+/// `\`, `=`, `(`, `)` and `@` inside fields are escaped with a backslash so
+/// the output parses back with [`str::parse`]. `raw_timestamp`, `vt` and
+/// `status` are not included; parsing sets `status` to
+/// [`ReadStatus::ReadError`] for an `Error` value and [`ReadStatus::Ok`]
+/// otherwise.
+impl std::fmt::Display for TagValue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        escape_field(&self.tag_id, f)?;
+        write!(f, "=")?;
+        escape_field(&self.value, f)?;
+        write!(f, " (")?;
+        escape_field(&self.quality, f)?;
+        write!(f, " @ ")?;
+        escape_field(&self.timestamp, f)?;
+        write!(f, ")")
+    }
+}
+
+/// Parses the format produced by [`TagValue`]'s `Display` impl.
+///
+/// # Examples
+///
+/// ```
+/// use opc_da_client::TagValue;
+///
+/// let tv: TagValue = r"Flow\=Rate=12.5 (Good @ 2026-01-01 00:00:00)".parse().unwrap();
+/// assert_eq!(tv.tag_id, "Flow=Rate");
+/// assert_eq!(tv.value, "12.5");
+/// assert_eq!(tv.to_string(), r"Flow\=Rate=12.5 (Good @ 2026-01-01 00:00:00)");
+/// ```
+impl std::str::FromStr for TagValue {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let missing = |what: &str| ParseError(format!("missing {what} in tag value '{s}'"));
+
+        let (tag_id, rest) = split_unescaped(s, '=').ok_or_else(|| missing("'='"))?;
+        let (value, rest) = split_unescaped(rest, '(').ok_or_else(|| missing("'('"))?;
+        let (quality, rest) = split_unescaped(rest, '@').ok_or_else(|| missing("'@'"))?;
+        let (timestamp, rest) = split_unescaped(rest, ')').ok_or_else(|| missing("')'"))?;
+        if !rest.is_empty() {
+            return Err(ParseError(format!("unexpected trailing text '{rest}'")));
+        }
+
+        let value = value
+            .strip_suffix(' ')
+            .ok_or_else(|| missing("space before '('"))?;
+        let quality = quality
+            .strip_suffix(' ')
+            .ok_or_else(|| missing("space before '@'"))?;
+        let timestamp = timestamp
+            .strip_prefix(' ')
+            .ok_or_else(|| missing("space after '@'"))?;
+
+        let status = if value == "Error" {
+            ReadStatus::ReadError
+        } else {
+            ReadStatus::Ok
+        };
+        Ok(Self {
+            tag_id,
+            value: value.to_string(),
+            quality: quality.to_string(),
+            timestamp: timestamp.to_string(),
+            raw_timestamp: None,
+            vt: None,
+            access_rights: 0,
+            canonical_data_type: 0,
+            status,
+        })
+    }
 }
 
+/// Error returned when text cannot be parsed into a [`TagValue`] or [`OpcValue`].
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("Parse error: {0}")]
+pub struct ParseError(pub String);
+
 /// Typed value to write to an OPC DA tag.
 ///
 /// # Examples
@@ -55,6 +217,69 @@ pub enum OpcValue {
     Float(f64),
     /// Boolean (`VT_BOOL`).
     Bool(bool),
+    /// One-dimensional array (`VT_ARRAY`). Uniform elements are written as
+    /// a typed array (integers mixed with floats as `VT_R8`), anything else
+    /// as an array of `VT_VARIANT`.
+    Array(Vec<OpcValue>),
+}
+
+impl OpcValue {
+    /// Parse `s` as the value type identified by the `VARTYPE` code `vt`.
+    ///
+    /// Integer types are range-checked against their width; `VT_BOOL`
+    /// accepts `true`/`false` and `1`/`0` (case-insensitive).
+    ///
+    /// # Errors
+    /// Returns [`ParseError`] if `s` is not valid for `vt`, is out of range,
+    /// or `vt` is not a writable scalar type.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use opc_da_client::OpcValue;
+    ///
+    /// assert_eq!(OpcValue::from_str_typed("1", 11).unwrap(), OpcValue::Bool(true));
+    /// assert_eq!(OpcValue::from_str_typed("1", 5).unwrap(), OpcValue::Float(1.0));
+    /// assert!(OpcValue::from_str_typed("300", 17).is_err()); // VT_UI1
+    /// ```
+    pub fn from_str_typed(s: &str, vt: u16) -> Result<Self, ParseError> {
+        let s = s.trim();
+        let invalid = |type_name: &str| ParseError(format!("'{s}' is not a valid {type_name}"));
+        let int_in = |type_name: &str, range: std::ops::RangeInclusive<i64>| {
+            s.parse::<i64>()
+                .ok()
+                .filter(|i| range.contains(i))
+                .and_then(|i| i32::try_from(i).ok())
+                .map(Self::Int)
+                .ok_or_else(|| invalid(type_name))
+        };
+
+        match vt {
+            // VT_BOOL
+            11 => match s.to_lowercase().as_str() {
+                "true" | "1" => Ok(Self::Bool(true)),
+                "false" | "0" => Ok(Self::Bool(false)),
+                _ => Err(invalid("VT_BOOL")),
+            },
+            // VT_I1, VT_I2, VT_I4 / VT_INT, VT_UI1, VT_UI2
+            16 => int_in("VT_I1", i64::from(i8::MIN)..=i64::from(i8::MAX)),
+            2 => int_in("VT_I2", i64::from(i16::MIN)..=i64::from(i16::MAX)),
+            3 | 22 => int_in("VT_I4", i64::from(i32::MIN)..=i64::from(i32::MAX)),
+            17 => int_in("VT_UI1", 0..=i64::from(u8::MAX)),
+            18 => int_in("VT_UI2", 0..=i64::from(u16::MAX)),
+            // VT_R4, VT_R8
+            4 | 5 => s
+                .parse::<f64>()
+                .map(Self::Float)
+                .map_err(|_| invalid("floating-point number")),
+            // VT_BSTR
+            8 => Ok(Self::String(s.to_string())),
+            other => Err(ParseError(format!(
+                "unsupported VARTYPE {} for typed parsing",
+                crate::vt_to_string(other)
+            ))),
+        }
+    }
 }
 
 /// Result of a single write operation.
@@ -68,6 +293,8 @@ pub enum OpcValue {
 ///     tag_id: "Tag1".to_string(),
 ///     success: true,
 ///     error: None,
+///     attempts: 1,
+///     sent_vt: Some(5), // VT_R8
 /// };
 /// assert!(wr.success);
 /// ```
@@ -79,6 +306,236 @@ pub struct WriteResult {
     pub success: bool,
     /// Error message if the write failed, `None` on success.
     pub error: Option<String>,
+    /// Number of attempts made, including retries after transient failures.
+    pub attempts: u32,
+    /// `VARTYPE` of the VARIANT sent to the server, after any type
+    /// coercion; `None` if no write was sent (e.g. the item was rejected).
+    pub sent_vt: Option<u16>,
+}
+
+/// Engineering-unit context of an item, read from its OPC item properties.
+///
+/// Each field is `None` when the server does not report that property.
+///
+/// # Examples
+///
+/// ```
+/// use opc_da_client::EngineeringUnits;
+///
+/// let eu = EngineeringUnits {
+///     units: Some("°C".to_string()),
+///     low: Some(0.0),
+///     high: Some(200.0),
+/// };
+/// assert_eq!(eu.annotate("73.2"), "73.2 °C (range 0–200)");
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct EngineeringUnits {
+    /// Unit label, e.g. `"°C"` (item property 100).
+    pub units: Option<String>,
+    /// Low end of the expected range (item property 103).
+    pub low: Option<f64>,
+    /// High end of the expected range (item property 102).
+    pub high: Option<f64>,
+}
+
+impl EngineeringUnits {
+    /// OPC DA item property holding the unit label.
+    pub const UNITS_PROPERTY: u32 = 100;
+    /// OPC DA item property holding the high end of the range.
+    pub const HIGH_PROPERTY: u32 = 102;
+    /// OPC DA item property holding the low end of the range.
+    pub const LOW_PROPERTY: u32 = 103;
+    /// OPC DA item property holding an analog item's range as a
+    /// `VT_ARRAY | VT_R8` of `[low, high]`, read when the high and low
+    /// properties are missing.
+    pub const EU_INFO_PROPERTY: u32 = 8;
+    /// The properties read for an item, in the order they are requested.
+    pub const PROPERTIES: [u32; 4] = [
+        Self::UNITS_PROPERTY,
+        Self::HIGH_PROPERTY,
+        Self::LOW_PROPERTY,
+        Self::EU_INFO_PROPERTY,
+    ];
+
+    /// Whether the server reported none of the properties.
+    pub const fn is_empty(&self) -> bool {
+        self.units.is_none() && self.low.is_none() && self.high.is_none()
+    }
+
+    /// `value` followed by the unit and, when both ends are known, the
+    /// range, e.g. `73.2 °C (range 0–200)`.
+    pub fn annotate(&self, value: &str) -> String {
+        let mut out = value.to_string();
+        if let Some(units) = &self.units {
+            out.push(' ');
+            out.push_str(units);
+        }
+        if let (Some(low), Some(high)) = (self.low, self.high) {
+            let _ = write!(out, " (range {low}–{high})");
+        }
+        out
+    }
+}
+
+/// Snapshot of an OPC DA server's `GetStatus` result.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ServerStatus {
+    /// Server state label (e.g., `"Running"`, `"Failed"`, `"Suspended"`).
+    pub state: String,
+    /// Free-form vendor description reported by the server.
+    pub vendor_info: String,
+    /// Server software version as `major.minor.build`.
+    pub version: String,
+    /// When the server process started, formatted as a local time string.
+    pub start_time: String,
+    /// Server clock at the time of the snapshot, formatted as a local time string.
+    pub current_time: String,
+    /// Number of groups currently defined on the server.
+    pub group_count: u32,
+}
+
+/// A tag discovered by [`OpcProvider::browse_tags`], with where it was found.
+///
+/// Tags from a flat enumeration have `depth` 0 and an empty `parent_branch`.
+/// In a hierarchical walk, leaves directly under the root have `depth` 1 and
+/// `parent_branch` is the `/`-separated path of browse names leading to the
+/// leaf (e.g. `"Channel1/Device1"`).
+///
+/// # Examples
+///
+/// ```
+/// use opc_da_client::BrowsedTag;
+///
+/// let tag = BrowsedTag::from("Random.Int4");
+/// assert_eq!(tag.depth, 0);
+/// assert!(tag.parent_branch.is_empty());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BrowsedTag {
+    /// The fully qualified tag identifier.
+    pub tag_id: String,
+    /// Level of the tag in the namespace: 1 for leaves under the root, 0 for
+    /// flat enumeration.
+    pub depth: usize,
+    /// Browse path of the branch containing the tag, `""` at the root.
+    pub parent_branch: String,
+}
+
+impl From<String> for BrowsedTag {
+    /// A tag with no known position in the namespace.
+    fn from(tag_id: String) -> Self {
+        Self {
+            tag_id,
+            depth: 0,
+            parent_branch: String::new(),
+        }
+    }
+}
+
+impl From<&str> for BrowsedTag {
+    fn from(tag_id: &str) -> Self {
+        Self::from(tag_id.to_string())
+    }
+}
+
+/// Counters and sink a running [`OpcProvider::browse_tags`] reports into.
+///
+/// Every field is shared: keep a clone to watch the browse from another task
+/// while it runs. The default sink collects tags; set it to `None` (e.g.
+/// after harvesting it on a timeout) and the browse stops writing to it.
+#[derive(Debug, Clone)]
+pub struct BrowseProgress {
+    /// Tags found so far.
+    pub tags_found: Arc<AtomicUsize>,
+    /// Number of branches below the root currently being explored.
+    pub depth: Arc<AtomicUsize>,
+    /// Set to the browse depth limit if the walk skipped branches below it,
+    /// so the caller can warn that tags were omitted; left alone otherwise.
+    pub depth_limit_hit: Arc<AtomicUsize>,
+    /// Receives each tag as it is found.
+    pub tags_sink: Arc<Mutex<Option<Vec<BrowsedTag>>>>,
+}
+
+impl Default for BrowseProgress {
+    fn default() -> Self {
+        Self {
+            tags_found: Arc::default(),
+            depth: Arc::default(),
+            depth_limit_hit: Arc::default(),
+            tags_sink: Arc::new(Mutex::new(Some(Vec::new()))),
+        }
+    }
+}
+
+/// Server-side access-rights filter for [`OpcProvider::browse_tags`].
+///
+/// Passed to `IOPCBrowseServerAddressSpace::BrowseOPCItemIDs`, so filtered-out
+/// items never cross the wire. Branches are not filtered.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum AccessRightsFilter {
+    /// Every item (no filter).
+    #[default]
+    Any,
+    /// Only items with `OPC_READABLE` access.
+    Readable,
+    /// Only items with `OPC_WRITEABLE` access.
+    Writable,
+}
+
+impl AccessRightsFilter {
+    /// The `dwAccessRightsFilter` mask sent to the server.
+    pub const fn mask(self) -> u32 {
+        match self {
+            Self::Any => 0,
+            Self::Readable => 0x1, // OPC_READABLE
+            Self::Writable => 0x2, // OPC_WRITEABLE
+        }
+    }
+}
+
+impl std::fmt::Display for AccessRightsFilter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::Any => "any",
+            Self::Readable => "readable",
+            Self::Writable => "writable",
+        })
+    }
+}
+
+impl std::str::FromStr for AccessRightsFilter {
+    type Err = ParseError;
+
+    /// Parses `any`, `readable` or `writable`, ignoring case.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "any" => Ok(Self::Any),
+            "readable" => Ok(Self::Readable),
+            "writable" | "writeable" => Ok(Self::Writable),
+            _ => Err(ParseError(format!(
+                "unknown access filter '{s}' (expected 'any', 'readable' or 'writable')"
+            ))),
+        }
+    }
+}
+
+/// Descriptive information about a server, captured when the connection is established.
+///
+/// Returned by [`OpcProvider::get_server_metadata`]. Fields are `None` when the
+/// server does not support the corresponding query.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ServerMetadata {
+    /// The server `ProgID`.
+    pub server: String,
+    /// Status snapshot taken at connect time.
+    pub status: Option<ServerStatus>,
+    /// Namespace organization: `"Flat"` or `"Hierarchical"`.
+    pub namespace_type: Option<String>,
+    /// Names of the optional COM interfaces the server exposes (e.g., `"IOPCBrowse"`).
+    pub interfaces: Vec<String>,
+    /// Highest OPC DA specification version implied by `interfaces` (`"2.0"`, `"3.0"`).
+    pub da_version: Option<String>,
 }
 
 /// Async trait for OPC DA operations.
@@ -95,7 +552,10 @@ pub trait OpcProvider: Send + Sync {
     /// cannot be enumerated.
     async fn list_servers(&self, host: &str) -> OpcResult<Vec<String>>;
 
-    /// Browse tags recursively, pushing discoveries to `tags_sink`.
+    /// Browse tags recursively, reporting into `progress` as it goes.
+    ///
+    /// `access` asks the server to return only items with the given access
+    /// rights; [`AccessRightsFilter::Any`] returns everything.
     ///
     /// # Errors
     /// Returns `Err` if the server connection fails, the `ProgID` cannot be
@@ -104,17 +564,26 @@ pub trait OpcProvider: Send + Sync {
         &self,
         server: &str,
         max_tags: usize,
-        progress: Arc<AtomicUsize>,
-        tags_sink: Arc<std::sync::Mutex<Vec<String>>>,
-    ) -> OpcResult<Vec<String>>;
+        access: AccessRightsFilter,
+        progress: BrowseProgress,
+    ) -> OpcResult<Vec<BrowsedTag>>;
 
     /// Read current values for the given tag IDs.
     ///
+    /// `progress` is advanced by the number of tags read as each chunk
+    /// completes, so callers can show how far a large read has got. It is
+    /// never reset, letting a caller that reads in several calls share one
+    /// counter across them.
+    ///
     /// # Errors
     /// Returns `Err` if the server connection fails, no items can be added
     /// to the OPC group, or the synchronous read operation fails.
-    async fn read_tag_values(&self, server: &str, tag_ids: Vec<String>)
-    -> OpcResult<Vec<TagValue>>;
+    async fn read_tag_values(
+        &self,
+        server: &str,
+        tag_ids: Vec<String>,
+        progress: Arc<AtomicUsize>,
+    ) -> OpcResult<Vec<TagValue>>;
 
     /// Write a value to a single OPC DA tag.
     ///
@@ -127,4 +596,311 @@ pub trait OpcProvider: Send + Sync {
         tag_id: &str,
         value: OpcValue,
     ) -> OpcResult<WriteResult>;
+
+    /// Write a value to each of several tags in one batch.
+    ///
+    /// All tags are added to a single OPC group and written with one
+    /// `IOPCSyncIO::Write` call. The result holds one [`WriteResult`] per
+    /// entry of `writes`, in the same order.
+    ///
+    /// # Errors
+    /// Returns `Err` if the server connection fails, the group cannot be
+    /// created, or the synchronous write call itself fails. Tags the server
+    /// rejects are reported inside their `WriteResult`.
+    async fn write_tag_values(
+        &self,
+        server: &str,
+        writes: Vec<(String, OpcValue)>,
+    ) -> OpcResult<Vec<WriteResult>>;
+
+    /// Look up the canonical data type (`VARTYPE`) of each tag without reading it.
+    ///
+    /// The items are added to a temporary OPC group and the
+    /// `vtCanonicalDataType` reported by the server is returned. The result
+    /// preserves the order of `tag_ids`; tags the server rejects map to `None`.
+    ///
+    /// # Errors
+    /// Returns `Err` if the server connection fails or the group cannot be
+    /// created.
+    async fn get_item_data_types(
+        &self,
+        server: &str,
+        tag_ids: Vec<String>,
+    ) -> OpcResult<Vec<Option<u16>>>;
+
+    /// Read the engineering-unit item properties (unit label and range) of
+    /// each tag.
+    ///
+    /// The result preserves the order of `tag_ids`; tags without any of the
+    /// properties, or whose properties cannot be read, map to `None`.
+    ///
+    /// # Errors
+    /// Returns [`OpcError::NotImplemented`](crate::OpcError::NotImplemented)
+    /// if the server does not support item properties, or `Err` if the
+    /// server connection fails.
+    async fn get_engineering_units(
+        &self,
+        server: &str,
+        tag_ids: Vec<String>,
+    ) -> OpcResult<Vec<Option<EngineeringUnits>>>;
+
+    /// Describe the server: status, namespace type, and supported interfaces.
+    ///
+    /// Metadata is captured once per connection and served from cache; it is
+    /// refreshed whenever the connection is re-established.
+    ///
+    /// # Errors
+    /// Returns `Err` if the server connection fails.
+    async fn get_server_metadata(&self, server: &str) -> OpcResult<ServerMetadata>;
+
+    /// Connect to `server` (or reuse the pooled connection) and return its
+    /// metadata, explaining a failure in more detail than the other methods.
+    ///
+    /// When the connection fails with a COM error, the backend inspects the
+    /// server's registration — whether the ProgID and CLSID are registered,
+    /// whether the server executable exists, and whether its DCOM AppID
+    /// overrides the default launch and access permissions — and returns
+    /// the findings alongside the original error. The default implementation
+    /// is [`get_server_metadata`](Self::get_server_metadata) without any
+    /// diagnostics.
+    ///
+    /// # Errors
+    /// Returns [`OpcError::Diagnosed`](crate::OpcError::Diagnosed) if the
+    /// connection fails and diagnostics could be gathered, otherwise the
+    /// connection error itself.
+    async fn connect_with_diagnostics(&self, server: &str) -> OpcResult<ServerMetadata> {
+        self.get_server_metadata(server).await
+    }
+
+    /// Reads tag values from a persistent, active OPC group ("live" mode).
+    ///
+    /// The first call for a server — or a call with a different tag set —
+    /// creates an active group for `tag_ids` that stays registered on the
+    /// server. Later calls read that group's cache (`OPC_DS_CACHE`) instead of
+    /// re-adding items, which is far cheaper for repeated polling. Results
+    /// have the same shape as [`read_tag_values`](Self::read_tag_values).
+    ///
+    /// # Errors
+    /// Returns `Err` if the server connection fails, the group cannot be
+    /// created, or the cache read fails.
+    async fn read_tag_values_live(
+        &self,
+        server: &str,
+        tag_ids: Vec<String>,
+    ) -> OpcResult<Vec<TagValue>>;
+
+    /// Removes the persistent group created by
+    /// [`read_tag_values_live`](Self::read_tag_values_live), if any.
+    ///
+    /// # Errors
+    /// Returns `Err` if the server rejects the group removal.
+    async fn stop_live_reads(&self, server: &str) -> OpcResult<()>;
+
+    /// Activates or deactivates items of the live-mode group created by
+    /// [`read_tag_values_live`](Self::read_tag_values_live), returning how
+    /// many the server changed.
+    ///
+    /// Inactive items stop receiving cache updates, so their live reads
+    /// report the last value with bad quality until they are reactivated.
+    /// Tags not in the live group are skipped. The state is lost when the
+    /// group is rebuilt for a different tag set.
+    ///
+    /// # Errors
+    /// Returns [`OpcError::NotImplemented`](crate::OpcError::NotImplemented)
+    /// if the server does not support item active state, or `Err` if no live
+    /// reads are running on `server` or the server rejects the call.
+    async fn set_items_active(
+        &self,
+        server: &str,
+        tag_ids: Vec<String>,
+        active: bool,
+    ) -> OpcResult<usize>;
+
+    /// Removes groups this client added to `server` and never removed, for
+    /// example after a crash, returning how many were removed.
+    ///
+    /// Every group visible to the connection whose name starts with the
+    /// client's `opc-da-client-` prefix is removed, including the live-mode
+    /// group. Private groups of a connection that has already gone away are
+    /// only visible if the server hands them to the new connection; the
+    /// server drops them itself once DCOM notices the old client is gone.
+    ///
+    /// # Errors
+    /// Returns `Err` if the server connection fails or the server cannot
+    /// enumerate its groups.
+    async fn cleanup_orphaned_groups(&self, server: &str) -> OpcResult<usize>;
+
+    /// Names of the public groups configured on `server`.
+    ///
+    /// Public groups are shared, server-defined groups (OPC DA 1.0/2.0
+    /// `IOPCServerPublicGroups`); some plants use them as the canonical tag
+    /// set.
+    ///
+    /// # Errors
+    /// Returns [`OpcError::NotImplemented`](crate::OpcError::NotImplemented)
+    /// if the server does not support public groups, or `Err` if the server
+    /// connection fails.
+    async fn list_public_groups(&self, server: &str) -> OpcResult<Vec<String>>;
+
+    /// Attach to the public group `group` and read every item it defines.
+    ///
+    /// The result has one entry per item, in the group's order, shaped like
+    /// [`read_tag_values`](Self::read_tag_values). The group itself is left
+    /// on the server.
+    ///
+    /// # Errors
+    /// Returns [`OpcError::NotImplemented`](crate::OpcError::NotImplemented)
+    /// if the server does not support public groups, or `Err` if the group
+    /// does not exist or the read fails.
+    async fn read_public_group(&self, server: &str, group: &str) -> OpcResult<Vec<TagValue>>;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tv(tag_id: &str, value: &str, quality: &str, timestamp: &str) -> TagValue {
+        TagValue {
+            tag_id: tag_id.into(),
+            value: value.into(),
+            quality: quality.into(),
+            timestamp: timestamp.into(),
+            raw_timestamp: None,
+            vt: None,
+            access_rights: 0,
+            canonical_data_type: 0,
+            status: if value == "Error" {
+                ReadStatus::ReadError
+            } else {
+                ReadStatus::Ok
+            },
+        }
+    }
+
+    #[test]
+    fn test_engineering_units_annotate_partial_properties() {
+        let units_only = EngineeringUnits {
+            units: Some("bar".into()),
+            ..EngineeringUnits::default()
+        };
+        assert_eq!(units_only.annotate("1.5"), "1.5 bar");
+
+        let range_only = EngineeringUnits {
+            low: Some(-100.0),
+            high: Some(100.0),
+            ..EngineeringUnits::default()
+        };
+        assert_eq!(range_only.annotate("42"), "42 (range -100–100)");
+
+        let low_only = EngineeringUnits {
+            low: Some(0.0),
+            ..EngineeringUnits::default()
+        };
+        assert_eq!(low_only.annotate("42"), "42");
+        assert!(!low_only.is_empty());
+        assert!(EngineeringUnits::default().is_empty());
+    }
+
+    #[test]
+    fn test_access_rights_filter() {
+        for filter in [
+            AccessRightsFilter::Any,
+            AccessRightsFilter::Readable,
+            AccessRightsFilter::Writable,
+        ] {
+            assert_eq!(filter.to_string().parse(), Ok(filter));
+        }
+        assert_eq!(AccessRightsFilter::default().mask(), 0);
+        assert_eq!(AccessRightsFilter::Readable.mask(), 1);
+        assert_eq!(AccessRightsFilter::Writable.mask(), 2);
+        assert_eq!("Writeable".parse(), Ok(AccessRightsFilter::Writable));
+        assert!("all".parse::<AccessRightsFilter>().is_err());
+    }
+
+    #[test]
+    fn test_tag_value_display() {
+        let value = tv("Channel1.Tag1", "42", "Good", "2026-01-01 00:00:00");
+        assert_eq!(
+            value.to_string(),
+            "Channel1.Tag1=42 (Good @ 2026-01-01 00:00:00)"
+        );
+    }
+
+    #[test]
+    fn test_tag_value_round_trip() {
+        let cases = [
+            tv("Channel1.Tag1", "42", "Good", "2026-01-01 00:00:00"),
+            tv("A=B", "x=y", "Uncertain", "N/A"),
+            tv(
+                "T",
+                "Error",
+                "Bad — 0xC0040007: Item ID not found (OPC_E_UNKNOWNITEMID)",
+                "",
+            ),
+            tv("T", "", "Unknown(0x0010)", "Invalid"),
+            tv(r"Path\To@Tag", " padded ", "Bad", "2026-12-31 23:59:59"),
+        ];
+        for original in cases {
+            let text = original.to_string();
+            let parsed: TagValue = text.parse().unwrap_or_else(|e| panic!("{text}: {e}"));
+            assert_eq!(parsed, original, "round trip of {text}");
+        }
+    }
+
+    #[test]
+    fn test_tag_value_from_str_errors() {
+        assert!("no-separator".parse::<TagValue>().is_err());
+        assert!("T=1 Good @ now".parse::<TagValue>().is_err());
+        assert!("T=1 (Good @ now) extra".parse::<TagValue>().is_err());
+    }
+
+    #[test]
+    fn test_from_str_typed() {
+        assert_eq!(OpcValue::from_str_typed("0", 11), Ok(OpcValue::Bool(false)));
+        assert_eq!(OpcValue::from_str_typed("1", 11), Ok(OpcValue::Bool(true)));
+        assert_eq!(
+            OpcValue::from_str_typed("TRUE", 11),
+            Ok(OpcValue::Bool(true))
+        );
+        assert!(OpcValue::from_str_typed("2", 11).is_err());
+
+        assert_eq!(OpcValue::from_str_typed("-5", 3), Ok(OpcValue::Int(-5)));
+        assert!(OpcValue::from_str_typed("40000", 2).is_err());
+        assert!(OpcValue::from_str_typed("-1", 18).is_err());
+        assert_eq!(OpcValue::from_str_typed("7", 5), Ok(OpcValue::Float(7.0)));
+        assert_eq!(
+            OpcValue::from_str_typed("42", 8),
+            Ok(OpcValue::String("42".into()))
+        );
+        assert!(OpcValue::from_str_typed("1", 9).is_err()); // VT_DISPATCH
+    }
+
+    /// Compiles only while `OpcProvider` stays object-safe.
+    fn takes_dyn(_: &dyn OpcProvider) {}
+
+    const fn assert_send_sync<T: Send + Sync + ?Sized>() {}
+
+    // `App` and the CLI hold the provider as `Arc<dyn OpcProvider>` and move
+    // clones into spawned tasks, which needs `Send + Sync` on every provider.
+    const _: () = {
+        assert_send_sync::<dyn OpcProvider>();
+        assert_send_sync::<crate::SimulatedOpcProvider>();
+        #[cfg(feature = "test-support")]
+        assert_send_sync::<MockOpcProvider>();
+    };
+
+    #[tokio::test]
+    async fn test_dyn_provider_crosses_task_boundary() {
+        let provider: std::sync::Arc<dyn OpcProvider> =
+            std::sync::Arc::new(crate::SimulatedOpcProvider::new());
+        takes_dyn(provider.as_ref());
+
+        let task_provider = std::sync::Arc::clone(&provider);
+        let servers =
+            tokio::task::spawn(async move { task_provider.list_servers("localhost").await })
+                .await
+                .unwrap()
+                .unwrap();
+        assert!(!servers.is_empty());
+    }
 }
@@ -0,0 +1,490 @@
+//! Offline [`OpcProvider`] generating synthetic servers, tags and values.
+//!
+//! [`SimulatedOpcProvider`] never touches COM, so it works on any platform
+//! and needs no OPC server. It is meant for UI development and tests that
+//! want realistic, changing data without a live connection.
+
+use crate::helpers::opc_value_vt;
+use crate::opc_da::errors::{OpcError, OpcResult};
+use crate::provider::{
+    AccessRightsFilter, BrowseProgress, BrowsedTag, EngineeringUnits, OpcProvider, OpcValue,
+    ReadStatus, ServerMetadata, ServerStatus, TagValue, WriteResult,
+};
+use async_trait::async_trait;
+use std::f64::consts::TAU;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, PoisonError};
+use std::time::{Duration, SystemTime};
+use tokio::time::Instant;
+
+/// The only server [`SimulatedOpcProvider`] reports.
+pub const SIMULATED_SERVER: &str = "Simulation.Server.1";
+
+/// Number of tags the simulated server exposes.
+pub const SIMULATED_TAG_COUNT: usize = 200;
+
+const TAG_PREFIX: &str = "Sim.Tag.";
+const VT_R8: u16 = 5;
+const VT_BOOL: u16 = 11;
+/// `OPC_READABLE | OPC_WRITEABLE`.
+const READ_WRITE: u32 = 3;
+
+/// Deterministic stand-in for a real OPC DA server.
+///
+/// [`list_servers`](OpcProvider::list_servers) returns [`SIMULATED_SERVER`]
+/// on every host, which holds [`SIMULATED_TAG_COUNT`] tags named
+/// `Sim.Tag.0` … `Sim.Tag.199`. Values depend only on the time since the
+/// provider was created:
+///
+/// - even-numbered tags are `VT_R8` sine waves of amplitude 100 whose period
+///   grows with the tag number, from 10 s to 19 s;
+/// - odd-numbered tags are `VT_BOOL` square waves that flip every second,
+///   with neighbouring tags out of phase.
+///
+/// Writes are accepted and discarded, since every value is generated. Live
+/// reads remember their tags until [`stop_live_reads`](OpcProvider::stop_live_reads),
+/// so [`set_items_active`](OpcProvider::set_items_active) behaves like it
+/// does on a real server; clones share that state. All operations complete
+/// on the calling task; nothing blocks a runtime thread.
+///
+/// # Examples
+///
+/// ```
+/// use opc_da_client::{OpcProvider, SimulatedOpcProvider};
+///
+/// # #[tokio::main(flavor = "current_thread")]
+/// # async fn main() -> opc_da_client::OpcResult<()> {
+/// let sim = SimulatedOpcProvider::new();
+/// let servers = sim.list_servers("localhost").await?;
+/// let values = sim
+///     .read_tag_values(&servers[0], vec!["Sim.Tag.0".into()], Default::default())
+///     .await?;
+/// assert_eq!(values[0].quality, "Good");
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct SimulatedOpcProvider {
+    started: Instant,
+    start_time: SystemTime,
+    /// Known tags of the running live reads; `None` when none are running.
+    live_tags: Arc<Mutex<Option<Vec<String>>>>,
+}
+
+impl Default for SimulatedOpcProvider {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SimulatedOpcProvider {
+    /// Creates a provider whose waveforms start at the current instant.
+    pub fn new() -> Self {
+        Self {
+            started: Instant::now(),
+            start_time: SystemTime::now(),
+            live_tags: Arc::default(),
+        }
+    }
+
+    fn check_server(server: &str) -> OpcResult<()> {
+        if server == SIMULATED_SERVER {
+            Ok(())
+        } else {
+            Err(OpcError::Connection(format!(
+                "unknown simulated server '{server}' (expected '{SIMULATED_SERVER}')"
+            )))
+        }
+    }
+
+    /// Tag number of a simulated tag ID, `None` for anything else.
+    fn tag_number(tag_id: &str) -> Option<usize> {
+        tag_id
+            .strip_prefix(TAG_PREFIX)?
+            .parse()
+            .ok()
+            .filter(|&n| n < SIMULATED_TAG_COUNT)
+    }
+
+    const fn tag_vt(n: usize) -> u16 {
+        if n % 2 == 0 { VT_R8 } else { VT_BOOL }
+    }
+
+    /// Value of tag `n` after `elapsed` time.
+    #[allow(clippy::cast_precision_loss)]
+    fn value_at(n: usize, elapsed: Duration) -> String {
+        if n % 2 == 0 {
+            let period = 10.0 + (n / 2 % 10) as f64;
+            let phase = elapsed.as_secs_f64() / period;
+            format!("{:.3}", 100.0 * (TAU * phase).sin())
+        } else {
+            let half_periods = elapsed.as_secs() + (n / 2) as u64;
+            (half_periods % 2 == 0).to_string()
+        }
+    }
+
+    fn read(&self, server: &str, tag_ids: Vec<String>) -> OpcResult<Vec<TagValue>> {
+        Self::check_server(server)?;
+        let elapsed = self.started.elapsed();
+        let now = self.start_time + elapsed;
+        let timestamp = chrono::DateTime::<chrono::Local>::from(now)
+            .format("%Y-%m-%d %H:%M:%S")
+            .to_string();
+
+        Ok(tag_ids
+            .into_iter()
+            .map(|tag_id| match Self::tag_number(&tag_id) {
+                Some(n) => TagValue {
+                    value: Self::value_at(n, elapsed),
+                    quality: "Good".to_string(),
+                    timestamp: timestamp.clone(),
+                    raw_timestamp: Some(now),
+                    vt: Some(Self::tag_vt(n)),
+                    access_rights: READ_WRITE,
+                    canonical_data_type: Self::tag_vt(n),
+                    status: ReadStatus::Ok,
+                    tag_id,
+                },
+                None => TagValue {
+                    tag_id,
+                    value: "Error".to_string(),
+                    quality: "Bad — not added to group".to_string(),
+                    timestamp: String::new(),
+                    raw_timestamp: None,
+                    vt: None,
+                    access_rights: 0,
+                    canonical_data_type: 0,
+                    status: ReadStatus::NotAdded,
+                },
+            })
+            .collect())
+    }
+}
+
+#[async_trait]
+impl OpcProvider for SimulatedOpcProvider {
+    async fn list_servers(&self, _host: &str) -> OpcResult<Vec<String>> {
+        Ok(vec![SIMULATED_SERVER.to_string()])
+    }
+
+    async fn browse_tags(
+        &self,
+        server: &str,
+        max_tags: usize,
+        _access: AccessRightsFilter,
+        progress: BrowseProgress,
+    ) -> OpcResult<Vec<BrowsedTag>> {
+        Self::check_server(server)?;
+        progress.depth.store(0, Ordering::Relaxed);
+        let tags: Vec<BrowsedTag> = (0..SIMULATED_TAG_COUNT.min(max_tags))
+            .map(|n| BrowsedTag::from(format!("{TAG_PREFIX}{n}")))
+            .collect();
+        if let Some(sink) = progress
+            .tags_sink
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .as_mut()
+        {
+            sink.extend(tags.iter().cloned());
+        }
+        progress.tags_found.store(tags.len(), Ordering::Relaxed);
+        Ok(tags)
+    }
+
+    async fn read_tag_values(
+        &self,
+        server: &str,
+        tag_ids: Vec<String>,
+        progress: Arc<AtomicUsize>,
+    ) -> OpcResult<Vec<TagValue>> {
+        let values = self.read(server, tag_ids)?;
+        progress.fetch_add(values.len(), Ordering::Relaxed);
+        Ok(values)
+    }
+
+    async fn write_tag_value(
+        &self,
+        server: &str,
+        tag_id: &str,
+        value: OpcValue,
+    ) -> OpcResult<WriteResult> {
+        Self::check_server(server)?;
+        let known = Self::tag_number(tag_id).is_some();
+        Ok(WriteResult {
+            tag_id: tag_id.to_string(),
+            success: known,
+            error: (!known).then(|| format!("Unknown simulated tag '{tag_id}'")),
+            attempts: 1,
+            sent_vt: known.then(|| opc_value_vt(&value)),
+        })
+    }
+
+    async fn write_tag_values(
+        &self,
+        server: &str,
+        writes: Vec<(String, OpcValue)>,
+    ) -> OpcResult<Vec<WriteResult>> {
+        let mut results = Vec::with_capacity(writes.len());
+        for (tag_id, value) in writes {
+            results.push(self.write_tag_value(server, &tag_id, value).await?);
+        }
+        Ok(results)
+    }
+
+    async fn get_item_data_types(
+        &self,
+        server: &str,
+        tag_ids: Vec<String>,
+    ) -> OpcResult<Vec<Option<u16>>> {
+        Self::check_server(server)?;
+        Ok(tag_ids
+            .iter()
+            .map(|tag_id| Self::tag_number(tag_id).map(Self::tag_vt))
+            .collect())
+    }
+
+    /// Sine-wave tags report their ±100 range; boolean tags have none.
+    async fn get_engineering_units(
+        &self,
+        server: &str,
+        tag_ids: Vec<String>,
+    ) -> OpcResult<Vec<Option<EngineeringUnits>>> {
+        Self::check_server(server)?;
+        Ok(tag_ids
+            .iter()
+            .map(|tag_id| {
+                Self::tag_number(tag_id)
+                    .filter(|&n| Self::tag_vt(n) == VT_R8)
+                    .map(|_| EngineeringUnits {
+                        units: None,
+                        low: Some(-100.0),
+                        high: Some(100.0),
+                    })
+            })
+            .collect())
+    }
+
+    async fn get_server_metadata(&self, server: &str) -> OpcResult<ServerMetadata> {
+        Self::check_server(server)?;
+        let format = |t: SystemTime| {
+            chrono::DateTime::<chrono::Local>::from(t)
+                .format("%Y-%m-%d %H:%M:%S")
+                .to_string()
+        };
+        Ok(ServerMetadata {
+            server: server.to_string(),
+            status: Some(ServerStatus {
+                state: "Running".to_string(),
+                vendor_info: "opc-da-client simulation".to_string(),
+                version: env!("CARGO_PKG_VERSION").to_string(),
+                start_time: format(self.start_time),
+                current_time: format(self.start_time + self.started.elapsed()),
+                group_count: 0,
+            }),
+            namespace_type: Some("Flat".to_string()),
+            interfaces: Vec::new(),
+            da_version: Some("2.0".to_string()),
+        })
+    }
+
+    async fn read_tag_values_live(
+        &self,
+        server: &str,
+        tag_ids: Vec<String>,
+    ) -> OpcResult<Vec<TagValue>> {
+        let values = self.read(server, tag_ids)?;
+        let known = values
+            .iter()
+            .filter(|v| v.status == ReadStatus::Ok)
+            .map(|v| v.tag_id.clone())
+            .collect();
+        *self
+            .live_tags
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner) = Some(known);
+        Ok(values)
+    }
+
+    async fn stop_live_reads(&self, server: &str) -> OpcResult<()> {
+        Self::check_server(server)?;
+        *self
+            .live_tags
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner) = None;
+        Ok(())
+    }
+
+    async fn set_items_active(
+        &self,
+        server: &str,
+        tag_ids: Vec<String>,
+        _active: bool,
+    ) -> OpcResult<usize> {
+        Self::check_server(server)?;
+        let live_tags = self
+            .live_tags
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        let Some(live_tags) = live_tags.as_ref() else {
+            return Err(OpcError::InvalidState(format!(
+                "no live reads running on '{server}'"
+            )));
+        };
+        Ok(tag_ids
+            .iter()
+            .filter(|tag_id| live_tags.contains(tag_id))
+            .count())
+    }
+
+    async fn cleanup_orphaned_groups(&self, server: &str) -> OpcResult<usize> {
+        Self::check_server(server)?;
+        Ok(0)
+    }
+
+    async fn list_public_groups(&self, server: &str) -> OpcResult<Vec<String>> {
+        Self::check_server(server)?;
+        Ok(Vec::new())
+    }
+
+    async fn read_public_group(&self, server: &str, group: &str) -> OpcResult<Vec<TagValue>> {
+        Self::check_server(server)?;
+        Err(OpcError::InvalidState(format!(
+            "simulated server has no public group '{group}'"
+        )))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_simulated_browse_returns_all_tags() {
+        let sim = SimulatedOpcProvider::new();
+        assert_eq!(
+            sim.list_servers("anywhere").await.unwrap(),
+            vec![SIMULATED_SERVER]
+        );
+
+        let progress = BrowseProgress::default();
+        let tags = sim
+            .browse_tags(
+                SIMULATED_SERVER,
+                usize::MAX,
+                AccessRightsFilter::Any,
+                progress.clone(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(tags.len(), SIMULATED_TAG_COUNT);
+        assert_eq!(tags[0].tag_id, "Sim.Tag.0");
+        assert_eq!(tags[199].tag_id, "Sim.Tag.199");
+        assert_eq!(
+            progress.tags_found.load(Ordering::Relaxed),
+            SIMULATED_TAG_COUNT
+        );
+        assert_eq!(
+            progress.tags_sink.lock().unwrap().as_ref().map(Vec::len),
+            Some(SIMULATED_TAG_COUNT)
+        );
+
+        assert!(
+            sim.read_tag_values("Other.Server", vec![], Arc::default())
+                .await
+                .is_err()
+        );
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_simulated_values_change_over_time() {
+        let sim = SimulatedOpcProvider::new();
+        let ids = vec![
+            "Sim.Tag.0".to_string(),
+            "Sim.Tag.1".to_string(),
+            "Sim.Tag.2".to_string(),
+            "Sim.Tag.3".to_string(),
+            "Sim.Tag.200".to_string(),
+        ];
+
+        let progress = Arc::new(AtomicUsize::new(0));
+        let first = sim
+            .read_tag_values(SIMULATED_SERVER, ids.clone(), Arc::clone(&progress))
+            .await
+            .unwrap();
+        assert_eq!(progress.load(Ordering::Relaxed), ids.len());
+        tokio::time::sleep(Duration::from_secs(1)).await;
+        let second = sim
+            .read_tag_values(SIMULATED_SERVER, ids, Arc::default())
+            .await
+            .unwrap();
+
+        for (a, b) in first.iter().zip(&second).take(4) {
+            assert_eq!(a.quality, "Good");
+            assert_ne!(a.value, b.value, "{} did not change", a.tag_id);
+        }
+        assert_eq!(first[0].vt, Some(VT_R8));
+        assert_eq!(first[1].vt, Some(VT_BOOL));
+        // Neighbouring square waves are out of phase.
+        assert_ne!(first[1].value, first[3].value);
+        assert!(first[4].quality.starts_with("Bad"));
+        assert_eq!(first[0].status, ReadStatus::Ok);
+        assert_eq!(first[4].status, ReadStatus::NotAdded);
+    }
+
+    #[tokio::test]
+    async fn test_simulated_batch_write_reports_each_tag() {
+        let sim = SimulatedOpcProvider::new();
+        let results = sim
+            .write_tag_values(
+                SIMULATED_SERVER,
+                vec![
+                    ("Sim.Tag.0".to_string(), OpcValue::Int(0)),
+                    ("Sim.Tag.200".to_string(), OpcValue::Int(0)),
+                    ("Sim.Tag.2".to_string(), OpcValue::Int(0)),
+                ],
+            )
+            .await
+            .unwrap();
+
+        let outcome: Vec<(&str, bool)> = results
+            .iter()
+            .map(|r| (r.tag_id.as_str(), r.success))
+            .collect();
+        assert_eq!(
+            outcome,
+            [
+                ("Sim.Tag.0", true),
+                ("Sim.Tag.200", false),
+                ("Sim.Tag.2", true)
+            ]
+        );
+        assert!(sim.write_tag_values("Other.Server", vec![]).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_simulated_set_items_active_needs_live_reads() {
+        let sim = SimulatedOpcProvider::new();
+        let tags = |ids: &[&str]| ids.iter().map(ToString::to_string).collect::<Vec<_>>();
+        assert!(matches!(
+            sim.set_items_active(SIMULATED_SERVER, tags(&["Sim.Tag.0"]), false)
+                .await,
+            Err(OpcError::InvalidState(_))
+        ));
+
+        sim.read_tag_values_live(SIMULATED_SERVER, tags(&["Sim.Tag.0", "Sim.Tag.1"]))
+            .await
+            .unwrap();
+        let changed = sim
+            .set_items_active(SIMULATED_SERVER, tags(&["Sim.Tag.0", "Sim.Tag.999"]), false)
+            .await
+            .unwrap();
+        assert_eq!(changed, 1, "unknown tags are skipped");
+
+        sim.stop_live_reads(SIMULATED_SERVER).await.unwrap();
+        assert!(
+            sim.set_items_active(SIMULATED_SERVER, tags(&["Sim.Tag.0"]), true)
+                .await
+                .is_err()
+        );
+    }
+}
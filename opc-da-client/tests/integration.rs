@@ -0,0 +1,136 @@
+//! End-to-end tests for [`OpcDaClient`] against a live, COM-registered OPC DA server.
+//!
+//! Gated behind the `integration-tests` feature because they need real Windows
+//! COM APIs and a registered server. This tree does not ship an embedded mock
+//! server, so the tests target the server named by `OPC_DA_INTEGRATION_SERVER`,
+//! which must expose the `VT_R8` tags `MockTag.Temperature` and `MockTag.Pressure`.
+//!
+//! ```powershell
+//! $env:OPC_DA_INTEGRATION_SERVER = "Vendor.MockServer.1"
+//! cargo test -p opc-da-client --features integration-tests --test integration
+//! ```
+#![cfg(feature = "integration-tests")]
+
+use opc_da_client::{AccessRightsFilter, BrowseProgress, OpcDaClient, OpcProvider, OpcValue};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+const MOCK_TAGS: [&str; 2] = ["MockTag.Temperature", "MockTag.Pressure"];
+
+fn server_progid() -> String {
+    std::env::var("OPC_DA_INTEGRATION_SERVER")
+        .expect("OPC_DA_INTEGRATION_SERVER must name a registered OPC DA server ProgID")
+}
+
+async fn client() -> OpcDaClient {
+    tokio::task::spawn_blocking(OpcDaClient::default)
+        .await
+        .expect("client construction panicked")
+}
+
+#[tokio::test]
+async fn test_list_servers_includes_mock_server() {
+    let client = client().await;
+    let servers = client.list_servers("localhost").await.unwrap();
+    assert!(
+        servers.contains(&server_progid()),
+        "registered servers: {servers:?}"
+    );
+}
+
+#[tokio::test]
+async fn test_browse_tags_finds_mock_tags() {
+    let client = client().await;
+    let progress = BrowseProgress::default();
+
+    let tags = client
+        .browse_tags(
+            &server_progid(),
+            1000,
+            AccessRightsFilter::Any,
+            progress.clone(),
+        )
+        .await
+        .unwrap();
+
+    for tag in MOCK_TAGS {
+        assert!(
+            tags.iter().any(|t| t.tag_id == tag),
+            "missing {tag} in {tags:?}"
+        );
+    }
+    assert_eq!(progress.tags_found.load(Ordering::Relaxed), tags.len());
+}
+
+#[tokio::test]
+async fn test_read_mock_tags_returns_floats() {
+    let client = client().await;
+    let tag_ids: Vec<String> = MOCK_TAGS.iter().map(ToString::to_string).collect();
+
+    let progress = Arc::new(AtomicUsize::new(0));
+    let values = client
+        .read_tag_values(&server_progid(), tag_ids, Arc::clone(&progress))
+        .await
+        .unwrap();
+
+    assert_eq!(values.len(), MOCK_TAGS.len());
+    assert_eq!(progress.load(Ordering::Relaxed), MOCK_TAGS.len());
+    for (tv, tag) in values.iter().zip(MOCK_TAGS) {
+        assert_eq!(tv.tag_id, tag);
+        assert_eq!(tv.quality, "Good");
+        assert!(tv.value.parse::<f64>().is_ok(), "{tag} = {}", tv.value);
+    }
+}
+
+#[tokio::test]
+async fn test_write_then_read_back() {
+    let client = client().await;
+    let server = server_progid();
+
+    let result = client
+        .write_tag_value(&server, MOCK_TAGS[0], OpcValue::Float(21.5))
+        .await
+        .unwrap();
+    assert!(result.success, "write failed: {:?}", result.error);
+    assert_eq!(result.sent_vt, Some(5), "Float is sent as VT_R8");
+
+    let values = client
+        .read_tag_values(&server, vec![MOCK_TAGS[0].to_string()], Arc::default())
+        .await
+        .unwrap();
+    let read_back: f64 = values[0].value.parse().unwrap();
+    assert!(
+        (read_back - 21.5).abs() < f64::EPSILON,
+        "read back {read_back}"
+    );
+}
+
+#[tokio::test]
+async fn test_batch_write_reports_each_tag() {
+    let client = client().await;
+    let server = server_progid();
+
+    let writes = MOCK_TAGS
+        .iter()
+        .map(|tag| ((*tag).to_string(), OpcValue::Float(0.0)))
+        .chain(std::iter::once((
+            "No.Such.Tag".to_string(),
+            OpcValue::Float(0.0),
+        )))
+        .collect();
+    let results = client.write_tag_values(&server, writes).await.unwrap();
+    assert_eq!(results.len(), MOCK_TAGS.len() + 1);
+    for (result, tag) in results.iter().zip(MOCK_TAGS) {
+        assert_eq!(result.tag_id, tag);
+        assert!(result.success, "write to {tag} failed: {:?}", result.error);
+    }
+    let unknown = results.last().unwrap();
+    assert!(!unknown.success);
+    assert!(
+        unknown
+            .error
+            .as_deref()
+            .unwrap()
+            .starts_with("Failed to add tag")
+    );
+}